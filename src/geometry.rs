@@ -2,9 +2,14 @@ pub mod operators;
 
 mod angle;
 mod angle_iterator;
+mod bezier_surface;
+mod bounding_box;
+mod error;
 mod line;
+mod octree;
 mod plane;
 mod point;
+mod polygon_2d;
 mod size;
 mod size_iterator;
 mod vector;
@@ -16,9 +21,14 @@ pub use self::angle_iterator::{
    AngleIterator, AngleIteratorBuilder, AngleIteratorInfinite,
    AngleParallelIterator, AngleParallelIteratorBuilder
 };
+pub use self::bezier_surface::{BezierError, BezierSurface};
+pub use self::bounding_box::BoundingBox;
+pub use self::error::GeometryError;
 pub use self::line::Line;
+pub use self::octree::Octree;
 pub use self::plane::Plane;
 pub use self::point::Point;
+pub use self::polygon_2d::Polygon2D;
 pub use self::size::{Size, SizeLiteral};
 pub use self::size_iterator::{
    SizeIterator, SizeIteratorBuilder, SizeIteratorInfinite,
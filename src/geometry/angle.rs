@@ -3,12 +3,14 @@ use crate::geometry::angle_iterator::{
 };
 use crate::geometry::Size;
 use crate::math::conversion::ToN64;
-use crate::math::rough_fp::{rough_cmp, rough_eq};
+use crate::math::rough_fp::{rough_cmp, rough_eq, rough_hash};
 use crate::math::unit::{Exp, Unit};
 use std::cmp::Ordering;
 use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
 use std::ops::{
-   Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign
+   Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub,
+   SubAssign
 };
 use noisy_float::prelude::*;
 
@@ -89,6 +91,9 @@ impl Angle {
    /// Also consider using `180.deg()`
    pub const PI: Angle = Angle(N64::unchecked_new(std::f64::consts::PI));
 
+   /// 2PI radian. Also consider using `360.deg()`
+   pub const TAU: Angle = Angle(N64::unchecked_new(std::f64::consts::TAU));
+
    pub const fn radian(radian: N64) -> Angle {
       Angle(radian)
    }
@@ -103,6 +108,58 @@ impl Angle {
       self.0.to_degrees()
    }
 
+   /// Converts this angle to a plain f64 value as radian
+   pub fn as_rad_f64(self) -> f64 {
+      self.0.raw()
+   }
+
+   /// Converts this angle to a plain f64 value as degree
+   pub fn as_deg_f64(self) -> f64 {
+      self.0.raw().to_degrees()
+   }
+
+   /// Angle from degrees, minutes, and seconds.
+   /// The sign of `deg` applies to the whole angle.
+   ///
+   /// ```
+   /// # use typed_scad::geometry::{Angle, AngleLiteral};
+   /// assert_eq!(Angle::from_dms(-12, 30, 36.0), -12.51.deg());
+   /// ```
+   pub fn from_dms(deg: i32, min: u32, sec: f64) -> Angle {
+      let degree = deg.unsigned_abs() as f64 + min as f64 / 60.0 + sec / 3600.0;
+      let degree = if deg < 0 { -degree } else { degree };
+      Angle(n64(degree.to_radians()))
+   }
+
+   /// Degrees, minutes, and seconds of this angle. The degrees have the sign
+   /// of this angle, so the sign is lost for an angle between -1° and 0°.
+   /// See also [format_dms](Angle::format_dms).
+   pub fn to_dms(self) -> (i32, u32, f64) {
+      let (negative, deg, min, sec) = self.dms();
+      let deg = deg as i32;
+      (if negative { -deg } else { deg }, min, sec)
+   }
+
+   /// Formats this angle like `-12°30'36"`.
+   pub fn format_dms(self) -> String {
+      let (negative, deg, min, sec) = self.dms();
+      let sign = if negative { "-" } else { "" };
+      format!("{}{}°{}'{}\"", sign, deg, min, sec)
+   }
+
+   fn dms(self) -> (bool, u32, u32, f64) {
+      let total = self.0.abs().to_degrees() * 3600.0;
+      // avoid 59.999... seconds by float-point arithmetic errors
+      let total = if rough_eq(total, total.round()) { total.round() } else { total };
+
+      let deg = (total / 3600.0).floor();
+      let min = ((total - deg * 3600.0) / 60.0).floor();
+      let sec = total - deg * 3600.0 - min * 60.0;
+
+      let negative = self.0 < 0.0 && total != 0.0;
+      (negative, deg.raw() as u32, min.raw() as u32, sec.raw())
+   }
+
    pub fn sin(self) -> N64 {
       self.0.sin()
    }
@@ -119,6 +176,39 @@ impl Angle {
       self.0.sin_cos()
    }
 
+   /// `cos / sin`. Infinity where `sin` is roughly 0.
+   pub fn cot(self) -> N64 {
+      let (sin, cos) = self.sin_cos();
+      if rough_eq(sin, n64(0.0)) { n64(f64::INFINITY) } else { cos / sin }
+   }
+
+   /// `1 / cos`. Infinity where `cos` is roughly 0.
+   pub fn sec(self) -> N64 {
+      let cos = self.cos();
+      if rough_eq(cos, n64(0.0)) { n64(f64::INFINITY) } else { cos.recip() }
+   }
+
+   /// `1 / sin`. Infinity where `sin` is roughly 0.
+   pub fn csc(self) -> N64 {
+      let sin = self.sin();
+      if rough_eq(sin, n64(0.0)) { n64(f64::INFINITY) } else { sin.recip() }
+   }
+
+   /// Hyperbolic sine of this angle as radian.
+   pub fn sinh(self) -> N64 {
+      self.0.sinh()
+   }
+
+   /// Hyperbolic cosine of this angle as radian.
+   pub fn cosh(self) -> N64 {
+      self.0.cosh()
+   }
+
+   /// Hyperbolic tangent of this angle as radian.
+   pub fn tanh(self) -> N64 {
+      self.0.tanh()
+   }
+
    pub fn asin(a: N64) -> Angle {
       Angle(N64::asin(a))
    }
@@ -127,6 +217,26 @@ impl Angle {
       Angle(N64::acos(a))
    }
 
+   /// [asin](Angle::asin) which returns `None` instead of panicking when
+   /// `a` is out of `[-1, 1]`.
+   pub fn checked_asin(a: N64) -> Option<Angle> {
+      if (-1.0..=1.0).contains(&a.raw()) {
+         Some(Angle::asin(a))
+      } else {
+         None
+      }
+   }
+
+   /// [acos](Angle::acos) which returns `None` instead of panicking when
+   /// `a` is out of `[-1, 1]`.
+   pub fn checked_acos(a: N64) -> Option<Angle> {
+      if (-1.0..=1.0).contains(&a.raw()) {
+         Some(Angle::acos(a))
+      } else {
+         None
+      }
+   }
+
    pub fn atan(a: N64) -> Angle {
       Angle(N64::atan(a))
    }
@@ -143,6 +253,84 @@ impl Angle {
       Angle(self.0.clamp(min.0, max.0))
    }
 
+   /// Whether this angle is on the arc from `start` to `end`
+   /// counterclockwise. Both ends are included, and float-point arithmetic
+   /// errors are allowed like [Angle::eq].
+   ///
+   /// ```
+   /// # use typed_scad::geometry::AngleLiteral;
+   /// assert!(5.deg().is_between(350.deg(), 10.deg()));
+   /// assert!(!5.deg().is_between(10.deg(), 350.deg()));
+   /// ```
+   pub fn is_between(self, start: Angle, end: Angle) -> bool {
+      if end - start >= Angle::TAU {
+         return true;
+      }
+
+      let normalize = |a: Angle| Angle(n64(a.0.raw().rem_euclid(Angle::TAU.0.raw())));
+      let span = normalize(end - start);
+      let offset = normalize(self - start);
+
+      // offset is almost 360° when this is slightly smaller than start
+      offset <= span || offset == Angle::TAU
+   }
+
+   /// Whether this angle is on the arc from `start` to `end` clockwise.
+   /// See [is_between](Angle::is_between).
+   pub fn is_between_cw(self, start: Angle, end: Angle) -> bool {
+      self.is_between(end, start)
+   }
+
+   /// The angle halfway between this and `other`, i.e. `(self + other) / 2`.
+   /// Which arc is bisected depends on the values. See
+   /// [bisect_short](Angle::bisect_short) to get the bisector geometrically.
+   ///
+   /// ```
+   /// # use typed_scad::geometry::AngleLiteral;
+   /// assert_eq!(0.deg().bisect(90.deg()), 45.deg());
+   /// assert_eq!(350.deg().bisect(10.deg()), 180.deg());
+   /// ```
+   pub fn bisect(self, other: Angle) -> Angle {
+      (self + other) / 2
+   }
+
+   /// The angle which bisects the shorter arc between this and `other`,
+   /// in the range of [0°, 360°). Angles are compared modulo 360°.
+   ///
+   /// ```
+   /// # use typed_scad::geometry::AngleLiteral;
+   /// assert_eq!(0.deg().bisect_short(90.deg()), 45.deg());
+   /// assert_eq!(350.deg().bisect_short(10.deg()), 0.deg());
+   /// assert_eq!(0.deg().bisect_short(700.deg()), 350.deg());
+   /// ```
+   pub fn bisect_short(self, other: Angle) -> Angle {
+      // the difference in (-180°, 180°]
+      let mut difference = (other - self).normalized();
+      if difference > Angle::PI {
+         difference -= Angle::TAU;
+      }
+
+      (self + difference / 2).normalized()
+   }
+
+   /// The angle which bisects the longer arc between this and `other`,
+   /// in the range of [0°, 360°).
+   /// The opposite of [bisect_short](Angle::bisect_short).
+   pub fn bisect_long(self, other: Angle) -> Angle {
+      (self.bisect_short(other) + Angle::PI).normalized()
+   }
+
+   /// This angle in the range of [0°, 360°). An angle slightly smaller than
+   /// a multiple of 360° is 0°.
+   fn normalized(self) -> Angle {
+      let normalized = Angle(n64(self.0.raw().rem_euclid(Angle::TAU.0.raw())));
+      if normalized == Angle::TAU {
+         Angle(n64(0.0))
+      } else {
+         normalized
+      }
+   }
+
    /// Prepare to iterate [Angle]s in the specified range.
    /// And [step][AngleIteratorBuilder::step] returns an [Iterator] for Angle.
    ///
@@ -209,6 +397,16 @@ impl PartialEq for Angle {
 
 impl Eq for Angle {}
 
+/// Rounds the value to the grid of the error which [Angle::eq] allows, so that
+/// roughly equal angles almost always have the same hash. Not always: 2 angles
+/// around the middle of a grid cell can be equal but round differently. So
+/// lookups in a `HashMap` or a `HashSet` keyed by angles are best-effort.
+impl Hash for Angle {
+   fn hash<H: Hasher>(&self, state: &mut H) {
+      rough_hash(self.0, state);
+   }
+}
+
 impl Add for Angle {
    type Output = Angle;
    fn add(self, rhs: Angle) -> Angle {
@@ -289,6 +487,19 @@ impl Div for Angle {
    }
 }
 
+impl Rem for Angle {
+   type Output = Angle;
+   fn rem(self, rhs: Angle) -> Angle {
+      Angle(self.0 % rhs.0)
+   }
+}
+
+impl RemAssign for Angle {
+   fn rem_assign(&mut self, rhs: Angle) {
+      *self = *self % rhs;
+   }
+}
+
 impl Neg for Angle {
    type Output = Angle;
    fn neg(self) -> Angle {
@@ -343,10 +554,34 @@ angle_literal!(usize, u8, u16, u32, u64, u128, isize, i8, i16, i32, i64, i128,
 #[cfg(test)]
 mod tests {
    use super::{Angle, AngleLiteral};
+   use crate::math::rough_fp::rough_eq;
    use noisy_float::prelude::*;
    use std::cmp::Ordering;
    use std::f64::consts::PI;
 
+   #[test]
+   fn reciprocal_trigonometric() {
+      assert!(rough_eq(45.deg().cot(), n64(1.0)));
+      assert!(rough_eq(60.deg().sec(), n64(2.0)));
+      assert!(rough_eq(30.deg().csc(), n64(2.0)));
+      assert!(rough_eq(90.deg().cot(), n64(0.0)));
+
+      assert_eq!(0.deg().cot(), f64::INFINITY);
+      assert_eq!(180.deg().csc(), f64::INFINITY);
+      assert_eq!(90.deg().sec(), f64::INFINITY);
+   }
+
+   #[test]
+   fn hyperbolic() {
+      assert_eq!(0.rad().sinh(), 0.0);
+      assert_eq!(0.rad().cosh(), 1.0);
+      assert_eq!(0.rad().tanh(), 0.0);
+
+      let a = 1.5.rad();
+      assert!(rough_eq(a.cosh() * a.cosh() - a.sinh() * a.sinh(), n64(1.0)));
+      assert!(rough_eq(a.tanh(), a.sinh() / a.cosh()));
+   }
+
    #[test]
    fn eq() {
       assert_eq!(Angle::from(0.42), Angle::from(0.42));
@@ -360,6 +595,47 @@ mod tests {
       assert_ne!(Angle::from(0.42), Angle::from(0.42 + 2.0 * PI));
    }
 
+   #[test]
+   fn bisect() {
+      assert_eq!(0.deg().bisect(90.deg()), 45.deg());
+      assert_eq!(90.deg().bisect(0.deg()), 45.deg());
+
+      assert_eq!(350.deg().bisect_short(10.deg()), 0.deg());
+      assert_eq!(10.deg().bisect_short(350.deg()), 0.deg());
+      assert_eq!(300.deg().bisect_short(10.deg()), 335.deg());
+      assert_eq!(170.deg().bisect_short((-170).deg()), 180.deg());
+      assert_eq!(20.deg().bisect_short(100.deg()), 60.deg());
+
+      assert_eq!(350.deg().bisect_long(10.deg()), 180.deg());
+      assert_eq!(20.deg().bisect_long(100.deg()), 240.deg());
+      assert_eq!(0.deg().bisect_long(90.deg()), 225.deg());
+
+      assert_eq!(0.deg().bisect_short(700.deg()), 350.deg());
+      assert_eq!((-350).deg().bisect_short(350.deg()), 0.deg());
+      assert_eq!(10.deg().bisect_short(1090.deg()), 10.deg());
+      assert_eq!((-90).deg().bisect_short((-720).deg()), 315.deg());
+      assert_eq!((-400).deg().bisect_short((-300).deg()), 10.deg());
+      assert_eq!(0.deg().bisect_long(700.deg()), 170.deg());
+      assert_eq!((-350).deg().bisect_long(350.deg()), 180.deg());
+   }
+
+   #[test]
+   fn dms() {
+      assert_eq!(Angle::from_dms(90, 30, 0.0), 90.5.deg());
+      assert_eq!(90.5.deg().to_dms(), (90, 30, 0.0));
+      assert_eq!(Angle::from_dms(-10, 20, 30.0), -(10.0 + 20.0 / 60.0 + 30.0 / 3600.0).deg());
+
+      for (d, m, s) in [(0, 0, 0.0), (45, 59, 59.5), (-179, 1, 2.25), (359, 0, 1.0)] {
+         let (deg, min, sec) = Angle::from_dms(d, m, s).to_dms();
+         assert_eq!((deg, min), (d, m));
+         assert!((sec - s).abs() < 1e-6);
+      }
+
+      assert_eq!(90.5.deg().format_dms(), "90°30'0\"");
+      assert_eq!((-0.5).deg().format_dms(), "-0°30'0\"");
+      assert_eq!((-0.5).deg().to_dms(), (0, 30, 0.0));
+   }
+
    #[test]
    fn display() {
       assert_eq!(
@@ -386,6 +662,67 @@ mod tests {
       assert_eq!(Angle::from(PI).to_degree(), n64(180.0));
    }
 
+   #[test]
+   fn as_f64() {
+      assert_eq!(Angle::from(0.42).as_rad_f64(), 0.42);
+      assert_eq!(180.deg().as_deg_f64(), 180.0);
+      assert_eq!(Angle::from(0.42).as_deg_f64().deg(), Angle::from(0.42));
+      assert_eq!(90.deg().as_rad_f64().rad(), 90.deg());
+   }
+
+   #[test]
+   fn is_between() {
+      assert!(270.deg().is_between(200.deg(), 300.deg()));
+      assert!(!100.deg().is_between(200.deg(), 300.deg()));
+      assert!(!270.deg().is_between(300.deg(), 200.deg()));
+      assert!((-90.deg()).is_between(200.deg(), 300.deg()));
+
+      assert!(5.deg().is_between(350.deg(), 10.deg()));
+      assert!(5.deg().is_between(-10.deg(), 10.deg()));
+      assert!(!20.deg().is_between(350.deg(), 10.deg()));
+
+      for a in [0.deg(), 90.deg(), 359.deg(), 720.deg()] {
+         assert!(a.is_between(0.deg(), 360.deg()));
+      }
+
+      assert!(200.deg().is_between(200.deg(), 300.deg()));
+      assert!(300.deg().is_between(200.deg(), 300.deg()));
+      assert!(200.deg().is_between(200.deg(), 200.deg()));
+      assert!(!201.deg().is_between(200.deg(), 200.deg()));
+
+      let error = Angle::from(5e-11);
+      assert!((300.deg() + error).is_between(200.deg(), 300.deg()));
+      assert!((200.deg() - error).is_between(200.deg(), 300.deg()));
+      assert!(!(300.deg() + Angle::from(1e-9)).is_between(200.deg(), 300.deg()));
+
+      assert!(100.deg().is_between_cw(200.deg(), 300.deg()));
+      assert!(!270.deg().is_between_cw(200.deg(), 300.deg()));
+      assert!(20.deg().is_between_cw(350.deg(), 10.deg()));
+   }
+
+   #[test]
+   fn checked_asin_acos() {
+      assert_eq!(Angle::checked_asin(n64(2.0)), None);
+      assert_eq!(Angle::checked_asin(n64(-1.5)), None);
+      assert_eq!(Angle::checked_asin(n64(0.5)), Some(Angle::asin(n64(0.5))));
+      assert_eq!(Angle::checked_asin(n64(1.0)), Some(90.deg()));
+
+      assert_eq!(Angle::checked_acos(n64(2.0)), None);
+      assert_eq!(Angle::checked_acos(n64(0.5)), Some(Angle::acos(n64(0.5))));
+      assert_eq!(Angle::checked_acos(n64(-1.0)), Some(180.deg()));
+   }
+
+   #[test]
+   fn rem() {
+      assert_eq!(Angle::TAU * 1.5 % Angle::TAU, Angle::PI);
+      assert_eq!(450.deg() % 360.deg(), 90.deg());
+      assert_eq!(-90.deg() % 360.deg(), -90.deg());
+
+      let mut angle = 450.deg();
+      angle %= 360.deg();
+      assert_eq!(angle, 90.deg());
+   }
+
    #[test]
    fn operators() {
       assert_eq!(Angle::from( 0.42) + Angle::from( 0.15), Angle::from(0.57));
@@ -431,6 +768,11 @@ mod tests {
       assert_eq!(Angle::from(-0.25) / Angle::from( 0.5), n64(-0.5));
       assert_eq!(Angle::from(-0.25) / Angle::from(-0.5), n64( 0.5));
 
+      assert_eq!(Angle::from( 0.42) % Angle::from( 0.25), Angle::from( 0.17));
+      assert_eq!(Angle::from( 0.42) % Angle::from(-0.25), Angle::from( 0.17));
+      assert_eq!(Angle::from(-0.42) % Angle::from( 0.25), Angle::from(-0.17));
+      assert_eq!(Angle::from(-0.42) % Angle::from(-0.25), Angle::from(-0.17));
+
       assert_eq!(-Angle::from(0.42), Angle::from(-0.42));
 
       assert!(Angle::from(0.42) > Angle::from(0.41));
@@ -1,5 +1,5 @@
-use crate::geometry::{Angle, AngleLiteral};
-use crate::math::rough_fp::FLOAT_POINT_ALLOWABLE_ERROR;
+use crate::geometry::Angle;
+use crate::math::rough_fp::step_count;
 use std::ops::{Range, RangeFrom, RangeInclusive};
 use rayon::iter::plumbing::{
    bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer
@@ -10,21 +10,7 @@ pub struct AngleIteratorBuilder<R>(pub R);
 pub struct AngleParallelIteratorBuilder<R>(pub R);
 
 fn angle_count(start: Angle, end: Angle, step: Angle) -> usize {
-   if start < end {
-      if step < 0.rad() {
-         0
-      } else {
-         ((end.0 - FLOAT_POINT_ALLOWABLE_ERROR - start.0) / step.0)
-            .raw() as usize + 1
-      }
-   } else {
-      if step > 0.rad() {
-         0
-      } else {
-         ((end.0 + FLOAT_POINT_ALLOWABLE_ERROR - start.0) / step.0)
-            .raw() as usize + 1
-      }
-   }
+   step_count(end.0 - start.0, step.0, false)
 }
 
 impl AngleIteratorBuilder<Range<Angle>> {
@@ -46,21 +32,7 @@ impl AngleParallelIteratorBuilder<Range<Angle>> {
 }
 
 fn angle_count_inclusive(start: Angle, end: Angle, step: Angle) -> usize {
-   if start < end {
-      if step < 0.rad() {
-         0
-      } else {
-         ((end.0 + FLOAT_POINT_ALLOWABLE_ERROR - start.0) / step.0)
-            .raw() as usize + 1
-      }
-   } else {
-      if step > 0.rad() {
-         0
-      } else {
-         ((end.0 - FLOAT_POINT_ALLOWABLE_ERROR - start.0) / step.0)
-            .raw() as usize + 1
-      }
-   }
+   step_count(end.0 - start.0, step.0, true)
 }
 
 impl AngleIteratorBuilder<RangeInclusive<Angle>> {
@@ -89,14 +61,15 @@ impl AngleIteratorBuilder<RangeFrom<Angle>> {
 }
 
 /// An [Iterator] for [Angle].
+///
+/// Each item is computed as `start + step * index`, rather than accumulating
+/// `step`, so that the items are the same however the iterator is split.
 #[derive(Clone)]
 pub struct AngleIterator {
-   next_left: Angle,
-   next_left_index: isize,
-   next_right: Angle,
-   next_right_index: isize,
+   start: Angle,
    step: Angle,
-   len: usize
+   next_left_index: isize,
+   next_right_index: isize
 }
 
 #[derive(Clone)]
@@ -115,13 +88,21 @@ pub struct AngleIteratorInfinite {
 
 impl AngleIterator {
    fn new(start: Angle, step: Angle, len: usize) -> AngleIterator {
+      AngleIterator::with_offset(start, step, 0, len)
+   }
+
+   /// Iterates `start + step * i` for `i` in `offset..(offset + len)`.
+   fn with_offset(
+      start: Angle,
+      step: Angle,
+      offset: usize,
+      len: usize
+   ) -> AngleIterator {
       AngleIterator {
-         next_left: start,
-         next_left_index: 0,
-         next_right: start + step * (len as isize - 1),
-         next_right_index: len as isize - 1,
+         start,
          step,
-         len
+         next_left_index: offset as isize,
+         next_right_index: (offset + len) as isize - 1
       }
    }
 }
@@ -141,14 +122,13 @@ impl Iterator for AngleIterator {
    fn next(&mut self) -> Option<Angle> {
       if self.next_left_index > self.next_right_index { return None; }
 
-      let next = self.next_left;
+      let next = self.start + self.step * self.next_left_index;
       self.next_left_index += 1;
-      self.next_left += self.step;
       Some(next)
    }
 
    fn size_hint(&self) -> (usize, Option<usize>) {
-      let remain_size = self.len - self.next_left_index as usize;
+      let remain_size = (self.next_right_index - self.next_left_index + 1) as usize;
       (remain_size, Some(remain_size))
    }
 }
@@ -173,9 +153,8 @@ impl DoubleEndedIterator for AngleIterator {
    fn next_back(&mut self) -> Option<Self::Item> {
       if self.next_right_index < self.next_left_index { return None; }
 
-      let next = self.next_right;
+      let next = self.start + self.step * self.next_right_index;
       self.next_right_index -= 1;
-      self.next_right -= self.step;
       Some(next)
    }
 }
@@ -208,9 +187,11 @@ impl IndexedParallelIterator for AngleParallelIterator {
    fn with_producer<CB>(self, callback: CB) -> CB::Output
       where CB: ProducerCallback<Self::Item>
    {
+      /// Produces `start + step * i` for `i` in `offset..(offset + len)`.
       struct AngleIterProducer {
          start: Angle,
          step: Angle,
+         offset: usize,
          len: usize
       }
       
@@ -219,18 +200,20 @@ impl IndexedParallelIterator for AngleParallelIterator {
          type IntoIter = AngleIterator;
 
          fn into_iter(self) -> Self::IntoIter {
-            AngleIterator::new(self.start, self.step, self.len)
+            AngleIterator::with_offset(self.start, self.step, self.offset, self.len)
          }
 
          fn split_at(self, index: usize) -> (Self, Self) {
             let left = AngleIterProducer {
                start: self.start,
                step: self.step,
+               offset: self.offset,
                len: index
             };
             let right = AngleIterProducer {
-               start: self.start + self.step * index,
+               start: self.start,
                step: self.step,
+               offset: self.offset + index,
                len: self.len - index
             };
             (left, right)
@@ -240,6 +223,7 @@ impl IndexedParallelIterator for AngleParallelIterator {
       callback.callback(AngleIterProducer {
          start: self.start,
          step: self.step,
+         offset: 0,
          len: self.len
       })
    }
@@ -337,6 +321,28 @@ mod tests {
       assert_eq!(iter.size_hint(), (usize::MAX, None));
    }
 
+   #[test]
+   fn step_boundary() {
+      let count = Angle::iterate(0.deg()..=10.deg()).step(2.5.deg()).count();
+      assert_eq!(count, 5);
+      let count = Angle::iterate(0.deg()..10.deg()).step(2.5.deg()).count();
+      assert_eq!(count, 4);
+      let count = Angle::iterate(10.deg()..=0.deg()).step(-2.5.deg()).count();
+      assert_eq!(count, 5);
+      let count = Angle::iterate(10.deg()..0.deg()).step(-2.5.deg()).count();
+      assert_eq!(count, 4);
+
+      for step in [1.deg(), -1.deg()] {
+         let count = Angle::iterate(3.deg()..=3.deg()).step(step).count();
+         assert_eq!(count, 1);
+         let count = Angle::iterate(3.deg()..3.deg()).step(step).count();
+         assert_eq!(count, 0);
+      }
+
+      let count = Angle::par_iterate(0.deg()..=10.deg()).step(2.5.deg()).count();
+      assert_eq!(count, 5);
+   }
+
    #[test]
    fn double_ended_iter() {
       let mut iter = Angle::iterate(42.deg()..=45.deg()).step(1.5.deg());
@@ -396,4 +402,18 @@ mod tests {
             assert_eq!(actual, expected.deg(), "{i}");
          });
    }
+
+   #[test]
+   fn parallel_iter_same_as_sequential() {
+      let sequential: Vec<_> = Angle::iterate(0.deg()..100.deg()).step(0.001.deg())
+         .collect();
+      let parallel: Vec<_> = Angle::par_iterate(0.deg()..100.deg()).step(0.001.deg())
+         .collect();
+
+      assert_eq!(parallel.len(), 100_000);
+      assert_eq!(
+         parallel.iter().map(|a| a.0).collect::<Vec<_>>(),
+         sequential.iter().map(|a| a.0).collect::<Vec<_>>()
+      );
+   }
 }
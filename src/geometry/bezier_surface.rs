@@ -0,0 +1,254 @@
+use crate::geometry::{Point, Vector};
+use crate::stl::{Facet, StlSolid};
+use crate::transform::Transform;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum BezierError {
+   #[error("A Bezier surface needs at least 2×2 control points.")]
+   TooFewControlPoints,
+   #[error("Each row of control points must have {expected} points, but row {row} has {actual}.")]
+   NotRectangular { row: usize, expected: usize, actual: usize }
+}
+
+/// Bezier surface patch of degree `m` × `n`.
+///
+/// `control_grid[i][j]` is the control point for `u` and `v` of `i / m` and
+/// `j / n`. The corners of the surface are the corner control points.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BezierSurface {
+   control_grid: Vec<Vec<Point>>,
+   m: usize,
+   n: usize
+}
+
+impl BezierSurface {
+   /// Returns [BezierError] if `control_grid` is not a rectangle of at least
+   /// 2×2 points.
+   pub fn new(control_grid: Vec<Vec<Point>>) -> Result<BezierSurface, BezierError> {
+      let columns = control_grid.first().map_or(0, Vec::len);
+      if control_grid.len() < 2 || columns < 2 {
+         return Err(BezierError::TooFewControlPoints);
+      }
+
+      if let Some((row, r)) = control_grid.iter().enumerate()
+         .find(|(_, r)| r.len() != columns)
+      {
+         return Err(BezierError::NotRectangular {
+            row, expected: columns, actual: r.len()
+         });
+      }
+
+      let m = control_grid.len() - 1;
+      let n = columns - 1;
+      Ok(BezierSurface { control_grid, m, n })
+   }
+
+   pub fn control_grid(&self) -> &[Vec<Point>] {
+      &self.control_grid
+   }
+
+   /// Degree in the direction of `u`.
+   pub fn m(&self) -> usize {
+      self.m
+   }
+
+   /// Degree in the direction of `v`.
+   pub fn n(&self) -> usize {
+      self.n
+   }
+
+   /// The point on this surface at `(u, v)`, in `[0, 1]²`.
+   pub fn sample(&self, u: f64, v: f64) -> Point {
+      let grid: Vec<Vec<Vector>> = self.control_grid.iter()
+         .map(|r| r.iter().map(|p| Vector::between(&Point::ORIGIN, p)).collect())
+         .collect();
+
+      Point::ORIGIN.translated(&evaluate(&grid, u, v))
+   }
+
+   /// Unit normal vector at `(u, v)`, which is the vector product of the
+   /// partial derivatives by `u` and `v`.
+   ///
+   /// Panics if the surface is degenerate at the point, e.g. at a corner
+   /// whose adjacent control points are the same.
+   pub fn normal(&self, u: f64, v: f64) -> Vector {
+      let g = &self.control_grid;
+
+      let du: Vec<Vec<Vector>> = (0..self.m)
+         .map(|i| {
+            (0..=self.n)
+               .map(|j| Vector::between(&g[i][j], &g[i + 1][j]) * self.m)
+               .collect()
+         })
+         .collect();
+
+      let dv: Vec<Vec<Vector>> = (0..=self.m)
+         .map(|i| {
+            (0..self.n)
+               .map(|j| Vector::between(&g[i][j], &g[i][j + 1]) * self.n)
+               .collect()
+         })
+         .collect();
+
+      evaluate(&du, u, v)
+         .vector_product(&evaluate(&dv, u, v))
+         .to_unit_vector()
+   }
+
+   /// Samples `(steps_u + 1) × (steps_v + 1)` points at regular intervals of
+   /// `u` and `v`, and connects them with 2 triangles for each quad.
+   ///
+   /// The facets face toward [normal](BezierSurface::normal). The result is
+   /// an open surface, not a closed solid.
+   ///
+   /// Panics if `steps_u` or `steps_v` is 0.
+   pub fn tessellate(&self, steps_u: usize, steps_v: usize) -> StlSolid {
+      if steps_u == 0 || steps_v == 0 {
+         panic!("A Bezier surface must be tessellated at least 1 step.");
+      }
+
+      let points: Vec<Vec<Point>> = (0..=steps_u)
+         .map(|i| {
+            (0..=steps_v)
+               .map(|j| {
+                  self.sample(i as f64 / steps_u as f64, j as f64 / steps_v as f64)
+               })
+               .collect()
+         })
+         .collect();
+
+      let facets = (0..steps_u)
+         .flat_map(|i| (0..steps_v).map(move |j| (i, j)))
+         .flat_map(|(i, j)| {
+            let p00 = points[i][j];
+            let p10 = points[i + 1][j];
+            let p01 = points[i][j + 1];
+            let p11 = points[i + 1][j + 1];
+            [
+               Facet::new([p00, p10, p11]),
+               Facet::new([p00, p11, p01])
+            ]
+         })
+         .collect();
+
+      StlSolid::new(facets)
+   }
+}
+
+/// Tensor-product Bezier by de Casteljau's algorithm, on each row and then
+/// on the results.
+fn evaluate(grid: &[Vec<Vector>], u: f64, v: f64) -> Vector {
+   let rows: Vec<Vector> = grid.iter().map(|r| de_casteljau(r, v)).collect();
+   de_casteljau(&rows, u)
+}
+
+fn de_casteljau(control_points: &[Vector], t: f64) -> Vector {
+   let mut points = control_points.to_vec();
+   for k in (1..points.len()).rev() {
+      for i in 0..k {
+         points[i] = points[i] * (1.0 - t) + points[i + 1] * t;
+      }
+   }
+   points[0]
+}
+
+#[cfg(test)]
+mod tests {
+   use super::{BezierError, BezierSurface};
+   use crate::geometry::{Point, SizeLiteral, Vector};
+   use crate::transform::Transform;
+
+   fn point(x: f64, y: f64, z: f64) -> Point {
+      Point::new(x.mm(), y.mm(), z.mm())
+   }
+
+   /// A dome over 0..3 × 0..3 whose middle control points are raised.
+   fn dome() -> BezierSurface {
+      let grid = (0..4)
+         .map(|i| {
+            (0..4)
+               .map(|j| {
+                  let z = if (1..=2).contains(&i) && (1..=2).contains(&j) { 3.0 } else { 0.0 };
+                  point(i as f64, j as f64, z)
+               })
+               .collect()
+         })
+         .collect();
+
+      BezierSurface::new(grid).unwrap()
+   }
+
+   #[test]
+   fn new() {
+      let error = BezierSurface::new(vec![vec![Point::ORIGIN; 3]]).unwrap_err();
+      assert_eq!(error, BezierError::TooFewControlPoints);
+
+      let error = BezierSurface::new(
+         vec![vec![Point::ORIGIN; 3], vec![Point::ORIGIN; 3], vec![Point::ORIGIN; 2]]
+      ).unwrap_err();
+      assert_eq!(
+         error,
+         BezierError::NotRectangular { row: 2, expected: 3, actual: 2 }
+      );
+
+      let surface = dome();
+      assert_eq!((surface.m(), surface.n()), (3, 3));
+   }
+
+   #[test]
+   fn sample() {
+      let surface = dome();
+      assert_eq!(surface.sample(0.0, 0.0), point(0.0, 0.0, 0.0));
+      assert_eq!(surface.sample(1.0, 0.0), point(3.0, 0.0, 0.0));
+      assert_eq!(surface.sample(0.0, 1.0), point(0.0, 3.0, 0.0));
+      assert_eq!(surface.sample(1.0, 1.0), point(3.0, 3.0, 0.0));
+
+      // B(0.5) = (1, 3, 3, 1) / 8, so the middle is 3 × (6/8)²
+      assert_eq!(surface.sample(0.5, 0.5), point(1.5, 1.5, 1.6875));
+
+      let bilinear = BezierSurface::new(vec![
+         vec![point(0.0, 0.0, 0.0), point(0.0, 2.0, 0.0)],
+         vec![point(2.0, 0.0, 0.0), point(2.0, 2.0, 2.0)]
+      ]).unwrap();
+      assert_eq!(bilinear.sample(0.5, 0.5), point(1.0, 1.0, 0.5));
+   }
+
+   #[test]
+   fn normal() {
+      let surface = dome();
+      assert_eq!(surface.normal(0.5, 0.5), Vector::Z_UNIT_VECTOR);
+
+      // rises toward the middle
+      let n = surface.normal(0.1, 0.5);
+      assert!(n.x() < 0.mm() && n.z() > 0.mm());
+
+      let flat = BezierSurface::new(vec![
+         vec![point(0.0, 0.0, 0.0), point(0.0, 1.0, 0.0)],
+         vec![point(1.0, 0.0, 0.0), point(1.0, 1.0, 0.0)]
+      ]).unwrap();
+      assert_eq!(flat.normal(0.3, 0.8), Vector::Z_UNIT_VECTOR);
+   }
+
+   #[test]
+   fn tessellate() {
+      let surface = dome();
+      let stl_solid = surface.tessellate(16, 16);
+      assert_eq!(stl_solid.facets.len(), 16 * 16 * 2);
+
+      // the middle of each quad is close to the smooth surface
+      for i in 0..16 {
+         for j in 0..16 {
+            let (u, v) = ((i as f64 + 0.5) / 16.0, (j as f64 + 0.5) / 16.0);
+            let quad = &stl_solid.facets[(i * 16 + j) * 2];
+            let [a, _, c] = quad.vertexes();
+            let middle = Point::ORIGIN.translated(
+               &((Vector::between(&Point::ORIGIN, &a) + Vector::between(&Point::ORIGIN, &c)) * 0.5)
+            );
+            assert!(middle.distance(&surface.sample(u, v)) < 0.05.mm());
+
+            assert!(quad.normal_vector().inner_product(&surface.normal(u, v)).0 > 0.9);
+         }
+      }
+   }
+}
@@ -0,0 +1,132 @@
+use crate::geometry::{Point, Size, Vector};
+
+/// Axis-aligned box which contains something.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoundingBox {
+   pub min: Point,
+   pub max: Point
+}
+
+impl BoundingBox {
+   pub const fn new(min: Point, max: Point) -> BoundingBox {
+      BoundingBox { min, max }
+   }
+
+   /// The smallest box which contains all the points.
+   /// `None` if no points are given.
+   pub fn of_points(points: impl IntoIterator<Item = Point>) -> Option<BoundingBox> {
+      let mut points = points.into_iter();
+      let first = points.next()?;
+
+      Some(
+         points.fold(BoundingBox::new(first, first), |b, p| {
+            b.union(&BoundingBox::new(p, p))
+         })
+      )
+   }
+
+   /// Sizes along X-Axis, Y-Axis, and Z-Axis.
+   pub fn size(&self) -> Vector {
+      Vector::between(&self.min, &self.max)
+   }
+
+   pub fn center(&self) -> Point {
+      Point::new(
+         (self.min.x() + self.max.x()) / 2,
+         (self.min.y() + self.max.y()) / 2,
+         (self.min.z() + self.max.z()) / 2
+      )
+   }
+
+   /// The smallest box which contains both boxes.
+   pub fn union(&self, other: &BoundingBox) -> BoundingBox {
+      BoundingBox {
+         min: Point::new(
+            self.min.x().min(other.min.x()),
+            self.min.y().min(other.min.y()),
+            self.min.z().min(other.min.z())
+         ),
+         max: Point::new(
+            self.max.x().max(other.max.x()),
+            self.max.y().max(other.max.y()),
+            self.max.z().max(other.max.z())
+         )
+      }
+   }
+
+   /// Whether the point is inside this box. Points on the surface are
+   /// regarded as inside.
+   pub fn contains_point(&self, point: &Point) -> bool {
+      let between = |min: Size, p: Size, max: Size| min <= p && p <= max;
+
+      between(self.min.x(), point.x(), self.max.x())
+         && between(self.min.y(), point.y(), self.max.y())
+         && between(self.min.z(), point.z(), self.max.z())
+   }
+
+   /// Whether the 2 boxes have any common point. Boxes which only touch
+   /// each other are regarded as intersecting.
+   pub fn intersects(&self, other: &BoundingBox) -> bool {
+      self.min.x() <= other.max.x() && other.min.x() <= self.max.x()
+         && self.min.y() <= other.max.y() && other.min.y() <= self.max.y()
+         && self.min.z() <= other.max.z() && other.min.z() <= self.max.z()
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::BoundingBox;
+   use crate::geometry::{Point, SizeLiteral, Vector};
+
+   fn bounding_box(min: (f64, f64, f64), max: (f64, f64, f64)) -> BoundingBox {
+      BoundingBox::new(
+         Point::new(min.0.mm(), min.1.mm(), min.2.mm()),
+         Point::new(max.0.mm(), max.1.mm(), max.2.mm())
+      )
+   }
+
+   #[test]
+   fn size_and_center() {
+      let b = bounding_box((1.0, 2.0, 3.0), (2.0, 4.0, 6.0));
+      assert_eq!(b.size(), Vector::new(1.mm(), 2.mm(), 3.mm()));
+      assert_eq!(b.center(), Point::new(1.5.mm(), 3.mm(), 4.5.mm()));
+   }
+
+   #[test]
+   fn of_points() {
+      let points = [
+         Point::new(1.mm(), 5.mm(), -1.mm()),
+         Point::new(3.mm(), 2.mm(), 0.mm()),
+         Point::new(2.mm(), 4.mm(), 7.mm())
+      ];
+      assert_eq!(
+         BoundingBox::of_points(points),
+         Some(bounding_box((1.0, 2.0, -1.0), (3.0, 5.0, 7.0)))
+      );
+      assert_eq!(BoundingBox::of_points([]), None);
+   }
+
+   #[test]
+   fn union() {
+      let a = bounding_box((0.0, 0.0, 0.0), (1.0, 1.0, 1.0));
+      let b = bounding_box((2.0, -1.0, 0.5), (3.0, 0.5, 0.7));
+      assert_eq!(a.union(&b), bounding_box((0.0, -1.0, 0.0), (3.0, 1.0, 1.0)));
+   }
+
+   #[test]
+   fn contains_point() {
+      let b = bounding_box((0.0, 0.0, 0.0), (1.0, 2.0, 3.0));
+      assert!(b.contains_point(&Point::new(0.5.mm(), 1.mm(), 1.mm())));
+      assert!(b.contains_point(&Point::new(1.mm(), 2.mm(), 3.mm())));
+      assert!(!b.contains_point(&Point::new(1.5.mm(), 1.mm(), 1.mm())));
+   }
+
+   #[test]
+   fn intersects() {
+      let a = bounding_box((0.0, 0.0, 0.0), (1.0, 1.0, 1.0));
+      assert!(a.intersects(&bounding_box((0.5, 0.5, 0.5), (2.0, 2.0, 2.0))));
+      assert!(a.intersects(&bounding_box((1.0, 0.0, 0.0), (2.0, 1.0, 1.0))));
+      assert!(!a.intersects(&bounding_box((1.5, 0.0, 0.0), (2.0, 1.0, 1.0))));
+      assert!(!a.intersects(&bounding_box((0.0, 0.0, -2.0), (1.0, 1.0, -1.0))));
+   }
+}
@@ -0,0 +1,16 @@
+use crate::geometry::Angle;
+use thiserror::Error;
+
+/// Errors of the `try_` variants of geometric operations.
+#[derive(Error, Debug, PartialEq)]
+pub enum GeometryError {
+   #[error("cannot convert to a unit vector \
+            since this vector does not point any direction.")]
+   ZeroVector,
+   #[error("The angle formed by 2 vectors must be 90 degrees, but {0} is given.")]
+   NotPerpendicular(Angle),
+   #[error("2 planes don't have an intersection.")]
+   ParallelPlanes,
+   #[error("The specified plane and line don't have an intersection.")]
+   ParallelLineAndPlane
+}
@@ -1,6 +1,9 @@
-use crate::geometry::{Angle, Plane, Point, Vector};
-use crate::geometry::operators::Intersection;
+use crate::geometry::{Angle, GeometryError, Plane, Point, Size, Vector};
+use crate::geometry::operators::{Intersection, TryIntersection};
+use crate::math::rough_fp::rough_eq;
+use crate::math::unit::Exp;
 use crate::transform::Transform;
+use noisy_float::prelude::*;
 
 /// Line in 3D.
 ///
@@ -33,6 +36,12 @@ impl Line {
       }
    }
 
+   /// Same as [Line::new]. The line passes `point` and goes along
+   /// `direction`.
+   pub const fn from_point_and_direction(point: &Point, direction: &Vector) -> Line {
+      Line::new(point, direction)
+   }
+
    pub fn from_2points(a: &Point, b: &Point) -> Line {
       Line {
          point: *a,
@@ -49,6 +58,39 @@ impl Line {
    pub const fn vector(&self) -> &Vector {
       &self.vector
    }
+
+   /// Reflects this line across the plane.
+   pub fn reflect_across_plane(&self, plane: &Plane) -> Line {
+      Line {
+         point: plane.reflect_point(&self.point),
+         vector: plane.reflect_vector(&self.vector)
+      }
+   }
+
+   /// returns the pair of points, one on this line and the other on `other`,
+   /// which are the nearest from each other.
+   /// Or `None` when 2 lines are parallel.
+   fn closest_points(&self, other: &Line) -> Option<(Point, Point)> {
+      let w = Vector::between(&other.point, &self.point);
+      let a: Exp<Size, 2> = self.vector.inner_product(&self.vector);
+      let b: Exp<Size, 2> = self.vector.inner_product(&other.vector);
+      let c: Exp<Size, 2> = other.vector.inner_product(&other.vector);
+      let d: Exp<Size, 2> = self.vector.inner_product(&w);
+      let e: Exp<Size, 2> = other.vector.inner_product(&w);
+
+      let denominator: Exp<Size, 4> = a * c - b * b;
+      if rough_eq(denominator.0, n64(0.0)) {
+         return None;
+      }
+
+      let t = N64::from((b * e - c * d) / denominator);
+      let s = N64::from((a * e - b * d) / denominator);
+
+      Some((
+         Point { matrix: self.point.matrix + self.vector.matrix * t },
+         Point { matrix: other.point.matrix + other.vector.matrix * s }
+      ))
+   }
 }
 
 impl PartialEq for Line {
@@ -83,10 +125,33 @@ impl Intersection<Plane> for Line {
    }
 }
 
+impl TryIntersection<Plane> for Line {
+   type Output = Point;
+   fn try_intersection(&self, rhs: &Plane) -> Result<Point, GeometryError> {
+      rhs.try_intersection(self)
+   }
+}
+
+impl Intersection<Line> for Line {
+   type Output = Option<Point>;
+
+   /// returns the intersection of 2 lines.
+   /// Or `None` when they are parallel or skew.
+   fn intersection(&self, rhs: &Line) -> Option<Point> {
+      let (a, b) = self.closest_points(rhs)?;
+      if a == b {
+         Some(a)
+      } else {
+         None
+      }
+   }
+}
+
 #[cfg(test)]
 mod tests {
    use super::Line;
    use crate::geometry::{Point, SizeLiteral, Vector};
+   use crate::geometry::operators::Intersection;
 
    #[test]
    fn nearest_point_from_origin() {
@@ -107,6 +172,16 @@ mod tests {
       assert_eq!(actual, Point::new(0.mm(), 0.5.mm(), 0.5.mm()));
    }
 
+   #[test]
+   fn from_point_and_direction() {
+      let point = Point::new(1.mm(), 2.mm(), 3.mm());
+      let direction = Vector::new(4.mm(), 5.mm(), 6.mm());
+      assert_eq!(
+         Line::from_point_and_direction(&point, &direction),
+         Line::new(&point, &direction)
+      );
+   }
+
    #[test]
    fn eq() {
       assert_eq!(
@@ -129,4 +204,47 @@ mod tests {
          Line::new(&Point::ORIGIN, &Vector::Y_UNIT_VECTOR)
       );
    }
+
+   #[test]
+   fn intersection_line() {
+      assert_eq!(
+         Line::X_AXIS.intersection(&Line::Y_AXIS),
+         Some(Point::ORIGIN)
+      );
+
+      let a = Line::new(
+         &Point::new(1.mm(), 0.mm(), 3.mm()),
+         &Vector::new(1.mm(), 1.mm(), 0.mm())
+      );
+      let b = Line::new(
+         &Point::new(5.mm(), 0.mm(), 3.mm()),
+         &Vector::new(-1.mm(), 1.mm(), 0.mm())
+      );
+      assert_eq!(
+         a.intersection(&b),
+         Some(Point::new(3.mm(), 2.mm(), 3.mm()))
+      );
+
+      // sharing the base point
+      let p = Point::new(2.mm(), -1.mm(), 4.mm());
+      let a = Line::new(&p, &Vector::new(1.mm(), 2.mm(), 3.mm()));
+      let b = Line::new(&p, &Vector::new(-3.mm(), 0.mm(), 1.mm()));
+      assert_eq!(a.intersection(&b), Some(p));
+   }
+
+   #[test]
+   fn intersection_parallel_line() {
+      let a = Line::new(&Point::ORIGIN, &Vector::X_UNIT_VECTOR);
+      let b = Line::new(&Point::new(0.mm(), 1.mm(), 0.mm()), &Vector::X_UNIT_VECTOR);
+      assert_eq!(a.intersection(&b), None);
+
+      assert_eq!(Line::X_AXIS.intersection(&Line::X_AXIS), None);
+   }
+
+   #[test]
+   fn intersection_skew_line() {
+      let a = Line::X_AXIS;
+      let b = Line::new(&Point::new(0.mm(), 0.mm(), 1.mm()), &Vector::Y_UNIT_VECTOR);
+      assert_eq!(a.intersection(&b), None);
+   }
 }
@@ -0,0 +1,308 @@
+use crate::geometry::{BoundingBox, Point, Size};
+
+/// Values more than this in a leaf are split into 8 children.
+const LEAF_CAPACITY: usize = 8;
+
+/// Nodes deeper than this are never split, for many equal points.
+const MAX_DEPTH: usize = 16;
+
+/// Spatial index which finds values by their points.
+///
+/// Each node is split into 8 children at its center when it holds too many
+/// values. A point on the border between children belongs to the upper one.
+pub struct Octree<T> {
+   root: Node<T>,
+   len: usize
+}
+
+struct Node<T> {
+   bounds: BoundingBox,
+   content: Content<T>
+}
+
+enum Content<T> {
+   Leaf(Vec<(Point, T)>),
+   Branch(Vec<Node<T>>)
+}
+
+impl<T> Octree<T> {
+   /// Empty octree for points in `bounds`.
+   pub fn new(bounds: BoundingBox) -> Octree<T> {
+      Octree {
+         root: Node::new(bounds),
+         len: 0
+      }
+   }
+
+   pub fn bounds(&self) -> &BoundingBox {
+      &self.root.bounds
+   }
+
+   pub fn len(&self) -> usize {
+      self.len
+   }
+
+   pub fn is_empty(&self) -> bool {
+      self.len == 0
+   }
+
+   /// Panics if the point is out of [bounds](Octree::bounds).
+   pub fn insert(&mut self, point: Point, value: T) {
+      if !self.root.bounds.contains_point(&point) {
+         panic!("The point {} is out of the bounds of the octree.", point);
+      }
+
+      self.root.insert(point, value, 0);
+      self.len += 1;
+   }
+
+   /// Values whose points are within `radius` from `center`. Points just on
+   /// the sphere are included, allowing float-point arithmetic errors.
+   pub fn query_sphere(&self, center: Point, radius: Size) -> Vec<&T> {
+      let mut found = vec![];
+      self.root.query_sphere(&center, radius, &mut found);
+      found
+   }
+
+   /// `k` values nearest to `center`, in order of the distance.
+   pub fn query_nearest(&self, center: Point, k: usize) -> Vec<&T> {
+      let mut nearest = vec![];
+      self.root.query_nearest(&center, k, &mut nearest);
+      nearest.into_iter().map(|(_, v)| v).collect()
+   }
+}
+
+impl<T> Node<T> {
+   fn new(bounds: BoundingBox) -> Node<T> {
+      Node {
+         bounds,
+         content: Content::Leaf(vec![])
+      }
+   }
+
+   fn insert(&mut self, point: Point, value: T, depth: usize) {
+      let full = match &mut self.content {
+         Content::Branch(children) => {
+            let i = child_index(&self.bounds, &point);
+            children[i].insert(point, value, depth + 1);
+            false
+         }
+         Content::Leaf(values) => {
+            values.push((point, value));
+            values.len() > LEAF_CAPACITY && depth < MAX_DEPTH
+         }
+      };
+
+      if full {
+         self.split(depth);
+      }
+   }
+
+   fn split(&mut self, depth: usize) {
+      let mut children: Vec<_> = (0..8)
+         .map(|i| Node::new(child_bounds(&self.bounds, i)))
+         .collect();
+
+      let content = std::mem::replace(&mut self.content, Content::Branch(vec![]));
+      if let Content::Leaf(values) = content {
+         for (point, value) in values {
+            let i = child_index(&self.bounds, &point);
+            children[i].insert(point, value, depth + 1);
+         }
+      }
+
+      self.content = Content::Branch(children);
+   }
+
+   fn query_sphere<'a>(&'a self, center: &Point, radius: Size, found: &mut Vec<&'a T>) {
+      if distance_to_box(&self.bounds, center) > radius {
+         return;
+      }
+
+      match &self.content {
+         Content::Leaf(values) => {
+            found.extend(
+               values.iter()
+                  .filter(|(p, _)| p.distance(center) <= radius)
+                  .map(|(_, v)| v)
+            );
+         }
+         Content::Branch(children) => {
+            for c in children {
+               c.query_sphere(center, radius, found);
+            }
+         }
+      }
+   }
+
+   /// Updates `nearest`, which is sorted by the distance and has at most `k`
+   /// values.
+   fn query_nearest<'a>(
+      &'a self,
+      center: &Point,
+      k: usize,
+      nearest: &mut Vec<(Size, &'a T)>
+   ) {
+      if k == 0 {
+         return;
+      }
+      if nearest.len() == k && distance_to_box(&self.bounds, center) > nearest[k - 1].0 {
+         return;
+      }
+
+      match &self.content {
+         Content::Leaf(values) => {
+            for (p, v) in values {
+               let distance = p.distance(center);
+               let i = nearest.partition_point(|(d, _)| *d <= distance);
+               if i < k {
+                  nearest.insert(i, (distance, v));
+                  nearest.truncate(k);
+               }
+            }
+         }
+         Content::Branch(children) => {
+            let mut children: Vec<_> = children.iter().collect();
+            children.sort_by_key(|c| distance_to_box(&c.bounds, center));
+            for c in children {
+               c.query_nearest(center, k, nearest);
+            }
+         }
+      }
+   }
+}
+
+/// Bit 0, 1, and 2 of the index are set for the upper side of X, Y, and Z.
+fn child_index(bounds: &BoundingBox, point: &Point) -> usize {
+   let center = bounds.center();
+
+   (point.x() >= center.x()) as usize
+      | ((point.y() >= center.y()) as usize) << 1
+      | ((point.z() >= center.z()) as usize) << 2
+}
+
+fn child_bounds(bounds: &BoundingBox, index: usize) -> BoundingBox {
+   let center = bounds.center();
+   let range = |bit: usize, min: Size, center: Size, max: Size| {
+      if index & bit == 0 { (min, center) } else { (center, max) }
+   };
+
+   let (min_x, max_x) = range(1, bounds.min.x(), center.x(), bounds.max.x());
+   let (min_y, max_y) = range(2, bounds.min.y(), center.y(), bounds.max.y());
+   let (min_z, max_z) = range(4, bounds.min.z(), center.z(), bounds.max.z());
+
+   BoundingBox::new(
+      Point::new(min_x, min_y, min_z),
+      Point::new(max_x, max_y, max_z)
+   )
+}
+
+/// 0 if the point is inside the box.
+fn distance_to_box(bounds: &BoundingBox, point: &Point) -> Size {
+   let nearest = Point::new(
+      point.x().clamp(bounds.min.x(), bounds.max.x()),
+      point.y().clamp(bounds.min.y(), bounds.max.y()),
+      point.z().clamp(bounds.min.z(), bounds.max.z())
+   );
+
+   point.distance(&nearest)
+}
+
+#[cfg(test)]
+mod tests {
+   use super::Octree;
+   use crate::geometry::{BoundingBox, Point, SizeLiteral};
+
+   /// Points on the grid of 1mm from (0, 0, 0) to (4, 4, 4), with their
+   /// indexes.
+   fn grid() -> (Vec<Point>, Octree<usize>) {
+      let points: Vec<_> = (0..125)
+         .map(|i| Point::new((i % 5).mm(), (i / 5 % 5).mm(), (i / 25).mm()))
+         .collect();
+
+      let mut octree = Octree::new(
+         BoundingBox::new(Point::ORIGIN, Point::new(4.mm(), 4.mm(), 4.mm()))
+      );
+      for (i, p) in points.iter().enumerate() {
+         octree.insert(*p, i);
+      }
+
+      (points, octree)
+   }
+
+   #[test]
+   fn query_sphere() {
+      let (points, octree) = grid();
+      assert_eq!(octree.len(), 125);
+
+      let center = Point::new(2.mm(), 2.mm(), 2.mm());
+      let mut found: Vec<_> = octree.query_sphere(center, 1.mm()).into_iter()
+         .map(|&i| points[i])
+         .collect();
+      found.sort_by_key(|p| (p.x(), p.y(), p.z()));
+
+      assert_eq!(
+         found,
+         vec![
+            Point::new(1.mm(), 2.mm(), 2.mm()),
+            Point::new(2.mm(), 1.mm(), 2.mm()),
+            Point::new(2.mm(), 2.mm(), 1.mm()),
+            Point::new(2.mm(), 2.mm(), 2.mm()),
+            Point::new(2.mm(), 2.mm(), 3.mm()),
+            Point::new(2.mm(), 3.mm(), 2.mm()),
+            Point::new(3.mm(), 2.mm(), 2.mm())
+         ]
+      );
+
+      for (center, radius) in [
+         (Point::new(0.3.mm(), 3.9.mm(), 1.2.mm()), 1.5.mm()),
+         (Point::new(4.mm(), 4.mm(), 4.mm()), 2.mm()),
+         (Point::new(-3.mm(), 2.mm(), 2.mm()), 3.mm())
+      ] {
+         let mut found: Vec<_> = octree.query_sphere(center, radius).into_iter()
+            .copied()
+            .collect();
+         found.sort();
+
+         let expected: Vec<_> = (0..points.len())
+            .filter(|&i| points[i].distance(&center) <= radius)
+            .collect();
+         assert_eq!(found, expected);
+      }
+   }
+
+   #[test]
+   fn node_border() {
+      let (points, octree) = grid();
+
+      // 2mm is the border of the root's children, and 1mm and 3mm are
+      // the borders of the grandchildren
+      for (i, p) in points.iter().enumerate() {
+         assert_eq!(octree.query_sphere(*p, 0.mm()), vec![&i]);
+      }
+
+      let found = octree.query_sphere(Point::new(1.5.mm(), 2.mm(), 2.mm()), 0.5.mm());
+      assert_eq!(found.len(), 2);
+   }
+
+   #[test]
+   fn query_nearest() {
+      let (points, octree) = grid();
+      let center = Point::new(1.2.mm(), 3.4.mm(), 0.6.mm());
+
+      let mut expected: Vec<_> = (0..points.len()).collect();
+      expected.sort_by_key(|&i| points[i].distance(&center));
+      let expected: Vec<_> = expected.iter().take(5).collect();
+
+      assert_eq!(octree.query_nearest(center, 5), expected);
+      assert!(octree.query_nearest(center, 0).is_empty());
+      assert_eq!(octree.query_nearest(center, 200).len(), 125);
+   }
+
+   #[test]
+   #[should_panic]
+   fn out_of_bounds() {
+      let (_, mut octree) = grid();
+      octree.insert(Point::new(5.mm(), 0.mm(), 0.mm()), 0);
+   }
+}
@@ -1,5 +1,13 @@
+use crate::geometry::GeometryError;
 
 pub trait Intersection<Rhs> {
    type Output;
    fn intersection(&self, rhs: &Rhs) -> Self::Output;
 }
+
+/// [Intersection] which returns an error instead of panicking when there is
+/// no intersection.
+pub trait TryIntersection<Rhs> {
+   type Output;
+   fn try_intersection(&self, rhs: &Rhs) -> Result<Self::Output, GeometryError>;
+}
@@ -1,9 +1,10 @@
-use crate::geometry::{Angle, Line, Point, Size, Vector};
-use crate::geometry::operators::Intersection;
+use crate::geometry::{Angle, GeometryError, Line, Point, Size, Vector};
+use crate::geometry::operators::{Intersection, TryIntersection};
 use crate::math::rough_fp::rough_eq;
 use crate::math::unit::Exp;
 use crate::transform::Transform;
 use noisy_float::prelude::*;
+use std::cmp::Ordering;
 
 /// Plane in 3D.
 ///
@@ -29,6 +30,21 @@ impl Plane {
    pub const YZ: Plane = Plane::new(&Point::ORIGIN, &Vector::X_UNIT_VECTOR);
    pub const ZX: Plane = Plane::new(&Point::ORIGIN, &Vector::Y_UNIT_VECTOR);
 
+   /// The plane to mirror across to negate X coordinates. Same as [Plane::YZ].
+   pub const fn mirror_x() -> Plane {
+      Plane::YZ
+   }
+
+   /// The plane to mirror across to negate Y coordinates. Same as [Plane::ZX].
+   pub const fn mirror_y() -> Plane {
+      Plane::ZX
+   }
+
+   /// The plane to mirror across to negate Z coordinates. Same as [Plane::XY].
+   pub const fn mirror_z() -> Plane {
+      Plane::XY
+   }
+
    pub const fn new(point: &Point, normal_vector: &Vector) -> Plane {
       Plane {
          point: *point,
@@ -44,6 +60,15 @@ impl Plane {
       }
    }
 
+   /// Plane which passes `point` and spreads along `u` and `v`.
+   /// The normal vector is `u × v`.
+   pub fn from_point_and_spans(point: &Point, u: &Vector, v: &Vector) -> Plane {
+      Plane {
+         point: *point,
+         normal_vector: u.vector_product(v)
+      }
+   }
+
    /// returns the point which is on this plane and the nearest from origin.
    pub fn point(&self) -> Point {
       Line::new(&Point::ORIGIN, &self.normal_vector)
@@ -53,6 +78,71 @@ impl Plane {
    pub const fn normal_vector(&self) -> &Vector {
       &self.normal_vector
    }
+
+   /// Whether the point is on this plane. Allows float-point arithmetic
+   /// errors like [Size::eq].
+   pub fn contains_point(&self, point: &Point) -> bool {
+      self.signed_distance(point) == Size::ZERO
+   }
+
+   /// Distance from this plane to the point. Positive on the side which
+   /// the normal vector points to.
+   pub fn signed_distance(&self, point: &Point) -> Size {
+      Size::from(
+         Vector::between(&self.point, point).inner_product(&self.normal_vector)
+            / self.normal_vector.norm()
+      )
+   }
+
+   /// Which side of this plane the point is on. [Ordering::Greater] for
+   /// the side which the normal vector points to, and [Ordering::Equal] if
+   /// the point is on this plane. Allows float-point arithmetic errors like
+   /// [contains_point](Plane::contains_point).
+   pub fn side_of(&self, point: &Point) -> Ordering {
+      self.signed_distance(point).cmp(&Size::ZERO)
+   }
+
+   /// Unit vectors `(u, v)` along this plane. `(u, v, normal vector)` is
+   /// right-handed, so that a polygon counterclockwise in `(u, v)` is
+   /// counterclockwise seen from the side which the normal vector points to.
+   pub fn basis(&self) -> (Vector, Vector) {
+      let normal = &self.normal_vector;
+
+      let u = [Vector::X_UNIT_VECTOR, Vector::Y_UNIT_VECTOR]
+         .into_iter()
+         .map(|v| normal.vector_product(&v))
+         .max_by_key(|v| v.norm())
+         .unwrap()
+         .to_unit_vector();
+      let v = normal.vector_product(&u).to_unit_vector();
+
+      (u, v)
+   }
+
+   /// The point where the segment from `p` to `q` crosses this plane.
+   /// `p` and `q` must not be on the same side of this plane.
+   ///
+   /// The result is interpolated from `p`. Pass the end points in the same
+   /// order to get exactly the same point.
+   pub fn segment_intersection(&self, p: &Point, q: &Point) -> Point {
+      let dp = self.signed_distance(p);
+      let dq = self.signed_distance(q);
+      let t = dp / (dp - dq);
+      p.translated_toward(&Vector::between(p, q), p.distance(q) * t)
+   }
+
+   /// Reflects the vector like a mirror of this plane. The position of this
+   /// plane doesn't matter since a vector has no position.
+   pub fn reflect_vector(&self, vector: &Vector) -> Vector {
+      let n = &self.normal_vector;
+      let component = Size::from(vector.inner_product(n) / n.norm());
+      *vector - *n * (component * 2 / n.norm())
+   }
+
+   /// Reflects the point across this plane.
+   pub fn reflect_point(&self, point: &Point) -> Point {
+      point.translated_toward(&self.normal_vector, -self.signed_distance(point) * 2)
+   }
 }
 
 impl PartialEq for Plane {
@@ -84,6 +174,15 @@ impl Intersection<Plane> for Plane {
    type Output = Line;
 
    fn intersection(&self, rhs: &Plane) -> Line {
+      self.try_intersection(rhs).unwrap_or_else(|e| panic!("{}", e))
+   }
+}
+
+impl TryIntersection<Plane> for Plane {
+   type Output = Line;
+
+   /// Returns [GeometryError::ParallelPlanes] if the planes are parallel.
+   fn try_intersection(&self, rhs: &Plane) -> Result<Line, GeometryError> {
       let sp = self.point;
       let sv = self.normal_vector;
       let rp = rhs.point;
@@ -112,10 +211,10 @@ impl Intersection<Plane> for Plane {
             Size::ZERO
          )
       } else {
-         panic!("2 planes don't have an intersection.");
+         return Err(GeometryError::ParallelPlanes);
       };
 
-      Line::new(&point, &vector)
+      Ok(Line::new(&point, &vector))
    }
 }
 
@@ -123,11 +222,21 @@ impl Intersection<Line> for Plane {
    type Output = Point;
 
    fn intersection(&self, rhs: &Line) -> Point {
+      self.try_intersection(rhs).unwrap_or_else(|e| panic!("{}", e))
+   }
+}
+
+impl TryIntersection<Line> for Plane {
+   type Output = Point;
+
+   /// Returns [GeometryError::ParallelLineAndPlane] if the line is parallel
+   /// to the plane.
+   fn try_intersection(&self, rhs: &Line) -> Result<Point, GeometryError> {
       let inner_product: Exp<Size, 2>
          = self.normal_vector.inner_product(&rhs.vector);
 
       if rough_eq(inner_product.0, n64(0.0)) {
-         panic!("The specified plane and line don't have an intersection.");
+         return Err(GeometryError::ParallelLineAndPlane);
       }
 
       let t = N64::from(
@@ -135,17 +244,18 @@ impl Intersection<Line> for Plane {
             .inner_product(&self.normal_vector) / inner_product
       );
 
-      Point {
+      Ok(Point {
          matrix: rhs.point.matrix + rhs.vector.matrix * t
-      }
+      })
    }
 }
 
 #[cfg(test)]
 mod tests {
    use super::Plane;
-   use crate::geometry::{Line, Point, SizeLiteral, Vector};
-   use crate::geometry::operators::Intersection;
+   use crate::geometry::{GeometryError, Line, Point, Size, SizeLiteral, Vector};
+   use crate::geometry::operators::{Intersection, TryIntersection};
+   use std::cmp::Ordering;
 
    #[test]
    fn nearest_point_from_origin() {
@@ -166,6 +276,36 @@ mod tests {
       assert_eq!(actual, Point::new(0.mm(), 0.5.mm(), 0.5.mm()));
    }
 
+   #[test]
+   fn from_point_and_spans() {
+      assert_eq!(
+         Plane::from_point_and_spans(
+            &Point::ORIGIN, &Vector::X_UNIT_VECTOR, &Vector::Y_UNIT_VECTOR
+         ),
+         Plane::XY
+      );
+
+      let plane = Plane::from_point_and_spans(
+         &Point::new(1.mm(), 2.mm(), 3.mm()),
+         &Vector::new(0.mm(), 2.mm(), 0.mm()),
+         &Vector::new(0.mm(), 1.mm(), 1.mm())
+      );
+      assert_eq!(plane.normal_vector().to_unit_vector(), Vector::X_UNIT_VECTOR);
+      assert_eq!(plane.point(), Point::new(1.mm(), 0.mm(), 0.mm()));
+   }
+
+   #[test]
+   fn contains_point() {
+      let plane = Plane::new(
+         &Point::new(1.mm(), 2.mm(), 3.mm()),
+         &Vector::new(1.mm(), 1.mm(), 0.mm())
+      );
+
+      assert!(plane.contains_point(&Point::new(1.mm(), 2.mm(), 3.mm())));
+      assert!(plane.contains_point(&Point::new(3.mm(), 0.mm(), -5.mm())));
+      assert!(!plane.contains_point(&Point::new(3.mm(), 1.mm(), 3.mm())));
+   }
+
    #[test]
    fn eq() {
       assert_eq!(
@@ -224,6 +364,18 @@ mod tests {
       assert_eq!(actual, expected);
    }
 
+   #[test]
+   fn try_intersection() {
+      assert_eq!(Plane::XY.try_intersection(&Plane::YZ).unwrap(), Line::Y_AXIS);
+      assert_eq!(Plane::XY.try_intersection(&Line::Z_AXIS).unwrap(), Point::ORIGIN);
+
+      let error = Plane::XY.try_intersection(&Plane::XY).unwrap_err();
+      assert_eq!(error, GeometryError::ParallelPlanes);
+
+      let error = Line::X_AXIS.try_intersection(&Plane::XY).unwrap_err();
+      assert_eq!(error, GeometryError::ParallelLineAndPlane);
+   }
+
    #[test]
    #[should_panic]
    fn intersection_same_planes() {
@@ -272,4 +424,77 @@ mod tests {
 
       Plane::XY.intersection(&line);
    }
+
+   #[test]
+   fn side_of() {
+      let plane = Plane::new(
+         &Point::new(0.mm(), 0.mm(), 1.mm()),
+         &Vector::new(0.mm(), 0.mm(), 2.mm())
+      );
+
+      assert_eq!(plane.side_of(&Point::new(3.mm(), 4.mm(), 2.mm())), Ordering::Greater);
+      assert_eq!(plane.side_of(&Point::new(3.mm(), 4.mm(), 0.mm())), Ordering::Less);
+      assert_eq!(plane.side_of(&Point::new(3.mm(), 4.mm(), 1.mm())), Ordering::Equal);
+      assert_eq!(plane.signed_distance(&Point::new(3.mm(), 4.mm(), -2.mm())), -3.mm());
+   }
+
+   #[test]
+   fn basis() {
+      let (u, v) = Plane::XY.basis();
+      assert_eq!(u.vector_product(&v), Vector::Z_UNIT_VECTOR);
+
+      let normal = Vector::new(1.mm(), 2.mm(), 3.mm());
+      let (u, v) = Plane::new(&Point::ORIGIN, &normal).basis();
+      assert_eq!(u.norm(), 1.mm());
+      assert_eq!(v.norm(), 1.mm());
+      assert_eq!(Size::from(u.inner_product(&v) / 1.mm()), 0.mm());
+      assert_eq!(u.vector_product(&v), normal.to_unit_vector());
+   }
+
+   #[test]
+   fn segment_intersection() {
+      let plane = Plane::new(
+         &Point::new(0.mm(), 0.mm(), 1.mm()),
+         &Vector::Z_UNIT_VECTOR
+      );
+
+      let p = Point::new(0.mm(), 0.mm(), 0.mm());
+      let q = Point::new(4.mm(), 2.mm(), 4.mm());
+      assert_eq!(plane.segment_intersection(&p, &q), Point::new(1.mm(), 0.5.mm(), 1.mm()));
+      assert_eq!(plane.segment_intersection(&q, &p), Point::new(1.mm(), 0.5.mm(), 1.mm()));
+   }
+
+   #[test]
+   fn reflect() {
+      let plane = Plane::new(
+         &Point::new(0.mm(), 0.mm(), 1.mm()),
+         &Vector::new(0.mm(), 1.mm(), 1.mm())
+      );
+
+      let v = Vector::new(1.mm(), 2.mm(), 3.mm());
+      let reflected = plane.reflect_vector(&v);
+      assert_eq!(reflected, Vector::new(1.mm(), -3.mm(), -2.mm()));
+      assert_eq!(plane.reflect_vector(&reflected), v);
+      let n = plane.normal_vector();
+      assert_eq!(
+         Size::from(reflected.inner_product(n) / n.norm()),
+         -Size::from(v.inner_product(n) / n.norm())
+      );
+
+      let on_plane = Vector::new(3.mm(), 1.mm(), -1.mm());
+      assert_eq!(plane.reflect_vector(&on_plane), on_plane);
+
+      let p = Point::new(1.mm(), 2.mm(), 3.mm());
+      assert_eq!(plane.reflect_point(&p), Point::new(1.mm(), -2.mm(), -1.mm()));
+      assert_eq!(p.reflect_across_plane(&plane), plane.reflect_point(&p));
+
+      let line = Line::new(&p, &v).reflect_across_plane(&plane);
+      assert_eq!(
+         line,
+         Line::new(
+            &Point::new(1.mm(), -2.mm(), -1.mm()),
+            &Vector::new(1.mm(), -3.mm(), -2.mm())
+         )
+      );
+   }
 }
@@ -1,10 +1,16 @@
-use crate::geometry::{Angle, Line, Size, Vector};
+use crate::geometry::{Angle, Line, Plane, Size, Vector};
 use crate::math::Matrix;
-use crate::transform::Transform;
+use crate::math::rough_fp::rough_eq;
+use crate::transform::{InvertThrough, Transform};
+use noisy_float::prelude::*;
 use std::fmt::{self, Debug, Display, Formatter};
+use std::iter::Sum;
 
 /// 3D Point.
-#[derive(Clone, Copy, PartialEq)]
+///
+/// Hashed by the coordinates, which are [Size]s. So like [Size], lookups in a
+/// `HashMap` or a `HashSet` keyed by points are best-effort.
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
 pub struct Point {
    pub matrix: Matrix<Size, 3, 1>
 }
@@ -33,9 +39,56 @@ impl Point {
       self.matrix.0[2][0]
    }
 
+   pub fn abs(self) -> Point {
+      Point {
+         matrix: self.matrix.map(|s| s.abs())
+      }
+   }
+
+   pub fn clamp(self, min: Size, max: Size) -> Point {
+      Point {
+         matrix: self.matrix.map(|s| s.clamp(min, max))
+      }
+   }
+
    pub fn distance(&self, another: &Point) -> Size {
       Vector::between(self, another).norm()
    }
+
+   /// Whether the distance to `other` is at most `epsilon`.
+   /// `==` allows only float-point arithmetic errors.
+   pub fn approx_eq(&self, other: &Point, epsilon: Size) -> bool {
+      self.distance(other) <= epsilon
+   }
+
+   /// Reflects this point across the plane.
+   /// Same as [Plane::reflect_point].
+   pub fn reflect_across_plane(&self, plane: &Plane) -> Point {
+      plane.reflect_point(self)
+   }
+
+   /// Whether the 3 points are on a line.
+   pub fn are_collinear(a: &Point, b: &Point, c: &Point) -> bool {
+      Point::collinearity_ratio(a, b, c) == Size::ZERO
+   }
+
+   /// How far the 3 points are from being on a line. This is the norm of
+   /// the vector product of AB and AC, that is twice the area of
+   /// the triangle ABC.
+   pub fn collinearity_ratio(a: &Point, b: &Point, c: &Point) -> Size {
+      Vector::between(a, b)
+         .vector_product(&Vector::between(a, c))
+         .norm()
+   }
+
+   /// Whether the 4 points are on a plane.
+   pub fn are_coplanar(a: &Point, b: &Point, c: &Point, d: &Point) -> bool {
+      let triple_product = Vector::between(a, b)
+         .vector_product(&Vector::between(a, c))
+         .inner_product(&Vector::between(a, d));
+
+      rough_eq(triple_product.0, n64(0.0))
+   }
 }
 
 impl Display for Point {
@@ -71,8 +124,103 @@ impl Transform for Point {
    }
 }
 
+impl InvertThrough for Point {
+   fn inverted_through(&self, center: &Point) -> Point {
+      Point {
+         matrix: center.matrix * 2 - self.matrix
+      }
+   }
+}
+
 impl Default for Point {
    fn default() -> Point {
       Point::ORIGIN
    }
 }
+
+/// Sum of position vectors from origin. Not meaningful as a point by itself,
+/// but useful to compute centroids.
+impl Sum for Point {
+   fn sum<I>(iter: I) -> Point where I: Iterator<Item = Point> {
+      let mut sum = Point::ORIGIN;
+      for p in iter {
+         sum.matrix += p.matrix;
+      }
+      sum
+   }
+}
+
+impl<'a> Sum<&'a Point> for Point {
+   fn sum<I>(iter: I) -> Point where I: Iterator<Item = &'a Point> {
+      iter.copied().sum()
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::Point;
+   use crate::geometry::SizeLiteral;
+   use std::collections::HashSet;
+
+   #[test]
+   fn approx_eq() {
+      let a = Point::new(1.mm(), 2.mm(), 3.mm());
+      let b = Point::new(1.00001.mm(), 2.mm(), 2.99999.mm());
+      assert_ne!(a, b);
+      assert!(a.approx_eq(&b, 0.001.mm()));
+      assert!(!a.approx_eq(&b, 0.00001.mm()));
+   }
+
+   #[test]
+   fn are_collinear() {
+      let a = Point::new(0.mm(), 0.mm(), 0.mm());
+      let b = Point::new(1.mm(), 2.mm(), 3.mm());
+      let c = Point::new(3.mm(), 6.mm(), 9.mm());
+      assert!(Point::are_collinear(&a, &b, &c));
+      assert!(Point::are_collinear(&a, &a, &c));
+
+      let b = Point::new(2.mm(), 0.mm(), 0.mm());
+      let c = Point::new(1.mm(), 3.0_f64.sqrt().mm(), 0.mm());
+      assert!(!Point::are_collinear(&a, &b, &c));
+      assert_eq!(Point::collinearity_ratio(&a, &b, &c), (2.0 * 3.0_f64.sqrt()).mm());
+
+      let c = Point::new(1.mm(), 0.mm(), 1e-12.mm());
+      assert!(Point::are_collinear(&a, &b, &c));
+      let c = Point::new(1.mm(), 0.mm(), 1e-6.mm());
+      assert!(!Point::are_collinear(&a, &b, &c));
+   }
+
+   #[test]
+   fn hash() {
+      let mut points = HashSet::new();
+      points.insert(Point::new(0.1.mm() * 3, 1.mm(), 2.mm()));
+      points.insert(Point::new(0.3.mm(), 1.mm(), 2.mm()));
+      points.insert(Point::new(0.3.mm(), 1.mm(), 3.mm()));
+      assert_eq!(points.len(), 2);
+   }
+
+   #[test]
+   fn sum() {
+      let points = [
+         Point::new(1.mm(), 2.mm(), 3.mm()),
+         Point::new(4.mm(), 5.mm(), 6.mm()),
+         Point::new(7.mm(), 8.mm(), 9.mm())
+      ];
+
+      assert_eq!(points.iter().sum::<Point>(), Point::new(12.mm(), 15.mm(), 18.mm()));
+      assert_eq!(points.into_iter().sum::<Point>(), Point::new(12.mm(), 15.mm(), 18.mm()));
+      assert_eq!(Vec::<Point>::new().into_iter().sum::<Point>(), Point::ORIGIN);
+   }
+
+   #[test]
+   fn are_coplanar() {
+      let a = Point::new(0.mm(), 0.mm(), 1.mm());
+      let b = Point::new(1.mm(), 0.mm(), 1.mm());
+      let c = Point::new(0.mm(), 1.mm(), 1.mm());
+      let d = Point::new(3.mm(), 5.mm(), 1.mm());
+      assert!(Point::are_coplanar(&a, &b, &c, &d));
+
+      let d = Point::new(3.mm(), 5.mm(), 1.001.mm());
+      assert!(!Point::are_coplanar(&a, &b, &c, &d));
+   }
+}
@@ -0,0 +1,58 @@
+use crate::geometry::Size;
+use crate::math::unit::Exp;
+
+/// Polygon on a plane, as its vertexes in order.
+///
+/// The last vertex is connected to the first one.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Polygon2D {
+   pub vertexes: Vec<(Size, Size)>
+}
+
+impl Polygon2D {
+   pub fn new(vertexes: Vec<(Size, Size)>) -> Polygon2D {
+      Polygon2D { vertexes }
+   }
+
+   /// Area of this polygon. Positive if the vertexes are counterclockwise,
+   /// and negative if clockwise.
+   pub fn signed_area(&self) -> Exp<Size, 2> {
+      let twice: Exp<Size, 2> = self.edges()
+         .map(|((ax, ay), (bx, by))| ax * by - bx * ay)
+         .sum();
+
+      twice * 0.5
+   }
+
+   /// Total length of the edges.
+   pub fn perimeter(&self) -> Size {
+      self.edges()
+         .map(|((ax, ay), (bx, by))| ((ax - bx).sq() + (ay - by).sq()).sqrt())
+         .sum()
+   }
+
+   fn edges(&self) -> impl Iterator<Item = ((Size, Size), (Size, Size))> + '_ {
+      self.vertexes.iter().copied()
+         .zip(self.vertexes.iter().copied().cycle().skip(1))
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::Polygon2D;
+   use crate::geometry::SizeLiteral;
+
+   #[test]
+   fn area_and_perimeter() {
+      let mut polygon = Polygon2D::new(vec![
+         (0.mm(), 0.mm()), (4.mm(), 0.mm()), (4.mm(), 3.mm())
+      ]);
+
+      assert_eq!(polygon.signed_area(), 6.mm() * 1.mm());
+      assert_eq!(polygon.perimeter(), 12.mm());
+
+      polygon.vertexes.reverse();
+      assert_eq!(polygon.signed_area(), -6.mm() * 1.mm());
+      assert_eq!(Polygon2D::default().perimeter(), 0.mm());
+   }
+}
@@ -2,14 +2,16 @@ use crate::geometry::size_iterator::{
    SizeIteratorBuilder, SizeParallelIteratorBuilder
 };
 use crate::math::conversion::ToN64;
-use crate::math::rough_fp::{rough_cmp, rough_eq};
+use crate::math::rough_fp::{rough_cmp, rough_eq, rough_hash};
 use crate::math::unit::{Exp, Unit};
 use noisy_float::prelude::*;
 use std::cmp::Ordering;
 use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
 use std::iter::Sum;
 use std::ops::{
-   Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign
+   Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub,
+   SubAssign
 };
 
 /// Size of something.
@@ -56,6 +58,16 @@ impl Size {
       self.0
    }
 
+   /// Converts this size to a plain f64 value as millimeter
+   pub fn as_mm_f64(self) -> f64 {
+      self.0.raw()
+   }
+
+   /// Converts this size to a plain f64 value as centimeter
+   pub fn as_cm_f64(self) -> f64 {
+      self.0.raw() / 10.0
+   }
+
    pub fn is_infinity(self) -> bool {
       self.0.is_infinite()
    }
@@ -94,6 +106,50 @@ impl Size {
    pub fn clamp(self, min: Size, max: Size) -> Size {
       Size(self.0.clamp(min.0, max.0))
    }
+
+   /// Calculates the least nonnegative remainder of `self % rhs`.
+   ///
+   /// ```
+   /// # use typed_scad::geometry::SizeLiteral;
+   /// assert_eq!((-1.mm()).rem_euclid(3.mm()), 2.mm());
+   /// ```
+   pub fn rem_euclid(self, rhs: Size) -> Size {
+      Size(n64(self.0.raw().rem_euclid(rhs.0.raw())))
+   }
+
+   /// `self / rhs`, or `None` if `rhs` is zero.
+   ///
+   /// ```
+   /// # use typed_scad::geometry::SizeLiteral;
+   /// # use noisy_float::prelude::*;
+   /// assert_eq!(3.mm().checked_div(2.mm()), Some(n64(1.5)));
+   /// assert_eq!(3.mm().checked_div(0.mm()), None);
+   /// ```
+   pub fn checked_div(self, rhs: Size) -> Option<N64> {
+      if rhs == Size::ZERO {
+         None
+      } else {
+         Some(self / rhs)
+      }
+   }
+
+   /// `self * self`.
+   pub fn sq(self) -> Exp<Size, 2> {
+      self * self
+   }
+
+   /// `self * self * self`.
+   ///
+   /// ```
+   /// # use typed_scad::geometry::SizeLiteral;
+   /// # use typed_scad::math::unit::Exp;
+   /// # use noisy_float::prelude::*;
+   /// let volume: Exp<_, 3> = 3.mm().cb();
+   /// assert_eq!(volume.0, 27.0);
+   /// ```
+   pub fn cb(self) -> Exp<Size, 3> {
+      self * self * self
+   }
 }
 
 impl<T: ToN64> From<T> for Size {
@@ -134,6 +190,16 @@ impl PartialEq for Size {
 
 impl Eq for Size {}
 
+/// Rounds the value to the grid of the error which [Size::eq] allows, so that
+/// roughly equal sizes almost always have the same hash. Not always: 2 sizes
+/// around the middle of a grid cell can be equal but round differently. So
+/// lookups in a `HashMap` or a `HashSet` keyed by sizes are best-effort.
+impl Hash for Size {
+   fn hash<H: Hasher>(&self, state: &mut H) {
+      rough_hash(self.0, state);
+   }
+}
+
 impl Add for Size {
    type Output = Size;
    fn add(self, rhs: Size) -> Size {
@@ -214,6 +280,19 @@ impl Div for Size {
    }
 }
 
+impl Rem for Size {
+   type Output = Size;
+   fn rem(self, rhs: Size) -> Size {
+      Size(self.0 % rhs.0)
+   }
+}
+
+impl RemAssign for Size {
+   fn rem_assign(&mut self, rhs: Size) {
+      *self = *self % rhs;
+   }
+}
+
 impl Neg for Size {
    type Output = Size;
    fn neg(self) -> Size {
@@ -229,6 +308,29 @@ impl Exp<Size, 2> {
    }
 }
 
+impl<const N: i32> Exp<Size, N>
+   where Exp<Size, {N / 2}>: Sized,
+         [(); 0 - (N % 2) as usize]: Sized
+{
+   /// Square root, for even `N`. Odd `N` fails to compile.
+   ///
+   /// ```
+   /// # use typed_scad::geometry::{Size, SizeLiteral};
+   /// # use typed_scad::math::unit::Exp;
+   /// # use typed_scad::size_pow;
+   /// let area: Exp<Size, 2> = size_pow!(3.mm(), 4).sqrt_if_even();
+   /// assert_eq!(area, 3.mm().sq());
+   /// ```
+   ///
+   /// ```compile_fail
+   /// # use typed_scad::geometry::SizeLiteral;
+   /// 3.mm().cb().sqrt_if_even();
+   /// ```
+   pub fn sqrt_if_even(self) -> Exp<Size, {N / 2}> {
+      unsafe { Exp::new(self.0.sqrt()) }
+   }
+}
+
 impl Mul<Size> for Size {
    type Output = Exp<Size, 2>;
    fn mul(self, rhs: Size) -> Exp<Size, 2> {
@@ -254,6 +356,13 @@ impl<const N: i32> Div<Size> for Exp<Size, N>
    }
 }
 
+/// As millimeter. Also provides `TryFrom<Size>` for f64.
+impl From<Size> for f64 {
+   fn from(size: Size) -> f64 {
+      size.as_mm_f64()
+   }
+}
+
 impl From<Exp<Size, 0>> for N64 {
    fn from(exp: Exp<Size, 0>) -> N64 {
       exp.0
@@ -311,6 +420,22 @@ mod tests {
    use super::{Size, SizeLiteral};
    use noisy_float::prelude::*;
    use std::cmp::Ordering;
+   use std::collections::HashMap;
+   use std::collections::hash_map::DefaultHasher;
+   use std::hash::{Hash, Hasher};
+
+   #[test]
+   fn pow() {
+      assert_eq!(3.mm().sq().0, 9.0);
+      assert_eq!(3.mm().cb().0, 27.0);
+      assert_eq!(9.mm().sq().sqrt(), 9.mm());
+      assert_eq!(9.mm().sq().sqrt_if_even().0, 9.0);
+      assert_eq!(crate::size_pow!(9.mm(), 4).sqrt_if_even(), 9.mm().sq());
+
+      let s = 2.mm();
+      assert_eq!(crate::size_pow!(s, 4), s.sq() * s.sq());
+      assert_eq!(crate::size_pow!(s + s, 3), 4.mm().cb());
+   }
 
    #[test]
    fn eq() {
@@ -323,6 +448,23 @@ mod tests {
       assert_eq!(Size::from(42.0), Size::from(42.0 - 1e-12));
    }
 
+   #[test]
+   fn hash() {
+      let hash = |s: Size| {
+         let mut hasher = DefaultHasher::new();
+         s.hash(&mut hasher);
+         hasher.finish()
+      };
+
+      assert_eq!(hash(Size::from(42.0)), hash(Size::from(42.0 + 1e-12)));
+      assert_eq!(hash(Size::from(0.0)), hash(Size::from(-1e-12)));
+      assert_ne!(hash(Size::from(42.0)), hash(Size::from(43.0)));
+
+      let mut map = HashMap::new();
+      map.insert(0.1.mm() * 3, "a");
+      assert_eq!(map.get(&0.3.mm()), Some(&"a"));
+   }
+
    #[test]
    fn display() {
       assert_eq!(
@@ -344,6 +486,17 @@ mod tests {
       assert_eq!(Size::from(42.0).to_millimeter(), n64(42.0));
    }
 
+   #[test]
+   fn as_f64() {
+      assert_eq!(42.mm().as_mm_f64(), 42.0);
+      assert_eq!(42.mm().as_cm_f64(), 4.2);
+      assert_eq!(Size::from(1.5.cm().as_mm_f64()), 1.5.cm());
+      assert_eq!(1.5.cm().as_cm_f64().cm(), 1.5.cm());
+
+      assert_eq!(f64::from(42.mm()), 42.0);
+      assert_eq!(f64::from(-42.mm()), -42.0);
+   }
+
    #[test]
    fn operators() {
       assert_eq!(Size::from( 42.0) + Size::from( 1.5), Size::from(43.5));
@@ -388,6 +541,11 @@ mod tests {
       assert_eq!(Size::from(-42.0) / Size::from( 1.5), n64(-28.0));
       assert_eq!(Size::from(-42.0) / Size::from(-1.5), n64( 28.0));
 
+      assert_eq!(Size::from( 42.0) % Size::from( 5.0), Size::from( 2.0));
+      assert_eq!(Size::from( 42.0) % Size::from(-5.0), Size::from( 2.0));
+      assert_eq!(Size::from(-42.0) % Size::from( 5.0), Size::from(-2.0));
+      assert_eq!(Size::from(-42.0) % Size::from(-5.0), Size::from(-2.0));
+
       assert_eq!(-Size::from(42.0), Size::from(-42.0));
 
       assert!(Size::from(42.0) > Size::from(41.0));
@@ -419,6 +577,24 @@ mod tests {
       );
    }
 
+   #[test]
+   fn rem() {
+      assert_eq!(5.mm() % 3.mm(), 2.mm());
+      assert_eq!((-1.mm()) % 3.mm(), -1.mm());
+      assert_eq!(0.3.mm() % 0.1.mm(), 0.1.mm());
+
+      let mut size = 5.mm();
+      size %= 3.mm();
+      assert_eq!(size, 2.mm());
+   }
+
+   #[test]
+   fn rem_euclid() {
+      assert_eq!(5.mm().rem_euclid(3.mm()), 2.mm());
+      assert_eq!((-1.mm()).rem_euclid(3.mm()), 2.mm());
+      assert_eq!((-1.mm()).rem_euclid(-3.mm()), 2.mm());
+   }
+
    #[test]
    fn sum() {
       let sum: Size = (1..=10)
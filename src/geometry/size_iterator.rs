@@ -1,5 +1,5 @@
-use crate::geometry::{Size, SizeLiteral};
-use crate::math::rough_fp::FLOAT_POINT_ALLOWABLE_ERROR;
+use crate::geometry::Size;
+use crate::math::rough_fp::step_count;
 use std::ops::{Range, RangeFrom, RangeInclusive};
 use rayon::iter::plumbing::{
    bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer
@@ -10,21 +10,7 @@ pub struct SizeIteratorBuilder<R>(pub R);
 pub struct SizeParallelIteratorBuilder<R>(pub R);
 
 fn size_count(start: Size, end: Size, step: Size) -> usize {
-   if start < end {
-      if step < 0.mm() {
-         0
-      } else {
-         ((end.0 - FLOAT_POINT_ALLOWABLE_ERROR - start.0) / step.0)
-            .raw() as usize + 1
-      }
-   } else {
-      if step > 0.mm() {
-         0
-      } else {
-         ((end.0 + FLOAT_POINT_ALLOWABLE_ERROR - start.0) / step.0)
-            .raw() as usize + 1
-      }
-   }
+   step_count(end.0 - start.0, step.0, false)
 }
 
 impl SizeIteratorBuilder<Range<Size>> {
@@ -46,21 +32,7 @@ impl SizeParallelIteratorBuilder<Range<Size>> {
 }
 
 fn size_count_inclusive(start: Size, end: Size, step: Size) -> usize {
-   if start < end {
-      if step < 0.mm() {
-         0
-      } else {
-         ((end.0 + FLOAT_POINT_ALLOWABLE_ERROR - start.0) / step.0)
-            .raw() as usize + 1
-      }
-   } else {
-      if step > 0.mm() {
-         0
-      } else {
-         ((end.0 - FLOAT_POINT_ALLOWABLE_ERROR - start.0) / step.0)
-            .raw() as usize + 1
-      }
-   }
+   step_count(end.0 - start.0, step.0, true)
 }
 
 impl SizeIteratorBuilder<RangeInclusive<Size>> {
@@ -89,14 +61,15 @@ impl SizeIteratorBuilder<RangeFrom<Size>> {
 }
 
 /// An [Iterator] for [Size].
+///
+/// Each item is computed as `start + step * index`, rather than accumulating
+/// `step`, so that the items are the same however the iterator is split.
 #[derive(Clone)]
 pub struct SizeIterator {
-   next_left: Size,
-   next_left_index: isize,
-   next_right: Size,
-   next_right_index: isize,
+   start: Size,
    step: Size,
-   len: usize
+   next_left_index: isize,
+   next_right_index: isize
 }
 
 #[derive(Clone)]
@@ -115,13 +88,21 @@ pub struct SizeIteratorInfinite {
 
 impl SizeIterator {
    fn new(start: Size, step: Size, len: usize) -> SizeIterator {
+      SizeIterator::with_offset(start, step, 0, len)
+   }
+
+   /// Iterates `start + step * i` for `i` in `offset..(offset + len)`.
+   fn with_offset(
+      start: Size,
+      step: Size,
+      offset: usize,
+      len: usize
+   ) -> SizeIterator {
       SizeIterator {
-         next_left: start,
-         next_left_index: 0,
-         next_right: start + step * (len as isize - 1),
-         next_right_index: len as isize - 1,
+         start,
          step,
-         len
+         next_left_index: offset as isize,
+         next_right_index: (offset + len) as isize - 1
       }
    }
 }
@@ -141,14 +122,13 @@ impl Iterator for SizeIterator {
    fn next(&mut self) -> Option<Size> {
       if self.next_left_index > self.next_right_index { return None; }
 
-      let next = self.next_left;
+      let next = self.start + self.step * self.next_left_index;
       self.next_left_index += 1;
-      self.next_left += self.step;
       Some(next)
    }
 
    fn size_hint(&self) -> (usize, Option<usize>) {
-      let remain_size = self.len - self.next_left_index as usize;
+      let remain_size = (self.next_right_index - self.next_left_index + 1) as usize;
       (remain_size, Some(remain_size))
    }
 }
@@ -173,9 +153,8 @@ impl DoubleEndedIterator for SizeIterator {
    fn next_back(&mut self) -> Option<Self::Item> {
       if self.next_right_index < self.next_left_index { return None; }
 
-      let next = self.next_right;
+      let next = self.start + self.step * self.next_right_index;
       self.next_right_index -= 1;
-      self.next_right -= self.step;
       Some(next)
    }
 }
@@ -208,9 +187,11 @@ impl IndexedParallelIterator for SizeParallelIterator {
    fn with_producer<CB>(self, callback: CB) -> CB::Output
       where CB: ProducerCallback<Self::Item>
    {
+      /// Produces `start + step * i` for `i` in `offset..(offset + len)`.
       struct SizeIterProducer {
          start: Size,
          step: Size,
+         offset: usize,
          len: usize
       }
 
@@ -219,18 +200,20 @@ impl IndexedParallelIterator for SizeParallelIterator {
          type IntoIter = SizeIterator;
 
          fn into_iter(self) -> Self::IntoIter {
-            SizeIterator::new(self.start, self.step, self.len)
+            SizeIterator::with_offset(self.start, self.step, self.offset, self.len)
          }
 
          fn split_at(self, index: usize) -> (Self, Self) {
             let left = SizeIterProducer {
                start: self.start,
                step: self.step,
+               offset: self.offset,
                len: index
             };
             let right = SizeIterProducer {
-               start: self.start + self.step * index,
+               start: self.start,
                step: self.step,
+               offset: self.offset + index,
                len: self.len - index
             };
             (left, right)
@@ -240,6 +223,7 @@ impl IndexedParallelIterator for SizeParallelIterator {
       callback.callback(SizeIterProducer {
          start: self.start,
          step: self.step,
+         offset: 0,
          len: self.len
       })
    }
@@ -337,6 +321,28 @@ mod tests {
       assert_eq!(iter.size_hint(), (usize::MAX, None));
    }
 
+   #[test]
+   fn step_boundary() {
+      let count = Size::iterate(0.mm()..=10.mm()).step(2.5.mm()).count();
+      assert_eq!(count, 5);
+      let count = Size::iterate(0.mm()..10.mm()).step(2.5.mm()).count();
+      assert_eq!(count, 4);
+      let count = Size::iterate(10.mm()..=0.mm()).step(-2.5.mm()).count();
+      assert_eq!(count, 5);
+      let count = Size::iterate(10.mm()..0.mm()).step(-2.5.mm()).count();
+      assert_eq!(count, 4);
+
+      for step in [1.mm(), -1.mm()] {
+         let count = Size::iterate(3.mm()..=3.mm()).step(step).count();
+         assert_eq!(count, 1);
+         let count = Size::iterate(3.mm()..3.mm()).step(step).count();
+         assert_eq!(count, 0);
+      }
+
+      let count = Size::par_iterate(0.mm()..=10.mm()).step(2.5.mm()).count();
+      assert_eq!(count, 5);
+   }
+
    #[test]
    fn double_ended_iter() {
       let mut iter = Size::iterate(42.mm()..=45.mm()).step(1.5.mm());
@@ -396,4 +402,18 @@ mod tests {
             assert_eq!(actual, expected.mm(), "{i}");
          });
    }
+
+   #[test]
+   fn parallel_iter_same_as_sequential() {
+      let sequential: Vec<_> = Size::iterate(0.mm()..100.mm()).step(0.001.mm())
+         .collect();
+      let parallel: Vec<_> = Size::par_iterate(0.mm()..100.mm()).step(0.001.mm())
+         .collect();
+
+      assert_eq!(parallel.len(), 100_000);
+      assert_eq!(
+         parallel.iter().map(|a| a.0).collect::<Vec<_>>(),
+         sequential.iter().map(|a| a.0).collect::<Vec<_>>()
+      );
+   }
 }
@@ -1,16 +1,22 @@
-use crate::geometry::{Angle, Size, SizeLiteral, Point, sin, acos, cos};
+use crate::geometry::{
+   Angle, GeometryError, Line, Size, SizeLiteral, Point, sin, acos, cos
+};
 use crate::math::Matrix;
 use crate::math::conversion::ToN64;
 use crate::math::unit::Exp;
+use crate::transform::{InvertThrough, Transform};
 use noisy_float::prelude::*;
 use std::fmt::{self, Debug, Display, Formatter};
 use std::iter::Sum;
 use std::ops::{
-   Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign
+   Add, AddAssign, BitXor, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign
 };
 
 /// 3D Vector.
-#[derive(Clone, Copy, PartialEq)]
+///
+/// Hashed by the components, which are [Size]s. So like [Size], lookups in a
+/// `HashMap` or a `HashSet` keyed by vectors are best-effort.
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
 pub struct Vector {
    pub matrix: Matrix<Size, 3, 1>
 }
@@ -64,16 +70,61 @@ impl Vector {
       (self.x() * self.x() + self.y() * self.y() + self.z() * self.z()).sqrt()
    }
 
+   /// Whether the norm of the difference from `other` is at most `epsilon`.
+   /// `==` allows only float-point arithmetic errors.
+   pub fn approx_eq(&self, other: &Vector, epsilon: Size) -> bool {
+      (*self - *other).norm() <= epsilon
+   }
+
+   pub fn abs(self) -> Vector {
+      Vector {
+         matrix: self.matrix.map(|s| s.abs())
+      }
+   }
+
+   /// returns a vector which has the same direction as this vector,
+   /// and whose norm is clamped to `min..=max`.
+   /// A zero vector has no direction, so it is returned as it is.
+   pub fn clamp_norm(self, min: Size, max: Size) -> Vector {
+      let norm = self.norm();
+      let clamped = norm.clamp(min, max);
+      if clamped == norm || norm == Size::ZERO {
+         self
+      } else {
+         self * (clamped / norm)
+      }
+   }
+
+   pub fn clamp_components(self, min: Size, max: Size) -> Vector {
+      Vector {
+         matrix: self.matrix.map(|s| s.clamp(min, max))
+      }
+   }
+
+   pub fn min_component(self) -> Size {
+      self.x().min(self.y()).min(self.z())
+   }
+
+   pub fn max_component(self) -> Size {
+      self.x().max(self.y()).max(self.z())
+   }
+
+   /// Panics if this vector is zero.
+   /// See [try_to_unit_vector](Vector::try_to_unit_vector).
    pub fn to_unit_vector(&self) -> Vector {
+      self.try_to_unit_vector().unwrap_or_else(|e| panic!("{}", e))
+   }
+
+   /// Returns [GeometryError::ZeroVector] if this vector is zero.
+   pub fn try_to_unit_vector(&self) -> Result<Vector, GeometryError> {
       let norm = self.norm();
       if norm == 0.mm() {
-         panic!("cannot convert to a unit vector \
-                 since this vector does not point any direction.");
+         return Err(GeometryError::ZeroVector);
       }
 
-      Vector {
+      Ok(Vector {
          matrix: self.matrix / norm.0
-      }
+      })
    }
 
    pub fn vector_product(&self, other: &Vector) -> Vector {
@@ -96,6 +147,13 @@ impl Vector {
       )
    }
 
+   /// The unit vector which bisects the angle between this and `other`.
+   ///
+   /// Panics if either vector is zero, or they point the opposite directions.
+   pub fn bisect(&self, other: &Vector) -> Vector {
+      (self.to_unit_vector() + other.to_unit_vector()).to_unit_vector()
+   }
+
    pub fn rotate(&mut self, axis: &Vector, angle: Angle) {
       *self = self.rotated(axis, angle);
    }
@@ -119,6 +177,38 @@ impl Vector {
    }
 }
 
+/// Vectors represent directions and distances, not positions.
+/// So [translated](Transform::translated) returns the same vector,
+/// and [rotated](Transform::rotated) ignores the point of the axis and only
+/// uses its direction.
+/// ```
+/// # use typed_scad::geometry::{AngleLiteral, Line, Point, SizeLiteral, Vector};
+/// # use typed_scad::transform::Transform;
+/// let v = Vector::X_UNIT_VECTOR;
+/// assert_eq!(Transform::translated(&v, &Vector::Y_UNIT_VECTOR), v);
+///
+/// let axis = Line::new(&Point::new(5.mm(), 5.mm(), 0.mm()), &Vector::Z_UNIT_VECTOR);
+/// assert_eq!(Transform::rotated(&v, &axis, 90.deg()), Vector::Y_UNIT_VECTOR);
+/// ```
+impl Transform for Vector {
+   fn translated(&self, _offset: &Vector) -> Vector {
+      *self
+   }
+
+   fn translate(&mut self, _offset: &Vector) {}
+
+   fn rotated(&self, axis: &Line, angle: Angle) -> Vector {
+      Vector::rotated(self, axis.vector(), angle)
+   }
+}
+
+/// Negates the vector. `center` does not affect vectors.
+impl InvertThrough for Vector {
+   fn inverted_through(&self, _center: &Point) -> Vector {
+      -*self
+   }
+}
+
 impl Display for Vector {
    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
       write!(f, "({}, {}, {})", self.x(), self.y(), self.z())
@@ -212,6 +302,40 @@ macro_rules! div {
 div!(usize, u8, u16, u32, u64, u128, isize, i8, i16, i32, i64, i128, f32, f64,
    N32, N64, R32, R64);
 
+/// Inner product. Same as [Vector::inner_product].
+/// ```
+/// # use noisy_float::prelude::*;
+/// # use typed_scad::geometry::{SizeLiteral, Vector};
+/// # use typed_scad::math::unit::Exp;
+/// let a = Vector::new(1.mm(), 2.mm(), 3.mm());
+/// let b = Vector::new(4.mm(), 5.mm(), 6.mm());
+/// assert_eq!(a * b, unsafe { Exp::new(n64(32.0)) });
+/// ```
+impl Mul for Vector {
+   type Output = Exp<Size, 2>;
+   fn mul(self, rhs: Vector) -> Exp<Size, 2> {
+      self.inner_product(&rhs)
+   }
+}
+
+/// Vector product. Same as [Vector::vector_product].
+///
+/// Note that `^` has lower precedence than `+` and `-`, unlike `*`.
+/// `a + b ^ c` means `(a + b) ^ c`.
+/// ```
+/// # use typed_scad::geometry::Vector;
+/// assert_eq!(
+///    Vector::X_UNIT_VECTOR ^ Vector::Y_UNIT_VECTOR,
+///    Vector::Z_UNIT_VECTOR
+/// );
+/// ```
+impl BitXor for Vector {
+   type Output = Vector;
+   fn bitxor(self, rhs: Vector) -> Vector {
+      self.vector_product(&rhs)
+   }
+}
+
 impl Neg for Vector {
    type Output = Vector;
    fn neg(self) -> Self::Output {
@@ -231,9 +355,18 @@ impl Sum for Vector {
    }
 }
 
+impl<'a> Sum<&'a Vector> for Vector {
+   fn sum<I>(iter: I) -> Vector where I: Iterator<Item = &'a Vector> {
+      iter.copied().sum()
+   }
+}
+
 #[cfg(test)]
 mod tests {
-   use crate::geometry::{AngleLiteral, Point, SizeLiteral};
+   use crate::geometry::{AngleLiteral, GeometryError, Line, Point, Size, SizeLiteral};
+   use crate::math::unit::Exp;
+   use crate::transform::{InvertThrough, Transform};
+   use noisy_float::prelude::*;
    use super::Vector;
 
    fn vector(x: f64, y: f64, z: f64) -> Vector {
@@ -255,6 +388,15 @@ mod tests {
       assert_eq!(actual, Vector::new(-2.mm(), -3.mm(), -4.mm()));
    }
 
+   #[test]
+   fn approx_eq() {
+      let a = vector(1.0, 2.0, 3.0);
+      let b = vector(1.0, 2.00001, 3.0);
+      assert_ne!(a, b);
+      assert!(a.approx_eq(&b, 0.001.mm()));
+      assert!(!a.approx_eq(&b, 0.000001.mm()));
+   }
+
    #[test]
    fn norm() {
       assert_eq!(Vector::new(0.mm(), 3.mm(), 4.mm()).norm(), 5.mm());
@@ -279,6 +421,17 @@ mod tests {
       Vector::new(0.mm(), 0.mm(), 0.mm()).to_unit_vector();
    }
 
+   #[test]
+   fn try_to_unit_vector() {
+      assert_eq!(
+         Vector::new(0.mm(), 3.mm(), 0.mm()).try_to_unit_vector().unwrap(),
+         Vector::Y_UNIT_VECTOR
+      );
+
+      let error = Vector::new(0.mm(), 0.mm(), 0.mm()).try_to_unit_vector().unwrap_err();
+      assert_eq!(error, GeometryError::ZeroVector);
+   }
+
    #[test]
    fn operators() {
       assert_eq!(vector( 1.0,  2.0,  3.0) + vector( 1.5,  1.5,  1.5), vector( 2.5,  3.5,  4.5));
@@ -312,6 +465,39 @@ mod tests {
       assert_eq!(-vector(1.0, 2.0, 3.0), vector(-1.0, -2.0, -3.0));
    }
 
+   #[test]
+   fn abs() {
+      assert_eq!(vector(-1.0, 2.0, -3.0).abs(), vector(1.0, 2.0, 3.0));
+   }
+
+   #[test]
+   fn clamp_norm() {
+      let v = vector(0.0, 3.0, 4.0);
+      assert_eq!(v.clamp_norm(1.mm(), 10.mm()), v);
+      assert_eq!(v.clamp_norm(10.mm(), 20.mm()), vector(0.0, 6.0, 8.0));
+      assert_eq!(v.clamp_norm(1.mm(), 2.5.mm()), vector(0.0, 1.5, 2.0));
+      assert_eq!(
+         v.clamp_norm(10.mm(), 20.mm()).to_unit_vector(),
+         v.to_unit_vector()
+      );
+      assert_eq!(Vector::ZERO.clamp_norm(1.mm(), 10.mm()), Vector::ZERO);
+   }
+
+   #[test]
+   fn clamp_components() {
+      assert_eq!(
+         vector(-5.0, 2.0, 7.0).clamp_components(-1.mm(), 3.mm()),
+         vector(-1.0, 2.0, 3.0)
+      );
+   }
+
+   #[test]
+   fn min_max_component() {
+      let v = vector(-1.0, 2.0, -3.0);
+      assert_eq!(v.min_component(), -3.mm());
+      assert_eq!(v.max_component(), 2.mm());
+   }
+
    #[test]
    fn vector_product() {
       assert_eq!(
@@ -345,6 +531,27 @@ mod tests {
       );
    }
 
+   #[test]
+   fn product_operators() {
+      let zero: Exp<Size, 2> = unsafe { Exp::new(n64(0.0)) };
+      let one: Exp<Size, 2> = unsafe { Exp::new(n64(1.0)) };
+      assert_eq!(Vector::X_UNIT_VECTOR * Vector::Y_UNIT_VECTOR, zero);
+      assert_eq!(Vector::X_UNIT_VECTOR * Vector::X_UNIT_VECTOR, one);
+
+      let a = vector(1.0, -2.0, 3.0);
+      let b = vector(4.0, 5.0, -6.0);
+      assert_eq!(a * b, a.inner_product(&b));
+      assert_eq!(a ^ b, a.vector_product(&b));
+   }
+
+   #[test]
+   fn bisect() {
+      let diagonal = vector(1.0, 1.0, 0.0) / 2.0_f64.sqrt();
+      assert_eq!(Vector::X_UNIT_VECTOR.bisect(&Vector::Y_UNIT_VECTOR), diagonal);
+      assert_eq!(vector(5.0, 0.0, 0.0).bisect(&vector(0.0, 0.5, 0.0)), diagonal);
+      assert_eq!(diagonal.angle_with(&Vector::X_UNIT_VECTOR), 45.deg());
+   }
+
    #[test]
    fn angle_with() {
       assert_eq!(
@@ -371,6 +578,11 @@ mod tests {
          .sum();
 
       assert_eq!(sum, Vector::new(55.mm(), 55.mm(), 55.mm()));
+
+      let vectors: Vec<_> = (0..5)
+         .map(|i| Vector::new(i.mm(), 0.mm(), 0.mm()))
+         .collect();
+      assert_eq!(vectors.iter().sum::<Vector>(), Vector::new(10.mm(), 0.mm(), 0.mm()));
    }
 
    #[test]
@@ -401,4 +613,29 @@ mod tests {
 
       assert_eq!(actual, expected);
    }
+
+   #[test]
+   fn transform() {
+      let v = vector(1.0, 2.0, 3.0);
+
+      assert_eq!(Transform::translated(&v, &vector(4.0, 5.0, 6.0)), v);
+
+      let mut translated = v;
+      Transform::translate(&mut translated, &vector(4.0, 5.0, 6.0));
+      assert_eq!(translated, v);
+
+      let axis = Line::new(
+         &Point::new(3.mm(), 4.mm(), 5.mm()),
+         &Vector::Z_UNIT_VECTOR
+      );
+      assert_eq!(
+         Transform::rotated(&v, &axis, 90.deg()),
+         v.rotated(&Vector::Z_UNIT_VECTOR, 90.deg())
+      );
+
+      assert_eq!(
+         v.inverted_through(&Point::new(3.mm(), 4.mm(), 5.mm())),
+         vector(-1.0, -2.0, -3.0)
+      );
+   }
 }
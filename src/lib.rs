@@ -3,6 +3,7 @@
 pub mod geometry;
 pub mod math;
 pub mod prelude;
+pub mod scad;
 pub mod solid;
 pub mod stl;
 pub mod transform;
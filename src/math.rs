@@ -1,7 +1,10 @@
+pub mod quantity;
 pub mod unit;
 pub(crate) mod conversion;
 pub(crate) mod rough_fp;
+mod abs;
 mod matrix;
 
+pub use abs::Abs;
 pub use matrix::Matrix;
 
@@ -0,0 +1,32 @@
+use crate::geometry::{Angle, Size};
+use crate::math::unit::{Exp, Unit};
+use noisy_float::prelude::*;
+
+/// Type which has an absolute value.
+pub trait Abs {
+   fn abs(self) -> Self;
+}
+
+impl Abs for Size {
+   fn abs(self) -> Size {
+      Size::abs(self)
+   }
+}
+
+impl Abs for Angle {
+   fn abs(self) -> Angle {
+      Angle::abs(self)
+   }
+}
+
+impl Abs for N64 {
+   fn abs(self) -> N64 {
+      Float::abs(self)
+   }
+}
+
+impl<U: Unit, const N: i32> Abs for Exp<U, N> {
+   fn abs(self) -> Exp<U, N> {
+      unsafe { Exp::new(Float::abs(self.0)) }
+   }
+}
@@ -1,13 +1,43 @@
+use crate::geometry::Angle;
+use crate::math::Abs;
 use crate::math::unit::Unit;
 use std::iter::Sum;
 use std::mem::{ManuallyDrop, MaybeUninit};
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
 use noisy_float::prelude::*;
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct Matrix<U: Unit, const M: usize, const N: usize>(pub [[U; N]; M]);
 
 impl<U: Unit, const M: usize, const N: usize> Matrix<U, M, N> {
+   /// Builds a matrix by calling `f(x, y)` for each column `x` and row `y`.
+   /// ```
+   /// # use typed_scad::geometry::{Size, SizeLiteral};
+   /// # use typed_scad::math::Matrix;
+   /// let m: Matrix<Size, 2, 3> = Matrix::from_fn(|x, y| (x * 10 + y).mm());
+   /// assert_eq!(m, Matrix([
+   ///    [0.mm(),  1.mm(),  2.mm()],
+   ///    [10.mm(), 11.mm(), 12.mm()]
+   /// ]));
+   /// ```
+   pub fn from_fn(mut f: impl FnMut(usize, usize) -> U) -> Matrix<U, M, N> {
+      let f = &mut f;
+
+      let mut x = 0;
+      Matrix(
+         [(); M].map(|_| {
+            let mut y = 0;
+            let column = [(); N].map(|_| {
+               let u = f(x, y);
+               y += 1;
+               u
+            });
+            x += 1;
+            column
+         })
+      )
+   }
+
    pub fn transpose(self) -> Matrix<U, N, M> {
       if M == 1 {
          let transmuter = Transmuter {
@@ -51,6 +81,94 @@ impl<U: Unit, const M: usize, const N: usize> Matrix<U, M, N> {
          self.0.map(|column| column.map(|u| f(u)))
       )
    }
+
+   fn elements(self) -> impl Iterator<Item = U> {
+      self.0.into_iter().flat_map(|column| column.into_iter())
+   }
+
+   pub fn abs(self) -> Matrix<U, M, N> where U: Abs {
+      self.map(|u| u.abs())
+   }
+
+   /// Panics if this matrix has no elements.
+   pub fn max_element(self) -> U where U: Ord {
+      self.elements().max().expect("The matrix has no elements.")
+   }
+
+   /// Panics if this matrix has no elements.
+   pub fn min_element(self) -> U where U: Ord {
+      self.elements().min().expect("The matrix has no elements.")
+   }
+
+   pub fn clamp_elements(self, min: U, max: U) -> Matrix<U, M, N>
+      where U: Ord + Copy
+   {
+      self.map(|u| Ord::clamp(u, min, max))
+   }
+
+   /// Sum of all elements. Not to be confused with the trace.
+   pub fn sum_elements(self) -> U where U: Sum {
+      self.elements().sum()
+   }
+
+   /// Element-wise product.
+   pub fn hadamard<Rhs: Unit>(self, other: Matrix<Rhs, M, N>)
+      -> Matrix<U::Output, M, N>
+      where U: Mul<Rhs>,
+            U::Output: Unit
+   {
+      let a = self.0.zip(other.0)
+         .map(|(ma, mb)| {
+            ma.zip(mb)
+               .map(|(na, nb)| na * nb)
+         });
+
+      Matrix(a)
+   }
+}
+
+impl Matrix<N64, 3, 3> {
+   /// Matrix to rotate a column vector around X-Axis.
+   pub fn rotation_x(angle: Angle) -> Matrix<N64, 3, 3> {
+      let (sin, cos) = angle.sin_cos();
+      Matrix::from_fn(|x, y| {
+         match (y, x) {
+            (0, 0) => n64(1.0),
+            (1, 1) | (2, 2) => cos,
+            (1, 2) => -sin,
+            (2, 1) => sin,
+            _ => n64(0.0)
+         }
+      })
+   }
+
+   /// Matrix to rotate a column vector around Y-Axis.
+   pub fn rotation_y(angle: Angle) -> Matrix<N64, 3, 3> {
+      let (sin, cos) = angle.sin_cos();
+      Matrix::from_fn(|x, y| {
+         match (y, x) {
+            (1, 1) => n64(1.0),
+            (0, 0) | (2, 2) => cos,
+            (0, 2) => sin,
+            (2, 0) => -sin,
+            _ => n64(0.0)
+         }
+      })
+   }
+
+   /// Matrix to rotate a column vector around Z-Axis.
+   pub fn rotation_z(angle: Angle) -> Matrix<N64, 3, 3> {
+      let (sin, cos) = angle.sin_cos();
+      Matrix::from_fn(|x, y| {
+         match (y, x) {
+            (2, 2) => n64(1.0),
+            (0, 0) | (1, 1) => cos,
+            (0, 1) => -sin,
+            (1, 0) => sin,
+            _ => n64(0.0)
+         }
+      })
+   }
 }
 
 union Transmuter<T, const M: usize, const N: usize> {
@@ -197,7 +315,31 @@ macro_rules! mul_num {
 }
 
 mul_num!(usize, u8, u16, u32, u64, u128, isize, i8, i16, i32, i64, i128, f32, f64,
-   N32, N64, R32, R64);
+   N32, R32, R64);
+
+// `Matrix * N64` is covered by `Mul<Rhs>` above since N64 is also a Unit.
+
+impl<U: Unit, const M: usize, const N: usize> Mul<Matrix<U, M, N>> for N64
+   where U: Mul<N64>,
+         U::Output: Unit
+{
+   type Output = Matrix<U::Output, M, N>;
+   fn mul(self, rhs: Matrix<U, M, N>) -> Self::Output {
+      rhs * self
+   }
+}
+
+impl<U: Unit, const M: usize, const N: usize> MulAssign<N64> for Matrix<U, M, N>
+   where U: MulAssign<N64>
+{
+   fn mul_assign(&mut self, rhs: N64) {
+      for column in &mut self.0 {
+         for value in column {
+            *value *= rhs;
+         }
+      }
+   }
+}
 
 impl<U: Unit, const M: usize, const N: usize, Rhs> Div<Rhs> for Matrix<U, M, N>
    where U: Div<Rhs>,
@@ -229,15 +371,16 @@ impl<U: Unit, const M: usize, const N: usize, Rhs>
    }
 }
 
-impl<U: Unit, const L: usize, const M: usize, const N: usize>
-   Mul<Matrix<U, N, L>> for Matrix<U, L, M>
-   where U: Mul<U>,
+impl<U: Unit, Rhs: Unit, const L: usize, const M: usize, const N: usize>
+   Mul<Matrix<Rhs, N, L>> for Matrix<U, L, M>
+   where U: Mul<Rhs>,
          U: Copy,
+         Rhs: Copy,
          U::Output: Unit,
          U::Output: Sum
 {
    type Output = Matrix<U::Output, N, M>;
-   fn mul(self, rhs: Matrix<U, N, L>) -> Self::Output {
+   fn mul(self, rhs: Matrix<Rhs, N, L>) -> Self::Output {
       let a = self.transpose().0.map(|self_row|
          rhs.0.map(|rhs_column|
             self_row.iter()
@@ -254,7 +397,7 @@ impl<U: Unit, const L: usize, const M: usize, const N: usize>
 #[cfg(test)]
 mod tests {
    use super::Matrix;
-   use crate::geometry::{Size, SizeLiteral};
+   use crate::geometry::{AngleLiteral, Size, SizeLiteral, Vector};
    use crate::math::unit::Exp;
    use noisy_float::prelude::*;
 
@@ -454,4 +597,84 @@ mod tests {
       let expected = Matrix([[2.mm(), 4.mm(), 6.mm()]]);
       assert_eq!(actual, expected);
    }
+
+   #[test]
+   fn from_fn() {
+      let actual: Matrix<Size, 3, 3>
+         = Matrix::from_fn(|x, y| if x == y { 1.0.mm() } else { 0.0.mm() });
+
+      let expected = Matrix([
+         [1.mm(), 0.mm(), 0.mm()],
+         [0.mm(), 1.mm(), 0.mm()],
+         [0.mm(), 0.mm(), 1.mm()]
+      ]);
+
+      assert_eq!(actual, expected);
+
+      let mut count = 0;
+      let _: Matrix<Size, 2, 4> = Matrix::from_fn(|_, _| {
+         count += 1;
+         0.mm()
+      });
+      assert_eq!(count, 2 * 4);
+   }
+
+   #[test]
+   fn rotation() {
+      assert_eq!(
+         Matrix::rotation_x(90.deg()) * Vector::Y_UNIT_VECTOR.matrix.transpose(),
+         Vector::Z_UNIT_VECTOR.matrix.transpose()
+      );
+
+      assert_eq!(
+         Matrix::rotation_y(90.deg()) * Vector::Z_UNIT_VECTOR.matrix.transpose(),
+         Vector::X_UNIT_VECTOR.matrix.transpose()
+      );
+
+      assert_eq!(
+         Matrix::rotation_z(90.deg()) * Vector::X_UNIT_VECTOR.matrix.transpose(),
+         Vector::Y_UNIT_VECTOR.matrix.transpose()
+      );
+   }
+
+   #[test]
+   fn element_wise() {
+      let a = Matrix([
+         [-1.mm(), 2.mm(), -3.mm()],
+         [4.mm(), -5.mm(), 6.mm()]
+      ]);
+
+      let expected = Matrix([
+         [1.mm(), 2.mm(), 3.mm()],
+         [4.mm(), 5.mm(), 6.mm()]
+      ]);
+      assert_eq!(a.abs(), expected);
+
+      assert_eq!(a.max_element(), 6.mm());
+      assert_eq!(a.min_element(), -5.mm());
+      assert_eq!(a.sum_elements(), 3.mm());
+
+      let expected = Matrix([
+         [-1.mm(), 2.mm(), -2.mm()],
+         [2.mm(), -2.mm(), 2.mm()]
+      ]);
+      assert_eq!(a.clamp_elements(-2.mm(), 2.mm()), expected);
+   }
+
+   #[test]
+   fn hadamard() {
+      let a = Matrix([
+         [-1.mm(), 2.mm(), -3.mm()],
+         [4.mm(), -5.mm(), 6.mm()]
+      ]);
+
+      let expected = unsafe {
+         Matrix([
+            [Exp::<Size, 2>::new(n64( 1.0)), Exp::<Size, 2>::new(n64( 4.0)), Exp::<Size, 2>::new(n64( 9.0))],
+            [Exp::<Size, 2>::new(n64(16.0)), Exp::<Size, 2>::new(n64(25.0)), Exp::<Size, 2>::new(n64(36.0))]
+         ])
+      };
+
+      assert_eq!(a.hadamard(a), expected);
+   }
 }
@@ -0,0 +1,344 @@
+//! Physical quantities other than [Size] and [Angle](crate::geometry::Angle),
+//! for mechanical calculations.
+
+use crate::geometry::Size;
+use crate::math::conversion::ToN64;
+use crate::math::unit::DerivedUnit;
+use noisy_float::prelude::*;
+
+/// Items used by the code [define_unit](crate::define_unit) generates.
+#[doc(hidden)]
+pub mod __private {
+   use crate::geometry::Size;
+   use crate::math::unit::{DerivedUnit, Unit};
+   use noisy_float::prelude::*;
+
+   pub use crate::math::conversion::ToN64;
+   pub use crate::math::rough_fp::{rough_cmp, rough_eq, rough_hash};
+
+   /// `value` in the unit `U` multiplied by `size`.
+   pub fn mul_size<U: Unit>(value: N64, size: Size) -> DerivedUnit<U, Size> {
+      unsafe { DerivedUnit::new(value * size.to_millimeter()) }
+   }
+}
+
+/// Defines a unit type which behaves like [Size], and its literal trait.
+///
+/// ```
+/// # use typed_scad::define_unit;
+/// # use typed_scad::geometry::SizeLiteral;
+/// define_unit! {
+///    /// Pressure.
+///    Pressure, PressureN64, PressureLiteral, "MPa", {
+///       MPa (from_MPa, to_MPa): 1.0,
+///       kPa (from_kPa, to_kPa): 0.001
+///    }
+/// }
+///
+/// assert_eq!(1.MPa(), 1000.kPa());
+/// assert_eq!(Pressure::from_kPa(500.0).to_MPa(), 0.5);
+/// let _ = 1.MPa() * 1.mm();
+/// ```
+/// defines `Pressure`, whose value is in MPa, `PressureN64` as the type of
+/// the value, and `PressureLiteral` with `MPa()` and `kPa()`. Multiplying it
+/// by [Size] results in `DerivedUnit<Pressure, Size>`.
+#[macro_export]
+macro_rules! define_unit {
+   (
+      $(#[$attr:meta])*
+      $name:ident, $n64:ident, $literal:ident, $symbol:literal, {
+         $($method:ident ($from:ident, $to:ident): $scale:expr),+
+      }
+   ) => {
+      #[doc = concat!("Value of [", stringify!($name), "] in ", $symbol, ".")]
+      pub type $n64 = $crate::prelude::N64;
+
+      $(#[$attr])*
+      #[derive(Clone, Copy, Default)]
+      pub struct $name($n64);
+
+      impl $name {
+         pub const ZERO: $name = $name($n64::unchecked_new(0.0));
+
+         #[doc = concat!("From the value in ", $symbol, ".")]
+         pub const fn new(value: $n64) -> $name {
+            $name(value)
+         }
+
+         #[doc = concat!("The value in ", $symbol, ".")]
+         pub const fn value(self) -> $n64 {
+            self.0
+         }
+
+         $(
+            #[allow(non_snake_case)]
+            #[doc = concat!("From the value in ", stringify!($method), ".")]
+            pub fn $from(value: f64) -> $name {
+               $name($crate::prelude::n64(value * $scale))
+            }
+
+            #[allow(non_snake_case)]
+            #[doc = concat!("The value in ", stringify!($method), ".")]
+            pub fn $to(self) -> f64 {
+               (self.0 / $scale).raw()
+            }
+         )+
+
+         pub fn abs(self) -> $name {
+            $name($crate::prelude::Float::abs(self.0))
+         }
+      }
+
+      impl std::fmt::Display for $name {
+         fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, concat!("{:.2}", $symbol), self.0)
+         }
+      }
+
+      impl std::fmt::Debug for $name {
+         fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            std::fmt::Display::fmt(self, f)
+         }
+      }
+
+      impl PartialOrd for $name {
+         fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+         }
+      }
+
+      impl Ord for $name {
+         fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            $crate::math::quantity::__private::rough_cmp(self.0, other.0)
+         }
+      }
+
+      impl PartialEq for $name {
+         fn eq(&self, other: &Self) -> bool {
+            $crate::math::quantity::__private::rough_eq(self.0, other.0)
+         }
+      }
+
+      impl Eq for $name {}
+
+      /// Rounds the value to the grid of the error which `eq` allows. Roughly
+      /// equal values almost always have the same hash, so lookups in a
+      /// `HashMap` keyed by this unit are best-effort.
+      impl std::hash::Hash for $name {
+         fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            $crate::math::quantity::__private::rough_hash(self.0, state);
+         }
+      }
+
+      impl std::ops::Add for $name {
+         type Output = $name;
+         fn add(self, rhs: $name) -> $name {
+            $name(self.0 + rhs.0)
+         }
+      }
+
+      impl std::ops::AddAssign for $name {
+         fn add_assign(&mut self, rhs: $name) {
+            *self = *self + rhs;
+         }
+      }
+
+      impl std::ops::Sub for $name {
+         type Output = $name;
+         fn sub(self, rhs: $name) -> $name {
+            $name(self.0 - rhs.0)
+         }
+      }
+
+      impl std::ops::SubAssign for $name {
+         fn sub_assign(&mut self, rhs: $name) {
+            *self = *self - rhs;
+         }
+      }
+
+      impl std::ops::Neg for $name {
+         type Output = $name;
+         fn neg(self) -> $name {
+            $name(-self.0)
+         }
+      }
+
+      impl std::ops::Div for $name {
+         type Output = $n64;
+         fn div(self, rhs: $name) -> $n64 {
+            self.0 / rhs.0
+         }
+      }
+
+      impl std::iter::Sum for $name {
+         fn sum<I: Iterator<Item = $name>>(iter: I) -> $name {
+            $name(iter.map(|v| v.0).sum())
+         }
+      }
+
+      impl $crate::math::unit::Unit for $name {}
+
+      impl std::ops::Mul<$crate::geometry::Size> for $name {
+         type Output = $crate::math::unit::DerivedUnit<$name, $crate::geometry::Size>;
+         fn mul(self, rhs: $crate::geometry::Size) -> Self::Output {
+            $crate::math::quantity::__private::mul_size(self.0, rhs)
+         }
+      }
+
+      impl std::ops::Mul<$name> for $crate::geometry::Size {
+         type Output = $crate::math::unit::DerivedUnit<$name, $crate::geometry::Size>;
+         fn mul(self, rhs: $name) -> Self::Output {
+            rhs * self
+         }
+      }
+
+      $(#[$attr])*
+      pub trait $literal {
+         $(
+            #[allow(non_snake_case)]
+            fn $method(self) -> $name;
+         )+
+      }
+
+      $crate::define_unit!(@scalars $name, $literal, { $($method: $scale),+ };
+         usize, u8, u16, u32, u64, u128, isize, i8, i16, i32, i64, i128, f32,
+         f64, $crate::prelude::N32, $crate::prelude::N64, $crate::prelude::R32,
+         $crate::prelude::R64);
+   };
+
+   (@scalars $name:ident, $literal:ident, $methods:tt; $($t:ty),+) => ($(
+      $crate::define_unit!(@scalar $name, $literal, $methods, $t);
+   )+);
+
+   (@scalar $name:ident, $literal:ident, { $($method:ident: $scale:expr),+ }, $t:ty) => (
+      impl std::ops::Mul<$t> for $name {
+         type Output = $name;
+         fn mul(self, rhs: $t) -> $name {
+            $name(self.0 * $crate::math::quantity::__private::ToN64::to_n64(rhs))
+         }
+      }
+
+      impl std::ops::MulAssign<$t> for $name {
+         fn mul_assign(&mut self, rhs: $t) {
+            *self = *self * rhs;
+         }
+      }
+
+      impl std::ops::Mul<$name> for $t {
+         type Output = $name;
+         fn mul(self, rhs: $name) -> $name {
+            rhs * self
+         }
+      }
+
+      impl std::ops::Div<$t> for $name {
+         type Output = $name;
+         fn div(self, rhs: $t) -> $name {
+            $name(self.0 / $crate::math::quantity::__private::ToN64::to_n64(rhs))
+         }
+      }
+
+      impl std::ops::DivAssign<$t> for $name {
+         fn div_assign(&mut self, rhs: $t) {
+            *self = *self / rhs;
+         }
+      }
+
+      impl $literal for $t {
+         $(
+            fn $method(self) -> $name {
+               $name($crate::math::quantity::__private::ToN64::to_n64(self) * $scale)
+            }
+         )+
+      }
+   );
+}
+
+define_unit! {
+   /// Force.
+   ///
+   /// ```
+   /// # use typed_scad::math::quantity::ForceLiteral;
+   /// assert_eq!(1.kN(), 1000.N());
+   /// assert_eq!(2.N() * 3, 6.N());
+   /// ```
+   Force, ForceN64, ForceLiteral, "N", {
+      N (from_N, to_N): 1.0,
+      mN (from_mN, to_mN): 0.001,
+      kN (from_kN, to_kN): 1000.0
+   }
+}
+
+/// Torque, or moment of force. Made by multiplying [Force] by [Size].
+///
+/// ```
+/// # use typed_scad::geometry::SizeLiteral;
+/// # use typed_scad::math::quantity::{ForceLiteral, Torque, TorqueLiteral};
+/// let torque: Torque = 2.N() * 3.mm();
+/// assert_eq!(torque, 6.N_mm());
+/// ```
+///
+/// Quantities of different units cannot be mixed.
+/// ```compile_fail
+/// # use typed_scad::geometry::SizeLiteral;
+/// # use typed_scad::math::quantity::ForceLiteral;
+/// let _ = 1.N() + 1.mm();
+/// ```
+pub type Torque = DerivedUnit<Force, Size>;
+
+pub trait TorqueLiteral {
+   #[allow(non_snake_case)]
+   fn N_mm(self) -> Torque;
+   #[allow(non_snake_case)]
+   fn N_m(self) -> Torque;
+}
+
+macro_rules! torque_literal {
+   ($($t:ty),+) => ($(
+      impl TorqueLiteral for $t {
+         fn N_mm(self) -> Torque {
+            unsafe { DerivedUnit::new(self.to_n64()) }
+         }
+
+         fn N_m(self) -> Torque {
+            unsafe { DerivedUnit::new(self.to_n64() * 1000.0) }
+         }
+      }
+   )+)
+}
+
+torque_literal!(usize, u8, u16, u32, u64, u128, isize, i8, i16, i32, i64, i128,
+   f32, f64, N32, N64, R32, R64);
+
+#[cfg(test)]
+mod tests {
+   use super::{Force, ForceLiteral, TorqueLiteral};
+   use crate::geometry::SizeLiteral;
+   use noisy_float::prelude::*;
+
+   #[test]
+   fn force() {
+      assert_eq!(1.N() + 2.N(), 3.N());
+      assert_eq!(1.N() - 500.mN(), 0.5.N());
+      assert_eq!(-(2.N()), Force::new(n64(-2.0)));
+      assert_eq!(6.N() / 3, 2.N());
+      assert_eq!(6.N() / 3.N(), 2.0);
+      assert_eq!(0.1.N() * 3, 0.3.N());
+      assert!(1.kN() > 999.N());
+      assert_eq!(1.5.kN().value(), 1500.0);
+      assert_eq!([1.N(), 2.N()].into_iter().sum::<Force>(), 3.N());
+      assert_eq!(format!("{}", 1.5.N()), "1.50N");
+      assert_eq!(Force::from_mN(1500.0), 1.5.N());
+      assert_eq!(2.kN().to_N(), 2000.0);
+      assert_eq!(Force::from_kN(0.5).to_mN(), 500000.0);
+   }
+
+   #[test]
+   fn torque() {
+      assert_eq!(1.N() * 1.mm(), 1.N_mm());
+      assert_eq!(2.mm() * 3.N(), 6.N_mm());
+      assert_eq!(10.N() * 0.1.cm(), 10.N_mm());
+      assert_eq!(1.N_m(), 1000.N_mm());
+      assert_ne!(1.N() * 2.mm(), 1.N_mm());
+   }
+}
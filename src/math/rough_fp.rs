@@ -1,13 +1,14 @@
 use std::cmp::Ordering;
+use std::hash::Hasher;
 use noisy_float::prelude::*;
 
 pub(crate) const FLOAT_POINT_ALLOWABLE_ERROR: N64 = N64::unchecked_new(1e-10);
 
-pub(crate) fn rough_eq(s: N64, o: N64) -> bool {
+pub fn rough_eq(s: N64, o: N64) -> bool {
    s > o - FLOAT_POINT_ALLOWABLE_ERROR && s < o + FLOAT_POINT_ALLOWABLE_ERROR
 }
 
-pub(crate) fn rough_cmp(s: N64, o: N64) -> Ordering {
+pub fn rough_cmp(s: N64, o: N64) -> Ordering {
    if s > o + FLOAT_POINT_ALLOWABLE_ERROR {
       Ordering::Greater
    } else if s < o - FLOAT_POINT_ALLOWABLE_ERROR {
@@ -16,3 +17,36 @@ pub(crate) fn rough_cmp(s: N64, o: N64) -> Ordering {
       Ordering::Equal
    }
 }
+
+/// Number of the items `step * i` (`i` = 0, 1, 2, ...) up to `distance`,
+/// which is the length of a range `start..end` or `start..=end` for iterators
+/// of `start + step * i`.
+///
+/// An item within [FLOAT_POINT_ALLOWABLE_ERROR] from `distance` is counted only
+/// if `inclusive`. That is, the count is `floor(distance / step + e) + 1` for
+/// inclusive ranges and `ceil(distance / step - e)` for exclusive ranges, where
+/// `e` is the allowable error in units of `step`. 0 if `distance` is in the
+/// opposite direction of `step`.
+pub(crate) fn step_count(distance: N64, step: N64, inclusive: bool) -> usize {
+   let steps = distance / step;
+   let error = FLOAT_POINT_ALLOWABLE_ERROR / step.abs();
+
+   if inclusive {
+      let last = (steps + error).floor();
+      if last < 0.0 { 0 } else { last.raw() as usize + 1 }
+   } else {
+      let count = (steps - error).ceil();
+      if count < 0.0 { 0 } else { count.raw() as usize }
+   }
+}
+
+/// Hashes the value rounded to the grid of [FLOAT_POINT_ALLOWABLE_ERROR], so
+/// that roughly equal values almost always have the same hash. Values just
+/// around the middle of the grid can be rounded to different points even if
+/// they are roughly equal, so this is not perfectly consistent with
+/// [rough_eq]. It is enough for deduplication where those values are rare.
+pub fn rough_hash<H: Hasher>(s: N64, state: &mut H) {
+   // adding 0.0 turns -0.0 into 0.0
+   let quantized = (s / FLOAT_POINT_ALLOWABLE_ERROR).round().raw() + 0.0;
+   state.write_u64(quantized.to_bits());
+}
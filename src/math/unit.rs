@@ -1,3 +1,4 @@
+use crate::geometry::Size;
 use crate::math::Matrix;
 use crate::math::conversion::ToN64;
 use crate::math::rough_fp::rough_eq;
@@ -14,6 +15,9 @@ pub trait Unit {}
 
 impl Unit for ! {}
 
+/// Dimensionless. e.g. elements of a rotation matrix.
+impl Unit for N64 {}
+
 /// A product of other units.
 ///
 /// # Examples
@@ -126,6 +130,24 @@ impl<
          Q: Unit, R: Unit, S: Unit, T: Unit, U: Unit, V: Unit,
 {}
 
+/// Allows float-point arithmetic errors like
+/// [Size::eq](crate::geometry::Size::eq).
+impl<
+      A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V,
+   >
+   PartialEq
+   for DerivedUnit<
+      A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V,
+   >
+   where A: Unit, B: Unit, C: Unit, D: Unit, E: Unit, F: Unit, G: Unit, H: Unit,
+         I: Unit, J: Unit, K: Unit, L: Unit, M: Unit, N: Unit, O: Unit, P: Unit,
+         Q: Unit, R: Unit, S: Unit, T: Unit, U: Unit, V: Unit,
+{
+   fn eq(&self, other: &Self) -> bool {
+      rough_eq(self.0, other.0)
+   }
+}
+
 /// exponentiation of unit. e.g. `Exp<Size, 2>` for mm².
 /// See also [DerivedUnit].
 #[derive(Clone, Copy, Debug, Default)]
@@ -160,6 +182,28 @@ impl<U: Unit, const N: i32> ExponentialUnit<U, N> {
    }
 }
 
+/// `s` to the power of `n`, as `Exp<Size, n>`. `n` must be an integer literal.
+///
+/// ```
+/// # use typed_scad::geometry::{Size, SizeLiteral};
+/// # use typed_scad::math::unit::Exp;
+/// # use typed_scad::size_pow;
+/// let moment: Exp<Size, 4> = size_pow!(2.mm(), 4);
+/// assert_eq!(moment.0, 16.0);
+/// ```
+#[macro_export]
+macro_rules! size_pow {
+   ($s:expr, $n:literal) => {
+      $crate::math::unit::__size_pow::<$n>($s)
+   };
+}
+
+/// Used by [size_pow].
+#[doc(hidden)]
+pub fn __size_pow<const N: i32>(s: Size) -> Exp<Size, N> {
+   ExponentialUnit(n64(s.to_millimeter().raw().powi(N)), PhantomData)
+}
+
 impl<U: Unit, const N: i32> Unit for Exp<U, N> {}
 
 impl<U: Unit, const N: i32> PartialEq for Exp<U, N> {
@@ -0,0 +1,10 @@
+mod euler;
+mod scad_describable;
+mod write_scad;
+
+pub use euler::axis_angle_to_euler;
+pub use scad_describable::{describe_solid, ScadDescribable};
+pub(crate) use scad_describable::{
+   describe_block, location_prefix, number_literal, vector_literal, write_line
+};
+pub use write_scad::write_scad;
@@ -0,0 +1,60 @@
+use crate::geometry::{Angle, AngleLiteral, Vector};
+use noisy_float::prelude::*;
+
+/// Converts a rotation around `axis` into the angles for OpenSCAD's
+/// `rotate([x, y, z])`, which rotates around X-Axis, Y-Axis, and Z-Axis
+/// in this order.
+pub fn axis_angle_to_euler(axis: &Vector, angle: Angle) -> (Angle, Angle, Angle) {
+   let x = Vector::X_UNIT_VECTOR.rotated(axis, angle);
+   let y = Vector::Y_UNIT_VECTOR.rotated(axis, angle);
+   let z = Vector::Z_UNIT_VECTOR.rotated(axis, angle);
+
+   // x, y, z are the columns of the rotation matrix Rz * Ry * Rx
+   let sin_y = Ord::clamp(-x.z().to_millimeter(), n64(-1.0), n64(1.0));
+   let angle_y = Angle::asin(sin_y);
+
+   if angle_y.cos() > 1e-9 {
+      (
+         Angle::atan2(y.z(), z.z()),
+         angle_y,
+         Angle::atan2(x.y(), x.x())
+      )
+   } else {
+      // gimbal lock. only the sum or difference of x and z is determined
+      (
+         0.deg(),
+         angle_y,
+         Angle::atan2(-y.x(), y.y())
+      )
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::axis_angle_to_euler;
+   use crate::geometry::{AngleLiteral, SizeLiteral, Vector};
+
+   #[test]
+   fn axis_angle_to_euler_angles() {
+      let cases = [
+         (Vector::X_UNIT_VECTOR, 30.deg()),
+         (Vector::Z_UNIT_VECTOR, 90.deg()),
+         (Vector::new(1.mm(), 2.mm(), 3.mm()), 50.deg()),
+         (Vector::new(-1.mm(), 1.mm(), 0.mm()), 120.deg()),
+         (Vector::Y_UNIT_VECTOR, 90.deg())
+      ];
+
+      let vector = Vector::new(3.mm(), -1.mm(), 2.mm());
+
+      for (axis, angle) in cases {
+         let (x, y, z) = axis_angle_to_euler(&axis, angle);
+
+         let actual = vector
+            .rotated(&Vector::X_UNIT_VECTOR, x)
+            .rotated(&Vector::Y_UNIT_VECTOR, y)
+            .rotated(&Vector::Z_UNIT_VECTOR, z);
+
+         assert_eq!(actual, vector.rotated(&axis, angle));
+      }
+   }
+}
@@ -0,0 +1,105 @@
+use crate::geometry::{Point, Size, Vector};
+use crate::solid::{Location, Solid};
+use anyhow::Result;
+use noisy_float::prelude::*;
+use std::io::Write;
+
+/// Solid which can be described in OpenSCAD language.
+/// See [write_scad](crate::scad::write_scad).
+pub trait ScadDescribable {
+   /// Writes this solid as OpenSCAD statements, indented by `indent` levels.
+   fn describe_scad(&self, output: &mut dyn Write, indent: usize) -> Result<()>;
+}
+
+/// Describes the solid with [ScadDescribable] if the solid implements it,
+/// otherwise writes its facets as a `polyhedron`.
+pub fn describe_solid(
+   solid: &dyn Solid,
+   output: &mut dyn Write,
+   indent: usize
+) -> Result<()> {
+   if let Some(describable) = solid.as_scad_describable() {
+      return describable.describe_scad(output, indent);
+   }
+
+   let mesh = solid.generate_stl_solid().to_indexed(Size::ZERO);
+
+   let points: Vec<_> = mesh.vertexes.iter()
+      .map(|v| vector_literal(v.x(), v.y(), v.z()))
+      .collect();
+
+   // OpenSCAD expects faces to be clockwise when seen from outside
+   let faces: Vec<_> = mesh.triangles.iter()
+      .map(|[a, b, c]| format!("[{}, {}, {}]", a, c, b))
+      .collect();
+
+   write_line(
+      output, indent,
+      &format!(
+         "polyhedron(points=[{}], faces=[{}]);",
+         points.join(", "), faces.join(", ")
+      )
+   )
+}
+
+pub(crate) fn write_line(output: &mut dyn Write, indent: usize, line: &str) -> Result<()> {
+   writeln!(output, "{}{}", "   ".repeat(indent), line)?;
+   Ok(())
+}
+
+/// Writes `header { children }`.
+pub(crate) fn describe_block(
+   output: &mut dyn Write,
+   indent: usize,
+   header: &str,
+   children: &[Box<dyn Solid>]
+) -> Result<()> {
+   write_line(output, indent, &format!("{} {{", header))?;
+   for c in children {
+      describe_solid(c.as_ref(), output, indent + 1)?;
+   }
+   write_line(output, indent, "}")
+}
+
+/// `multmatrix` to move a solid from the origin to the location.
+/// Empty for the default location.
+pub(crate) fn location_prefix(location: &Location) -> String {
+   if *location == Location::default() {
+      return String::new();
+   }
+
+   let columns = [
+      location.right_vector(),
+      location.back_vector(),
+      location.top_vector(),
+      Vector::between(&Point::ORIGIN, &location.point())
+   ];
+
+   let row = |f: fn(&Vector) -> Size| {
+      let elements: Vec<_> = columns.iter()
+         .map(|c| number_literal(f(c).to_millimeter()))
+         .collect();
+      format!("[{}]", elements.join(", "))
+   };
+
+   format!(
+      "multmatrix([{}, {}, {}, [0, 0, 0, 1]]) ",
+      row(|v| v.x()), row(|v| v.y()), row(|v| v.z())
+   )
+}
+
+pub(crate) fn vector_literal(x: Size, y: Size, z: Size) -> String {
+   format!(
+      "[{}, {}, {}]",
+      number_literal(x.to_millimeter()),
+      number_literal(y.to_millimeter()),
+      number_literal(z.to_millimeter())
+   )
+}
+
+/// Rounded not to write `1.0000000000000002` for a computation error.
+/// `-0` is written as `0`.
+pub(crate) fn number_literal(n: N64) -> String {
+   let rounded = (n.raw() * 1e9).round() / 1e9 + 0.0;
+   format!("{}", rounded)
+}
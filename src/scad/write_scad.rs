@@ -0,0 +1,125 @@
+use crate::scad::ScadDescribable;
+use anyhow::Result;
+use std::io::Write;
+
+/// Writes the specified solid as OpenSCAD source code.
+///
+/// Useful to inspect a model in OpenSCAD's viewer.
+/// ```
+/// # use typed_scad::geometry::SizeLiteral;
+/// # use typed_scad::scad::write_scad;
+/// # use typed_scad::solid::{cube, Location};
+/// let mut output = Vec::new();
+/// write_scad(&mut output, &cube(Location::default(), (1.mm(), 2.mm(), 3.mm())))
+///    .unwrap();
+///
+/// assert_eq!(String::from_utf8(output).unwrap(), "cube([1, 2, 3]);\n");
+/// ```
+pub fn write_scad(output: &mut dyn Write, solid: &dyn ScadDescribable) -> Result<()> {
+   solid.describe_scad(output, 0)
+}
+
+#[cfg(test)]
+mod tests {
+   use super::write_scad;
+   use crate::geometry::{AngleLiteral, Line, Point, SizeLiteral, Vector};
+   use crate::scad::ScadDescribable;
+   use crate::solid::{
+      cube, cylinder, rotate, sphere, translate, union, Location, Solid
+   };
+   use crate::stl::{Facet, StlSolid};
+   use crate::transform::Transform;
+
+   fn to_scad(solid: &dyn ScadDescribable) -> String {
+      let mut output = Vec::new();
+      write_scad(&mut output, solid).unwrap();
+      String::from_utf8(output).unwrap()
+   }
+
+   /// Numbers in the parentheses just after the first `keyword`.
+   fn numbers_after(scad: &str, keyword: &str) -> Vec<f64> {
+      let start = scad.find(keyword).unwrap() + keyword.len();
+      let end = start + scad[start..].find(')').unwrap();
+
+      scad[start..end]
+         .split(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+         .filter(|s| !s.is_empty())
+         .map(|s| s.parse().unwrap())
+         .collect()
+   }
+
+   #[test]
+   fn primitives() {
+      let scad = to_scad(&cylinder(Location::default(), 5.mm(), 2.mm()));
+      assert_eq!(numbers_after(&scad, "cylinder("), vec![5.0, 2.0]);
+      assert!(scad.contains("h=5") && scad.contains("r=2"));
+
+      let location = Location::default()
+         .translated(&Vector::new(1.mm(), 2.mm(), 3.mm()));
+      let scad = to_scad(&sphere(location, 4.mm()));
+      assert_eq!(
+         numbers_after(&scad, "multmatrix("),
+         vec![1.0, 0.0, 0.0, 1.0,
+              0.0, 1.0, 0.0, 2.0,
+              0.0, 0.0, 1.0, 3.0,
+              0.0, 0.0, 0.0, 1.0]
+      );
+      assert_eq!(numbers_after(&scad, "sphere("), vec![4.0]);
+   }
+
+   #[test]
+   fn parents() {
+      let solid = translate(Vector::new(1.mm(), 2.mm(), 3.mm()), |mut c| {
+         c <<= rotate(Line::Z_AXIS, 90.deg(), |mut c| {
+            c <<= cube(Location::default(), (1.mm(), 2.mm(), 3.mm()));
+         });
+         c <<= union(|_| {});
+      });
+
+      assert_eq!(
+         to_scad(&solid),
+         "translate([1, 2, 3]) {\n\
+         \x20  rotate([0, 0, 90]) {\n\
+         \x20     cube([1, 2, 3]);\n\
+         \x20  }\n\
+         \x20  union() {\n\
+         \x20  }\n\
+         }\n"
+      );
+
+      let axis = Line::from_2points(
+         &Point::new(1.mm(), 0.mm(), 0.mm()),
+         &Point::new(1.mm(), 1.mm(), 0.mm())
+      );
+      let scad = to_scad(&rotate(axis, 30.deg(), |_| {}));
+      assert_eq!(numbers_after(&scad, "translate("), vec![1.0, 0.0, 0.0]);
+      assert_eq!(numbers_after(&scad, "rotate("), vec![0.0, 30.0, 0.0]);
+   }
+
+   #[test]
+   fn polyhedron() {
+      struct Triangle;
+      impl Solid for Triangle {
+         fn generate_stl_solid(&self) -> StlSolid {
+            StlSolid::new(vec![
+               Facet::new([
+                  Point::ORIGIN,
+                  Point::new(1.mm(), 0.mm(), 0.mm()),
+                  Point::new(0.mm(), 1.mm(), 0.mm())
+               ])
+            ])
+         }
+      }
+
+      let scad = to_scad(&union(|mut c| {
+         c <<= Triangle;
+      }));
+
+      assert_eq!(
+         scad,
+         "union() {\n\
+         \x20  polyhedron(points=[[0, 0, 0], [1, 0, 0], [0, 1, 0]], faces=[[0, 2, 1]]);\n\
+         }\n"
+      );
+   }
+}
@@ -8,13 +8,37 @@ mod solid_parent;
 
 pub use location::Location;
 pub use location_builder::LocationBuilder;
+pub use primitive::clip::{clip, ClipByPlane};
 pub use primitive::cone::{cone, Cone};
-pub use primitive::cube::{cube, Cube};
+pub use primitive::cube::{cube, Cube, CubeAnchor};
 pub use primitive::cylinder::{cylinder, Cylinder};
+pub use primitive::cylindrical_surface::{cylindrical_surface, CylindricalSurface};
+pub use primitive::difference::{difference, Difference};
+pub use primitive::group::{group, Group};
+pub use primitive::helix::{helix, HelixPath};
+pub use primitive::high_precision::{with_precision, HighPrecision};
+pub use primitive::hull::{hull, Hull};
+pub use primitive::intersection::{intersection, IntersectionSolid};
+pub use primitive::loft::{loft, Loft};
+pub use primitive::minkowski::{minkowski, Minkowski};
+pub use primitive::named::{named, NamedSolid};
+pub use primitive::oblique_cylinder::{oblique_cylinder, ObliqueCylinder};
+pub use primitive::pattern::{
+   grid_pattern, linear_pattern, polar_pattern, GridPattern, LinearPattern,
+   PolarPattern
+};
+pub use primitive::place::{place, Place};
+pub use primitive::resize::{resize, Resize};
 pub use primitive::rotate::{rotate, Rotate};
-pub use primitive::scale::{scale, Scale};
+pub use primitive::rounded_cone::{rounded_cone, RoundedCone};
+pub use primitive::scale::{scale, scale_along, scale_xyz, Scale, ScaleAlong};
+pub use primitive::scope::{scope, scope_transformed, Scope};
+pub use primitive::shell::{shell, Shell};
 pub use primitive::sphere::{sphere, Sphere};
+pub use primitive::spherical_cap::{hemisphere, spherical_cap, SphericalCap};
+pub use primitive::text::{text, Text};
 pub use primitive::translate::{translate, Translate};
+pub use primitive::union::{union, Union};
 pub use primitive::precision;
-pub use solid::Solid;
+pub use solid::{Solid, union_all};
 pub use solid_parent::SolidParent;
@@ -4,5 +4,5 @@ mod build_env;
 mod child_receiver;
 
 pub use build_context::BuildContext;
-pub use build_env::{BuildEnv, env};
+pub use build_env::{BuildEnv, env, EnvScope, scope};
 pub use child_receiver::ChildReceiver;
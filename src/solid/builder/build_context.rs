@@ -19,6 +19,25 @@ impl<P: SolidParent + 'static> BuildContext<'_, P> {
    }
 }
 
+impl<'a, P: SolidParent + ?Sized> BuildContext<'a, P> {
+   /// Pushes the solid only when `condition` is true.
+   /// `solid` is not called otherwise.
+   pub fn push_if<S: Solid + 'static>(
+      &mut self,
+      condition: bool,
+      solid: impl FnOnce() -> S
+   ) {
+      self.child_receiver.push_if(condition, solid);
+   }
+
+   /// Pushes all the solids in order.
+   pub fn add_all<S: Solid + 'static>(&mut self, iter: impl IntoIterator<Item = S>) {
+      for solid in iter {
+         *self <<= solid;
+      }
+   }
+}
+
 impl<'a, P: SolidParent + ?Sized> Deref for BuildContext<'a, P> {
    type Target = P;
    fn deref(&self) -> &P {
@@ -40,11 +59,29 @@ impl<'a, P: SolidParent + ?Sized, S: Solid + 'static>
    }
 }
 
+/// Pushes the solid if it is `Some`. Does nothing for `None`.
+impl<'a, P: SolidParent + ?Sized, S: Solid + 'static>
+   ShlAssign<Option<S>> for BuildContext<'a, P>
+{
+   fn shl_assign(&mut self, rhs: Option<S>) {
+      self.child_receiver <<= rhs;
+   }
+}
+
+/// Pushes all the solids in order.
+impl<'a, P: SolidParent + ?Sized, S: Solid + 'static>
+   ShlAssign<Vec<S>> for BuildContext<'a, P>
+{
+   fn shl_assign(&mut self, rhs: Vec<S>) {
+      self.child_receiver <<= rhs;
+   }
+}
+
 #[cfg(test)]
 mod tests {
    use super::BuildContext;
    use crate::geometry::{Point, SizeLiteral};
-   use crate::solid::{Solid, SolidParent};
+   use crate::solid::{cube, Cube, Location, Solid, SolidParent};
    use crate::solid::solid_parent::PushBorrowing;
    use crate::stl::{Facet, StlSolid};
 
@@ -54,7 +91,7 @@ mod tests {
       fn generate_stl_solid(&self) -> StlSolid {
          StlSolid {
             facets: vec![
-               Facet { vertexes: self.0.vertexes }
+               Facet::new(self.0.vertexes())
             ]
          }
       }
@@ -84,15 +121,13 @@ mod tests {
             facets: self.children.iter()
                .flat_map(|c|
                   c.generate_stl_solid().facets.into_iter().map(|f|
-                     Facet {
-                        vertexes: f.vertexes.map(|v|
-                           Point {
-                              matrix: v.matrix.map(|s|
-                                 s * self.multiply
-                              )
-                           }
-                        )
-                     }
+                     Facet::new(f.vertexes().map(|v|
+                        Point {
+                           matrix: v.matrix.map(|s|
+                              s * self.multiply
+                           )
+                        }
+                     ))
                   )
                )
                .collect()
@@ -120,36 +155,30 @@ mod tests {
    fn build_context() {
       let solid_parent = solid_parent(1, |mut p| {
          p <<= solid(
-            Facet {
-               vertexes: [
-                  Point::new(1.mm(), 2.mm(), 3.mm()),
-                  Point::new(4.mm(), 5.mm(), 6.mm()),
-                  Point::new(7.mm(), 8.mm(), 9.mm())
-               ]
-            }
+            Facet::new([
+               Point::new(1.mm(), 2.mm(), 3.mm()),
+               Point::new(4.mm(), 5.mm(), 6.mm()),
+               Point::new(7.mm(), 8.mm(), 9.mm())
+            ])
          );
 
          p <<= solid_parent(1, |mut p| {
             p.multiply += 1;
 
             p <<= solid(
-               Facet {
-                  vertexes: [
-                     Point::new(1.mm(), 2.mm(), 3.mm()),
-                     Point::new(4.mm(), 5.mm(), 6.mm()),
-                     Point::new(7.mm(), 8.mm(), 9.mm())
-                  ]
-               }
+               Facet::new([
+                  Point::new(1.mm(), 2.mm(), 3.mm()),
+                  Point::new(4.mm(), 5.mm(), 6.mm()),
+                  Point::new(7.mm(), 8.mm(), 9.mm())
+               ])
             );
 
             p <<= solid(
-               Facet {
-                  vertexes: [
-                     Point::new(10.mm(), 11.mm(), 12.mm()),
-                     Point::new(13.mm(), 14.mm(), 15.mm()),
-                     Point::new(16.mm(), 17.mm(), 18.mm())
-                  ]
-               }
+               Facet::new([
+                  Point::new(10.mm(), 11.mm(), 12.mm()),
+                  Point::new(13.mm(), 14.mm(), 15.mm()),
+                  Point::new(16.mm(), 17.mm(), 18.mm())
+               ])
             );
          });
       });
@@ -168,9 +197,42 @@ mod tests {
       ];
       assert_eq!(
          stl_solid.facets.iter()
-            .flat_map(|f| f.vertexes)
+            .flat_map(|f| f.vertexes())
             .collect::<Vec<_>>(),
          expected
       );
    }
+
+   #[test]
+   fn optional_children() {
+      let cube = || cube(Location::default(), (1.mm(), 1.mm(), 1.mm()));
+      let vertexes = |p: &SolidParentImpl| -> Vec<Point> {
+         p.generate_stl_solid().facets.iter().flat_map(|f| f.vertexes()).collect()
+      };
+
+      let with_none = solid_parent(1, |mut p| {
+         p <<= cube();
+         p <<= None::<Cube>;
+      });
+      let without_none = solid_parent(1, |mut p| {
+         p <<= cube();
+      });
+      assert_eq!(vertexes(&with_none), vertexes(&without_none));
+
+      let cubes = solid_parent(1, |mut p| {
+         p <<= vec![cube(), cube(), cube()];
+      });
+      assert_eq!(cubes.generate_stl_solid().facet_count(), 36);
+
+      let added = solid_parent(1, |mut p| {
+         p.add_all((0..3).map(|_| cube()));
+      });
+      assert_eq!(added.generate_stl_solid().facet_count(), 36);
+
+      let conditional = solid_parent(1, |mut p| {
+         p.push_if(true, cube);
+         p.push_if(false, cube);
+      });
+      assert_eq!(conditional.generate_stl_solid().facet_count(), 12);
+   }
 }
@@ -17,12 +17,53 @@ pub fn env<T: 'static, D: Fn() -> T>(
    value: T,
    build_action: impl FnOnce() -> ()
 ) {
-   let cell_inner_mut = env.cell_inner_mut();
-   let old_value = mem::replace(cell_inner_mut, Box::new(value));
+   let _scope = scope(env, value);
    build_action();
-   *cell_inner_mut = old_value;
 }
 
+/// Sets the value of the env until the returned [EnvScope] is dropped.
+/// An alternative to [env] without a closure.
+///
+/// The previous value is restored even on panic. Scopes must be dropped in
+/// the reverse order of creation, which is the default for local variables.
+/// ```
+/// # use typed_scad::geometry::AngleLiteral;
+/// # use typed_scad::solid::builder::scope;
+/// # use typed_scad::solid::precision::FRAGMENT_MINIMUM_ANGLE;
+/// {
+///    let _scope = scope(&FRAGMENT_MINIMUM_ANGLE, 2.deg());
+///    assert_eq!(*FRAGMENT_MINIMUM_ANGLE, 2.deg());
+/// }
+/// assert_eq!(*FRAGMENT_MINIMUM_ANGLE, 12.deg());
+/// ```
+pub fn scope<T: 'static, D: Fn() -> T>(
+   env: &BuildEnv<T, D>,
+   value: T
+) -> EnvScope<'_, T, D> {
+   let old_value = mem::replace(env.cell_inner_mut(), Box::new(value));
+   EnvScope { env, old_value: Some(old_value) }
+}
+
+/// Restores the previous value of the env on drop. See [scope].
+#[must_use = "the value is restored immediately if the scope is not held"]
+pub struct EnvScope<'a, T: 'static, D: Fn() -> T> {
+   env: &'a BuildEnv<T, D>,
+   old_value: Option<Box<dyn Any>>
+}
+
+impl<'a, T: 'static, D: Fn() -> T> Drop for EnvScope<'a, T, D> {
+   fn drop(&mut self) {
+      if let Some(old_value) = self.old_value.take() {
+         *self.env.cell_inner_mut() = old_value;
+      }
+   }
+}
+
+/// Value which can be changed for a scope by [env] or [scope].
+///
+/// The value is thread-local. Solids must read envs on the thread which
+/// called `generate_stl_solid`, before they start parallel work with rayon,
+/// since rayon's threads never see the value set by [env].
 pub struct BuildEnv<T: 'static, D: Fn() -> T = fn() -> T> {
    id: Lazy<u32>,
    default: D
@@ -68,7 +109,8 @@ impl<T: 'static, D: Fn() -> T> Deref for BuildEnv<T, D> {
 
 #[cfg(test)]
 mod tests {
-   use super::{BuildEnv, env};
+   use super::{BuildEnv, env, scope};
+   use std::panic;
 
    #[test]
    fn id() {
@@ -127,4 +169,42 @@ mod tests {
          assert_eq!(*a_ref, 1);
       });
    }
+
+   #[test]
+   fn scoped_env() {
+      let a = BuildEnv::new(|| 0);
+
+      {
+         let _scope = scope(&a, 1);
+         assert_eq!(*a, 1);
+
+         {
+            let _scope = scope(&a, 2);
+            assert_eq!(*a, 2);
+         }
+
+         assert_eq!(*a, 1);
+      }
+
+      assert_eq!(*a, 0);
+   }
+
+   #[test]
+   fn restore_on_panic() {
+      static A: BuildEnv<i32> = BuildEnv::new(|| 0);
+
+      let result = panic::catch_unwind(|| {
+         let _scope = scope(&A, 1);
+         assert_eq!(*A, 1);
+         panic!();
+      });
+      assert!(result.is_err());
+      assert_eq!(*A, 0);
+
+      let result = panic::catch_unwind(|| {
+         env(&A, 2, || panic!());
+      });
+      assert!(result.is_err());
+      assert_eq!(*A, 0);
+   }
 }
@@ -9,6 +9,18 @@ impl<'a, P: SolidParent + ?Sized> ChildReceiver<'a, P> {
    pub(crate) fn new(parent: &mut P) -> ChildReceiver<P> {
       ChildReceiver { parent }
    }
+
+   /// Pushes the solid only when `condition` is true.
+   /// `solid` is not called otherwise.
+   pub fn push_if<S: Solid + 'static>(
+      &mut self,
+      condition: bool,
+      solid: impl FnOnce() -> S
+   ) {
+      if condition {
+         self.parent.push(solid());
+      }
+   }
 }
 
 impl<'a, P: SolidParent + ?Sized, S: Solid + 'static>
@@ -19,9 +31,31 @@ impl<'a, P: SolidParent + ?Sized, S: Solid + 'static>
    }
 }
 
+/// Pushes the solid if it is `Some`. Does nothing for `None`.
+impl<'a, P: SolidParent + ?Sized, S: Solid + 'static>
+   ShlAssign<Option<S>> for ChildReceiver<'a, P>
+{
+   fn shl_assign(&mut self, rhs: Option<S>) {
+      if let Some(solid) = rhs {
+         self.parent.push(solid);
+      }
+   }
+}
+
+/// Pushes all the solids in order.
+impl<'a, P: SolidParent + ?Sized, S: Solid + 'static>
+   ShlAssign<Vec<S>> for ChildReceiver<'a, P>
+{
+   fn shl_assign(&mut self, rhs: Vec<S>) {
+      for solid in rhs {
+         self.parent.push(solid);
+      }
+   }
+}
+
 #[cfg(test)]
 mod tests {
-   use crate::geometry::{Point, SizeLiteral};
+   use crate::geometry::{Point, Size, SizeLiteral};
    use crate::solid::{Solid, SolidParent};
    use crate::solid::solid_parent::PushBorrowing;
    use crate::stl::{Facet, StlSolid};
@@ -38,7 +72,7 @@ mod tests {
       fn generate_stl_solid(&self) -> StlSolid {
          StlSolid {
             facets: vec![
-               Facet { vertexes: self.0.vertexes }
+               Facet::new(self.0.vertexes())
             ]
          }
       }
@@ -68,28 +102,64 @@ mod tests {
       }
    }
 
+   fn solid(x: i32) -> SolidImpl {
+      SolidImpl::new(
+         Facet::new([
+            Point::new(x.mm(), 0.mm(), 0.mm()),
+            Point::new(x.mm(), 1.mm(), 0.mm()),
+            Point::new(x.mm(), 0.mm(), 1.mm())
+         ])
+      )
+   }
+
+   fn xs(solid_parent: &SolidParentImpl) -> Vec<Size> {
+      solid_parent.generate_stl_solid().facets.iter()
+         .map(|f| f.vertexes()[0].x())
+         .collect()
+   }
+
+   #[test]
+   fn option_and_vec() {
+      let mut solid_parent = SolidParentImpl::new();
+      solid_parent.push_children(|mut p| {
+         p <<= Some(solid(1));
+         p <<= None::<SolidImpl>;
+         p <<= vec![solid(2), solid(3)];
+         p <<= Vec::<SolidImpl>::new();
+      });
+
+      assert_eq!(xs(&solid_parent), vec![1.mm(), 2.mm(), 3.mm()]);
+   }
+
+   #[test]
+   fn push_if() {
+      let mut solid_parent = SolidParentImpl::new();
+      solid_parent.push_children(|mut p| {
+         p.push_if(true, || solid(1));
+         p.push_if(false, || -> SolidImpl { panic!() });
+      });
+
+      assert_eq!(xs(&solid_parent), vec![1.mm()]);
+   }
+
    #[test]
    fn push_children() {
       let mut solid_parent = SolidParentImpl::new();
       solid_parent.push_children(|mut p| {
          p <<= SolidImpl::new(
-            Facet {
-               vertexes: [
-                  Point::new(1.mm(), 2.mm(), 3.mm()),
-                  Point::new(4.mm(), 5.mm(), 6.mm()),
-                  Point::new(7.mm(), 8.mm(), 9.mm())
-               ]
-            }
+            Facet::new([
+               Point::new(1.mm(), 2.mm(), 3.mm()),
+               Point::new(4.mm(), 5.mm(), 6.mm()),
+               Point::new(7.mm(), 8.mm(), 9.mm())
+            ])
          );
 
          p <<= SolidImpl::new(
-            Facet {
-               vertexes: [
-                  Point::new(10.mm(), 11.mm(), 12.mm()),
-                  Point::new(13.mm(), 14.mm(), 15.mm()),
-                  Point::new(16.mm(), 17.mm(), 18.mm())
-               ]
-            }
+            Facet::new([
+               Point::new(10.mm(), 11.mm(), 12.mm()),
+               Point::new(13.mm(), 14.mm(), 15.mm()),
+               Point::new(16.mm(), 17.mm(), 18.mm())
+            ])
          );
       });
 
@@ -104,7 +174,7 @@ mod tests {
       ];
       assert_eq!(
          stl_solid.facets.iter()
-            .flat_map(|f| f.vertexes)
+            .flat_map(|f| f.vertexes())
             .collect::<Vec<_>>(),
          expected
       );
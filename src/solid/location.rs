@@ -1,4 +1,4 @@
-use crate::geometry::{Angle, AngleLiteral, Line, Point, Vector};
+use crate::geometry::{Angle, AngleLiteral, GeometryError, Line, Point, Vector};
 use crate::solid::LocationBuilder;
 use crate::transform::Transform;
 
@@ -48,21 +48,60 @@ impl Location {
       right_vector: Vector,
       back_vector: Vector
    ) -> Location {
-      if right_vector.angle_with(&back_vector) != 90.deg() {
-         panic!("The angle formed by 2 vectors must be 90 degrees.");
-      }
+      Location::try_new(point, right_vector, back_vector)
+         .unwrap_or_else(|_| panic!("The angle formed by 2 vectors must be 90 degrees."))
+   }
 
-      Location {
-         point,
-         right_vector: right_vector.to_unit_vector(),
-         back_vector: back_vector.to_unit_vector()
+   /// Location on `point` with the right and back vectors. Returns
+   /// [GeometryError] if either vector is zero or they are not perpendicular.
+   /// Use [build](Location::build) for other combinations of vectors.
+   pub fn try_new(
+      point: Point,
+      right_vector: Vector,
+      back_vector: Vector
+   ) -> Result<Location, GeometryError> {
+      let right_vector = right_vector.try_to_unit_vector()?;
+      let back_vector = back_vector.try_to_unit_vector()?;
+
+      let angle = right_vector.angle_with(&back_vector);
+      if angle != 90.deg() {
+         return Err(GeometryError::NotPerpendicular(angle));
       }
+
+      Ok(Location { point, right_vector, back_vector })
    }
 
    pub fn build(point: Point) -> LocationBuilder<false, false, false> {
       LocationBuilder::new(point)
    }
 
+   /// Location on `point` whose [top_vector](Location::top_vector) points
+   /// `target`.
+   ///
+   /// The right vector is chosen to be horizontal. Or X-Axis when the top
+   /// vector is parallel to Z-Axis.
+   ///
+   /// Panics when `point` and `target` are the same.
+   pub fn looking_at(point: Point, target: Point) -> Location {
+      if point == target {
+         panic!("Cannot determine the direction since 2 points are the same.");
+      }
+
+      let top_vector = Vector::between(&point, &target);
+
+      let horizontal = Vector::Z_UNIT_VECTOR.vector_product(&top_vector);
+
+      let right_vector = if horizontal == Vector::ZERO {
+         Vector::X_UNIT_VECTOR
+      } else {
+         horizontal
+      };
+
+      Location::build(point)
+         .right_vector(right_vector)
+         .top_vector(top_vector)
+   }
+
    pub fn point(&self) -> Point {
       self.point
    }
@@ -90,6 +129,19 @@ impl Location {
    pub fn top_vector(&self) -> Vector {
       self.right_vector.vector_product(&self.back_vector)
    }
+
+   /// Converts a point relative to this location into the global
+   /// coordinates. X, Y, and Z of `local` are along
+   /// [right_vector](Location::right_vector),
+   /// [back_vector](Location::back_vector), and
+   /// [top_vector](Location::top_vector).
+   pub fn to_global(&self, local: &Point) -> Point {
+      let offset = self.right_vector() * local.x().to_millimeter()
+         + self.back_vector() * local.y().to_millimeter()
+         + self.top_vector() * local.z().to_millimeter();
+
+      self.point.translated(&offset)
+   }
 }
 
 impl Default for Location {
@@ -119,3 +171,34 @@ impl Transform for Location {
       }
    }
 }
+
+#[cfg(test)]
+mod tests {
+   use super::Location;
+   use crate::geometry::{AngleLiteral, GeometryError, Point, SizeLiteral, Vector};
+
+   #[test]
+   fn try_new() {
+      let point = Point::new(1.mm(), 2.mm(), 3.mm());
+
+      let location = Location::try_new(
+         point,
+         Vector::new(2.mm(), 0.mm(), 0.mm()),
+         Vector::new(0.mm(), 0.mm(), 3.mm())
+      ).unwrap();
+      assert_eq!(location.point(), point);
+      assert_eq!(location.back_vector(), Vector::Z_UNIT_VECTOR);
+      assert_eq!(location.top_vector(), -Vector::Y_UNIT_VECTOR);
+
+      let error = Location::try_new(
+         point,
+         Vector::X_UNIT_VECTOR,
+         Vector::new(1.mm(), 1.mm(), 0.mm())
+      ).unwrap_err();
+      assert_eq!(error, GeometryError::NotPerpendicular(45.deg()));
+
+      let error = Location::try_new(point, Vector::X_UNIT_VECTOR, Vector::ZERO)
+         .unwrap_err();
+      assert_eq!(error, GeometryError::ZeroVector);
+   }
+}
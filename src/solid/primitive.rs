@@ -1,9 +1,33 @@
 
+pub(in crate::solid) mod clip;
 pub(in crate::solid) mod cone;
 pub(in crate::solid) mod cube;
 pub(in crate::solid) mod cylinder;
+pub(in crate::solid) mod cylindrical_surface;
+pub(in crate::solid) mod difference;
+pub(in crate::solid) mod group;
+pub(in crate::solid) mod helix;
+pub(in crate::solid) mod high_precision;
+pub(in crate::solid) mod hull;
+pub(in crate::solid) mod intersection;
+pub(in crate::solid) mod loft;
+pub(in crate::solid) mod minkowski;
+pub(in crate::solid) mod named;
+pub(in crate::solid) mod oblique_cylinder;
+pub(in crate::solid) mod pattern;
+pub(in crate::solid) mod place;
+pub(in crate::solid) mod polygon;
+pub(in crate::solid) mod resize;
 pub(in crate::solid) mod rotate;
+pub(in crate::solid) mod rounded_cone;
 pub(in crate::solid) mod scale;
+pub(in crate::solid) mod scope;
+pub(in crate::solid) mod shell;
 pub(in crate::solid) mod sphere;
+pub(in crate::solid) mod spherical_cap;
+#[cfg(test)]
+pub(in crate::solid) mod test_support;
+pub(in crate::solid) mod text;
 pub(in crate::solid) mod translate;
+pub(in crate::solid) mod union;
 pub mod precision;
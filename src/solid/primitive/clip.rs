@@ -0,0 +1,198 @@
+use crate::geometry::{Plane, Point, Size, Vector};
+use crate::solid::{Solid, SolidParent};
+use crate::solid::builder::BuildContext;
+use crate::solid::primitive::polygon::{signed_area, triangulate};
+use crate::solid::solid_parent::PushBorrowing;
+use crate::stl::{Facet, StlSolid};
+use std::cmp::Ordering;
+
+/// Cuts its children by a plane, and keeps only one side.
+///
+/// The side which the normal vector of `plane` points to is kept if
+/// `keep_positive_side` is `true`. The cross section is closed with facets on
+/// the plane. Every child must generate a closed solid, and each cross
+/// section must be a polygon without holes.
+pub struct ClipByPlane {
+   pub plane: Plane,
+   pub keep_positive_side: bool,
+   pub children: Vec<Box<dyn Solid>>
+}
+
+impl ClipByPlane {
+   pub fn new(plane: Plane, keep_positive_side: bool) -> ClipByPlane {
+      ClipByPlane {
+         plane,
+         keep_positive_side,
+         children: vec![]
+      }
+   }
+}
+
+pub fn clip(
+   plane: Plane,
+   keep_positive_side: bool,
+   build_action: impl FnOnce(BuildContext<ClipByPlane>)
+) -> ClipByPlane {
+   BuildContext::build(
+      ClipByPlane::new(plane, keep_positive_side),
+      build_action
+   )
+}
+
+impl Solid for ClipByPlane {
+   fn generate_stl_solid(&self) -> StlSolid {
+      let normal = self.plane.normal_vector().to_unit_vector();
+      let normal = if self.keep_positive_side { normal } else { -normal };
+      let plane = Plane::new(&self.plane.point(), &normal);
+
+      StlSolid {
+         facets: self.children.iter()
+            .flat_map(|c| clip_solid(&c.generate_stl_solid(), &plane))
+            .collect()
+      }
+   }
+}
+
+/// Keeps the positive side of the plane.
+fn clip_solid(stl_solid: &StlSolid, plane: &Plane) -> Vec<Facet> {
+   let mut clipped = StlSolid {
+      facets: stl_solid.facets.iter()
+         .flat_map(|f| clip_facet(f, plane))
+         .collect()
+   };
+
+   let caps: Vec<_> = clipped.hole_borders().iter()
+      .flat_map(|border| cap(border, plane))
+      .collect();
+
+   clipped.facets.extend(caps);
+   clipped.facets
+}
+
+fn clip_facet(facet: &Facet, plane: &Plane) -> Vec<Facet> {
+   let sides = facet.vertexes().map(|v| plane.side_of(&v));
+
+   if sides.iter().all(|&s| s == Ordering::Equal) {
+      // on the plane. Keep it only if it faces toward the removed side
+      let facing = facet.normal_vector().inner_product(plane.normal_vector());
+      return if facing.0 < 0.0 {
+         vec![Facet::new(facet.vertexes())]
+      } else {
+         vec![]
+      };
+   }
+   if !sides.contains(&Ordering::Less) {
+      return vec![Facet::new(facet.vertexes())];
+   }
+   if !sides.contains(&Ordering::Greater) {
+      return vec![];
+   }
+
+   let mut polygon = vec![];
+   for i in 0..3 {
+      let j = (i + 1) % 3;
+      let (pi, pj) = (facet.vertexes()[i], facet.vertexes()[j]);
+
+      if sides[i] != Ordering::Less {
+         polygon.push(pi);
+      }
+
+      // always interpolate from the kept vertex, so that adjacent facets get
+      // the same point on their common edge
+      match (sides[i], sides[j]) {
+         (Ordering::Greater, Ordering::Less) => polygon.push(plane.segment_intersection(&pi, &pj)),
+         (Ordering::Less, Ordering::Greater) => polygon.push(plane.segment_intersection(&pj, &pi)),
+         _ => {}
+      }
+   }
+
+   (1..(polygon.len() - 1))
+      .map(|i| Facet::new([polygon[0], polygon[i], polygon[i + 1]]))
+      .collect()
+}
+
+/// Facets which close a border on the plane. They face opposite to
+/// the normal vector of the plane.
+fn cap(border: &[Point], plane: &Plane) -> Vec<Facet> {
+   // (u, v, normal) is right-handed, so that the cap is counterclockwise in
+   // the plane of (u, v)
+   let (u, v) = Plane::new(&border[0], &-*plane.normal_vector()).basis();
+
+   let origin = border[0];
+   let polygon: Vec<_> = border.iter()
+      .map(|p| {
+         let offset = Vector::between(&origin, p);
+         (
+            Size::from(offset.inner_product(&u) / u.norm()),
+            Size::from(offset.inner_product(&v) / v.norm())
+         )
+      })
+      .collect();
+
+   if signed_area(&polygon).0 < 0.0 {
+      panic!("Clipping a solid whose cross section has holes is not supported.");
+   }
+
+   triangulate(&polygon).into_iter()
+      .map(|[a, b, c]| Facet::new([border[a], border[b], border[c]]))
+      .collect()
+}
+
+impl SolidParent for ClipByPlane {
+   fn push<S: Solid + 'static>(&mut self, child: S) -> &mut S {
+      self.children.push_borrowing(child)
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::clip;
+   use crate::geometry::{Plane, Point, Size, SizeLiteral, Vector};
+   use crate::solid::{cube, sphere, Location, Solid};
+
+   #[test]
+   fn hemisphere() {
+      let sphere_solid = sphere(Location::default(), 10.mm()).generate_stl_solid();
+      let solid = clip(Plane::XY, true, |mut c| {
+         c <<= sphere(Location::default(), 10.mm());
+      }).generate_stl_solid();
+
+      let lowest = solid.facets.iter()
+         .flat_map(|f| f.vertexes())
+         .map(|v| v.z())
+         .min()
+         .unwrap();
+      assert_eq!(lowest, Size::ZERO);
+
+      let cap_facets: Vec<_> = solid.facets.iter()
+         .filter(|f| f.vertexes().iter().all(|v| v.z() == Size::ZERO))
+         .collect();
+      assert!(!cap_facets.is_empty());
+      for f in cap_facets {
+         assert_eq!(f.normal_vector(), -Vector::Z_UNIT_VECTOR);
+      }
+
+      assert!(solid.is_watertight());
+      let ratio = (solid.volume().0 / sphere_solid.volume().0).raw();
+      assert!((ratio - 0.5).abs() < 0.01, "{}", ratio);
+   }
+
+   #[test]
+   fn negative_side() {
+      let plane = Plane::new(
+         &Point::new(0.mm(), 0.mm(), 2.mm()),
+         &Vector::new(1.mm(), 0.mm(), 1.mm())
+      );
+
+      let solid = clip(plane, false, |mut c| {
+         c <<= cube(Location::default(), (2.mm(), 2.mm(), 2.mm()));
+      }).generate_stl_solid();
+
+      // the cut goes diagonally through the cube. The lower half remains
+      assert!(solid.is_watertight());
+      assert!((solid.volume().0.raw() - 4.0).abs() < 1e-9, "{}", solid.volume().0);
+      for v in solid.facets.iter().flat_map(|f| f.vertexes()) {
+         assert!(v.x() + v.z() <= 2.mm(), "{:?}", v);
+      }
+   }
+}
@@ -1,18 +1,53 @@
-use crate::geometry::{Angle, AngleLiteral, Line, Size, Vector};
+use crate::geometry::{Angle, AngleLiteral, Line, Point, Size, Vector};
+use crate::scad::{location_prefix, number_literal, write_line, ScadDescribable};
 use crate::solid::{Location, Solid};
-use crate::solid::precision::FRAGMENT_MINIMUM_ANGLE;
+use crate::solid::precision::{fragment_angle, fragment_count, FragmentSpec};
 use crate::stl::{Facet, StlSolid};
 use crate::transform::Transform;
+use anyhow::Result;
+use std::io::Write;
 
 pub struct Cone {
    pub location: Location,
    pub height: Size,
-   pub bottom_radius: Size
+   pub bottom_radius: Size,
+   pub fragments: Option<FragmentSpec>
 }
 
 impl Cone {
    pub fn new(location: Location, height: Size, bottom_radius: Size) -> Cone {
-      Cone { location, height, bottom_radius }
+      Cone { location, height, bottom_radius, fragments: None }
+   }
+
+   /// Overrides the precision specified by [env](crate::solid::builder::env).
+   /// See [FragmentSpec].
+   pub fn with_fragment_angle(self, angle: Angle) -> Cone {
+      Cone { fragments: Some(FragmentSpec::Angle(angle)), ..self }
+   }
+
+   /// Overrides the precision specified by [env](crate::solid::builder::env).
+   /// See [FragmentSpec].
+   pub fn with_fragment_count(self, count: usize) -> Cone {
+      Cone { fragments: Some(FragmentSpec::Count(count)), ..self }
+   }
+
+   /// Cone whose mid-height is on [Location::point].
+   pub fn centered(location: Location, height: Size, bottom_radius: Size) -> Cone {
+      let location = location
+         .translated_toward(&location.top_vector(), -height / 2);
+
+      Cone::new(location, height, bottom_radius)
+   }
+
+   /// Cone whose bottom center is `bottom` and apex is `apex`.
+   ///
+   /// Panics when `bottom` and `apex` are the same.
+   pub fn between(bottom: Point, apex: Point, bottom_radius: Size) -> Cone {
+      Cone::new(
+         Location::looking_at(bottom, apex),
+         bottom.distance(&apex),
+         bottom_radius
+      )
    }
 }
 
@@ -22,7 +57,7 @@ pub fn cone(location: Location, height: Size, bottom_radius: Size) -> Cone {
 
 impl Solid for Cone {
    fn generate_stl_solid(&self) -> StlSolid {
-      let minimum_angle = *FRAGMENT_MINIMUM_ANGLE;
+      let fragment_angle = fragment_angle(self.bottom_radius, self.fragments);
 
       let back = &self.location.back_vector();
       let top = &self.location.top_vector();
@@ -32,7 +67,7 @@ impl Solid for Cone {
       let top_point = bottom_point.translated_toward(top, height);
 
       let points: Vec<_>
-         = Angle::iterate(0.deg()..360.deg()).step(minimum_angle)
+         = Angle::iterate(0.deg()..360.deg()).step(fragment_angle)
          .map(|a| back.rotated(top, a))
          .map(|v| bottom_point.translated_toward(&v, radius))
          .collect();
@@ -42,11 +77,11 @@ impl Solid for Cone {
       let zipped_points = points.iter().zip(shifted_points);
 
       let bottom_facets = zipped_points.clone().map(|(a, b)|
-         Facet { vertexes: [bottom_point, *b, *a] }
+         Facet::new([bottom_point, *b, *a])
       );
 
       let side_facets = zipped_points.map(|(a, b)|
-         Facet { vertexes: [*a, *b, top_point] }
+         Facet::new([*a, *b, top_point])
       );
 
       StlSolid {
@@ -55,6 +90,29 @@ impl Solid for Cone {
             .collect()
       }
    }
+
+   /// The bottom, and 1 facet for each side.
+   fn facet_count(&self) -> usize {
+      fragment_count(self.bottom_radius, self.fragments) * 2
+   }
+
+   fn as_scad_describable(&self) -> Option<&dyn ScadDescribable> {
+      Some(self)
+   }
+}
+
+impl ScadDescribable for Cone {
+   fn describe_scad(&self, output: &mut dyn Write, indent: usize) -> Result<()> {
+      write_line(
+         output, indent,
+         &format!(
+            "{}cylinder(h={}, r1={}, r2=0);",
+            location_prefix(&self.location),
+            number_literal(self.height.to_millimeter()),
+            number_literal(self.bottom_radius.to_millimeter())
+         )
+      )
+   }
 }
 
 impl Transform for Cone {
@@ -62,7 +120,8 @@ impl Transform for Cone {
       Self {
          location: self.location.translated(offset),
          height: self.height,
-         bottom_radius: self.bottom_radius
+         bottom_radius: self.bottom_radius,
+         fragments: self.fragments
       }
    }
 
@@ -70,18 +129,20 @@ impl Transform for Cone {
       Self {
          location: self.location.rotated(axis, angle),
          height: self.height,
-         bottom_radius: self.bottom_radius
+         bottom_radius: self.bottom_radius,
+         fragments: self.fragments
       }
    }
 }
 
 #[cfg(test)]
 mod tests {
-   use super::cone;
+   use super::{cone, Cone};
    use crate::geometry::{AngleLiteral, Point, SizeLiteral, Vector};
    use crate::solid::{Location, Solid};
    use crate::solid::builder::env;
    use crate::solid::precision::FRAGMENT_MINIMUM_ANGLE;
+   use crate::transform::Transform;
    use noisy_float::prelude::*;
 
    fn fragment_count() -> usize {
@@ -126,7 +187,7 @@ mod tests {
          .iter()
          .enumerate()
          .for_each(|(i, f)| {
-            let [a, b, _] = f.vertexes;
+            let [a, b, _] = f.vertexes();
 
             let expected = Vector::between(&a, &b)
                .vector_product(&Vector::new(-a.x(), -a.y(), 3.mm()))
@@ -136,13 +197,54 @@ mod tests {
          });
    }
 
+   #[test]
+   fn centered() {
+      let expected: Vec<_> = cone(Location::default(), 3.mm(), 5.mm())
+         .generate_stl_solid().facets.iter()
+         .flat_map(|f| f.vertexes())
+         .map(|v| v.translated_toward(&Vector::Z_UNIT_VECTOR, -1.5.mm()))
+         .collect();
+
+      let actual: Vec<_> = Cone::centered(Location::default(), 3.mm(), 5.mm())
+         .generate_stl_solid().facets.iter()
+         .flat_map(|f| f.vertexes())
+         .collect();
+
+      assert_eq!(actual, expected);
+   }
+
+   #[test]
+   fn between() {
+      let expected: Vec<_> = cone(Location::default(), 10.mm(), 5.mm())
+         .generate_stl_solid().facets.iter()
+         .flat_map(|f| f.vertexes())
+         .collect();
+
+      let actual: Vec<_> = Cone::between(
+            Point::ORIGIN,
+            Point::new(0.mm(), 0.mm(), 10.mm()),
+            5.mm()
+         )
+         .generate_stl_solid().facets.iter()
+         .flat_map(|f| f.vertexes())
+         .collect();
+
+      assert_eq!(actual, expected);
+   }
+
+   #[test]
+   #[should_panic]
+   fn between_same_points() {
+      Cone::between(Point::ORIGIN, Point::ORIGIN, 1.mm());
+   }
+
    #[test]
    fn height() {
       let cone = cone(Location::default(), 3.mm(), 5.mm());
       let solid = cone.generate_stl_solid();
 
       solid.facets.iter()
-         .flat_map(|f| f.vertexes)
+         .flat_map(|f| f.vertexes())
          .for_each(|v| assert!(v.z() == 0.mm() || v.z() == 3.mm()));
    }
 
@@ -153,9 +255,21 @@ mod tests {
 
       solid.facets[0..fragment_count()]
          .iter()
-         .flat_map(|f| f.vertexes)
+         .flat_map(|f| f.vertexes())
          .filter(|&v| v != Point::ORIGIN)
          .map(|v| Vector::between(&Point::ORIGIN, &v))
          .for_each(|v| assert_eq!(v.norm(), 5.mm()));
    }
+
+   #[test]
+   fn facet_count() {
+      let cone = cone(Location::default(), 5.mm(), 2.mm());
+      assert_eq!(cone.facet_count(), cone.generate_stl_solid().facet_count());
+
+      for angle in [7.deg(), 45.deg(), 100.deg()] {
+         let cone = Cone::new(Location::default(), 5.mm(), 2.mm())
+            .with_fragment_angle(angle);
+         assert_eq!(cone.facet_count(), cone.generate_stl_solid().facet_count());
+      }
+   }
 }
@@ -1,17 +1,107 @@
-use crate::geometry::{Angle, Line, Size, Vector};
+use crate::geometry::{Angle, BoundingBox, Line, Point, Size, Vector};
+use crate::math::unit::Exp;
+use crate::scad::{location_prefix, vector_literal, write_line, ScadDescribable};
 use crate::solid::{Location, Solid};
 use crate::stl::{Facet, StlSolid};
 use crate::transform::Transform;
+use anyhow::Result;
+use std::io::Write;
 
+/// Rectangular parallelepiped.
+///
+/// By default, [Location::point] is the left-front-bottom corner and the cube
+/// grows toward right, back, and top.
+/// Use [Cube::anchored] to place [Location::point] on another position.
 pub struct Cube {
    location: Location,
    size: (Size, Size, Size)
 }
 
+/// Positions on a [Cube]. Corners, midpoints of edges, centers of faces,
+/// and the center of the cube.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CubeAnchor {
+   LeftFrontBottom, FrontBottom, RightFrontBottom,
+   LeftBottom,      Bottom,      RightBottom,
+   LeftBackBottom,  BackBottom,  RightBackBottom,
+
+   LeftFront,       Front,       RightFront,
+   Left,            Center,      Right,
+   LeftBack,        Back,        RightBack,
+
+   LeftFrontTop,    FrontTop,    RightFrontTop,
+   LeftTop,         Top,         RightTop,
+   LeftBackTop,     BackTop,     RightBackTop
+}
+
+impl CubeAnchor {
+   /// returns the position as the numbers of halves of the size
+   /// from the left-front-bottom corner.
+   fn halves(self) -> (i32, i32, i32) {
+      use CubeAnchor::*;
+      match self {
+         LeftFrontBottom => (0, 0, 0), FrontBottom => (1, 0, 0), RightFrontBottom => (2, 0, 0),
+         LeftBottom      => (0, 1, 0), Bottom      => (1, 1, 0), RightBottom      => (2, 1, 0),
+         LeftBackBottom  => (0, 2, 0), BackBottom  => (1, 2, 0), RightBackBottom  => (2, 2, 0),
+
+         LeftFront       => (0, 0, 1), Front       => (1, 0, 1), RightFront       => (2, 0, 1),
+         Left            => (0, 1, 1), Center      => (1, 1, 1), Right            => (2, 1, 1),
+         LeftBack        => (0, 2, 1), Back        => (1, 2, 1), RightBack        => (2, 2, 1),
+
+         LeftFrontTop    => (0, 0, 2), FrontTop    => (1, 0, 2), RightFrontTop    => (2, 0, 2),
+         LeftTop         => (0, 1, 2), Top         => (1, 1, 2), RightTop         => (2, 1, 2),
+         LeftBackTop     => (0, 2, 2), BackTop     => (1, 2, 2), RightBackTop     => (2, 2, 2)
+      }
+   }
+}
+
 impl Cube {
    pub fn new(location: Location, size: (Size, Size, Size)) -> Cube {
       Cube { location, size }
    }
+
+   /// Cube whose center is on [Location::point].
+   pub fn centered(location: Location, size: (Size, Size, Size)) -> Cube {
+      Cube::new(location, size).anchored(CubeAnchor::Center)
+   }
+
+   /// Axis-aligned cube whose opposite corners are the specified points.
+   pub fn from_corners(a: &Point, b: &Point) -> Cube {
+      let min = Point::new(a.x().min(b.x()), a.y().min(b.y()), a.z().min(b.z()));
+      let size = Vector::between(a, b).abs();
+
+      Cube::new(
+         Location::default().translated(&Vector::between(&Point::ORIGIN, &min)),
+         (size.x(), size.y(), size.z())
+      )
+   }
+
+   /// Moves this cube so that the specified anchor is on the current
+   /// [Location::point].
+   pub fn anchored(self, anchor: CubeAnchor) -> Cube {
+      let (x, y, z) = anchor.halves();
+      let (size_x, size_y, size_z) = self.size;
+
+      let anchor_point = self.location.point()
+         .translated_toward(&self.location.right_vector(), size_x * x / 2)
+         .translated_toward(&self.location.back_vector(),  size_y * y / 2)
+         .translated_toward(&self.location.top_vector(),   size_z * z / 2);
+
+      let offset = Vector::between(&anchor_point, &self.location.point());
+
+      Cube {
+         location: self.location.translated(&offset),
+         size: self.size
+      }
+   }
+
+   /// The point at `(x, y, z)` in the coordinates of [Location].
+   fn point_at(&self, x: Size, y: Size, z: Size) -> Point {
+      self.location.point()
+         .translated_toward(&self.location.right_vector(), x)
+         .translated_toward(&self.location.back_vector(), y)
+         .translated_toward(&self.location.top_vector(), z)
+   }
 }
 
 pub fn cube(location: Location, size: (Size, Size, Size)) -> Cube {
@@ -20,17 +110,7 @@ pub fn cube(location: Location, size: (Size, Size, Size)) -> Cube {
 
 impl Solid for Cube {
    fn generate_stl_solid(&self) -> StlSolid {
-      let point = self.location.point();
-      let right_vector = self.location.right_vector();
-      let back_vector = self.location.back_vector();
-      let top_vector = self.location.top_vector();
-
-      let p = |x, y, z| {
-         point
-            .translated_toward(&right_vector, x)
-            .translated_toward(&back_vector, y)
-            .translated_toward(&top_vector, z)
-      };
+      let p = |x, y, z| self.point_at(x, y, z);
 
       let size_0 = Size::ZERO;
       let (size_x, size_y, size_z) = self.size;
@@ -46,21 +126,57 @@ impl Solid for Cube {
 
       StlSolid {
          facets: vec![
-            Facet { vertexes: [left_front_bottom, left_back_bottom, right_back_bottom] },
-            Facet { vertexes: [right_back_bottom, right_front_bottom, left_front_bottom] },
-            Facet { vertexes: [left_front_bottom, right_front_bottom, right_front_top] },
-            Facet { vertexes: [right_front_top, left_front_top, left_front_bottom] },
-            Facet { vertexes: [right_front_bottom, right_back_bottom, right_front_top] },
-            Facet { vertexes: [right_back_top, right_front_top, right_back_bottom] },
-            Facet { vertexes: [right_back_bottom, left_back_bottom, right_back_top] },
-            Facet { vertexes: [left_back_top, right_back_top, left_back_bottom] },
-            Facet { vertexes: [left_back_bottom, left_front_bottom, left_back_top] },
-            Facet { vertexes: [left_front_top, left_back_top, left_front_bottom] },
-            Facet { vertexes: [left_front_top, right_front_top, right_back_top] },
-            Facet { vertexes: [right_back_top, left_back_top, left_front_top] }
+            Facet::new([left_front_bottom, left_back_bottom, right_back_bottom]),
+            Facet::new([right_back_bottom, right_front_bottom, left_front_bottom]),
+            Facet::new([left_front_bottom, right_front_bottom, right_front_top]),
+            Facet::new([right_front_top, left_front_top, left_front_bottom]),
+            Facet::new([right_front_bottom, right_back_bottom, right_front_top]),
+            Facet::new([right_back_top, right_front_top, right_back_bottom]),
+            Facet::new([right_back_bottom, left_back_bottom, right_back_top]),
+            Facet::new([left_back_top, right_back_top, left_back_bottom]),
+            Facet::new([left_back_bottom, left_front_bottom, left_back_top]),
+            Facet::new([left_front_top, left_back_top, left_front_bottom]),
+            Facet::new([left_front_top, right_front_top, right_back_top]),
+            Facet::new([right_back_top, left_back_top, left_front_top])
          ]
       }
    }
+
+   fn approximate_volume(&self) -> Option<Exp<Size, 3>> {
+      let (x, y, z) = self.size;
+      Some(x * y * z)
+   }
+
+   fn facet_count(&self) -> usize {
+      12
+   }
+
+   /// Computed from the corners without generating facets.
+   fn bounding_box(&self) -> Option<BoundingBox> {
+      let (size_x, size_y, size_z) = self.size;
+      let corners = [Size::ZERO, size_x].into_iter()
+         .flat_map(|x| [Size::ZERO, size_y].map(|y| (x, y)))
+         .flat_map(|(x, y)| [Size::ZERO, size_z].map(|z| self.point_at(x, y, z)));
+
+      BoundingBox::of_points(corners)
+   }
+
+   fn as_scad_describable(&self) -> Option<&dyn ScadDescribable> {
+      Some(self)
+   }
+}
+
+impl ScadDescribable for Cube {
+   fn describe_scad(&self, output: &mut dyn Write, indent: usize) -> Result<()> {
+      let (x, y, z) = self.size;
+      write_line(
+         output, indent,
+         &format!(
+            "{}cube({});",
+            location_prefix(&self.location), vector_literal(x, y, z)
+         )
+      )
+   }
 }
 
 impl Transform for Cube {
@@ -81,10 +197,116 @@ impl Transform for Cube {
 
 #[cfg(test)]
 mod tests {
-   use super::cube;
-   use crate::geometry::{Point, SizeLiteral, Vector};
+   use super::{cube, Cube, CubeAnchor};
+   use crate::geometry::{AngleLiteral, BoundingBox, Line, Point, SizeLiteral, Vector};
+   use crate::math::unit::Exp;
    use crate::solid::{Location, Solid};
    use crate::stl::Facet;
+   use crate::transform::Transform;
+   use noisy_float::prelude::*;
+
+   fn vertexes(cube: &Cube) -> Vec<Point> {
+      let mut vertexes: Vec<_> = cube.generate_stl_solid().facets.iter()
+         .flat_map(|f| f.vertexes())
+         .collect();
+
+      vertexes.sort_by(|a, b|
+         a.x().cmp(&b.x())
+            .then(a.y().cmp(&b.y()))
+            .then(a.z().cmp(&b.z()))
+      );
+      vertexes.dedup();
+      vertexes
+   }
+
+   fn box_vertexes(min: (f64, f64, f64), max: (f64, f64, f64)) -> Vec<Point> {
+      let mut vertexes = vec![];
+      for x in [min.0, max.0] {
+         for y in [min.1, max.1] {
+            for z in [min.2, max.2] {
+               vertexes.push(Point::new(x.mm(), y.mm(), z.mm()));
+            }
+         }
+      }
+      vertexes
+   }
+
+   #[test]
+   fn centered() {
+      let cube = Cube::centered(Location::default(), (1.mm(), 1.mm(), 1.mm()));
+      assert_eq!(
+         vertexes(&cube),
+         box_vertexes((-0.5, -0.5, -0.5), (0.5, 0.5, 0.5))
+      );
+
+      let cube = Cube::centered(Location::default(), (2.mm(), 4.mm(), 6.mm()));
+      assert_eq!(
+         vertexes(&cube),
+         box_vertexes((-1.0, -2.0, -3.0), (1.0, 2.0, 3.0))
+      );
+
+      let location = Location::default()
+         .rotated(&Line::Z_AXIS, 90.deg())
+         .translated(&Vector::new(10.mm(), 0.mm(), 0.mm()));
+      let cube = Cube::centered(location, (2.mm(), 4.mm(), 6.mm()));
+      assert_eq!(
+         vertexes(&cube),
+         box_vertexes((8.0, -1.0, -3.0), (12.0, 1.0, 3.0))
+      );
+   }
+
+   #[test]
+   fn from_corners() {
+      let cube = Cube::from_corners(
+         &Point::new(3.mm(), 1.mm(), 5.mm()),
+         &Point::new(1.mm(), 4.mm(), 2.mm())
+      );
+      assert_eq!(
+         vertexes(&cube),
+         box_vertexes((1.0, 1.0, 2.0), (3.0, 4.0, 5.0))
+      );
+
+      let cube = Cube::from_corners(
+         &Point::new(1.mm(), 2.mm(), 3.mm()),
+         &Point::new(4.mm(), 6.mm(), 8.mm())
+      );
+      assert_eq!(cube.size, (3.mm(), 4.mm(), 5.mm()));
+      assert_eq!(cube.location.point(), Point::new(1.mm(), 2.mm(), 3.mm()));
+   }
+
+   #[test]
+   fn anchored() {
+      let size = (2.mm(), 4.mm(), 6.mm());
+
+      let cube = Cube::new(Location::default(), size)
+         .anchored(CubeAnchor::LeftFrontBottom);
+      assert_eq!(
+         vertexes(&cube),
+         box_vertexes((0.0, 0.0, 0.0), (2.0, 4.0, 6.0))
+      );
+
+      let cube = Cube::new(Location::default(), size)
+         .anchored(CubeAnchor::RightBackTop);
+      assert_eq!(
+         vertexes(&cube),
+         box_vertexes((-2.0, -4.0, -6.0), (0.0, 0.0, 0.0))
+      );
+
+      let cube = Cube::new(Location::default(), size)
+         .anchored(CubeAnchor::FrontTop);
+      assert_eq!(
+         vertexes(&cube),
+         box_vertexes((-1.0, 0.0, -6.0), (1.0, 4.0, 0.0))
+      );
+
+      let location = Location::default().rotated(&Line::Z_AXIS, 90.deg());
+      let cube = Cube::new(location, size)
+         .anchored(CubeAnchor::RightBackTop);
+      assert_eq!(
+         vertexes(&cube),
+         box_vertexes((0.0, -2.0, -6.0), (4.0, 0.0, 0.0))
+      );
+   }
 
    #[test]
    fn planes() {
@@ -94,7 +316,7 @@ mod tests {
          expected_normal_vector: &Vector
       ) {
          assert!(
-            facet.vertexes.iter().all(|v| expected_points.contains(v))
+            facet.vertexes().iter().all(|v| expected_points.contains(v))
          );
 
          assert_eq!(
@@ -160,4 +382,31 @@ mod tests {
       assert_plane(&solid.facets[10], &expected_points, &Vector::Z_UNIT_VECTOR);
       assert_plane(&solid.facets[11], &expected_points, &Vector::Z_UNIT_VECTOR);
    }
+
+   #[test]
+   fn approximate_volume() {
+      let cube = cube(Location::default(), (2.mm(), 3.mm(), 4.mm()));
+      assert_eq!(cube.approximate_volume(), Some(unsafe { Exp::new(n64(24.0)) }));
+      assert_eq!(cube.approximate_volume(), Some(cube.generate_stl_solid().volume()));
+   }
+
+   #[test]
+   fn facet_count() {
+      let cube = cube(Location::default(), (1.mm(), 2.mm(), 3.mm()));
+      assert_eq!(cube.facet_count(), cube.generate_stl_solid().facet_count());
+   }
+
+   #[test]
+   fn bounding_box() {
+      let cube = cube(Location::default(), (1.mm(), 2.mm(), 3.mm()))
+         .translated(&Vector::new(1.mm(), 1.mm(), 1.mm()));
+      let expected = BoundingBox::new(
+         Point::new(1.mm(), 1.mm(), 1.mm()),
+         Point::new(2.mm(), 3.mm(), 4.mm())
+      );
+      assert_eq!(cube.bounding_box(), Some(expected));
+
+      let rotated = cube.rotated(&Line::Z_AXIS, 30.deg());
+      assert_eq!(rotated.bounding_box(), rotated.generate_stl_solid().bounding_box());
+   }
 }
@@ -1,21 +1,98 @@
-use crate::geometry::{Angle, AngleLiteral, Line, Size, Vector};
+use crate::geometry::{Angle, AngleLiteral, BoundingBox, Line, Point, Size, Vector};
+use crate::math::unit::Exp;
+use crate::scad::{location_prefix, number_literal, write_line, ScadDescribable};
 use crate::solid::{Location, Solid};
-use crate::solid::precision::FRAGMENT_MINIMUM_ANGLE;
+use crate::solid::precision::{
+   circle_perimeter, fragment_angle, fragment_count, FragmentSpec
+};
 use crate::stl::{Facet, StlSolid};
 use crate::transform::Transform;
+use anyhow::Result;
 use rayon::prelude::{
    IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator
 };
+use std::f64::consts::PI;
+use std::io::Write;
 
+/// Cylinder, or conical frustum if [top_radius](Cylinder::top_radius) is
+/// specified. Like `cylinder(r1=, r2=)` of OpenSCAD, a radius of 0 makes
+/// a cone.
 pub struct Cylinder {
    pub location: Location,
    pub height: Size,
-   pub radius: Size
+   /// The radius of the bottom.
+   pub radius: Size,
+   /// The radius of the top. `None` for the same as [radius](Cylinder::radius).
+   pub top_radius: Option<Size>,
+   pub fragments: Option<FragmentSpec>
 }
 
 impl Cylinder {
    pub fn new(location: Location, height: Size, radius: Size) -> Cylinder {
-      Cylinder { location, height, radius }
+      Cylinder { location, height, radius, top_radius: None, fragments: None }
+   }
+
+   /// Conical frustum. `top_radius` of 0 makes a cone, the same as
+   /// [Cone](crate::solid::Cone).
+   pub fn tapered(
+      location: Location,
+      height: Size,
+      bottom_radius: Size,
+      top_radius: Size
+   ) -> Cylinder {
+      Cylinder {
+         location,
+         height,
+         radius: bottom_radius,
+         top_radius: Some(top_radius),
+         fragments: None
+      }
+   }
+
+   /// Overrides the precision specified by [env](crate::solid::builder::env).
+   /// See [FragmentSpec].
+   pub fn with_fragment_angle(self, angle: Angle) -> Cylinder {
+      Cylinder { fragments: Some(FragmentSpec::Angle(angle)), ..self }
+   }
+
+   /// Overrides the precision specified by [env](crate::solid::builder::env).
+   /// See [FragmentSpec].
+   pub fn with_fragment_count(self, count: usize) -> Cylinder {
+      Cylinder { fragments: Some(FragmentSpec::Count(count)), ..self }
+   }
+
+   /// Cylinder whose mid-height is on [Location::point].
+   pub fn centered(location: Location, height: Size, radius: Size) -> Cylinder {
+      let location = location
+         .translated_toward(&location.top_vector(), -height / 2);
+
+      Cylinder::new(location, height, radius)
+   }
+
+   /// Cylinder whose bottom center is `a` and top center is `b`.
+   ///
+   /// Panics when `a` and `b` are the same.
+   pub fn between(a: Point, b: Point, radius: Size) -> Cylinder {
+      Cylinder::new(Location::looking_at(a, b), a.distance(&b), radius)
+   }
+
+   /// The perimeter of the bottom of the generated solid. Shorter than `2πr`
+   /// since the circle is approximated by a polygon. See [circle_perimeter].
+   pub fn side_perimeter(&self) -> Size {
+      circle_perimeter(self.radius, self.fragment_angle())
+   }
+
+   fn actual_top_radius(&self) -> Size {
+      self.top_radius.unwrap_or(self.radius)
+   }
+
+   /// The larger circle determines the precision.
+   fn fragment_radius(&self) -> Size {
+      self.radius.max(self.actual_top_radius())
+   }
+
+   fn fragment_angle(&self) -> Angle {
+      fragment_angle(self.fragment_radius(), self.fragments)
    }
 }
 
@@ -23,52 +100,107 @@ pub fn cylinder(location: Location, height: Size, radius: Size) -> Cylinder {
    Cylinder::new(location, height, radius)
 }
 
-impl Solid for Cylinder {
-   fn generate_stl_solid(&self) -> StlSolid {
-      let minimum_angle = *FRAGMENT_MINIMUM_ANGLE;
+/// Bounding box of the circle around `center` on the plane perpendicular to
+/// the unit vector `normal`. Along each axis, the circle extends
+/// `r × √(1 - n²)` where `n` is the component of `normal`.
+fn circle_bounding_box(center: Point, normal: &Vector, radius: Size) -> BoundingBox {
+   let extent = |n: Size| radius * (1.0 - n.as_mm_f64().powi(2)).max(0.0).sqrt();
+   let offset = Vector::new(extent(normal.x()), extent(normal.y()), extent(normal.z()));
+   BoundingBox::new(center.translated(&-offset), center.translated(&offset))
+}
 
-      let back = &self.location.back_vector();
-      let top = &self.location.top_vector();
-      let radius = self.radius;
-      let height = self.height;
-      let bottom_point = self.location.point();
-      let top_point = bottom_point.translated_toward(top, height);
+/// The 2 circles of a cylinder. Both
+/// [generate_stl_solid](Solid::generate_stl_solid) and
+/// [generate_facets_lazy](Solid::generate_facets_lazy) generate facets from
+/// the points at each angle through this.
+#[derive(Clone, Copy)]
+struct Circles {
+   back: Vector,
+   top: Vector,
+   bottom_point: Point,
+   top_point: Point,
+   radius: Size,
+   top_radius: Size
+}
 
-      let bottom_points: Vec<_>
-         = Angle::par_iterate(0.deg()..360.deg()).step(minimum_angle)
-         .map(|a| back.rotated(top, a))
-         .map(|v| bottom_point.translated_toward(&v, radius))
-         .collect();
+impl Circles {
+   fn new(cylinder: &Cylinder) -> Circles {
+      let top = cylinder.location.top_vector();
+      let bottom_point = cylinder.location.point();
+
+      Circles {
+         back: cylinder.location.back_vector(),
+         top,
+         bottom_point,
+         top_point: bottom_point.translated_toward(&top, cylinder.height),
+         radius: cylinder.radius,
+         top_radius: cylinder.actual_top_radius()
+      }
+   }
 
-      let top_points: Vec<_>
-         = bottom_points.par_iter()
-         .map(|p| p.translated_toward(top, height))
-         .collect();
+   /// The points on the bottom circle and the top circle at the angle.
+   fn points_at(&self, angle: Angle) -> [Point; 2] {
+      let direction = self.back.rotated(&self.top, angle);
+      [
+         self.bottom_point.translated_toward(&direction, self.radius),
+         self.top_point.translated_toward(&direction, self.top_radius)
+      ]
+   }
 
-      let first_bottom = bottom_points.first();
-      let shifted_bottom = bottom_points.par_iter().skip(1).chain(first_bottom);
-      let zipped_bottom_points = bottom_points.par_iter().zip(shifted_bottom);
+   /// The facet of the bottom between the points at 2 adjacent angles.
+   /// `None` for a bottom of radius 0.
+   fn bottom_facet(&self, [[a, _], [b, _]]: [[Point; 2]; 2]) -> Option<Facet> {
+      (self.radius != Size::ZERO)
+         .then(|| Facet::new([self.bottom_point, b, a]))
+   }
 
-      let first_top = top_points.first();
-      let shifted_top = top_points.par_iter().skip(1).chain(first_top);
-      let zipped_top_points = top_points.par_iter().zip(shifted_top);
+   /// The facet of the top between the points at 2 adjacent angles.
+   /// `None` for a top of radius 0.
+   fn top_facet(&self, [[_, a], [_, b]]: [[Point; 2]; 2]) -> Option<Facet> {
+      (self.top_radius != Size::ZERO)
+         .then(|| Facet::new([self.top_point, a, b]))
+   }
 
-      let bottom_facets = zipped_bottom_points.clone().map(|(a, b)|
-         Facet { vertexes: [bottom_point, *b, *a] }
-      );
+   /// Facets of the side between the points at 2 adjacent angles. A circle of
+   /// radius 0 has its center as both points.
+   fn side_facets(
+      &self,
+      [[bottom_a, top_a], [bottom_b, top_b]]: [[Point; 2]; 2]
+   ) -> Vec<Facet> {
+      if self.top_radius == Size::ZERO {
+         vec![Facet::new([bottom_a, bottom_b, top_a])]
+      } else if self.radius == Size::ZERO {
+         vec![Facet::new([bottom_a, top_b, top_a])]
+      } else {
+         vec![
+            Facet::new([bottom_a, top_b, top_a]),
+            Facet::new([top_b, bottom_a, bottom_b])
+         ]
+      }
+   }
+}
 
-      let top_facets = zipped_top_points.clone().map(|(a, b)|
-         Facet { vertexes: [top_point, *a, *b] }
-      );
+impl Solid for Cylinder {
+   fn generate_stl_solid(&self) -> StlSolid {
+      let circles = Circles::new(self);
 
-      let side_facets
-         = zipped_bottom_points.zip(zipped_top_points)
-         .flat_map(|((bottom_a, bottom_b), (top_a, top_b))|
-            [
-               Facet { vertexes: [*bottom_a, *top_b, *top_a] },
-               Facet { vertexes: [*top_b, *bottom_a, *bottom_b] }
-            ]
-         );
+      let points: Vec<_>
+         = Angle::par_iterate(0.deg()..360.deg()).step(self.fragment_angle())
+         .map(|a| circles.points_at(a))
+         .collect();
+
+      let first = points.first();
+      let shifted = points.par_iter().skip(1).chain(first);
+      let fragments = points.par_iter().zip(shifted).map(|(a, b)| [*a, *b]);
+
+      let bottom_facets = fragments.clone()
+         .filter_map(|f| circles.bottom_facet(f));
+
+      let side_facets = fragments.clone()
+         .flat_map(|f| circles.side_facets(f));
+
+      let top_facets = fragments
+         .filter_map(|f| circles.top_facet(f));
 
       StlSolid {
          facets: bottom_facets
@@ -77,6 +209,91 @@ impl Solid for Cylinder {
             .collect()
       }
    }
+
+   fn generate_facets_lazy(&self) -> Box<dyn Iterator<Item = Facet> + '_> {
+      let circles = Circles::new(self);
+
+      let angles = Angle::iterate(0.deg()..360.deg()).step(self.fragment_angle());
+      let fragments = angles.clone().zip(angles.skip(1).chain([0.deg()]))
+         .map(move |(a, b)| [circles.points_at(a), circles.points_at(b)]);
+
+      let bottom_facets = fragments.clone()
+         .filter_map(move |f| circles.bottom_facet(f));
+
+      let side_facets = fragments.clone()
+         .flat_map(move |f| circles.side_facets(f));
+
+      let top_facets = fragments
+         .filter_map(move |f| circles.top_facet(f));
+
+      Box::new(
+         bottom_facets
+            .chain(side_facets)
+            .chain(top_facets)
+      )
+   }
+
+   fn approximate_volume(&self) -> Option<Exp<Size, 3>> {
+      let r1 = self.radius;
+      let r2 = self.actual_top_radius();
+      Some((r1 * r1 + r1 * r2 + r2 * r2) * self.height * (PI / 3.0))
+   }
+
+   /// The bottom, the top, and 2 facets for each side. A circle of radius 0
+   /// has neither its face nor the second facets of the side, so a cylinder
+   /// of radius 0 has only the degenerate first facets of the side.
+   fn facet_count(&self) -> usize {
+      let fragment_count = fragment_count(self.fragment_radius(), self.fragments);
+      let collapsed_count = [self.radius, self.actual_top_radius()].iter()
+         .filter(|&&r| r == Size::ZERO)
+         .count();
+
+      match collapsed_count {
+         0 => fragment_count * 4,
+         1 => fragment_count * 2,
+         _ => fragment_count
+      }
+   }
+
+   /// The union of the boxes of the 2 circles, computed without generating
+   /// facets. Contains the generated facets, whose vertexes are on the
+   /// circles.
+   fn bounding_box(&self) -> Option<BoundingBox> {
+      let top = self.location.top_vector();
+      let bottom_point = self.location.point();
+      let top_point = bottom_point.translated_toward(&top, self.height);
+
+      let bottom = circle_bounding_box(bottom_point, &top, self.radius);
+      let top = circle_bounding_box(top_point, &top, self.actual_top_radius());
+      Some(bottom.union(&top))
+   }
+
+   fn as_scad_describable(&self) -> Option<&dyn ScadDescribable> {
+      Some(self)
+   }
+}
+
+impl ScadDescribable for Cylinder {
+   fn describe_scad(&self, output: &mut dyn Write, indent: usize) -> Result<()> {
+      let radius = match self.top_radius {
+         Some(top_radius) if top_radius != self.radius => format!(
+            "r1={}, r2={}",
+            number_literal(self.radius.to_millimeter()),
+            number_literal(top_radius.to_millimeter())
+         ),
+         _ => format!("r={}", number_literal(self.radius.to_millimeter()))
+      };
+
+      write_line(
+         output, indent,
+         &format!(
+            "{}cylinder(h={}, {});",
+            location_prefix(&self.location),
+            number_literal(self.height.to_millimeter()),
+            radius
+         )
+      )
+   }
 }
 
 impl Transform for Cylinder {
@@ -84,7 +301,9 @@ impl Transform for Cylinder {
       Cylinder {
          location: self.location.translated(offset),
          height: self.height,
-         radius: self.radius
+         radius: self.radius,
+         top_radius: self.top_radius,
+         fragments: self.fragments
       }
    }
 
@@ -92,17 +311,24 @@ impl Transform for Cylinder {
       Cylinder {
          location: self.location.rotated(axis, angle),
          height: self.height,
-         radius: self.radius
+         radius: self.radius,
+         top_radius: self.top_radius,
+         fragments: self.fragments
       }
    }
 }
 
 #[cfg(test)]
 mod tests {
-   use crate::geometry::{AngleLiteral, Point, SizeLiteral, Vector};
-   use crate::solid::{cylinder, Location, Solid};
+   use crate::geometry::{AngleLiteral, BoundingBox, Line, Point, SizeLiteral, Vector};
+   use crate::math::unit::Exp;
+   use crate::scad::ScadDescribable;
+   use crate::solid::{cone, cylinder, Cylinder, Location, Solid};
    use crate::solid::builder::env;
-   use crate::solid::precision::FRAGMENT_MINIMUM_ANGLE;
+   use crate::solid::precision::{
+      FRAGMENT_MAXIMUM_DEVIATION, FRAGMENT_MINIMUM_ANGLE
+   };
+   use crate::transform::Transform;
    use noisy_float::prelude::*;
 
    fn fragment_count() -> usize {
@@ -133,6 +359,34 @@ mod tests {
       });
    }
 
+   #[test]
+   fn fragments() {
+      env(&FRAGMENT_MINIMUM_ANGLE, 90.deg(), || {
+         let solid = cylinder(Location::default(), 3.mm(), 5.mm())
+            .with_fragment_count(10)
+            .generate_stl_solid();
+         assert_eq!(solid.facets.len(), 10 * 4);
+
+         let solid = cylinder(Location::default(), 3.mm(), 5.mm())
+            .with_fragment_angle(30.deg())
+            .generate_stl_solid();
+         assert_eq!(solid.facets.len(), 12 * 4);
+      });
+   }
+
+   #[test]
+   fn fragment_maximum_deviation() {
+      env(&FRAGMENT_MAXIMUM_DEVIATION, 0.1.mm(), || {
+         let small = cylinder(Location::default(), 3.mm(), 1.mm())
+            .generate_stl_solid();
+         let large = cylinder(Location::default(), 3.mm(), 100.mm())
+            .generate_stl_solid();
+
+         assert_eq!(small.facets.len(), fragment_count() * 4);
+         assert!(large.facets.len() > small.facets.len());
+      });
+   }
+
    #[test]
    fn normal_vector() {
       let cylinder = cylinder(Location::default(), 3.mm(), 5.mm());
@@ -153,10 +407,10 @@ mod tests {
          .enumerate()
          .for_each(|(i, facet)| {
             let (a, b, pos) = if i % 2 == 0 {
-               let [_, b, a] = facet.vertexes;
+               let [_, b, a] = facet.vertexes();
                (a, b, format!("{i} top"))
             } else {
-               let [_, a, b] = facet.vertexes;
+               let [_, a, b] = facet.vertexes();
                (a, b, format!("{i} bottom"))
             };
 
@@ -168,13 +422,82 @@ mod tests {
          });
    }
 
+   #[test]
+   fn centered() {
+      let expected: Vec<_> = cylinder(Location::default(), 3.mm(), 5.mm())
+         .generate_stl_solid().facets.iter()
+         .flat_map(|f| f.vertexes())
+         .map(|v| v.translated_toward(&Vector::Z_UNIT_VECTOR, -1.5.mm()))
+         .collect();
+
+      let actual: Vec<_> = Cylinder::centered(Location::default(), 3.mm(), 5.mm())
+         .generate_stl_solid().facets.iter()
+         .flat_map(|f| f.vertexes())
+         .collect();
+
+      assert_eq!(actual, expected);
+   }
+
+   #[test]
+   fn between() {
+      let expected: Vec<_> = cylinder(Location::default(), 10.mm(), 5.mm())
+         .generate_stl_solid().facets.iter()
+         .flat_map(|f| f.vertexes())
+         .collect();
+
+      let actual: Vec<_> = Cylinder::between(
+            Point::ORIGIN,
+            Point::new(0.mm(), 0.mm(), 10.mm()),
+            5.mm()
+         )
+         .generate_stl_solid().facets.iter()
+         .flat_map(|f| f.vertexes())
+         .collect();
+
+      assert_eq!(actual, expected);
+
+      let cylinder = Cylinder::between(
+         Point::new(1.mm(), 2.mm(), 3.mm()),
+         Point::new(4.mm(), 2.mm(), 3.mm()),
+         1.mm()
+      );
+      assert_eq!(cylinder.height, 3.mm());
+      assert_eq!(cylinder.location.top_vector(), Vector::X_UNIT_VECTOR);
+   }
+
+   #[test]
+   #[should_panic]
+   fn between_same_points() {
+      Cylinder::between(Point::ORIGIN, Point::ORIGIN, 1.mm());
+   }
+
+   #[test]
+   fn facets_lazy() {
+      let cylinders = [
+         cylinder(Location::default(), 3.mm(), 5.mm()),
+         Cylinder::tapered(Location::default(), 3.mm(), 5.mm(), 2.mm()),
+         Cylinder::tapered(Location::default(), 3.mm(), 5.mm(), 0.mm()),
+         Cylinder::tapered(Location::default(), 3.mm(), 0.mm(), 5.mm())
+      ];
+
+      for cylinder in cylinders {
+         let expected = cylinder.generate_stl_solid();
+         let actual: Vec<_> = cylinder.generate_facets_lazy().collect();
+
+         assert_eq!(actual.len(), expected.facet_count());
+         for (i, (a, e)) in actual.iter().zip(expected.facets()).enumerate() {
+            assert_eq!(a.vertexes(), e.vertexes(), "at facet {i}");
+         }
+      }
+   }
+
    #[test]
    fn height() {
       let cylinder = cylinder(Location::default(), 3.mm(), 5.mm());
       let solid = cylinder.generate_stl_solid();
 
       solid.facets.iter()
-         .flat_map(|f| f.vertexes)
+         .flat_map(|f| f.vertexes())
          .for_each(|v| assert!(v.z() == 0.mm() || v.z() == 3.mm()));
    }
 
@@ -185,7 +508,7 @@ mod tests {
 
       let (bottom_vertexes, top_vertexes): (Vec<_>, Vec<_>)
          = solid.facets.iter()
-         .flat_map(|f| f.vertexes)
+         .flat_map(|f| f.vertexes())
          .partition(|v| v.z() == 0.mm());
 
       let bottom_center = Point::ORIGIN;
@@ -202,4 +525,157 @@ mod tests {
             assert_eq!(top_center.distance(v), 5.mm())
          );
    }
+
+   #[test]
+   fn approximate_volume() {
+      let cylinder = cylinder(Location::default(), 5.mm(), 2.mm());
+      let expected = unsafe { Exp::new(n64(std::f64::consts::PI * 4.0 * 5.0)) };
+      assert_eq!(cylinder.approximate_volume(), Some(expected));
+
+      let actual = cylinder.with_fragment_angle(1.deg())
+         .generate_stl_solid().volume();
+      assert!(actual.0 < expected.0);
+      assert!(actual.0 > expected.0 * 0.999);
+   }
+
+   #[test]
+   fn side_perimeter() {
+      let cylinder = cylinder(Location::default(), 5.mm(), 2.mm());
+      let solid = cylinder.generate_stl_solid();
+
+      let bottom_perimeter = solid.facets[..fragment_count()].iter()
+         .map(|f| f.vertexes()[1].distance(&f.vertexes()[2]))
+         .sum();
+      assert_eq!(cylinder.side_perimeter(), bottom_perimeter);
+
+      let ideal = 2.mm() * std::f64::consts::TAU;
+      assert!(cylinder.side_perimeter() < ideal);
+      assert!(cylinder.with_fragment_angle(1.deg()).side_perimeter() > ideal * 0.9999);
+   }
+
+   #[test]
+   fn facet_count() {
+      let cylinder = cylinder(Location::default(), 5.mm(), 2.mm());
+      assert_eq!(cylinder.facet_count(), cylinder.generate_stl_solid().facet_count());
+
+      for angle in [7.deg(), 45.deg(), 100.deg()] {
+         let cylinder = Cylinder::new(Location::default(), 5.mm(), 2.mm())
+            .with_fragment_angle(angle);
+         assert_eq!(cylinder.facet_count(), cylinder.generate_stl_solid().facet_count());
+      }
+
+      let radii = [(2.mm(), 0.mm()), (0.mm(), 2.mm()), (0.mm(), 0.mm())];
+      for (bottom, top) in radii {
+         let cylinder = Cylinder::tapered(Location::default(), 5.mm(), bottom, top);
+         assert_eq!(cylinder.facet_count(), cylinder.generate_stl_solid().facet_count());
+      }
+   }
+
+   #[test]
+   fn tapered() {
+      let straight = Cylinder::tapered(Location::default(), 3.mm(), 5.mm(), 5.mm());
+      let expected: Vec<_> = cylinder(Location::default(), 3.mm(), 5.mm())
+         .generate_stl_solid().facets.iter()
+         .flat_map(|f| f.vertexes())
+         .collect();
+      let actual: Vec<_> = straight.generate_stl_solid().facets.iter()
+         .flat_map(|f| f.vertexes())
+         .collect();
+      assert_eq!(actual, expected);
+
+      let frustum = Cylinder::tapered(Location::default(), 3.mm(), 5.mm(), 2.mm())
+         .with_fragment_angle(1.deg());
+      let solid = frustum.generate_stl_solid();
+      assert!(solid.is_watertight());
+      assert_eq!(frustum.facet_count(), solid.facet_count());
+
+      let top_center = Point::new(0.mm(), 0.mm(), 3.mm());
+      solid.facets.iter()
+         .flat_map(|f| f.vertexes())
+         .filter(|v| v.z() == 3.mm() && *v != top_center)
+         .for_each(|v| assert_eq!(top_center.distance(&v), 2.mm()));
+
+      // π × h × (r1² + r1r2 + r2²) / 3
+      let expected = std::f64::consts::PI * 3.0 * (25.0 + 10.0 + 4.0) / 3.0;
+      assert_eq!(frustum.approximate_volume().unwrap().0, expected);
+      assert!(solid.volume().0 < expected);
+      assert!(solid.volume().0 > expected * 0.999);
+
+      let lazy: Vec<_> = frustum.generate_facets_lazy()
+         .flat_map(|f| f.vertexes())
+         .collect();
+      let vertexes: Vec<_> = solid.facets.iter().flat_map(|f| f.vertexes()).collect();
+      assert_eq!(lazy, vertexes);
+   }
+
+   #[test]
+   fn pointed() {
+      let pointed = Cylinder::tapered(Location::default(), 3.mm(), 5.mm(), 0.mm());
+
+      let expected: Vec<_> = cone(Location::default(), 3.mm(), 5.mm())
+         .generate_stl_solid().facets.iter()
+         .flat_map(|f| f.vertexes())
+         .collect();
+      let actual: Vec<_> = pointed.generate_stl_solid().facets.iter()
+         .flat_map(|f| f.vertexes())
+         .collect();
+      assert_eq!(actual, expected);
+
+      let lazy: Vec<_> = pointed.generate_facets_lazy()
+         .flat_map(|f| f.vertexes())
+         .collect();
+      assert_eq!(lazy, expected);
+
+      assert_eq!(pointed.facet_count(), expected.len() / 3);
+
+      let inverted = Cylinder::tapered(Location::default(), 3.mm(), 0.mm(), 5.mm())
+         .generate_stl_solid();
+      assert!(inverted.is_watertight());
+      assert!(inverted.volume().0 > 0.0);
+   }
+
+   #[test]
+   fn scad() {
+      let describe = |cylinder: &Cylinder| {
+         let mut output = vec![];
+         cylinder.describe_scad(&mut output, 0).unwrap();
+         String::from_utf8(output).unwrap()
+      };
+
+      assert_eq!(
+         describe(&cylinder(Location::default(), 3.mm(), 5.mm())),
+         "cylinder(h=3, r=5);\n"
+      );
+      assert_eq!(
+         describe(&Cylinder::tapered(Location::default(), 3.mm(), 5.mm(), 2.mm())),
+         "cylinder(h=3, r1=5, r2=2);\n"
+      );
+   }
+
+   #[test]
+   fn bounding_box() {
+      // 8 fragments have vertexes on the axes
+      let straight = cylinder(Location::default(), 3.mm(), 2.mm())
+         .with_fragment_count(8)
+         .translated(&Vector::new(1.mm(), 1.mm(), 1.mm()));
+      let expected = BoundingBox::new(
+         Point::new(-1.mm(), -1.mm(), 1.mm()),
+         Point::new(3.mm(), 3.mm(), 4.mm())
+      );
+      assert_eq!(straight.bounding_box(), Some(expected));
+      assert_eq!(straight.generate_stl_solid().bounding_box(), Some(expected));
+
+      let tapered = Cylinder::tapered(Location::default(), 3.mm(), 2.mm(), 5.mm())
+         .with_fragment_count(8);
+      assert_eq!(tapered.bounding_box(), tapered.generate_stl_solid().bounding_box());
+
+      let tilted = Cylinder::tapered(Location::default(), 3.mm(), 2.mm(), 1.mm())
+         .with_fragment_angle(0.5.deg())
+         .rotated(&Line::X_AXIS, 30.deg());
+      let analytic = tilted.bounding_box().unwrap();
+      let mesh = tilted.generate_stl_solid().bounding_box().unwrap();
+      assert!(analytic.contains_point(&mesh.min) && analytic.contains_point(&mesh.max));
+      assert!(Vector::between(&analytic.min, &mesh.min).norm() < 0.001.mm());
+      assert!(Vector::between(&analytic.max, &mesh.max).norm() < 0.001.mm());
+   }
 }
@@ -0,0 +1,185 @@
+use crate::geometry::{Angle, AngleLiteral, Line, Size, Vector};
+use crate::solid::{Location, Solid};
+use crate::solid::precision::{fragment_angle, FragmentSpec};
+use crate::stl::{Facet, StlSolid};
+use crate::transform::Transform;
+
+/// The lateral surface of a [Cylinder](crate::solid::Cylinder), without
+/// the top and the bottom.
+///
+/// The surface starts at `start_angle` from [Location::back_vector] around
+/// [Location::top_vector], and sweeps `sweep_angle`. This is not a closed
+/// solid. When `sweep_angle` is less than 360 degrees, the 2 edges along the
+/// height are also open.
+pub struct CylindricalSurface {
+   pub location: Location,
+   pub height: Size,
+   pub radius: Size,
+   pub start_angle: Angle,
+   pub sweep_angle: Angle,
+   pub fragments: Option<FragmentSpec>
+}
+
+impl CylindricalSurface {
+   pub fn new(
+      location: Location,
+      height: Size,
+      radius: Size,
+      start_angle: Angle,
+      sweep_angle: Angle
+   ) -> CylindricalSurface {
+      CylindricalSurface {
+         location, height, radius, start_angle, sweep_angle, fragments: None
+      }
+   }
+
+   /// Overrides the precision specified by [env](crate::solid::builder::env).
+   /// See [FragmentSpec].
+   pub fn with_fragment_angle(self, angle: Angle) -> CylindricalSurface {
+      CylindricalSurface { fragments: Some(FragmentSpec::Angle(angle)), ..self }
+   }
+
+   /// Overrides the precision specified by [env](crate::solid::builder::env).
+   /// See [FragmentSpec].
+   pub fn with_fragment_count(self, count: usize) -> CylindricalSurface {
+      CylindricalSurface { fragments: Some(FragmentSpec::Count(count)), ..self }
+   }
+}
+
+pub fn cylindrical_surface(
+   location: Location,
+   height: Size,
+   radius: Size,
+   start_angle: Angle,
+   sweep_angle: Angle
+) -> CylindricalSurface {
+   CylindricalSurface::new(location, height, radius, start_angle, sweep_angle)
+}
+
+impl Solid for CylindricalSurface {
+   fn generate_stl_solid(&self) -> StlSolid {
+      if self.sweep_angle <= 0.deg() || self.sweep_angle > 360.deg() {
+         panic!("The sweep angle of a cylindrical surface must be in (0°, 360°].");
+      }
+
+      let fragment_angle = fragment_angle(self.radius, self.fragments);
+
+      let back = &self.location.back_vector();
+      let top = &self.location.top_vector();
+      let radius = self.radius;
+      let height = self.height;
+      let bottom_point = self.location.point();
+
+      let bottom_points: Vec<_>
+         = Angle::iterate(0.deg()..self.sweep_angle).step(fragment_angle)
+         .chain([self.sweep_angle])
+         .map(|a| back.rotated(top, self.start_angle + a))
+         .map(|v| bottom_point.translated_toward(&v, radius))
+         .collect();
+
+      let top_points: Vec<_> = bottom_points.iter()
+         .map(|p| p.translated_toward(top, height))
+         .collect();
+
+      let facets = bottom_points.windows(2).zip(top_points.windows(2))
+         .flat_map(|(bottom, top)| {
+            let (bottom_a, bottom_b) = (bottom[0], bottom[1]);
+            let (top_a, top_b) = (top[0], top[1]);
+            [
+               Facet::new([bottom_a, top_b, top_a]),
+               Facet::new([top_b, bottom_a, bottom_b])
+            ]
+         })
+         .collect();
+
+      StlSolid { facets }
+   }
+}
+
+impl Transform for CylindricalSurface {
+   fn translated(&self, offset: &Vector) -> Self {
+      CylindricalSurface {
+         location: self.location.translated(offset),
+         height: self.height,
+         radius: self.radius,
+         start_angle: self.start_angle,
+         sweep_angle: self.sweep_angle,
+         fragments: self.fragments
+      }
+   }
+
+   fn rotated(&self, axis: &Line, angle: Angle) -> Self {
+      CylindricalSurface {
+         location: self.location.rotated(axis, angle),
+         height: self.height,
+         radius: self.radius,
+         start_angle: self.start_angle,
+         sweep_angle: self.sweep_angle,
+         fragments: self.fragments
+      }
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::cylindrical_surface;
+   use crate::geometry::{Angle, AngleLiteral, Point, SizeLiteral, Vector};
+   use crate::solid::{cylinder, Location, Solid};
+   use crate::solid::precision::fragment_count;
+   use crate::transform::Transform;
+
+   #[test]
+   fn whole() {
+      let surface = cylindrical_surface(
+         Location::default(), 3.mm(), 5.mm(), 0.deg(), 360.deg()
+      ).generate_stl_solid();
+
+      let cylinder = cylinder(Location::default(), 3.mm(), 5.mm())
+         .generate_stl_solid();
+
+      let n = fragment_count(5.mm(), None);
+      let side_facets = &cylinder.facets[n..(n * 3)];
+
+      assert_eq!(surface.facet_count(), side_facets.len());
+      for (actual, expected) in surface.facets().zip(side_facets) {
+         assert_eq!(actual.vertexes(), expected.vertexes());
+      }
+   }
+
+   #[test]
+   fn half() {
+      let surface = cylindrical_surface(
+         Location::default(), 3.mm(), 5.mm(), 90.deg(), 180.deg()
+      ).generate_stl_solid();
+
+      assert_eq!(surface.facet_count(), fragment_count(5.mm(), None));
+   }
+
+   #[test]
+   fn vertexes() {
+      let location = Location::default()
+         .translated(&Vector::new(1.mm(), 2.mm(), 3.mm()));
+      let surface = cylindrical_surface(
+         location, 4.mm(), 5.mm(), 30.deg(), 100.deg()
+      ).generate_stl_solid();
+
+      for v in surface.facets().flat_map(|f| f.vertexes()) {
+         assert!(v.z() == 3.mm() || v.z() == 7.mm());
+
+         let axis = Point::new(1.mm(), 2.mm(), v.z());
+         assert_eq!(axis.distance(&v), 5.mm());
+
+         // angle from the back vector
+         let angle = Angle::atan2(-(v.x() - 1.mm()), v.y() - 2.mm());
+         assert!(angle >= 30.deg() && angle <= 130.deg(), "{:?}", angle);
+      }
+
+      let first = surface.facets[0].vertexes()[0];
+      let expected = Point::new(
+         1.mm() - 5.mm() * 30.deg().sin(),
+         2.mm() + 5.mm() * 30.deg().cos(),
+         3.mm()
+      );
+      assert_eq!(first, expected);
+   }
+}
@@ -0,0 +1,122 @@
+use crate::scad::{describe_block, ScadDescribable};
+use crate::solid::{Solid, SolidParent};
+use crate::solid::builder::BuildContext;
+use crate::solid::solid_parent::PushBorrowing;
+use crate::stl::{boolean, StlSolid};
+use anyhow::Result;
+use std::io::Write;
+
+/// Subtracts its children from the first child.
+///
+/// The first pushed child is the base, and every subsequent child is
+/// subtracted from it. The cut surfaces are taken from the subtracted
+/// children with their facets reversed, so that they face toward the hole.
+/// Every child must generate a closed solid.
+#[derive(Default)]
+pub struct Difference {
+   pub children: Vec<Box<dyn Solid>>
+}
+
+impl Difference {
+   pub fn new() -> Difference {
+      Difference {
+         children: vec![]
+      }
+   }
+}
+
+pub fn difference(build_action: impl FnOnce(BuildContext<Difference>)) -> Difference {
+   BuildContext::build(
+      Difference::new(),
+      build_action
+   )
+}
+
+impl Solid for Difference {
+   fn generate_stl_solid(&self) -> StlSolid {
+      self.children.iter()
+         .map(|c| c.generate_stl_solid())
+         .reduce(|a, b| boolean::difference(&a, &b))
+         .unwrap_or_else(|| StlSolid::new(vec![]))
+   }
+
+   fn as_scad_describable(&self) -> Option<&dyn ScadDescribable> {
+      Some(self)
+   }
+}
+
+impl ScadDescribable for Difference {
+   fn describe_scad(&self, output: &mut dyn Write, indent: usize) -> Result<()> {
+      describe_block(output, indent, "difference()", &self.children)
+   }
+}
+
+impl SolidParent for Difference {
+   fn push<S: Solid + 'static>(&mut self, child: S) -> &mut S {
+      self.children.push_borrowing(child)
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::difference;
+   use crate::geometry::{AngleLiteral, Point, SizeLiteral, Vector};
+   use crate::solid::{cube, cylinder, Location, Solid};
+   use crate::solid::primitive::test_support::assert_roughly;
+   use crate::transform::Transform;
+
+   #[test]
+   fn through_hole() {
+      let solid = difference(|mut c| {
+         c <<= cube(Location::default(), (4.mm(), 4.mm(), 4.mm()));
+
+         let location = Location::default()
+            .translated(&Vector::new(2.mm(), 2.mm(), -1.mm()));
+         c <<= cylinder(location, 6.mm(), 1.mm());
+      }).generate_stl_solid();
+
+      assert!(solid.intersecting_facet_pairs().is_empty());
+
+      let cylinder_volume = cylinder(Location::default(), 6.mm(), 1.mm())
+         .generate_stl_solid().volume().0.raw();
+      assert_roughly(solid.volume(), 64.0 - cylinder_volume * 4.0 / 6.0);
+
+      let bore_facets = solid.facets()
+         .filter(|f| f.vertexes().iter().all(|v|
+            v.x() > 0.5.mm() && v.x() < 3.5.mm()
+               && v.y() > 0.5.mm() && v.y() < 3.5.mm()
+         ))
+         .filter(|f| f.normal_vector().z() == 0.mm());
+
+      let mut bore_facet_count = 0;
+      for f in bore_facets {
+         let [a, b, c] = f.vertexes();
+         let center = Point::new(
+            (a.x() + b.x() + c.x()) / 3,
+            (a.y() + b.y() + c.y()) / 3,
+            0.mm()
+         );
+         let toward_axis = Vector::between(
+            &center, &Point::new(2.mm(), 2.mm(), 0.mm())
+         );
+         assert!(f.normal_vector().angle_with(&toward_axis) < 30.deg());
+         bore_facet_count += 1;
+      }
+      assert!(bore_facet_count > 0);
+   }
+
+   #[test]
+   fn single_child() {
+      let solid = difference(|mut c| {
+         c <<= cube(Location::default(), (1.mm(), 2.mm(), 3.mm()));
+      }).generate_stl_solid();
+
+      assert_eq!(solid.facet_count(), 12);
+   }
+
+   #[test]
+   fn empty() {
+      let solid = difference(|_| {}).generate_stl_solid();
+      assert_eq!(solid.facet_count(), 0);
+   }
+}
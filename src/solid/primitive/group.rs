@@ -0,0 +1,191 @@
+use crate::geometry::{Angle, Line, Size, Vector};
+use crate::math::unit::Exp;
+use crate::solid::{Solid, SolidParent};
+use crate::solid::builder::BuildContext;
+use crate::solid::solid_parent::PushBorrowing;
+use crate::stl::StlSolid;
+use crate::transform::Transform;
+use std::fmt::{self, Debug, Formatter};
+use std::rc::Rc;
+
+/// Just holds its children, to organize an assembly.
+///
+/// The children are generated as is. A group can be moved as a whole by
+/// [Transform]. The movement is applied when the group is generated, and
+/// the moved group shares its children with the original one.
+pub struct Group {
+   pub name: Option<String>,
+   children: Rc<Vec<Box<dyn Solid>>>,
+   /// Movements applied to the generated facets, in order.
+   movements: Vec<Movement>
+}
+
+/// [Transform] of a parent which is applied to the generated facets.
+#[derive(Clone, Copy)]
+pub(in crate::solid) enum Movement {
+   Translation(Vector),
+   Rotation(Line, Angle)
+}
+
+impl Movement {
+   pub(in crate::solid) fn apply(&self, stl_solid: &mut StlSolid) {
+      match self {
+         Movement::Translation(offset) => stl_solid.translate(offset),
+         Movement::Rotation(axis, angle) => stl_solid.rotate(axis, *angle)
+      }
+   }
+}
+
+impl Group {
+   pub fn new() -> Group {
+      Group {
+         name: None,
+         children: Rc::new(vec![]),
+         movements: vec![]
+      }
+   }
+
+   pub fn with_name(self, name: &str) -> Group {
+      Group { name: Some(name.to_string()), ..self }
+   }
+
+   pub fn children(&self) -> &[Box<dyn Solid>] {
+      &self.children
+   }
+
+   fn moved(&self, movement: Movement) -> Group {
+      Group {
+         name: self.name.clone(),
+         children: self.children.clone(),
+         movements: self.movements.iter().copied().chain([movement]).collect()
+      }
+   }
+}
+
+impl Default for Group {
+   fn default() -> Group {
+      Group::new()
+   }
+}
+
+pub fn group(build_action: impl FnOnce(BuildContext<Group>)) -> Group {
+   BuildContext::build(
+      Group::new(),
+      build_action
+   )
+}
+
+impl Debug for Group {
+   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+      f.debug_struct("Group")
+         .field("name", &self.name)
+         .field("children", &self.children.len())
+         .finish()
+   }
+}
+
+impl Solid for Group {
+   fn generate_stl_solid(&self) -> StlSolid {
+      let mut stl_solid = StlSolid::EMPTY;
+      for c in self.children.iter() {
+         stl_solid.extend(c.generate_stl_solid());
+      }
+
+      for m in &self.movements {
+         m.apply(&mut stl_solid);
+      }
+
+      stl_solid
+   }
+
+   /// Sum of the volumes of the children. Overlapping parts are counted
+   /// twice. Returns `None` if any child returns `None`.
+   fn approximate_volume(&self) -> Option<Exp<Size, 3>> {
+      self.children.iter()
+         .map(|c| c.approximate_volume())
+         .sum()
+   }
+}
+
+impl SolidParent for Group {
+   /// Panics if this group shares its children with a moved group.
+   fn push<S: Solid + 'static>(&mut self, child: S) -> &mut S {
+      Rc::get_mut(&mut self.children)
+         .expect("Cannot add a child to a group which is shared with a moved group.")
+         .push_borrowing(child)
+   }
+}
+
+impl Transform for Group {
+   fn translated(&self, offset: &Vector) -> Self {
+      self.moved(Movement::Translation(*offset))
+   }
+
+   fn rotated(&self, axis: &Line, angle: Angle) -> Self {
+      self.moved(Movement::Rotation(*axis, angle))
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::group;
+   use crate::geometry::{AngleLiteral, Line, Point, SizeLiteral, Vector};
+   use crate::solid::{cube, Location, Solid};
+   use crate::stl::{Facet, StlSolid};
+   use crate::transform::Transform;
+
+   struct SolidImpl(Facet);
+
+   impl Solid for SolidImpl {
+      fn generate_stl_solid(&self) -> StlSolid {
+         StlSolid {
+            facets: vec![
+               Facet::new(self.0.vertexes())
+            ]
+         }
+      }
+   }
+
+   fn solid(a: Point, b: Point, c: Point) -> SolidImpl {
+      SolidImpl(Facet::new([a, b, c]))
+   }
+
+   #[test]
+   fn nested() {
+      let a = Point::new(1.mm(), 2.mm(), 3.mm());
+      let b = Point::new(4.mm(), 5.mm(), 6.mm());
+      let c = Point::new(7.mm(), 8.mm(), 9.mm());
+
+      let group = group(|mut g| {
+         g <<= solid(a, b, c);
+
+         g <<= group(|mut g| {
+            g <<= solid(c, b, a);
+            g <<= solid(b, c, a);
+         });
+      });
+
+      assert_eq!(group.vertexes().collect::<Vec<_>>(), vec![a, b, c, c, b, a, b, c, a]);
+   }
+
+   #[test]
+   fn transform() {
+      let group = group(|mut g| {
+         g <<= cube(Location::default(), (1.mm(), 1.mm(), 1.mm()));
+      }).with_name("box");
+
+      let moved = group
+         .translated(&Vector::new(1.mm(), 0.mm(), 0.mm()))
+         .rotated(&Line::Z_AXIS, 90.deg());
+
+      let expected: Vec<_> = group.vertexes()
+         .map(|v| v.translated(&Vector::new(1.mm(), 0.mm(), 0.mm())))
+         .map(|v| v.rotated(&Line::Z_AXIS, 90.deg()))
+         .collect();
+      assert_eq!(moved.vertexes().collect::<Vec<_>>(), expected);
+      assert_eq!(group.vertexes().next(), Some(Point::ORIGIN));
+
+      assert_eq!(moved.name.as_deref(), Some("box"));
+      assert_eq!(format!("{:?}", moved), "Group { name: Some(\"box\"), children: 1 }");
+   }
+}
@@ -0,0 +1,245 @@
+use crate::geometry::{Angle, AngleLiteral, Line, Point, Size, SizeLiteral, Vector};
+use crate::solid::Solid;
+use crate::solid::precision::{fragment_angle, FragmentSpec};
+use crate::solid::primitive::polygon::{signed_area, triangulate};
+use crate::stl::{Facet, StlSolid};
+use crate::transform::Transform;
+
+/// A 2D profile swept along a helix. For springs, screw threads, etc.
+///
+/// The helix winds counterclockwise around `axis` seen from the tip of the
+/// axis vector, rising `pitch` per revolution. It starts `radius` away from
+/// the point of `axis` nearest from the origin, toward the direction nearest
+/// to X axis.
+///
+/// `profile` is a polygon on the plane perpendicular to the helix. x of the
+/// profile is the distance away from the axis, and y is the distance along the
+/// axis. The profile keeps this posture against the axis through the whole
+/// helix, so it doesn't spin around the path.
+pub struct HelixPath {
+   pub axis: Line,
+   pub pitch: Size,
+   pub radius: Size,
+   pub turns: f64,
+   pub profile: Vec<(Size, Size)>,
+   pub fragments: Option<FragmentSpec>
+}
+
+impl HelixPath {
+   pub fn new(
+      axis: Line,
+      pitch: Size,
+      radius: Size,
+      turns: f64,
+      profile: Vec<(Size, Size)>
+   ) -> HelixPath {
+      HelixPath { axis, pitch, radius, turns, profile, fragments: None }
+   }
+
+   /// Overrides the precision specified by [env](crate::solid::builder::env).
+   /// See [FragmentSpec].
+   pub fn with_fragment_angle(self, angle: Angle) -> HelixPath {
+      HelixPath { fragments: Some(FragmentSpec::Angle(angle)), ..self }
+   }
+
+   /// Overrides the precision specified by [env](crate::solid::builder::env).
+   /// See [FragmentSpec].
+   pub fn with_fragment_count(self, count: usize) -> HelixPath {
+      HelixPath { fragments: Some(FragmentSpec::Count(count)), ..self }
+   }
+}
+
+pub fn helix(
+   axis: Line,
+   pitch: Size,
+   radius: Size,
+   turns: f64,
+   profile: Vec<(Size, Size)>
+) -> HelixPath {
+   HelixPath::new(axis, pitch, radius, turns, profile)
+}
+
+impl Solid for HelixPath {
+   fn generate_stl_solid(&self) -> StlSolid {
+      if self.pitch <= Size::ZERO {
+         panic!("The pitch of a helix must be positive.");
+      }
+      if self.turns <= 0.0 {
+         panic!("The turns of a helix must be positive.");
+      }
+      if self.profile.len() < 3 {
+         panic!("A profile of a helix must have at least 3 vertexes.");
+      }
+
+      let fragment_angle = fragment_angle(self.radius, self.fragments);
+
+      let axis = self.axis.vector().to_unit_vector();
+      let center = self.axis.point();
+
+      // the direction nearest to X axis on the plane perpendicular to the axis.
+      // Y axis is used instead when the axis is X axis
+      let start_direction = [Vector::X_UNIT_VECTOR, Vector::Y_UNIT_VECTOR]
+         .into_iter()
+         .map(|v| axis.vector_product(&v).vector_product(&axis))
+         .find(|v| v.norm() > 0.1.mm())
+         .unwrap()
+         .to_unit_vector();
+
+      // the profile is in the plane of (outward, upward), whose normal vector
+      // is opposite to the direction of the path. Make it clockwise in
+      // the plane so that it is counterclockwise seen from ahead.
+      let vertex_count = self.profile.len();
+      let clockwise = signed_area(&self.profile).0 < 0.0;
+      let profile: Vec<_> = (0..vertex_count)
+         .map(|i| self.profile[if clockwise { i } else { vertex_count - 1 - i }])
+         .collect();
+
+      let total_angle = 360.deg() * self.turns;
+      let circumference = self.radius * std::f64::consts::TAU;
+
+      let rings: Vec<Vec<Point>>
+         = Angle::iterate(0.deg()..total_angle).step(fragment_angle)
+         .chain([total_angle])
+         .map(|a| {
+            let outward = start_direction.rotated(&axis, a);
+            let forward = axis.vector_product(&outward) * circumference.to_millimeter()
+               + axis * self.pitch.to_millimeter();
+            let upward = outward.vector_product(&forward);
+
+            let point = center
+               .translated_toward(&axis, self.pitch * (a / 360.deg()))
+               .translated_toward(&outward, self.radius);
+
+            profile.iter()
+               .map(|&(x, y)|
+                  point
+                     .translated_toward(&outward, x)
+                     .translated_toward(&upward, y)
+               )
+               .collect()
+         })
+         .collect();
+
+      let cap_triangles = triangulate(
+         &profile.iter().map(|&(x, y)| (-x, y)).collect::<Vec<_>>()
+      );
+
+      let start_ring = &rings[0];
+      let start_facets = cap_triangles.iter()
+         .map(|&[a, b, c]| Facet::new([start_ring[a], start_ring[c], start_ring[b]]));
+
+      let end_ring = rings.last().unwrap();
+      let end_facets = cap_triangles.iter()
+         .map(|&[a, b, c]| Facet::new([end_ring[a], end_ring[b], end_ring[c]]));
+
+      let side_facets = rings.windows(2)
+         .flat_map(|w| {
+            let (back, front) = (&w[0], &w[1]);
+            (0..vertex_count).flat_map(move |i| {
+               let j = (i + 1) % vertex_count;
+               [
+                  Facet::new([back[i], back[j], front[j]]),
+                  Facet::new([front[j], front[i], back[i]])
+               ]
+            })
+         });
+
+      StlSolid {
+         facets: start_facets
+            .chain(side_facets)
+            .chain(end_facets)
+            .collect()
+      }
+   }
+}
+
+impl Transform for HelixPath {
+   fn translated(&self, offset: &Vector) -> Self {
+      Self {
+         axis: self.axis.translated(offset),
+         pitch: self.pitch,
+         radius: self.radius,
+         turns: self.turns,
+         profile: self.profile.clone(),
+         fragments: self.fragments
+      }
+   }
+
+   fn rotated(&self, axis: &Line, angle: Angle) -> Self {
+      Self {
+         axis: self.axis.rotated(axis, angle),
+         pitch: self.pitch,
+         radius: self.radius,
+         turns: self.turns,
+         profile: self.profile.clone(),
+         fragments: self.fragments
+      }
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::helix;
+   use crate::geometry::{Line, Point, Size, SizeLiteral};
+   use crate::solid::Solid;
+   use crate::stl::Facet;
+
+   fn square(size: Size) -> Vec<(Size, Size)> {
+      let h: Size = size / 2;
+      vec![(-h, -h), (h, -h), (h, h), (-h, h)]
+   }
+
+   #[test]
+   fn center_of_mass() {
+      let solid = helix(Line::Z_AXIS, 3.mm(), 5.mm(), 2.0, square(1.mm()))
+         .with_fragment_count(36)
+         .generate_stl_solid();
+
+      let center = solid.center_of_mass().unwrap();
+      assert!(center.x().abs() < 0.01.mm(), "{:?}", center);
+      assert!(center.y().abs() < 0.01.mm(), "{:?}", center);
+      assert!((center.z() - 3.mm()).abs() < 0.01.mm(), "{:?}", center);
+   }
+
+   #[test]
+   fn profile() {
+      let solid = helix(Line::Z_AXIS, 3.mm(), 5.mm(), 1.5, square(1.mm()))
+         .with_fragment_count(36)
+         .generate_stl_solid();
+
+      let assert_square = |facets: &[Facet], center: Point| {
+         let mut vertexes: Vec<_> = facets.iter().flat_map(|f| f.vertexes()).collect();
+         vertexes.sort_by_key(|v| (v.x(), v.y(), v.z()));
+         vertexes.dedup();
+         assert_eq!(vertexes.len(), 4);
+
+         for v in &vertexes {
+            assert_eq!(center.distance(v), 0.5.mm() * 2.0_f64.sqrt());
+         }
+         let mut edges: Vec<_> = vertexes.iter()
+            .flat_map(|a| vertexes.iter().map(|b| a.distance(b)))
+            .filter(|&d| d > Size::ZERO)
+            .collect();
+         edges.sort();
+         assert_eq!(edges[..8], [1.mm(); 8]);
+      };
+
+      assert_square(&solid.facets[..2], Point::new(5.mm(), 0.mm(), 0.mm()));
+      assert!(solid.facets[0].normal_vector().y() < -0.9.mm());
+
+      // after 1.5 turns, the end is on the opposite side, 4.5mm above
+      let end_facets = &solid.facets[(solid.facets.len() - 2)..];
+      assert_square(end_facets, Point::new(-5.mm(), 0.mm(), 4.5.mm()));
+      assert!(end_facets[0].normal_vector().y() < -0.9.mm());
+   }
+
+   #[test]
+   fn watertight() {
+      let solid = helix(Line::Z_AXIS, 3.mm(), 5.mm(), 2.5, square(1.mm()))
+         .generate_stl_solid();
+
+      assert!(solid.is_watertight());
+      assert!(solid.volume().0 > 0.0);
+      assert!(solid.intersecting_facet_pairs().is_empty());
+   }
+}
@@ -0,0 +1,131 @@
+use crate::geometry::{Angle, Line, Size, Vector};
+use crate::math::unit::Exp;
+use crate::solid::{Solid, SolidParent};
+use crate::stl::StlSolid;
+use crate::transform::Transform;
+
+/// Wraps a solid to generate it with the specified
+/// [FRAGMENT_MINIMUM_ANGLE](crate::solid::precision::FRAGMENT_MINIMUM_ANGLE),
+/// regardless of the env outside.
+/// See [generate_stl_with_precision](Solid::generate_stl_with_precision).
+///
+/// ```
+/// # use typed_scad::geometry::{AngleLiteral, SizeLiteral};
+/// # use typed_scad::solid::{cylinder, with_precision, Location, Solid};
+/// let smooth = with_precision(cylinder(Location::default(), 3.mm(), 5.mm()), 1.deg());
+/// let rough = cylinder(Location::default(), 3.mm(), 5.mm());
+///
+/// assert!(smooth.generate_stl_solid().facet_count()
+///    > rough.generate_stl_solid().facet_count());
+/// ```
+pub struct HighPrecision<S> {
+   pub inner: S,
+   precision: Angle
+}
+
+impl<S> HighPrecision<S> {
+   pub fn new(inner: S, precision: Angle) -> HighPrecision<S> {
+      HighPrecision { inner, precision }
+   }
+
+   pub fn precision(&self) -> Angle {
+      self.precision
+   }
+}
+
+pub fn with_precision<S>(solid: S, angle: Angle) -> HighPrecision<S> {
+   HighPrecision::new(solid, angle)
+}
+
+impl<S: Solid> Solid for HighPrecision<S> {
+   fn generate_stl_solid(&self) -> StlSolid {
+      self.inner.generate_stl_with_precision(self.precision)
+   }
+
+   fn approximate_volume(&self) -> Option<Exp<Size, 3>> {
+      self.inner.approximate_volume()
+   }
+}
+
+impl<S: SolidParent> SolidParent for HighPrecision<S> {
+   fn push<C: Solid + 'static>(&mut self, child: C) -> &mut C {
+      self.inner.push(child)
+   }
+}
+
+impl<S: Transform> Transform for HighPrecision<S> {
+   fn translated(&self, offset: &Vector) -> Self {
+      HighPrecision {
+         inner: self.inner.translated(offset),
+         precision: self.precision
+      }
+   }
+
+   fn rotated(&self, axis: &Line, angle: Angle) -> Self {
+      HighPrecision {
+         inner: self.inner.rotated(axis, angle),
+         precision: self.precision
+      }
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::with_precision;
+   use crate::geometry::{AngleLiteral, Point, SizeLiteral, Vector};
+   use crate::solid::{cylinder, translate, Location, Solid, SolidParent};
+   use crate::solid::builder::env;
+   use crate::solid::precision::FRAGMENT_MAXIMUM_DEVIATION;
+   use crate::transform::Transform;
+
+   #[test]
+   fn facet_count() {
+      let rough = cylinder(Location::default(), 3.mm(), 5.mm());
+      let smooth = with_precision(cylinder(Location::default(), 3.mm(), 5.mm()), 1.deg());
+
+      assert!(
+         smooth.generate_stl_solid().facet_count()
+            > rough.generate_stl_solid().facet_count()
+      );
+   }
+
+   #[test]
+   fn ignores_maximum_deviation_outside() {
+      let cylinder = cylinder(Location::default(), 3.mm(), 5.mm());
+      let expected = cylinder.generate_stl_with_precision(1.deg()).facet_count();
+      let smooth = with_precision(cylinder, 1.deg());
+
+      for deviation in [10.mm(), 0.001.mm()] {
+         env(&FRAGMENT_MAXIMUM_DEVIATION, deviation, || {
+            assert_eq!(smooth.generate_stl_solid().facet_count(), expected);
+         });
+      }
+   }
+
+   #[test]
+   fn geometry() {
+      let location = Location::default()
+         .translated(&Vector::new(1.mm(), 2.mm(), 3.mm()));
+      let smooth = with_precision(cylinder(location, 3.mm(), 5.mm()), 1.deg());
+
+      for v in smooth.generate_stl_solid().facets().flat_map(|f| f.vertexes()) {
+         assert!(v.z() == 3.mm() || v.z() == 6.mm());
+
+         let on_axis = Point::new(1.mm(), 2.mm(), v.z());
+         assert!(v == on_axis || on_axis.distance(&v) == 5.mm());
+      }
+   }
+
+   #[test]
+   fn solid_parent() {
+      let mut smooth = with_precision(translate(Vector::X_UNIT_VECTOR, |_| {}), 1.deg());
+      smooth.push(cylinder(Location::default(), 3.mm(), 5.mm()));
+
+      let rough = cylinder(Location::default(), 3.mm(), 5.mm());
+
+      assert!(
+         smooth.generate_stl_solid().facet_count()
+            > rough.generate_stl_solid().facet_count()
+      );
+   }
+}
@@ -0,0 +1,130 @@
+use crate::scad::{describe_block, ScadDescribable};
+use crate::solid::{Solid, SolidParent};
+use crate::solid::builder::BuildContext;
+use crate::solid::solid_parent::PushBorrowing;
+use crate::stl::{convex_hull, StlSolid};
+use anyhow::Result;
+use std::io::Write;
+
+/// Convex hull of all vertexes of its children.
+///
+/// Panics on generating when the vertexes don't make a solid with a volume,
+/// e.g. all of them are on a plane. See [convex_hull].
+#[derive(Default)]
+pub struct Hull {
+   pub children: Vec<Box<dyn Solid>>
+}
+
+impl Hull {
+   pub fn new() -> Hull {
+      Hull {
+         children: vec![]
+      }
+   }
+}
+
+pub fn hull(build_action: impl FnOnce(BuildContext<Hull>)) -> Hull {
+   BuildContext::build(
+      Hull::new(),
+      build_action
+   )
+}
+
+impl Solid for Hull {
+   fn generate_stl_solid(&self) -> StlSolid {
+      let vertexes: Vec<_> = self.children.iter()
+         .flat_map(|c| c.vertexes())
+         .collect();
+
+      convex_hull(&vertexes).unwrap_or_else(|e| panic!("{}", e))
+   }
+
+   fn as_scad_describable(&self) -> Option<&dyn ScadDescribable> {
+      Some(self)
+   }
+}
+
+impl ScadDescribable for Hull {
+   fn describe_scad(&self, output: &mut dyn Write, indent: usize) -> Result<()> {
+      describe_block(output, indent, "hull()", &self.children)
+   }
+}
+
+impl SolidParent for Hull {
+   fn push<S: Solid + 'static>(&mut self, child: S) -> &mut S {
+      self.children.push_borrowing(child)
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::hull;
+   use crate::geometry::{Point, Size, SizeLiteral, Vector};
+   use crate::solid::{cube, sphere, Location, Solid};
+   use crate::stl::{Facet, StlSolid};
+   use crate::transform::Transform;
+
+   #[test]
+   fn two_spheres() {
+      let location = Location::default()
+         .translated(&Vector::new(5.mm(), 0.mm(), 0.mm()));
+
+      let solid = hull(|mut c| {
+         c <<= sphere(Location::default(), 2.mm());
+         c <<= sphere(location, 1.mm());
+      }).generate_stl_solid();
+
+      assert!(solid.intersecting_facet_pairs().is_empty());
+
+      let inputs = sphere(Location::default(), 2.mm()).unique_vertexes()
+         .into_iter()
+         .chain(sphere(location, 1.mm()).unique_vertexes());
+
+      let planes: Vec<_> = solid.facets()
+         .map(|f| (f.vertexes()[0], f.normal_vector()))
+         .collect();
+
+      for v in inputs {
+         for (point, normal) in &planes {
+            let distance = Size::from(
+               Vector::between(point, &v).inner_product(normal) / normal.norm()
+            );
+            assert!(distance < 1e-6.mm());
+         }
+      }
+   }
+
+   #[test]
+   fn cube_vertexes() {
+      let solid = hull(|mut c| {
+         c <<= cube(Location::default(), (1.mm(), 2.mm(), 3.mm()));
+      }).generate_stl_solid();
+
+      assert_eq!(solid.facet_count(), 12);
+   }
+
+   #[test]
+   #[should_panic(expected = "on a plane")]
+   fn coplanar() {
+      struct Square;
+      impl Solid for Square {
+         fn generate_stl_solid(&self) -> StlSolid {
+            let [a, b, c, d] = [
+               Point::ORIGIN,
+               Point::new(1.mm(), 0.mm(), 0.mm()),
+               Point::new(1.mm(), 1.mm(), 0.mm()),
+               Point::new(0.mm(), 1.mm(), 0.mm())
+            ];
+
+            StlSolid::new(vec![
+               Facet::new([a, b, c]),
+               Facet::new([a, c, d])
+            ])
+         }
+      }
+
+      hull(|mut c| {
+         c <<= Square;
+      }).generate_stl_solid();
+   }
+}
@@ -0,0 +1,129 @@
+use crate::scad::{describe_block, ScadDescribable};
+use crate::solid::{Solid, SolidParent};
+use crate::solid::builder::BuildContext;
+use crate::solid::solid_parent::PushBorrowing;
+use crate::stl::{boolean, StlSolid};
+use anyhow::Result;
+use std::io::Write;
+
+/// The common part of all its children.
+///
+/// Named not to conflict with [Intersection](crate::geometry::Intersection).
+/// When the children have no common part, the generated [StlSolid] has no
+/// facets. Every child must generate a closed solid.
+#[derive(Default)]
+pub struct IntersectionSolid {
+   pub children: Vec<Box<dyn Solid>>
+}
+
+impl IntersectionSolid {
+   pub fn new() -> IntersectionSolid {
+      IntersectionSolid {
+         children: vec![]
+      }
+   }
+}
+
+pub fn intersection(
+   build_action: impl FnOnce(BuildContext<IntersectionSolid>)
+) -> IntersectionSolid {
+   BuildContext::build(
+      IntersectionSolid::new(),
+      build_action
+   )
+}
+
+impl Solid for IntersectionSolid {
+   fn generate_stl_solid(&self) -> StlSolid {
+      self.children.iter()
+         .map(|c| c.generate_stl_solid())
+         .reduce(|a, b| boolean::intersection(&a, &b))
+         .unwrap_or_else(|| StlSolid::new(vec![]))
+   }
+
+   fn as_scad_describable(&self) -> Option<&dyn ScadDescribable> {
+      Some(self)
+   }
+}
+
+impl ScadDescribable for IntersectionSolid {
+   fn describe_scad(&self, output: &mut dyn Write, indent: usize) -> Result<()> {
+      describe_block(output, indent, "intersection()", &self.children)
+   }
+}
+
+impl SolidParent for IntersectionSolid {
+   fn push<S: Solid + 'static>(&mut self, child: S) -> &mut S {
+      self.children.push_borrowing(child)
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::intersection;
+   use crate::geometry::{Size, SizeLiteral, Vector};
+   use crate::solid::{cube, sphere, Location, Solid};
+   use crate::stl::StlSolid;
+   use crate::transform::Transform;
+
+   #[test]
+   fn lens() {
+      let solid = intersection(|mut c| {
+         c <<= sphere(Location::default(), 3.mm());
+
+         let location = Location::default()
+            .translated(&Vector::new(4.mm(), 0.mm(), 0.mm()));
+         c <<= sphere(location, 3.mm());
+      }).generate_stl_solid();
+
+      assert!(solid.facet_count() > 0);
+      assert!(solid.intersecting_facet_pairs().is_empty());
+
+      let vertexes: Vec<_> = solid.facets().flat_map(|f| f.vertexes()).collect();
+      let min = |f: fn(&_) -> Size| vertexes.iter().map(f).min().unwrap();
+      let max = |f: fn(&_) -> Size| vertexes.iter().map(f).max().unwrap();
+
+      // the lens spans x in [1, 3], and its rim is a circle of radius √5
+      let tolerance = 0.2.mm();
+      let rim_radius = 5.0_f64.sqrt().mm();
+      assert!((min(|v| v.x()) - 1.mm()).abs() < tolerance);
+      assert!((max(|v| v.x()) - 3.mm()).abs() < tolerance);
+      assert!((min(|v| v.y()) + rim_radius).abs() < tolerance);
+      assert!((max(|v| v.y()) - rim_radius).abs() < tolerance);
+      assert!((min(|v| v.z()) + rim_radius).abs() < tolerance);
+      assert!((max(|v| v.z()) - rim_radius).abs() < tolerance);
+   }
+
+   #[test]
+   fn disjoint_cubes() {
+      let solid = intersection(|mut c| {
+         c <<= cube(Location::default(), (1.mm(), 1.mm(), 1.mm()));
+
+         let location = Location::default()
+            .translated(&Vector::new(3.mm(), 0.mm(), 0.mm()));
+         c <<= cube(location, (1.mm(), 1.mm(), 1.mm()));
+      }).generate_stl_solid();
+
+      assert_eq!(solid.facet_count(), 0);
+   }
+
+   #[test]
+   fn single_child() {
+      let solid = intersection(|mut c| {
+         c <<= cube(Location::default(), (1.mm(), 2.mm(), 3.mm()));
+      }).generate_stl_solid();
+
+      let expected = cube(Location::default(), (1.mm(), 2.mm(), 3.mm()))
+         .generate_stl_solid();
+      let vertexes = |s: &StlSolid| -> Vec<_> {
+         s.facets().flat_map(|f| f.vertexes()).collect()
+      };
+      assert_eq!(vertexes(&solid), vertexes(&expected));
+   }
+
+   #[test]
+   fn empty() {
+      let solid = intersection(|_| {}).generate_stl_solid();
+      assert_eq!(solid.facet_count(), 0);
+   }
+}
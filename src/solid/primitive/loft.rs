@@ -0,0 +1,299 @@
+use crate::geometry::{Angle, Line, Point, Size, Vector};
+use crate::solid::{Location, Solid};
+use crate::solid::primitive::polygon::{signed_area, triangulate};
+use crate::stl::{Facet, StlSolid};
+use crate::transform::Transform;
+
+/// A solid which connects 2 cross-sections.
+///
+/// Profiles are polygons on the plane of [Location::right_vector] and
+/// [Location::back_vector]. `bottom_profile` is on [Location::point] and
+/// `top_profile` is `height` away toward [Location::top_vector].
+/// Every profile must have the same number of vertexes. The n-th vertex of a
+/// profile is connected to the n-th vertex of the next profile.
+pub struct Loft {
+   pub location: Location,
+   pub bottom_profile: Vec<(Size, Size)>,
+   pub top_profile: Vec<(Size, Size)>,
+   pub height: Size,
+   /// Profiles between the bottom and the top, with their heights from the
+   /// bottom. See [Loft::with_intermediate_profiles].
+   pub intermediate_profiles: Vec<(Size, Vec<(Size, Size)>)>
+}
+
+impl Loft {
+   pub fn new(
+      location: Location,
+      bottom_profile: Vec<(Size, Size)>,
+      top_profile: Vec<(Size, Size)>,
+      height: Size
+   ) -> Loft {
+      Loft {
+         location, bottom_profile, top_profile, height,
+         intermediate_profiles: vec![]
+      }
+   }
+
+   /// Adds profiles between the bottom and the top. Each section is a pair
+   /// of the height from the bottom and the profile.
+   /// Heights must be in (0, height) and increasing.
+   pub fn with_intermediate_profiles(
+      self,
+      sections: Vec<(Size, Vec<(Size, Size)>)>
+   ) -> Loft {
+      Loft { intermediate_profiles: sections, ..self }
+   }
+
+   fn sections(&self) -> Vec<(Size, &[(Size, Size)])> {
+      [(Size::ZERO, &self.bottom_profile[..])].into_iter()
+         .chain(self.intermediate_profiles.iter().map(|(h, p)| (*h, &p[..])))
+         .chain([(self.height, &self.top_profile[..])])
+         .collect()
+   }
+}
+
+pub fn loft(
+   location: Location,
+   bottom_profile: Vec<(Size, Size)>,
+   top_profile: Vec<(Size, Size)>,
+   height: Size
+) -> Loft {
+   Loft::new(location, bottom_profile, top_profile, height)
+}
+
+impl Solid for Loft {
+   fn generate_stl_solid(&self) -> StlSolid {
+      let sections = self.sections();
+
+      let vertex_count = self.bottom_profile.len();
+      if vertex_count < 3 {
+         panic!("A profile of a loft must have at least 3 vertexes.");
+      }
+      if sections.iter().any(|(_, p)| p.len() != vertex_count) {
+         panic!("All profiles of a loft must have the same number of vertexes.");
+      }
+      if sections.windows(2).any(|w| w[0].0 >= w[1].0) {
+         panic!("Heights of profiles of a loft must be increasing.");
+      }
+
+      // make every profile counterclockwise seen from the top
+      let counterclockwise = signed_area(&self.bottom_profile).0 > 0.0;
+      let index = |i: usize| {
+         if counterclockwise { i } else { vertex_count - 1 - i }
+      };
+
+      let point = self.location.point();
+      let right = &self.location.right_vector();
+      let back = &self.location.back_vector();
+      let top = &self.location.top_vector();
+
+      let rings: Vec<Vec<Point>> = sections.iter()
+         .map(|(height, profile)|
+            (0..vertex_count)
+               .map(|i| profile[index(i)])
+               .map(|(x, y)|
+                  point
+                     .translated_toward(right, x)
+                     .translated_toward(back, y)
+                     .translated_toward(top, *height)
+               )
+               .collect()
+         )
+         .collect();
+
+      let cap_triangles = |profile: &[(Size, Size)]| {
+         let profile: Vec<_> = (0..vertex_count).map(|i| profile[index(i)]).collect();
+         triangulate(&profile)
+      };
+
+      let bottom_ring = &rings[0];
+      let bottom_facets = cap_triangles(&self.bottom_profile).into_iter()
+         .map(|[a, b, c]| Facet::new([bottom_ring[a], bottom_ring[c], bottom_ring[b]]));
+
+      let top_ring = rings.last().unwrap();
+      let top_facets = cap_triangles(&self.top_profile).into_iter()
+         .map(|[a, b, c]| Facet::new([top_ring[a], top_ring[b], top_ring[c]]));
+
+      let side_facets = rings.windows(2)
+         .flat_map(|w| {
+            let (lower, upper) = (&w[0], &w[1]);
+            (0..vertex_count).flat_map(move |i| {
+               let j = (i + 1) % vertex_count;
+               [
+                  Facet::new([lower[i], lower[j], upper[j]]),
+                  Facet::new([upper[j], upper[i], lower[i]])
+               ]
+            })
+         });
+
+      StlSolid {
+         facets: bottom_facets
+            .chain(side_facets)
+            .chain(top_facets)
+            .collect()
+      }
+   }
+}
+
+impl Transform for Loft {
+   fn translated(&self, offset: &Vector) -> Self {
+      Self {
+         location: self.location.translated(offset),
+         bottom_profile: self.bottom_profile.clone(),
+         top_profile: self.top_profile.clone(),
+         height: self.height,
+         intermediate_profiles: self.intermediate_profiles.clone()
+      }
+   }
+
+   fn rotated(&self, axis: &Line, angle: Angle) -> Self {
+      Self {
+         location: self.location.rotated(axis, angle),
+         bottom_profile: self.bottom_profile.clone(),
+         top_profile: self.top_profile.clone(),
+         height: self.height,
+         intermediate_profiles: self.intermediate_profiles.clone()
+      }
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::loft;
+   use crate::geometry::{Angle, AngleLiteral, Point, Size, SizeLiteral, Vector};
+   use crate::math::unit::Exp;
+   use crate::solid::{cube, Location, Solid};
+   use noisy_float::prelude::*;
+
+   fn square(size: Size) -> Vec<(Size, Size)> {
+      vec![
+         (Size::ZERO, Size::ZERO),
+         (size, Size::ZERO),
+         (size, size),
+         (Size::ZERO, size)
+      ]
+   }
+
+   fn circle(radius: Size, count: usize) -> Vec<(Size, Size)> {
+      (0..count)
+         .map(|i| 360.deg() / count as f64 * i as f64)
+         .map(|a: Angle| (radius * a.cos(), radius * a.sin()))
+         .collect()
+   }
+
+   /// A triangle with `count_per_edge` vertexes on each edge.
+   fn triangle(radius: Size, count_per_edge: usize) -> Vec<(Size, Size)> {
+      let corners = circle(radius, 3);
+      (0..3)
+         .flat_map(|i| {
+            let (a, b) = (corners[i], corners[(i + 1) % 3]);
+            (0..count_per_edge).map(move |j| {
+               let t = j as f64 / count_per_edge as f64;
+               (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+            })
+         })
+         .collect()
+   }
+
+   #[test]
+   fn same_squares() {
+      let loft = loft(Location::default(), square(1.mm()), square(1.mm()), 1.mm());
+      let solid = loft.generate_stl_solid();
+
+      assert_eq!(solid.facet_count(), 12);
+      assert_eq!(solid.volume(), unsafe { Exp::new(n64(1.0)) });
+
+      let mut actual = loft.unique_vertexes();
+      let mut expected = cube(Location::default(), (1.mm(), 1.mm(), 1.mm()))
+         .unique_vertexes();
+      let key = |p: &Point| (p.x(), p.y(), p.z());
+      actual.sort_by_key(key);
+      expected.sort_by_key(key);
+      assert_eq!(actual, expected);
+   }
+
+   #[test]
+   fn clockwise_profile() {
+      let mut profile = square(1.mm());
+      profile.reverse();
+      let solid = loft(Location::default(), profile.clone(), profile, 1.mm())
+         .generate_stl_solid();
+
+      assert_eq!(solid.volume(), unsafe { Exp::new(n64(1.0)) });
+   }
+
+   #[test]
+   fn circle_to_triangle() {
+      let solid = loft(
+         Location::default(),
+         circle(3.mm(), 12),
+         triangle(3.mm(), 4),
+         5.mm()
+      ).generate_stl_solid();
+
+      let (bottom_facets, rest) = solid.facets.split_at(10);
+      let top_facets = &rest[(rest.len() - 10)..];
+
+      for f in bottom_facets {
+         assert_eq!(f.normal_vector(), -Vector::Z_UNIT_VECTOR);
+         for v in f.vertexes() {
+            assert_eq!(v.z(), 0.mm());
+            assert_eq!(Point::ORIGIN.distance(&v), 3.mm());
+         }
+      }
+
+      // every vertex of the top is on an edge of the triangle,
+      // whose distance from the center is 1.5mm
+      let corners: Vec<_> = circle(3.mm(), 3).into_iter()
+         .map(|(x, y)| Point::new(x, y, 5.mm()))
+         .collect();
+      for f in top_facets {
+         assert_eq!(f.normal_vector(), Vector::Z_UNIT_VECTOR);
+         for v in f.vertexes() {
+            assert_eq!(v.z(), 5.mm());
+            let on_edge = (0..3).any(|i| {
+               let (a, b) = (corners[i], corners[(i + 1) % 3]);
+               a.distance(&v) + v.distance(&b) == a.distance(&b)
+            });
+            assert!(on_edge, "{:?}", v);
+         }
+      }
+
+      assert!(solid.intersecting_facet_pairs().is_empty());
+   }
+
+   #[test]
+   fn side_normal_vector() {
+      let solid = loft(
+         Location::default(),
+         circle(4.mm(), 16),
+         circle(2.mm(), 16),
+         3.mm()
+      ).generate_stl_solid();
+
+      let side_facets = &solid.facets[14..(solid.facets.len() - 14)];
+      assert_eq!(side_facets.len(), 32);
+
+      for f in side_facets {
+         let center = Point::new(0.mm(), 0.mm(), f.vertexes()[0].z());
+         let outward = Vector::between(&center, &f.vertexes()[0]);
+         assert!(f.normal_vector().inner_product(&outward).0 > 0.0);
+      }
+   }
+
+   #[test]
+   fn intermediate_profiles() {
+      let loft = loft(Location::default(), square(2.mm()), square(2.mm()), 2.mm())
+         .with_intermediate_profiles(vec![(1.mm(), square(1.mm()))]);
+
+      assert_eq!(loft.generate_stl_solid().facet_count(), 2 + 8 * 2 + 2);
+      assert!(loft.vertexes().any(|v| v == Point::new(1.mm(), 1.mm(), 1.mm())));
+   }
+
+   #[test]
+   #[should_panic]
+   fn different_vertex_counts() {
+      loft(Location::default(), square(1.mm()), circle(1.mm(), 8), 1.mm())
+         .generate_stl_solid();
+   }
+}
@@ -0,0 +1,167 @@
+use crate::geometry::{Point, Vector};
+use crate::scad::{describe_block, ScadDescribable};
+use crate::solid::{Solid, SolidParent};
+use crate::solid::builder::BuildContext;
+use crate::solid::solid_parent::PushBorrowing;
+use crate::stl::{boolean, convex_hull, StlSolid};
+use crate::transform::Transform;
+use anyhow::Result;
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+use std::io::Write;
+
+/// Minkowski sum of its children. Typically used to round a solid by
+/// a sphere.
+///
+/// The first child is summed with each subsequent child. Every child except
+/// the first must be convex.
+///
+/// For each facet of the first child, the convex hull of the second child
+/// placed on the vertexes of the facet is computed. They cover the region
+/// around the surface of the first child, and the first child moved by an
+/// interior point of the second child fills the inside. All of them are
+/// merged by [union](crate::solid::union). So this is slow. It takes about
+/// `(number of facets of the first child)` times as long as a union of
+/// 2 solids as complex as the second child.
+#[derive(Default)]
+pub struct Minkowski {
+   pub children: Vec<Box<dyn Solid>>
+}
+
+impl Minkowski {
+   pub fn new() -> Minkowski {
+      Minkowski {
+         children: vec![]
+      }
+   }
+}
+
+pub fn minkowski(build_action: impl FnOnce(BuildContext<Minkowski>)) -> Minkowski {
+   BuildContext::build(
+      Minkowski::new(),
+      build_action
+   )
+}
+
+impl Solid for Minkowski {
+   fn generate_stl_solid(&self) -> StlSolid {
+      self.children.iter()
+         .map(|c| c.generate_stl_solid())
+         .reduce(|a, b| minkowski_sum(&a, &b))
+         .unwrap_or_else(|| StlSolid::new(vec![]))
+   }
+
+   fn as_scad_describable(&self) -> Option<&dyn ScadDescribable> {
+      Some(self)
+   }
+}
+
+impl ScadDescribable for Minkowski {
+   fn describe_scad(&self, output: &mut dyn Write, indent: usize) -> Result<()> {
+      describe_block(output, indent, "minkowski()", &self.children)
+   }
+}
+
+impl SolidParent for Minkowski {
+   fn push<S: Solid + 'static>(&mut self, child: S) -> &mut S {
+      self.children.push_borrowing(child)
+   }
+}
+
+/// `b` must be convex.
+fn minkowski_sum(a: &StlSolid, b: &StlSolid) -> StlSolid {
+   let b_vertexes: Vec<_> = b.facets().flat_map(|f| f.vertexes()).collect();
+   if b_vertexes.is_empty() {
+      return StlSolid::new(vec![]);
+   }
+
+   // the centroid of the vertexes is inside of the convex `b`
+   let interior = b_vertexes.iter()
+      .map(|v| Vector::between(&Point::ORIGIN, v))
+      .fold(Vector::ZERO, |sum, v| sum + v)
+      * (1.0 / b_vertexes.len() as f64);
+
+   let surface = a.facets.par_iter()
+      .map(|f| {
+         let points: Vec<_> = f.vertexes().iter()
+            .flat_map(|v| {
+               let offset = Vector::between(&Point::ORIGIN, v);
+               b_vertexes.iter().map(move |bv| bv.translated(&offset))
+            })
+            .collect();
+
+         convex_hull(&points).unwrap_or_else(|e| panic!("{}", e))
+      })
+      .reduce_with(|x, y| boolean::union(&x, &y));
+
+   match surface {
+      Some(surface) => boolean::union(&a.translated(&interior), &surface),
+      None => StlSolid::new(vec![])
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::minkowski;
+   use crate::geometry::{Point, Size, SizeLiteral};
+   use crate::solid::{cube, sphere, Location, Solid};
+   use crate::solid::primitive::test_support::assert_roughly;
+   use std::f64::consts::PI;
+
+   #[test]
+   fn rounded_cube() {
+      let solid = minkowski(|mut c| {
+         c <<= cube(Location::default(), (2.mm(), 3.mm(), 4.mm()));
+         c <<= sphere(Location::default(), 0.5.mm()).with_fragment_count(8);
+      }).generate_stl_solid();
+
+      assert!(solid.intersecting_facet_pairs().is_empty());
+
+      let vertexes: Vec<_> = solid.facets().flat_map(|f| f.vertexes()).collect();
+      let min = |f: fn(&Point) -> Size| vertexes.iter().map(f).min().unwrap();
+      let max = |f: fn(&Point) -> Size| vertexes.iter().map(f).max().unwrap();
+
+      assert_eq!(min(|v| v.x()), -0.5.mm());
+      assert_eq!(max(|v| v.x()), 2.5.mm());
+      assert_eq!(min(|v| v.y()), -0.5.mm());
+      assert_eq!(max(|v| v.y()), 3.5.mm());
+      assert_eq!(min(|v| v.z()), -0.5.mm());
+      assert_eq!(max(|v| v.z()), 4.5.mm());
+
+      // not hollow
+      assert!(solid.contains_point(&Point::new(1.mm(), 1.5.mm(), 2.mm())));
+      assert!(solid.contains_point(&Point::new(0.2.mm(), 0.2.mm(), 0.2.mm())));
+
+      // the corner is rounded
+      let corner = Point::ORIGIN;
+      let nearest = vertexes.iter()
+         .filter(|v| v.x() < 0.mm() && v.y() < 0.mm() && v.z() < 0.mm())
+         .map(|v| corner.distance(v))
+         .collect::<Vec<_>>();
+      assert!(!nearest.is_empty());
+      for d in nearest {
+         assert!((d - 0.5.mm()).abs() < 1e-6.mm());
+      }
+   }
+
+   #[test]
+   fn volume() {
+      let solid = minkowski(|mut c| {
+         c <<= cube(Location::default(), (2.mm(), 3.mm(), 4.mm()));
+         c <<= sphere(Location::default(), 0.5.mm()).with_fragment_count(16);
+      }).generate_stl_solid();
+
+      // V + Sr + πr²(a + b + c) + 4πr³/3 for a box of a×b×c
+      let expected = 24.0 + 52.0 * 0.5 + PI * 0.25 * 9.0 + PI * 4.0 / 3.0 * 0.125;
+      assert_roughly(solid.volume(), expected);
+      assert!(solid.volume().0 < expected);
+   }
+
+   #[test]
+   fn single_child() {
+      let solid = minkowski(|mut c| {
+         c <<= cube(Location::default(), (1.mm(), 2.mm(), 3.mm()));
+      }).generate_stl_solid();
+
+      assert_eq!(solid.facet_count(), 12);
+   }
+}
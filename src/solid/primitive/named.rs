@@ -0,0 +1,102 @@
+use crate::geometry::{Angle, Line, Size, Vector};
+use crate::math::unit::Exp;
+use crate::scad::ScadDescribable;
+use crate::solid::Solid;
+use crate::stl::StlSolid;
+use crate::transform::Transform;
+use std::fmt::{self, Debug, Display, Formatter};
+
+/// Wraps a solid with a name, to identify it while debugging.
+///
+/// With the `tracing` feature, generating the solid is recorded in a span
+/// named `generate_stl_solid` with the name.
+pub struct NamedSolid<S: Solid> {
+   pub name: String,
+   pub inner: S
+}
+
+impl<S: Solid> NamedSolid<S> {
+   pub fn new(name: impl Into<String>, inner: S) -> NamedSolid<S> {
+      NamedSolid { name: name.into(), inner }
+   }
+}
+
+pub fn named<S: Solid>(name: impl Into<String>, solid: S) -> NamedSolid<S> {
+   NamedSolid::new(name, solid)
+}
+
+impl<S: Solid> Display for NamedSolid<S> {
+   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+      write!(f, "{}", self.name)
+   }
+}
+
+impl<S: Solid> Debug for NamedSolid<S> {
+   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+      f.debug_tuple("NamedSolid").field(&self.name).finish()
+   }
+}
+
+impl<S: Solid> Solid for NamedSolid<S> {
+   fn generate_stl_solid(&self) -> StlSolid {
+      #[cfg(feature = "tracing")]
+      let _span = tracing::span!(
+         tracing::Level::DEBUG, "generate_stl_solid", name = self.name.as_str()
+      ).entered();
+
+      self.inner.generate_stl_solid()
+   }
+
+   fn approximate_volume(&self) -> Option<Exp<Size, 3>> {
+      self.inner.approximate_volume()
+   }
+
+   fn as_scad_describable(&self) -> Option<&dyn ScadDescribable> {
+      self.inner.as_scad_describable()
+   }
+}
+
+impl<S: Solid + Transform> Transform for NamedSolid<S> {
+   fn translated(&self, offset: &Vector) -> Self {
+      Self {
+         name: self.name.clone(),
+         inner: self.inner.translated(offset)
+      }
+   }
+
+   fn rotated(&self, axis: &Line, angle: Angle) -> Self {
+      Self {
+         name: self.name.clone(),
+         inner: self.inner.rotated(axis, angle)
+      }
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::named;
+   use crate::geometry::{AngleLiteral, Line, SizeLiteral, Vector};
+   use crate::solid::{cube, Location, Solid};
+   use crate::transform::Transform;
+
+   #[test]
+   fn delegate() {
+      let cube = || cube(Location::default(), (1.mm(), 2.mm(), 3.mm()));
+      let named = named("box", cube());
+
+      assert!(named.vertexes().eq(cube().vertexes()));
+      assert_eq!(named.to_string(), "box");
+      assert_eq!(format!("{:?}", named), "NamedSolid(\"box\")");
+
+      let offset = Vector::new(1.mm(), 0.mm(), 0.mm());
+      let moved = named
+         .translated(&offset)
+         .rotated(&Line::Z_AXIS, 90.deg());
+      let expected = cube()
+         .translated(&offset)
+         .rotated(&Line::Z_AXIS, 90.deg());
+
+      assert_eq!(moved.name, "box");
+      assert!(moved.vertexes().eq(expected.vertexes()));
+   }
+}
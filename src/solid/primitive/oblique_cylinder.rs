@@ -0,0 +1,240 @@
+use crate::geometry::{Angle, AngleLiteral, Line, Point, Size, Vector};
+use crate::math::unit::Exp;
+use crate::solid::{Location, Solid};
+use crate::solid::precision::{fragment_angle, FragmentSpec};
+use crate::stl::{Facet, StlSolid};
+use crate::transform::Transform;
+use std::f64::consts::PI;
+
+/// [Cylinder](crate::solid::Cylinder) whose top is cut by a tilted plane.
+///
+/// The top plane passes the point `height` above [Location::point] on the
+/// axis, and is tilted by `top_tilt` around the horizontal direction
+/// `tilt_direction`, which is measured from [Location::back_vector] around
+/// [Location::top_vector]. The top face is an ellipse.
+pub struct ObliqueCylinder {
+   pub location: Location,
+   pub height: Size,
+   pub radius: Size,
+   pub top_tilt: Angle,
+   pub tilt_direction: Angle,
+   pub fragments: Option<FragmentSpec>
+}
+
+impl ObliqueCylinder {
+   pub fn new(
+      location: Location,
+      height: Size,
+      radius: Size,
+      top_tilt: Angle,
+      tilt_direction: Angle
+   ) -> ObliqueCylinder {
+      ObliqueCylinder {
+         location, height, radius, top_tilt, tilt_direction, fragments: None
+      }
+   }
+
+   /// Overrides the precision specified by [env](crate::solid::builder::env).
+   /// See [FragmentSpec].
+   pub fn with_fragment_angle(self, angle: Angle) -> ObliqueCylinder {
+      ObliqueCylinder { fragments: Some(FragmentSpec::Angle(angle)), ..self }
+   }
+
+   /// Overrides the precision specified by [env](crate::solid::builder::env).
+   /// See [FragmentSpec].
+   pub fn with_fragment_count(self, count: usize) -> ObliqueCylinder {
+      ObliqueCylinder { fragments: Some(FragmentSpec::Count(count)), ..self }
+   }
+
+   /// The normal vector of the top face.
+   pub fn top_normal_vector(&self) -> Vector {
+      let top = self.location.top_vector();
+      let tilt_axis = self.location.back_vector().rotated(&top, self.tilt_direction);
+      top.rotated(&tilt_axis, self.top_tilt)
+   }
+}
+
+pub fn oblique_cylinder(
+   location: Location,
+   height: Size,
+   radius: Size,
+   top_tilt: Angle,
+   tilt_direction: Angle
+) -> ObliqueCylinder {
+   ObliqueCylinder::new(location, height, radius, top_tilt, tilt_direction)
+}
+
+impl Solid for ObliqueCylinder {
+   fn generate_stl_solid(&self) -> StlSolid {
+      if self.top_tilt.abs() >= 90.deg()
+         || self.radius * self.top_tilt.abs().tan() >= self.height
+      {
+         panic!("The top of an oblique cylinder must not reach the bottom.");
+      }
+
+      let fragment_angle = fragment_angle(self.radius, self.fragments);
+
+      let back = &self.location.back_vector();
+      let top = &self.location.top_vector();
+      let radius = self.radius;
+      let height = self.height;
+      let bottom_point = self.location.point();
+      let top_point = bottom_point.translated_toward(top, height);
+      let top_normal = self.top_normal_vector();
+
+      let directions: Vec<_>
+         = Angle::iterate(0.deg()..360.deg()).step(fragment_angle)
+         .map(|a| back.rotated(top, a))
+         .collect();
+
+      let bottom_points: Vec<_> = directions.iter()
+         .map(|v| bottom_point.translated_toward(v, radius))
+         .collect();
+
+      // move up or down from the top of a regular cylinder onto the top plane
+      let top_points: Vec<_> = directions.iter()
+         .map(|v| {
+            let offset = -radius
+               * (v.inner_product(&top_normal).0 / top.inner_product(&top_normal).0);
+            top_point
+               .translated_toward(v, radius)
+               .translated_toward(top, offset)
+         })
+         .collect();
+
+      let zipped = |points: &Vec<Point>| {
+         let first = points[0];
+         points.clone().into_iter()
+            .zip(points.clone().into_iter().skip(1).chain([first]))
+            .collect::<Vec<_>>()
+      };
+
+      let zipped_bottom_points = zipped(&bottom_points);
+      let zipped_top_points = zipped(&top_points);
+
+      let bottom_facets = zipped_bottom_points.iter().map(|(a, b)|
+         Facet::new([bottom_point, *b, *a])
+      );
+
+      let top_facets = zipped_top_points.iter().map(|(a, b)|
+         Facet::new([top_point, *a, *b])
+      );
+
+      let side_facets
+         = zipped_bottom_points.iter().zip(&zipped_top_points)
+         .flat_map(|((bottom_a, bottom_b), (top_a, top_b))|
+            [
+               Facet::new([*bottom_a, *top_b, *top_a]),
+               Facet::new([*top_b, *bottom_a, *bottom_b])
+            ]
+         );
+
+      StlSolid {
+         facets: bottom_facets
+            .chain(side_facets)
+            .chain(top_facets)
+            .collect()
+      }
+   }
+
+   /// The top plane passes the axis, so the volume is the same as
+   /// the regular cylinder.
+   fn approximate_volume(&self) -> Option<Exp<Size, 3>> {
+      Some(self.radius * self.radius * self.height * PI)
+   }
+}
+
+impl Transform for ObliqueCylinder {
+   fn translated(&self, offset: &Vector) -> Self {
+      Self {
+         location: self.location.translated(offset),
+         height: self.height,
+         radius: self.radius,
+         top_tilt: self.top_tilt,
+         tilt_direction: self.tilt_direction,
+         fragments: self.fragments
+      }
+   }
+
+   fn rotated(&self, axis: &Line, angle: Angle) -> Self {
+      Self {
+         location: self.location.rotated(axis, angle),
+         height: self.height,
+         radius: self.radius,
+         top_tilt: self.top_tilt,
+         tilt_direction: self.tilt_direction,
+         fragments: self.fragments
+      }
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::oblique_cylinder;
+   use crate::geometry::{AngleLiteral, Plane, Point, SizeLiteral, Vector};
+   use crate::solid::{cylinder, Location, Solid};
+
+   #[test]
+   fn no_tilt() {
+      let expected: Vec<_> = cylinder(Location::default(), 5.mm(), 2.mm())
+         .generate_stl_solid().facets.iter()
+         .flat_map(|f| f.vertexes())
+         .collect();
+
+      let actual: Vec<_>
+         = oblique_cylinder(Location::default(), 5.mm(), 2.mm(), 0.deg(), 0.deg())
+         .generate_stl_solid().facets.iter()
+         .flat_map(|f| f.vertexes())
+         .collect();
+
+      assert_eq!(actual, expected);
+   }
+
+   #[test]
+   fn top_plane() {
+      let cylinder = oblique_cylinder(
+         Location::default(), 5.mm(), 2.mm(), 30.deg(), 90.deg()
+      );
+      let normal = cylinder.top_normal_vector();
+      let solid = cylinder.generate_stl_solid();
+
+      // tilted around -X, so the top faces toward +Y
+      assert_eq!(normal, Vector::new(0.mm(), 0.5.mm(), (0.75_f64).sqrt().mm()));
+
+      let plane = Plane::new(&Point::new(0.mm(), 0.mm(), 5.mm()), &normal);
+      let top_facets = &solid.facets[(solid.facets.len() * 3 / 4)..];
+      for f in top_facets {
+         assert_eq!(f.normal_vector(), normal);
+         for v in f.vertexes() {
+            assert!(plane.contains_point(&v), "{:?}", v);
+         }
+      }
+
+      let volume = solid.volume().0.raw();
+      let expected = cylinder.approximate_volume().unwrap().0.raw();
+      assert!((volume - expected).abs() / expected < 0.01);
+      assert!(solid.is_watertight());
+   }
+
+   #[test]
+   fn side_normal_vector() {
+      let solid = oblique_cylinder(
+         Location::default(), 5.mm(), 2.mm(), 20.deg(), 45.deg()
+      ).generate_stl_solid();
+
+      let count = solid.facets.len() / 4;
+      for f in &solid.facets[count..(count * 3)] {
+         assert_eq!(f.normal_vector().z(), 0.mm());
+
+         let outward = Vector::new(f.vertexes()[0].x(), f.vertexes()[0].y(), 0.mm());
+         assert!(f.normal_vector().inner_product(&outward).0 > 0.0);
+      }
+   }
+
+   #[test]
+   #[should_panic]
+   fn too_much_tilt() {
+      oblique_cylinder(Location::default(), 1.mm(), 2.mm(), 30.deg(), 0.deg())
+         .generate_stl_solid();
+   }
+}
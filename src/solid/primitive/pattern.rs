@@ -0,0 +1,293 @@
+use crate::geometry::{Angle, AngleLiteral, Line, Point, Size, Vector};
+use crate::math::unit::Exp;
+use crate::solid::{Solid, SolidParent};
+use crate::solid::builder::BuildContext;
+use crate::solid::solid_parent::PushBorrowing;
+use crate::stl::{Facet, StlSolid};
+use crate::transform::Transform;
+
+/// Repeats its children `count` times, moving each copy by `offset` from the
+/// previous one. The first copy is at the original position.
+pub struct LinearPattern {
+   pub offset: Vector,
+   pub count: usize,
+   pub children: Vec<Box<dyn Solid>>
+}
+
+/// Repeats its children on a lattice. `x.1` copies toward `x.0` for each of
+/// `y.1` rows toward `y.0`. The first copy is at the original position.
+pub struct GridPattern {
+   pub x: (Vector, usize),
+   pub y: (Vector, usize),
+   pub children: Vec<Box<dyn Solid>>
+}
+
+/// Repeats its children `count` times around `axis`.
+///
+/// When `total_angle` is 360 degrees, copies are placed evenly around the
+/// axis. Otherwise the first copy is at the original position and the last
+/// one is rotated by `total_angle`.
+pub struct PolarPattern {
+   pub axis: Line,
+   pub count: usize,
+   pub total_angle: Angle,
+   pub children: Vec<Box<dyn Solid>>
+}
+
+impl LinearPattern {
+   pub fn new(offset: Vector, count: usize) -> LinearPattern {
+      LinearPattern {
+         offset,
+         count,
+         children: vec![]
+      }
+   }
+}
+
+impl GridPattern {
+   pub fn new(x: (Vector, usize), y: (Vector, usize)) -> GridPattern {
+      GridPattern {
+         x,
+         y,
+         children: vec![]
+      }
+   }
+}
+
+impl PolarPattern {
+   pub fn new(axis: Line, count: usize, total_angle: Angle) -> PolarPattern {
+      PolarPattern {
+         axis,
+         count,
+         total_angle,
+         children: vec![]
+      }
+   }
+
+   /// Angles of each copy.
+   fn angles(&self) -> Vec<Angle> {
+      let step = if self.total_angle == 360.deg() || self.count <= 1 {
+         self.total_angle / self.count.max(1) as f64
+      } else {
+         self.total_angle / (self.count - 1) as f64
+      };
+
+      (0..self.count).map(|i| step * i as f64).collect()
+   }
+}
+
+pub fn linear_pattern(
+   offset: Vector,
+   count: usize,
+   build_action: impl FnOnce(BuildContext<LinearPattern>)
+) -> LinearPattern {
+   BuildContext::build(
+      LinearPattern::new(offset, count),
+      build_action
+   )
+}
+
+pub fn grid_pattern(
+   x: (Vector, usize),
+   y: (Vector, usize),
+   build_action: impl FnOnce(BuildContext<GridPattern>)
+) -> GridPattern {
+   BuildContext::build(
+      GridPattern::new(x, y),
+      build_action
+   )
+}
+
+pub fn polar_pattern(
+   axis: Line,
+   count: usize,
+   total_angle: Angle,
+   build_action: impl FnOnce(BuildContext<PolarPattern>)
+) -> PolarPattern {
+   BuildContext::build(
+      PolarPattern::new(axis, count, total_angle),
+      build_action
+   )
+}
+
+/// Generates the children once and copies the facets for each instance.
+fn repeat(
+   children: &[Box<dyn Solid>],
+   count: usize,
+   transform: impl Fn(usize, &Point) -> Point
+) -> StlSolid {
+   if count == 0 {
+      return StlSolid::new(vec![]);
+   }
+
+   let facets: Vec<_> = children.iter()
+      .flat_map(|c| c.generate_stl_solid().facets)
+      .collect();
+
+   StlSolid {
+      facets: (0..count)
+         .flat_map(|i|
+            facets.iter().map(move |f| (i, f))
+         )
+         .map(|(i, f)| Facet::new(f.vertexes().map(|v| transform(i, &v))))
+         .collect()
+   }
+}
+
+/// Sum of the volumes of the children multiplied by the number of copies.
+fn repeated_volume(
+   children: &[Box<dyn Solid>],
+   count: usize
+) -> Option<Exp<Size, 3>> {
+   let volume: Option<Exp<Size, 3>> = children.iter()
+      .map(|c| c.approximate_volume())
+      .sum();
+
+   volume.map(|v| v * count as f64)
+}
+
+impl Solid for LinearPattern {
+   fn generate_stl_solid(&self) -> StlSolid {
+      repeat(&self.children, self.count, |i, v| {
+         v.translated(&(self.offset * i as f64))
+      })
+   }
+
+   fn approximate_volume(&self) -> Option<Exp<Size, 3>> {
+      repeated_volume(&self.children, self.count)
+   }
+}
+
+impl Solid for GridPattern {
+   fn generate_stl_solid(&self) -> StlSolid {
+      let (x_offset, x_count) = self.x;
+      let (y_offset, y_count) = self.y;
+
+      repeat(&self.children, x_count * y_count, |i, v| {
+         let offset = x_offset * (i % x_count) as f64
+            + y_offset * (i / x_count) as f64;
+         v.translated(&offset)
+      })
+   }
+
+   fn approximate_volume(&self) -> Option<Exp<Size, 3>> {
+      repeated_volume(&self.children, self.x.1 * self.y.1)
+   }
+}
+
+impl Solid for PolarPattern {
+   fn generate_stl_solid(&self) -> StlSolid {
+      let angles = self.angles();
+
+      repeat(&self.children, self.count, |i, v| {
+         v.rotated(&self.axis, angles[i])
+      })
+   }
+
+   fn approximate_volume(&self) -> Option<Exp<Size, 3>> {
+      repeated_volume(&self.children, self.count)
+   }
+}
+
+impl SolidParent for LinearPattern {
+   fn push<S: Solid + 'static>(&mut self, child: S) -> &mut S {
+      self.children.push_borrowing(child)
+   }
+}
+
+impl SolidParent for GridPattern {
+   fn push<S: Solid + 'static>(&mut self, child: S) -> &mut S {
+      self.children.push_borrowing(child)
+   }
+}
+
+impl SolidParent for PolarPattern {
+   fn push<S: Solid + 'static>(&mut self, child: S) -> &mut S {
+      self.children.push_borrowing(child)
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::{grid_pattern, linear_pattern, polar_pattern};
+   use crate::geometry::{AngleLiteral, Line, Point, SizeLiteral, Vector};
+   use crate::solid::{cube, Location, Solid};
+   use crate::transform::Transform;
+
+   #[test]
+   fn polar() {
+      let pattern = polar_pattern(Line::Z_AXIS, 6, 360.deg(), |mut c| {
+         c <<= cube(Location::default(), (1.mm(), 1.mm(), 1.mm()))
+            .translated(&Vector::new(5.mm(), 0.mm(), 0.mm()));
+      });
+      let solid = pattern.generate_stl_solid();
+      assert_eq!(solid.facet_count(), 6 * 12);
+
+      let corner = Point::new(5.mm(), 0.mm(), 0.mm());
+      for (i, instance) in solid.facets.chunks(12).enumerate() {
+         let expected = corner.rotated(&Line::Z_AXIS, 60.deg() * i as f64);
+         assert!(instance.iter().flat_map(|f| f.vertexes()).any(|v| v == expected));
+      }
+   }
+
+   #[test]
+   fn polar_partial() {
+      let pattern = polar_pattern(Line::Z_AXIS, 3, 90.deg(), |mut c| {
+         c <<= cube(Location::default(), (1.mm(), 1.mm(), 1.mm()));
+      });
+      assert_eq!(pattern.angles(), vec![0.deg(), 45.deg(), 90.deg()]);
+   }
+
+   #[test]
+   fn grid() {
+      let pattern = grid_pattern(
+         (Vector::new(2.mm(), 0.mm(), 0.mm()), 3),
+         (Vector::new(0.mm(), 3.mm(), 0.mm()), 4),
+         |mut c| {
+            c <<= cube(Location::default(), (1.mm(), 1.mm(), 1.mm()));
+         }
+      );
+      let solid = pattern.generate_stl_solid();
+      assert_eq!(solid.facet_count(), 12 * 12);
+
+      let mut actual: Vec<_> = solid.facets.chunks(12)
+         .map(|instance| {
+            instance.iter().flat_map(|f| f.vertexes())
+               .min_by_key(|v| v.x() + v.y() + v.z())
+               .unwrap()
+         })
+         .collect();
+      actual.sort_by_key(|v| (v.x(), v.y()));
+
+      let mut expected: Vec<_> = (0..3)
+         .flat_map(|x| (0..4).map(move |y| Point::new(2.mm() * x, 3.mm() * y, 0.mm())))
+         .collect();
+      expected.sort_by_key(|v| (v.x(), v.y()));
+
+      assert_eq!(actual, expected);
+   }
+
+   #[test]
+   fn count() {
+      let linear = |count| {
+         linear_pattern(Vector::X_UNIT_VECTOR, count, |mut c| {
+            c <<= cube(Location::default(), (1.mm(), 1.mm(), 1.mm()));
+         })
+      };
+
+      assert_eq!(linear(0).generate_stl_solid().facet_count(), 0);
+
+      let single = linear(1).generate_stl_solid();
+      let original = cube(Location::default(), (1.mm(), 1.mm(), 1.mm()))
+         .generate_stl_solid();
+      for (a, b) in single.facets().zip(original.facets()) {
+         assert_eq!(a.vertexes(), b.vertexes());
+      }
+
+      let solid = linear(3).generate_stl_solid();
+      assert_eq!(solid.facet_count(), 36);
+      assert!(solid.facets[24..].iter()
+         .flat_map(|f| f.vertexes())
+         .all(|v| v.x() >= 2.mm() && v.x() <= 3.mm()));
+   }
+}
@@ -0,0 +1,151 @@
+use crate::geometry::Size;
+use crate::math::unit::Exp;
+use crate::scad::{describe_block, location_prefix, ScadDescribable};
+use crate::solid::{Location, Solid, SolidParent};
+use crate::solid::builder::BuildContext;
+use crate::solid::solid_parent::PushBorrowing;
+use crate::stl::StlSolid;
+use anyhow::Result;
+use std::io::Write;
+
+/// Moves its children from the origin to the location.
+///
+/// The children are built around the origin, and then translated to
+/// [Location::point] and rotated so that X-Axis, Y-Axis, and Z-Axis become
+/// the right, back, and top vectors of the location. The same as nesting
+/// [Rotate](crate::solid::Rotate) in [Translate](crate::solid::Translate).
+/// ```
+/// # use typed_scad::geometry::{Point, SizeLiteral, Vector};
+/// # use typed_scad::solid::{cylinder, place, Location, Solid};
+/// // a cylinder lying along X-Axis from X=5mm
+/// let location = Location::build(Point::new(5.mm(), 0.mm(), 0.mm()))
+///    .top_vector(Vector::X_UNIT_VECTOR)
+///    .back_vector(Vector::Y_UNIT_VECTOR);
+///
+/// let placed = place(location, |mut c| {
+///    c <<= cylinder(Location::default(), 10.mm(), 1.mm());
+/// });
+///
+/// let bounding_box = placed.bounding_box().unwrap();
+/// assert_eq!(bounding_box.min.x(), 5.mm());
+/// assert_eq!(bounding_box.max.x(), 15.mm());
+/// ```
+pub struct Place {
+   pub location: Location,
+   pub children: Vec<Box<dyn Solid>>
+}
+
+impl Place {
+   pub fn new(location: Location) -> Place {
+      Place {
+         location,
+         children: vec![]
+      }
+   }
+}
+
+pub fn place(
+   location: Location,
+   build_action: impl FnOnce(BuildContext<Place>)
+) -> Place {
+   BuildContext::build(
+      Place::new(location),
+      build_action
+   )
+}
+
+impl Solid for Place {
+   fn generate_stl_solid(&self) -> StlSolid {
+      let mut stl_solid = StlSolid::EMPTY;
+      for c in self.children.iter() {
+         stl_solid.extend(c.generate_stl_solid());
+      }
+
+      for f in &mut stl_solid.facets {
+         let mut vertexes = f.vertexes();
+         for v in &mut vertexes {
+            *v = self.location.to_global(v);
+         }
+         f.set_vertexes(vertexes);
+      }
+
+      stl_solid
+   }
+
+   /// Sum of the volumes of the children. Overlapping parts are counted
+   /// twice. Returns `None` if any child returns `None`.
+   fn approximate_volume(&self) -> Option<Exp<Size, 3>> {
+      self.children.iter()
+         .map(|c| c.approximate_volume())
+         .sum()
+   }
+
+   fn as_scad_describable(&self) -> Option<&dyn ScadDescribable> {
+      Some(self)
+   }
+}
+
+impl ScadDescribable for Place {
+   fn describe_scad(&self, output: &mut dyn Write, indent: usize) -> Result<()> {
+      let prefix = location_prefix(&self.location);
+      let header = if prefix.is_empty() { "union()" } else { prefix.trim_end() };
+      describe_block(output, indent, header, &self.children)
+   }
+}
+
+impl SolidParent for Place {
+   fn push<S: Solid + 'static>(&mut self, child: S) -> &mut S {
+      self.children.push_borrowing(child)
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::place;
+   use crate::geometry::{AngleLiteral, Line, Point, SizeLiteral, Vector};
+   use crate::scad::ScadDescribable;
+   use crate::solid::{cube, rotate, translate, Location, Solid};
+   use crate::transform::Transform;
+
+   #[test]
+   fn same_as_translate_and_rotate() {
+      let axis = Line::new(&Point::ORIGIN, &Vector::new(1.mm(), 2.mm(), 3.mm()));
+      let offset = Vector::new(4.mm(), -5.mm(), 6.mm());
+      let location = Location::default()
+         .rotated(&axis, 40.deg())
+         .translated(&offset);
+
+      let placed = place(location, |mut c| {
+         c <<= cube(Location::default(), (1.mm(), 2.mm(), 3.mm()));
+      });
+
+      let nested = translate(offset, |mut c| {
+         c <<= rotate(axis, 40.deg(), |mut c| {
+            c <<= cube(Location::default(), (1.mm(), 2.mm(), 3.mm()));
+         });
+      });
+
+      assert!(placed.vertexes().eq(nested.vertexes()));
+      assert_eq!(placed.approximate_volume(), nested.approximate_volume());
+      assert!(placed.generate_stl_solid().is_watertight());
+      assert_eq!(placed.generate_stl_solid().volume(), 6.mm() * 1.mm() * 1.mm());
+   }
+
+   #[test]
+   fn describe_scad() {
+      let describe = |location: Location| {
+         let placed = place(location, |_| {});
+         let mut output = vec![];
+         placed.describe_scad(&mut output, 0).unwrap();
+         String::from_utf8(output).unwrap()
+      };
+
+      assert_eq!(describe(Location::default()), "union() {\n}\n");
+
+      let location = Location::default().translated(&Vector::new(1.mm(), 2.mm(), 3.mm()));
+      assert_eq!(
+         describe(location),
+         "multmatrix([[1, 0, 0, 1], [0, 1, 0, 2], [0, 0, 1, 3], [0, 0, 0, 1]]) {\n}\n"
+      );
+   }
+}
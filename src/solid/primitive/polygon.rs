@@ -0,0 +1,100 @@
+use crate::geometry::Size;
+use crate::math::unit::Exp;
+
+fn cross(o: (Size, Size), a: (Size, Size), b: (Size, Size)) -> Exp<Size, 2> {
+   (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+}
+
+/// Twice the area of the polygon. Positive when counterclockwise.
+pub(in crate::solid) fn signed_area(polygon: &[(Size, Size)]) -> Exp<Size, 2> {
+   let o = polygon[0];
+   polygon.windows(2)
+      .map(|w| cross(o, w[0], w[1]))
+      .reduce(|a, b| a + b)
+      .unwrap()
+}
+
+/// Splits a counterclockwise simple polygon into counterclockwise triangles
+/// by ear clipping. Returns indexes of the vertexes.
+pub(in crate::solid) fn triangulate(polygon: &[(Size, Size)]) -> Vec<[usize; 3]> {
+   let mut remaining: Vec<usize> = (0..polygon.len()).collect();
+   let mut triangles = Vec::with_capacity(polygon.len() - 2);
+
+   let is_inside = |p: (Size, Size), [a, b, c]: [(Size, Size); 3]| {
+      cross(a, b, p).0 >= 0.0 && cross(b, c, p).0 >= 0.0 && cross(c, a, p).0 >= 0.0
+   };
+
+   while remaining.len() > 3 {
+      let len = remaining.len();
+      let triangle_at = |i: usize| [
+         remaining[(i + len - 1) % len],
+         remaining[i],
+         remaining[(i + 1) % len]
+      ];
+
+      let is_ear = |i: usize| {
+         let triangle = triangle_at(i);
+         let [a, b, c] = triangle.map(|v| polygon[v]);
+         cross(a, b, c).0 > 0.0
+            && remaining.iter()
+               .filter(|v| !triangle.contains(v))
+               .all(|&v| !is_inside(polygon[v], [a, b, c]))
+      };
+
+      // when no ear is found because of arithmetic errors,
+      // clip the most convex vertex to make sure that this loop ends
+      let ear = (0..len).find(|&i| is_ear(i))
+         .unwrap_or_else(|| {
+            (0..len)
+               .max_by_key(|&i| {
+                  let [a, b, c] = triangle_at(i).map(|v| polygon[v]);
+                  cross(a, b, c).0
+               })
+               .unwrap()
+         });
+
+      triangles.push(triangle_at(ear));
+      remaining.remove(ear);
+   }
+
+   triangles.push([remaining[0], remaining[1], remaining[2]]);
+   triangles
+}
+
+#[cfg(test)]
+mod tests {
+   use super::{cross, signed_area, triangulate};
+   use crate::geometry::SizeLiteral;
+
+   #[test]
+   fn concave_polygon() {
+      let polygon = vec![
+         (0.mm(), 0.mm()),
+         (3.mm(), 0.mm()),
+         (3.mm(), 3.mm()),
+         (2.mm(), 3.mm()),
+         (2.mm(), 1.mm()),
+         (1.mm(), 1.mm()),
+         (1.mm(), 3.mm()),
+         (0.mm(), 3.mm())
+      ];
+
+      let triangles = triangulate(&polygon);
+      assert_eq!(triangles.len(), 6);
+
+      let area: f64 = triangles.iter()
+         .map(|t| t.map(|i| polygon[i]))
+         .map(|[a, b, c]| cross(a, b, c).0.raw() / 2.0)
+         .sum();
+      assert_eq!(area, 7.0);
+   }
+
+   #[test]
+   fn area() {
+      let polygon = vec![(0.mm(), 0.mm()), (2.mm(), 0.mm()), (0.mm(), 3.mm())];
+      assert_eq!(signed_area(&polygon).0, 6.0);
+
+      let polygon: Vec<_> = polygon.into_iter().rev().collect();
+      assert_eq!(signed_area(&polygon).0, -6.0);
+   }
+}
@@ -1,4 +1,179 @@
-use crate::geometry::{Angle, AngleLiteral};
+use crate::geometry::{Angle, AngleLiteral, Size};
 use crate::solid::builder::BuildEnv;
+use noisy_float::prelude::*;
+
+pub use crate::solid::builder::scope;
 
 pub static FRAGMENT_MINIMUM_ANGLE: BuildEnv<Angle> = BuildEnv::new(|| 12.deg());
+
+/// Maximum distance between a circle and its approximating polygon.
+/// Infinity by default, which means only [FRAGMENT_MINIMUM_ANGLE] is used.
+pub static FRAGMENT_MAXIMUM_DEVIATION: BuildEnv<Size>
+   = BuildEnv::new(|| Size::INFINITY);
+
+/// Precision of a round solid specified for each solid.
+///
+/// The precision is chosen in the following order.
+/// 1. `FragmentSpec` specified for the solid. e.g.
+///    [Cylinder::with_fragment_angle](crate::solid::Cylinder::with_fragment_angle)
+/// 2. [FRAGMENT_MINIMUM_ANGLE] and [FRAGMENT_MAXIMUM_DEVIATION] specified by
+///    [env](crate::solid::builder::env)
+/// 3. The default values of [FRAGMENT_MINIMUM_ANGLE] and
+///    [FRAGMENT_MAXIMUM_DEVIATION]
+///
+/// Since env is thread-local, it does not reach solids generated on other
+/// threads like rayon's thread pool. Use `FragmentSpec` in such cases.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FragmentSpec {
+   Angle(Angle),
+   Count(usize)
+}
+
+impl FragmentSpec {
+   pub fn angle(self) -> Angle {
+      match self {
+         FragmentSpec::Angle(angle) => angle,
+         FragmentSpec::Count(0) => panic!("Fragment count must be positive."),
+         FragmentSpec::Count(count) => 360.deg() / count
+      }
+   }
+}
+
+/// The angle of a fragment of a circle with the specified radius.
+///
+/// `fragments` if specified. Otherwise the finer of [FRAGMENT_MINIMUM_ANGLE]
+/// and the angle which satisfies [FRAGMENT_MAXIMUM_DEVIATION].
+pub fn fragment_angle(radius: Size, fragments: Option<FragmentSpec>) -> Angle {
+   if let Some(fragments) = fragments {
+      return fragments.angle();
+   }
+
+   let minimum_angle = *FRAGMENT_MINIMUM_ANGLE;
+   let deviation = *FRAGMENT_MAXIMUM_DEVIATION;
+
+   if radius <= Size::ZERO {
+      return minimum_angle;
+   }
+
+   let cos = (1.0 - (deviation / radius).raw()).clamp(-1.0, 1.0);
+   let deviation_angle = Angle::acos(n64(cos)) * 2;
+
+   if deviation_angle <= 0.deg() {
+      return minimum_angle;
+   }
+
+   Ord::min(minimum_angle, deviation_angle)
+}
+
+/// The number of fragments of a circle with the specified radius.
+/// See [fragment_angle].
+///
+/// Counted in the same way as round solids iterate the angles of their
+/// vertexes, so this is always the number of the vertexes on a circle.
+pub fn fragment_count(radius: Size, fragments: Option<FragmentSpec>) -> usize {
+   Angle::iterate(0.deg()..360.deg())
+      .step(fragment_angle(radius, fragments))
+      .count()
+}
+
+/// The perimeter of the polygon which approximates a circle with
+/// `fragment_angle`. Shorter than `2πr`.
+///
+/// The last fragment is narrower if 360° is not divisible by `fragment_angle`,
+/// in the same way as round solids.
+pub fn circle_perimeter(radius: Size, fragment_angle: Angle) -> Size {
+   let angles: Vec<_> = Angle::iterate(0.deg()..360.deg()).step(fragment_angle)
+      .chain([360.deg()])
+      .collect();
+
+   angles.windows(2)
+      .map(|w| {
+         let half: Angle = (w[1] - w[0]) / 2;
+         radius * half.sin() * 2
+      })
+      .sum()
+}
+
+#[cfg(test)]
+mod tests {
+   use super::{
+      circle_perimeter, fragment_angle, fragment_count, FragmentSpec,
+      FRAGMENT_MAXIMUM_DEVIATION, FRAGMENT_MINIMUM_ANGLE
+   };
+   use crate::geometry::{Angle, AngleLiteral, Size, SizeLiteral};
+   use crate::solid::builder::env;
+
+   #[test]
+   fn infinite_deviation() {
+      env(&FRAGMENT_MINIMUM_ANGLE, 360.deg(), || {
+         assert_eq!(fragment_angle(100.mm(), None), 360.deg());
+      });
+
+      env(&FRAGMENT_MAXIMUM_DEVIATION, Size::INFINITY, || {
+         assert_eq!(fragment_angle(1.mm(), None), *FRAGMENT_MINIMUM_ANGLE);
+         assert_eq!(fragment_angle(100.mm(), None), *FRAGMENT_MINIMUM_ANGLE);
+      });
+   }
+
+   #[test]
+   fn deviation() {
+      env(&FRAGMENT_MAXIMUM_DEVIATION, 0.1.mm(), || {
+         assert_eq!(fragment_angle(1.mm(), None), *FRAGMENT_MINIMUM_ANGLE);
+         assert!(fragment_angle(100.mm(), None) < *FRAGMENT_MINIMUM_ANGLE);
+
+         let half_angle: Angle = fragment_angle(100.mm(), None) / 2;
+         let actual = 100.mm() - 100.mm() * half_angle.cos();
+         assert_eq!(actual, 0.1.mm());
+      });
+   }
+
+   #[test]
+   fn fragment_spec() {
+      env(&FRAGMENT_MINIMUM_ANGLE, 90.deg(), || {
+         assert_eq!(fragment_angle(1.mm(), None), 90.deg());
+
+         let spec = Some(FragmentSpec::Angle(5.deg()));
+         assert_eq!(fragment_angle(1.mm(), spec), 5.deg());
+
+         let spec = Some(FragmentSpec::Count(8));
+         assert_eq!(fragment_angle(1.mm(), spec), 45.deg());
+      });
+   }
+
+   #[test]
+   fn count() {
+      assert_eq!(fragment_count(1.mm(), None), 30);
+
+      env(&FRAGMENT_MINIMUM_ANGLE, 90.deg(), || {
+         assert_eq!(fragment_count(1.mm(), None), 4);
+      });
+
+      env(&FRAGMENT_MINIMUM_ANGLE, 7.deg(), || {
+         assert_eq!(fragment_count(1.mm(), None), 52);
+      });
+
+      assert_eq!(fragment_count(1.mm(), Some(FragmentSpec::Count(5))), 5);
+      assert_eq!(fragment_count(1.mm(), None), 30);
+   }
+
+   #[test]
+   fn perimeter() {
+      assert_eq!(circle_perimeter(1.mm(), 90.deg()), 2.0_f64.sqrt().mm() * 4);
+      assert_eq!(circle_perimeter(2.mm(), 60.deg()), 12.mm());
+
+      // 100° + 100° + 100° + 60°
+      let expected = (100.0_f64 / 2.0).to_radians().sin() * 2.0 * 3.0
+         + (60.0_f64 / 2.0).to_radians().sin() * 2.0;
+      assert_eq!(circle_perimeter(1.mm(), 100.deg()), expected.mm());
+
+      let ideal = 2.mm() * std::f64::consts::TAU;
+      let mut previous = Size::ZERO;
+      for angle in [30.deg(), 10.deg(), 1.deg(), 0.1.deg()] {
+         let perimeter = circle_perimeter(2.mm(), angle);
+         assert!(perimeter > previous);
+         assert!(perimeter < ideal);
+         previous = perimeter;
+      }
+      assert!(ideal - previous < 0.0001.mm());
+   }
+}
@@ -0,0 +1,224 @@
+use crate::geometry::{Angle, BoundingBox, Line, Point, Size, Vector};
+use crate::solid::{Solid, SolidParent};
+use crate::solid::builder::BuildContext;
+use crate::solid::primitive::group::Movement;
+use crate::solid::solid_parent::PushBorrowing;
+use crate::stl::StlSolid;
+use crate::transform::Transform;
+use std::rc::Rc;
+
+/// Scales its children so that their bounding box fits `target`.
+///
+/// `target` is the sizes along X-Axis, Y-Axis, and Z-Axis. The children are
+/// scaled about the minimum corner of their bounding box. An axis of `None` is
+/// not scaled, or if `keep_aspect` is `true`, scaled by the largest factor of
+/// the other axes. An axis along which the children have no extent is not
+/// scaled.
+///
+/// Like [Group](crate::solid::Group), [Transform] is applied after resizing,
+/// and the moved one shares its children with the original one.
+pub struct Resize {
+   pub target: (Option<Size>, Option<Size>, Option<Size>),
+   pub keep_aspect: bool,
+   children: Rc<Vec<Box<dyn Solid>>>,
+   /// Movements applied to the resized facets, in order.
+   movements: Vec<Movement>
+}
+
+impl Resize {
+   pub fn new(
+      target: (Option<Size>, Option<Size>, Option<Size>),
+      keep_aspect: bool
+   ) -> Resize {
+      Resize {
+         target,
+         keep_aspect,
+         children: Rc::new(vec![]),
+         movements: vec![]
+      }
+   }
+
+   pub fn children(&self) -> &[Box<dyn Solid>] {
+      &self.children
+   }
+
+   fn moved(&self, movement: Movement) -> Resize {
+      Resize {
+         target: self.target,
+         keep_aspect: self.keep_aspect,
+         children: self.children.clone(),
+         movements: self.movements.iter().copied().chain([movement]).collect()
+      }
+   }
+}
+
+pub fn resize(
+   target: (Option<Size>, Option<Size>, Option<Size>),
+   keep_aspect: bool,
+   build_action: impl FnOnce(BuildContext<Resize>)
+) -> Resize {
+   BuildContext::build(
+      Resize::new(target, keep_aspect),
+      build_action
+   )
+}
+
+impl Solid for Resize {
+   fn generate_stl_solid(&self) -> StlSolid {
+      let mut stl_solid = StlSolid::EMPTY;
+      for c in self.children.iter() {
+         stl_solid.extend(c.generate_stl_solid());
+      }
+
+      if let Some(BoundingBox { min, max }) = stl_solid.bounding_box() {
+         let extents = [max.x() - min.x(), max.y() - min.y(), max.z() - min.z()];
+         let (x, y, z) = self.target;
+         let targets = [x, y, z];
+
+         for t in targets.iter().flatten() {
+            if *t <= Size::ZERO {
+               panic!("The target size of resize must be positive.");
+            }
+         }
+
+         let factors = [0, 1, 2].map(|i| match targets[i] {
+            Some(t) if extents[i] > Size::ZERO => Some((t / extents[i]).raw()),
+            _ => None
+         });
+
+         let auto_factor = if self.keep_aspect {
+            factors.iter().flatten().copied().reduce(f64::max).unwrap_or(1.0)
+         } else {
+            1.0
+         };
+
+         let [fx, fy, fz] = [0, 1, 2].map(|i|
+            if targets[i].is_none() { auto_factor } else { factors[i].unwrap_or(1.0) }
+         );
+
+         for f in &mut stl_solid.facets {
+            let mut vertexes = f.vertexes();
+            for v in &mut vertexes {
+               let offset = Vector::between(&min, v);
+               *v = Point::new(
+                  min.x() + offset.x() * fx,
+                  min.y() + offset.y() * fy,
+                  min.z() + offset.z() * fz
+               );
+            }
+            f.set_vertexes(vertexes);
+         }
+      }
+
+      for m in &self.movements {
+         m.apply(&mut stl_solid);
+      }
+
+      stl_solid
+   }
+}
+
+impl SolidParent for Resize {
+   /// Panics if this shares its children with a moved one.
+   fn push<S: Solid + 'static>(&mut self, child: S) -> &mut S {
+      Rc::get_mut(&mut self.children)
+         .expect("Cannot add a child to a resize which is shared with a moved one.")
+         .push_borrowing(child)
+   }
+}
+
+impl Transform for Resize {
+   fn translated(&self, offset: &Vector) -> Self {
+      self.moved(Movement::Translation(*offset))
+   }
+
+   fn rotated(&self, axis: &Line, angle: Angle) -> Self {
+      self.moved(Movement::Rotation(*axis, angle))
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::resize;
+   use crate::geometry::{Point, SizeLiteral, Vector};
+   use crate::solid::{cube, Location, Solid};
+   use crate::stl::{Facet, StlSolid};
+   use crate::transform::Transform;
+
+   fn bounds(solid: &impl Solid) -> (Point, Point) {
+      let b = solid.bounding_box().unwrap();
+      (b.min, b.max)
+   }
+
+   #[test]
+   fn stretch() {
+      let location = Location::default().translated(&Vector::new(1.mm(), 2.mm(), 3.mm()));
+
+      let resized = resize((Some(10.mm()), None, None), false, |mut c| {
+         c <<= cube(location, (1.mm(), 1.mm(), 1.mm()));
+      });
+      assert_eq!(
+         bounds(&resized),
+         (Point::new(1.mm(), 2.mm(), 3.mm()), Point::new(11.mm(), 3.mm(), 4.mm()))
+      );
+
+      let resized = resize((Some(10.mm()), None, Some(2.mm())), false, |mut c| {
+         c <<= cube(location, (1.mm(), 1.mm(), 1.mm()));
+      });
+      assert_eq!(
+         bounds(&resized),
+         (Point::new(1.mm(), 2.mm(), 3.mm()), Point::new(11.mm(), 3.mm(), 5.mm()))
+      );
+   }
+
+   #[test]
+   fn keep_aspect() {
+      let resized = resize((Some(10.mm()), None, None), true, |mut c| {
+         c <<= cube(Location::default(), (1.mm(), 1.mm(), 1.mm()));
+      });
+      assert_eq!(
+         bounds(&resized),
+         (Point::ORIGIN, Point::new(10.mm(), 10.mm(), 10.mm()))
+      );
+      assert!(resized.generate_stl_solid().is_watertight());
+   }
+
+   #[test]
+   fn flat() {
+      struct Flat;
+
+      impl Solid for Flat {
+         fn generate_stl_solid(&self) -> StlSolid {
+            StlSolid {
+               facets: vec![
+                  Facet::new([
+                     Point::ORIGIN,
+                     Point::new(1.mm(), 0.mm(), 0.mm()),
+                     Point::new(0.mm(), 2.mm(), 0.mm())
+                  ])
+               ]
+            }
+         }
+      }
+
+      let resized = resize((Some(3.mm()), None, Some(5.mm())), true, |mut c| {
+         c <<= Flat;
+      });
+      assert_eq!(
+         bounds(&resized),
+         (Point::ORIGIN, Point::new(3.mm(), 6.mm(), 0.mm()))
+      );
+   }
+
+   #[test]
+   fn transform() {
+      let resized = resize((Some(10.mm()), None, None), false, |mut c| {
+         c <<= cube(Location::default(), (1.mm(), 1.mm(), 1.mm()));
+      }).translated(&Vector::new(0.mm(), 0.mm(), 5.mm()));
+
+      assert_eq!(
+         bounds(&resized),
+         (Point::new(0.mm(), 0.mm(), 5.mm()), Point::new(10.mm(), 1.mm(), 6.mm()))
+      );
+   }
+}
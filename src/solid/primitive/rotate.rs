@@ -1,9 +1,16 @@
-use crate::geometry::{Angle, Line, Point, Vector};
+use crate::geometry::{Angle, Line, Point, Size, Vector};
+use crate::math::unit::Exp;
+use crate::scad::{
+   axis_angle_to_euler, describe_block, number_literal, vector_literal,
+   ScadDescribable
+};
 use crate::solid::builder::BuildContext;
 use crate::solid::{Solid, SolidParent};
 use crate::solid::solid_parent::PushBorrowing;
 use crate::stl::StlSolid;
 use crate::transform::Transform;
+use anyhow::Result;
+use std::io::Write;
 use std::mem;
 
 pub struct Rotate {
@@ -35,32 +42,73 @@ pub fn rotate(
 
 impl Solid for Rotate {
    fn generate_stl_solid(&self) -> StlSolid {
-      let mut stl_solid = StlSolid {
-         facets: self.children.iter()
-            .flat_map(|c| c.generate_stl_solid().facets)
-            .collect()
-      };
+      let mut stl_solid = StlSolid::EMPTY;
+      for c in self.children.iter() {
+         stl_solid.extend(c.generate_stl_solid());
+      }
 
       if self.axis.point() == Point::ORIGIN {
          let axis = self.axis.vector();
          for f in &mut stl_solid.facets {
-            for v in &mut f.vertexes {
+            let mut vertexes = f.vertexes();
+            for v in &mut vertexes {
                unsafe {
                   mem::transmute::<&mut Point, &mut Vector>(v)
                      .rotate(axis, self.angle);
                }
             }
+            f.set_vertexes(vertexes);
          }
       } else {
          for f in &mut stl_solid.facets {
-            for v in &mut f.vertexes {
+            let mut vertexes = f.vertexes();
+            for v in &mut vertexes {
                v.rotate(&self.axis, self.angle);
             }
+            f.set_vertexes(vertexes);
          }
       }
 
       stl_solid
    }
+
+   /// Sum of the volumes of the children. Overlapping parts are counted
+   /// twice. Returns `None` if any child returns `None`.
+   fn approximate_volume(&self) -> Option<Exp<Size, 3>> {
+      self.children.iter()
+         .map(|c| c.approximate_volume())
+         .sum()
+   }
+
+   fn as_scad_describable(&self) -> Option<&dyn ScadDescribable> {
+      Some(self)
+   }
+}
+
+impl ScadDescribable for Rotate {
+   fn describe_scad(&self, output: &mut dyn Write, indent: usize) -> Result<()> {
+      let (x, y, z) = axis_angle_to_euler(self.axis.vector(), self.angle);
+      let rotate = format!(
+         "rotate([{}, {}, {}])",
+         number_literal(x.to_degree()),
+         number_literal(y.to_degree()),
+         number_literal(z.to_degree())
+      );
+
+      let p = self.axis.point();
+      let header = if p == Point::ORIGIN {
+         rotate
+      } else {
+         format!(
+            "translate({}) {} translate({})",
+            vector_literal(p.x(), p.y(), p.z()),
+            rotate,
+            vector_literal(-p.x(), -p.y(), -p.z())
+         )
+      };
+
+      describe_block(output, indent, &header, &self.children)
+   }
 }
 
 impl SolidParent for Rotate {
@@ -83,13 +131,11 @@ mod tests {
          fn generate_stl_solid(&self) -> StlSolid {
             StlSolid {
                facets: vec![
-                  Facet {
-                     vertexes: [
-                        Point::new(0.mm(), 1.mm(), 2.mm()),
-                        Point::new(3.mm(), 4.mm(), 5.mm()),
-                        Point::new(6.mm(), 7.mm(), 8.mm())
-                     ]
-                  }
+                  Facet::new([
+                     Point::new(0.mm(), 1.mm(), 2.mm()),
+                     Point::new(3.mm(), 4.mm(), 5.mm()),
+                     Point::new(6.mm(), 7.mm(), 8.mm())
+                  ])
                ]
             }
          }
@@ -101,7 +147,7 @@ mod tests {
       let s = r.generate_stl_solid();
 
       let actual: Vec<_> = s.facets.iter()
-         .flat_map(|f| f.vertexes)
+         .flat_map(|f| f.vertexes())
          .collect();
       let expected = vec![
          Point::new(-1.mm(), 0.mm(), 2.mm()),
@@ -121,7 +167,7 @@ mod tests {
       let s = r.generate_stl_solid();
 
       let actual: Vec<_> = s.facets.iter()
-         .flat_map(|f| f.vertexes)
+         .flat_map(|f| f.vertexes())
          .collect();
       let expected = vec![
          Point::new( 1.mm(), 0.mm(), 2.mm()),
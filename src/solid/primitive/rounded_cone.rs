@@ -0,0 +1,235 @@
+use crate::geometry::{Angle, AngleLiteral, Line, Point, Size, Vector};
+use crate::solid::{Location, Solid};
+use crate::solid::precision::{fragment_angle, FragmentSpec};
+use crate::stl::{Facet, StlSolid};
+use crate::transform::Transform;
+
+/// [Cone](crate::solid::Cone) whose apex is rounded by a sphere.
+///
+/// The sphere of `tip_radius` touches the side of the cone, so the side
+/// continues smoothly into the sphere. Because of the rounding, the solid is
+/// a little lower than `height`, which is the height of the original sharp
+/// apex.
+pub struct RoundedCone {
+   pub location: Location,
+   pub height: Size,
+   pub bottom_radius: Size,
+   pub tip_radius: Size,
+   pub fragments: Option<FragmentSpec>
+}
+
+impl RoundedCone {
+   pub fn new(
+      location: Location,
+      height: Size,
+      bottom_radius: Size,
+      tip_radius: Size
+   ) -> RoundedCone {
+      RoundedCone { location, height, bottom_radius, tip_radius, fragments: None }
+   }
+
+   /// Overrides the precision specified by [env](crate::solid::builder::env).
+   /// See [FragmentSpec].
+   pub fn with_fragment_angle(self, angle: Angle) -> RoundedCone {
+      RoundedCone { fragments: Some(FragmentSpec::Angle(angle)), ..self }
+   }
+
+   /// Overrides the precision specified by [env](crate::solid::builder::env).
+   /// See [FragmentSpec].
+   pub fn with_fragment_count(self, count: usize) -> RoundedCone {
+      RoundedCone { fragments: Some(FragmentSpec::Count(count)), ..self }
+   }
+
+   /// Half of the apex angle of the original sharp cone.
+   fn half_angle(&self) -> Angle {
+      Angle::atan2(self.bottom_radius, self.height)
+   }
+
+   /// Distance from the bottom to the center of the sphere.
+   fn sphere_height(&self) -> Size {
+      self.height - self.tip_radius / self.half_angle().sin()
+   }
+
+   /// Distance from the bottom to the circle where the side touches
+   /// the sphere.
+   fn tangent_height(&self) -> Size {
+      self.sphere_height() + self.tip_radius * self.half_angle().sin()
+   }
+}
+
+pub fn rounded_cone(
+   location: Location,
+   height: Size,
+   bottom_radius: Size,
+   tip_radius: Size
+) -> RoundedCone {
+   RoundedCone::new(location, height, bottom_radius, tip_radius)
+}
+
+impl Solid for RoundedCone {
+   fn generate_stl_solid(&self) -> StlSolid {
+      if self.tip_radius < Size::ZERO {
+         panic!("The tip radius of a rounded cone must not be negative.");
+      }
+      if self.tangent_height() < Size::ZERO {
+         panic!("The tip radius of a rounded cone is too large for the cone.");
+      }
+
+      let fragment_angle = fragment_angle(self.bottom_radius, self.fragments);
+
+      let back = &self.location.back_vector();
+      let top = &self.location.top_vector();
+      let bottom_point = self.location.point();
+      let tip_radius = self.tip_radius;
+      let sphere_center = bottom_point.translated_toward(top, self.sphere_height());
+      let apex = sphere_center.translated_toward(top, tip_radius);
+
+      let directions: Vec<_>
+         = Angle::iterate(0.deg()..360.deg()).step(fragment_angle)
+         .map(|a| back.rotated(top, a))
+         .collect();
+
+      let ring = |center: Point, radius: Size| -> Vec<Point> {
+         directions.iter()
+            .map(|d| center.translated_toward(d, radius))
+            .collect()
+      };
+
+      let mut rings = vec![ring(bottom_point, self.bottom_radius)];
+
+      if tip_radius > Size::ZERO {
+         // polar angle of the tangent circle from the top of the sphere
+         let tangent_polar = 90.deg() - self.half_angle();
+
+         let mut polars: Vec<_>
+            = Angle::iterate(0.deg()..tangent_polar).step(fragment_angle)
+            .skip(1)
+            .chain([tangent_polar])
+            .collect();
+         polars.reverse();
+
+         rings.extend(
+            polars.into_iter().map(|polar| {
+               ring(
+                  sphere_center.translated_toward(top, tip_radius * polar.cos()),
+                  tip_radius * polar.sin()
+               )
+            })
+         );
+      }
+
+      let zipped = |ring: &Vec<Point>| {
+         let first = ring[0];
+         ring.clone().into_iter()
+            .zip(ring.clone().into_iter().skip(1).chain([first]))
+            .collect::<Vec<_>>()
+      };
+
+      let bottom_facets = zipped(&rings[0]).into_iter()
+         .map(|(a, b)| Facet::new([bottom_point, b, a]));
+
+      let side_facets = rings.windows(2)
+         .flat_map(|w| zipped(&w[0]).into_iter().zip(zipped(&w[1])))
+         .flat_map(|((a, b), (c, d))| [
+            Facet::new([a, b, d]),
+            Facet::new([d, c, a])
+         ]);
+
+      let apex_facets = zipped(rings.last().unwrap()).into_iter()
+         .map(|(a, b)| Facet::new([a, b, apex]));
+
+      StlSolid {
+         facets: bottom_facets
+            .chain(side_facets)
+            .chain(apex_facets)
+            .collect()
+      }
+   }
+}
+
+impl Transform for RoundedCone {
+   fn translated(&self, offset: &Vector) -> Self {
+      Self {
+         location: self.location.translated(offset),
+         height: self.height,
+         bottom_radius: self.bottom_radius,
+         tip_radius: self.tip_radius,
+         fragments: self.fragments
+      }
+   }
+
+   fn rotated(&self, axis: &Line, angle: Angle) -> Self {
+      Self {
+         location: self.location.rotated(axis, angle),
+         height: self.height,
+         bottom_radius: self.bottom_radius,
+         tip_radius: self.tip_radius,
+         fragments: self.fragments
+      }
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::rounded_cone;
+   use crate::geometry::{Point, SizeLiteral};
+   use crate::solid::{cone, Location, Solid};
+
+   #[test]
+   fn sharp() {
+      let expected: Vec<_> = cone(Location::default(), 10.mm(), 5.mm())
+         .generate_stl_solid().facets.iter()
+         .flat_map(|f| f.vertexes())
+         .collect();
+
+      let actual: Vec<_> = rounded_cone(Location::default(), 10.mm(), 5.mm(), 0.mm())
+         .generate_stl_solid().facets.iter()
+         .flat_map(|f| f.vertexes())
+         .collect();
+
+      assert_eq!(actual, expected);
+   }
+
+   #[test]
+   fn sphere() {
+      // the half angle is 45 degrees
+      let cone = rounded_cone(Location::default(), 5.mm(), 5.mm(), 1.mm());
+      let center = Point::new(0.mm(), 0.mm(), 5.mm() - 2.0_f64.sqrt().mm());
+      let tangent_height = center.z() + (0.5_f64.sqrt()).mm();
+
+      let solid = cone.generate_stl_solid();
+      let cap_vertexes: Vec<_> = solid.facets.iter()
+         .flat_map(|f| f.vertexes())
+         .filter(|v| v.z() >= tangent_height)
+         .collect();
+
+      assert!(!cap_vertexes.is_empty());
+      for v in cap_vertexes {
+         assert_eq!(center.distance(&v), 1.mm());
+      }
+
+      let top = solid.facets.iter()
+         .flat_map(|f| f.vertexes())
+         .map(|v| v.z())
+         .max()
+         .unwrap();
+      assert_eq!(top, center.z() + 1.mm());
+   }
+
+   #[test]
+   fn watertight() {
+      let solid = rounded_cone(Location::default(), 8.mm(), 3.mm(), 1.mm())
+         .generate_stl_solid();
+
+      assert!(solid.is_watertight());
+      assert!(solid.intersecting_facet_pairs().is_empty());
+      assert!(solid.facets.iter().all(|f| f.area().0 > 0.0));
+   }
+
+   #[test]
+   #[should_panic]
+   fn too_large_tip() {
+      rounded_cone(Location::default(), 5.mm(), 1.mm(), 3.mm())
+         .generate_stl_solid();
+   }
+}
@@ -1,17 +1,33 @@
-use crate::geometry::Point;
+use crate::geometry::{Point, Size, Vector};
+use crate::math::unit::Exp;
+use crate::scad::{describe_block, number_literal, vector_literal, ScadDescribable};
 use crate::solid::builder::BuildContext;
 use crate::solid::{Solid, SolidParent};
 use crate::solid::solid_parent::PushBorrowing;
-use crate::stl::StlSolid;
+use crate::stl::{Facet, StlSolid};
+use crate::transform::Transform;
+use anyhow::Result;
+use noisy_float::prelude::*;
+use std::io::Write;
 
+/// Scales its children about `scale_origin`. `scale` is the factors for
+/// X-Axis, Y-Axis, and Z-Axis.
+///
+/// Negative factors mirror the children. Facets are flipped as necessary
+/// so that their normal vectors still face outward.
 pub struct Scale {
-   pub scale: f64,
+   pub scale: (N64, N64, N64),
    pub scale_origin: Point,
    pub children: Vec<Box<dyn Solid>>
 }
 
 impl Scale {
    pub fn new(scale: f64, scale_origin: Point) -> Scale {
+      let scale = n64(scale);
+      Scale::new_xyz((scale, scale, scale), scale_origin)
+   }
+
+   pub fn new_xyz(scale: (N64, N64, N64), scale_origin: Point) -> Scale {
       Scale {
          scale,
          scale_origin,
@@ -31,46 +47,203 @@ pub fn scale(
    )
 }
 
+pub fn scale_xyz(
+   scale: (N64, N64, N64),
+   scale_origin: Point,
+   build_action: impl FnOnce(BuildContext<Scale>)
+) -> Scale {
+   BuildContext::build(
+      Scale::new_xyz(scale, scale_origin),
+      build_action
+   )
+}
+
 impl Solid for Scale {
    fn generate_stl_solid(&self) -> StlSolid {
-      let mut stl_solid = StlSolid {
-         facets: self.children.iter()
-            .flat_map(|c| c.generate_stl_solid().facets)
-            .collect()
-      };
+      let mut stl_solid = StlSolid::EMPTY;
+      for c in self.children.iter() {
+         stl_solid.extend(c.generate_stl_solid());
+      }
 
-      if self.scale_origin == Point::ORIGIN {
-         for f in &mut stl_solid.facets {
-            for v in &mut f.vertexes {
-               v.matrix *= self.scale;
-            }
+      let (x, y, z) = self.scale;
+      let origin = self.scale_origin;
+
+      for f in &mut stl_solid.facets {
+         let mut vertexes = f.vertexes();
+         for v in &mut vertexes {
+            let offset = Vector::between(&origin, v);
+            *v = Point::new(
+               origin.x() + offset.x() * x,
+               origin.y() + offset.y() * y,
+               origin.z() + offset.z() * z
+            );
          }
+         f.set_vertexes(vertexes);
+      }
+
+      if x * y * z < 0.0 {
+         flip(&mut stl_solid);
+      }
+
+      stl_solid
+   }
+
+   /// Sum of the volumes of the children. Overlapping parts are counted
+   /// twice. Returns `None` if any child returns `None`.
+   fn approximate_volume(&self) -> Option<Exp<Size, 3>> {
+      let (x, y, z) = self.scale;
+
+      self.children.iter()
+         .map(|c| c.approximate_volume())
+         .sum::<Option<Exp<Size, 3>>>()
+         .map(|v| v * (x * y * z).abs())
+   }
+
+   fn as_scad_describable(&self) -> Option<&dyn ScadDescribable> {
+      Some(self)
+   }
+}
+
+impl ScadDescribable for Scale {
+   fn describe_scad(&self, output: &mut dyn Write, indent: usize) -> Result<()> {
+      let scale = match self.scale {
+         (x, y, z) if x == y && y == z => format!("scale({})", number_literal(x)),
+         (x, y, z) => format!(
+            "scale([{}, {}, {}])",
+            number_literal(x), number_literal(y), number_literal(z)
+         )
+      };
+
+      let o = self.scale_origin;
+      let header = if o == Point::ORIGIN {
+         scale
       } else {
-         for f in &mut stl_solid.facets {
-            for v in &mut f.vertexes {
-               v.matrix -= self.scale_origin.matrix;
-               v.matrix *= self.scale;
-               v.matrix += self.scale_origin.matrix;
-            }
+         format!(
+            "translate({}) {} translate({})",
+            vector_literal(o.x(), o.y(), o.z()),
+            scale,
+            vector_literal(-o.x(), -o.y(), -o.z())
+         )
+      };
+
+      describe_block(output, indent, &header, &self.children)
+   }
+}
+
+impl SolidParent for Scale {
+   fn push<S: Solid + 'static>(&mut self, child: S) -> &mut S {
+      self.children.push_borrowing(child)
+   }
+}
+
+/// Stretches its children along `direction` by `factor`, about the plane
+/// which passes `scale_origin` and is perpendicular to `direction`.
+///
+/// A negative factor mirrors the children, and facets are flipped so that
+/// their normal vectors still face outward.
+pub struct ScaleAlong {
+   pub direction: Vector,
+   pub factor: N64,
+   pub scale_origin: Point,
+   pub children: Vec<Box<dyn Solid>>
+}
+
+impl ScaleAlong {
+   pub fn new(direction: Vector, factor: N64, scale_origin: Point) -> ScaleAlong {
+      ScaleAlong {
+         direction,
+         factor,
+         scale_origin,
+         children: vec![]
+      }
+   }
+}
+
+pub fn scale_along(
+   direction: &Vector,
+   factor: N64,
+   scale_origin: Point,
+   build_action: impl FnOnce(BuildContext<ScaleAlong>)
+) -> ScaleAlong {
+   BuildContext::build(
+      ScaleAlong::new(*direction, factor, scale_origin),
+      build_action
+   )
+}
+
+impl Solid for ScaleAlong {
+   fn generate_stl_solid(&self) -> StlSolid {
+      let mut stl_solid = StlSolid::EMPTY;
+      for c in self.children.iter() {
+         stl_solid.extend(c.generate_stl_solid());
+      }
+
+      let direction = &self.direction;
+
+      for f in &mut stl_solid.facets {
+         let mut vertexes = f.vertexes();
+         for v in &mut vertexes {
+            // the distance from the plane
+            let projection = Size::from(
+               Vector::between(&self.scale_origin, v).inner_product(direction)
+                  / direction.norm()
+            );
+
+            v.translate_toward(direction, projection * (self.factor - 1.0));
          }
+         f.set_vertexes(vertexes);
+      }
+
+      if self.factor < 0.0 {
+         flip(&mut stl_solid);
       }
 
       stl_solid
    }
+
+   /// Sum of the volumes of the children. Overlapping parts are counted
+   /// twice. Returns `None` if any child returns `None`.
+   fn approximate_volume(&self) -> Option<Exp<Size, 3>> {
+      self.children.iter()
+         .map(|c| c.approximate_volume())
+         .sum::<Option<Exp<Size, 3>>>()
+         .map(|v| v * self.factor.abs())
+   }
 }
 
-impl SolidParent for Scale {
+impl SolidParent for ScaleAlong {
    fn push<S: Solid + 'static>(&mut self, child: S) -> &mut S {
       self.children.push_borrowing(child)
    }
 }
 
+/// Reverses the winding of all facets.
+fn flip(stl_solid: &mut StlSolid) {
+   for f in &mut stl_solid.facets {
+      let [a, b, c] = f.vertexes();
+      *f = Facet::new([a, c, b]);
+   }
+}
+
 #[cfg(test)]
 mod tests {
-   use crate::geometry::{Point, SizeLiteral};
-   use crate::solid::Solid;
+   use crate::geometry::{Point, SizeLiteral, Vector};
+   use crate::math::unit::Exp;
+   use crate::solid::{cube, Location, Solid};
    use crate::stl::{Facet, StlSolid};
-   use super::scale;
+   use noisy_float::prelude::*;
+   use super::{scale, scale_along, scale_xyz};
+
+   fn sorted_vertexes(solid: &StlSolid) -> Vec<Point> {
+      let mut vertexes: Vec<_> = solid.facets().flat_map(|f| f.vertexes()).collect();
+      vertexes.sort_by(|a, b|
+         a.x().cmp(&b.x())
+            .then(a.y().cmp(&b.y()))
+            .then(a.z().cmp(&b.z()))
+      );
+      vertexes.dedup();
+      vertexes
+   }
 
    #[test]
    fn vertexes() {
@@ -79,13 +252,11 @@ mod tests {
          fn generate_stl_solid(&self) -> StlSolid {
             StlSolid {
                facets: vec![
-                  Facet {
-                     vertexes: [
-                        Point::new(0.mm(), 1.mm(), 2.mm()),
-                        Point::new(3.mm(), 4.mm(), 5.mm()),
-                        Point::new(6.mm(), 7.mm(), 8.mm())
-                     ]
-                  }
+                  Facet::new([
+                     Point::new(0.mm(), 1.mm(), 2.mm()),
+                     Point::new(3.mm(), 4.mm(), 5.mm()),
+                     Point::new(6.mm(), 7.mm(), 8.mm())
+                  ])
                ]
             }
          }
@@ -97,7 +268,7 @@ mod tests {
       let s = s.generate_stl_solid();
 
       let actual: Vec<_> = s.facets.iter()
-         .flat_map(|f| f.vertexes)
+         .flat_map(|f| f.vertexes())
          .collect();
       let expected = vec![
          Point::new(0.0.mm(),  1.5.mm(),  3.0.mm()),
@@ -114,7 +285,7 @@ mod tests {
       let s = s.generate_stl_solid();
 
       let actual: Vec<_> = s.facets.iter()
-         .flat_map(|f| f.vertexes)
+         .flat_map(|f| f.vertexes())
          .collect();
       let expected = vec![
          Point::new(-0.5.mm(),  1.0.mm(),  2.5.mm()),
@@ -124,4 +295,69 @@ mod tests {
 
       assert_eq!(expected, actual);
    }
+
+   #[test]
+   fn approximate_volume() {
+      let s = scale(2.0, Point::ORIGIN, |mut c| {
+         c <<= cube(Location::default(), (1.mm(), 2.mm(), 3.mm()));
+      });
+      assert_eq!(s.approximate_volume(), Some(unsafe { Exp::new(n64(48.0)) }));
+   }
+
+   #[test]
+   fn non_uniform() {
+      let s = scale_xyz((n64(2.0), n64(1.0), n64(1.0)), Point::ORIGIN, |mut c| {
+         c <<= cube(Location::default(), (1.mm(), 2.mm(), 3.mm()));
+      });
+      let solid = s.generate_stl_solid();
+
+      let expected = cube(Location::default(), (2.mm(), 2.mm(), 3.mm()))
+         .generate_stl_solid();
+      assert_eq!(sorted_vertexes(&solid), sorted_vertexes(&expected));
+      assert_eq!(solid.volume(), unsafe { Exp::new(n64(12.0)) });
+      assert_eq!(s.approximate_volume(), Some(unsafe { Exp::new(n64(12.0)) }));
+   }
+
+   #[test]
+   fn mirror() {
+      let solid = scale_xyz((n64(-1.0), n64(1.0), n64(1.0)), Point::ORIGIN, |mut c| {
+         c <<= cube(Location::default(), (1.mm(), 2.mm(), 3.mm()));
+      }).generate_stl_solid();
+
+      let original = cube(Location::default(), (1.mm(), 2.mm(), 3.mm()))
+         .generate_stl_solid();
+
+      for (actual, original) in solid.facets().zip(original.facets()) {
+         let [a, b, c] = original.vertexes();
+         let mirrored = |p: Point| Point::new(-p.x(), p.y(), p.z());
+         assert_eq!(actual.vertexes(), [mirrored(a), mirrored(c), mirrored(b)]);
+
+         let n = original.normal_vector();
+         assert_eq!(actual.normal_vector(), Vector::new(-n.x(), n.y(), n.z()));
+      }
+
+      assert_eq!(solid.volume(), unsafe { Exp::new(n64(6.0)) });
+   }
+
+   #[test]
+   fn along() {
+      let solid = scale_along(&Vector::X_UNIT_VECTOR, n64(2.0), Point::ORIGIN, |mut c| {
+         c <<= cube(Location::default(), (1.mm(), 2.mm(), 3.mm()));
+      }).generate_stl_solid();
+
+      let expected = cube(Location::default(), (2.mm(), 2.mm(), 3.mm()))
+         .generate_stl_solid();
+      assert_eq!(sorted_vertexes(&solid), sorted_vertexes(&expected));
+
+      let direction = Vector::new(1.mm(), 1.mm(), 0.mm());
+      let origin = Point::new(1.mm(), 0.mm(), 0.mm());
+      let solid = scale_along(&direction, n64(-3.0), origin, |mut c| {
+         c <<= cube(Location::default(), (1.mm(), 2.mm(), 3.mm()));
+      }).generate_stl_solid();
+
+      assert_eq!(solid.volume(), unsafe { Exp::new(n64(18.0)) });
+
+      // the vertex on the plane does not move
+      assert!(solid.facets().flat_map(|f| f.vertexes()).any(|v| v == origin));
+   }
 }
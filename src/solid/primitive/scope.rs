@@ -0,0 +1,250 @@
+use crate::geometry::{Angle, Line, Size, Vector};
+use crate::math::unit::Exp;
+use crate::solid::{Solid, SolidParent};
+use crate::solid::builder::BuildContext;
+use crate::solid::primitive::group::Movement;
+use crate::solid::solid_parent::PushBorrowing;
+use crate::stl::StlSolid;
+use crate::transform::Transform;
+use std::fmt::{self, Debug, Formatter};
+use std::rc::Rc;
+
+/// Holds its children, and applies an arbitrary function to the combined
+/// facets of them.
+///
+/// For operations which don't fit into the other solids, e.g. displacing
+/// the vertexes of a group. Like [Group](crate::solid::Group), a scope can
+/// be moved as a whole by [Transform], which is applied after the function.
+pub struct Scope {
+   transform: Option<Rc<dyn Fn(StlSolid) -> StlSolid>>,
+   children: Rc<Vec<Box<dyn Solid>>>,
+   movements: Vec<Movement>
+}
+
+impl Scope {
+   /// Scope which passes through its children.
+   pub fn new() -> Scope {
+      Scope {
+         transform: None,
+         children: Rc::new(vec![]),
+         movements: vec![]
+      }
+   }
+
+   pub fn transformed(transform: impl Fn(StlSolid) -> StlSolid + 'static) -> Scope {
+      Scope {
+         transform: Some(Rc::new(transform)),
+         ..Scope::new()
+      }
+   }
+
+   pub fn children(&self) -> &[Box<dyn Solid>] {
+      &self.children
+   }
+
+   fn moved(&self, movement: Movement) -> Scope {
+      Scope {
+         transform: self.transform.clone(),
+         children: self.children.clone(),
+         movements: self.movements.iter().copied().chain([movement]).collect()
+      }
+   }
+}
+
+impl Default for Scope {
+   fn default() -> Scope {
+      Scope::new()
+   }
+}
+
+pub fn scope(build_action: impl FnOnce(BuildContext<Scope>)) -> Scope {
+   BuildContext::build(
+      Scope::new(),
+      build_action
+   )
+}
+
+/// ```
+/// # use typed_scad::geometry::{Point, SizeLiteral};
+/// # use typed_scad::solid::{cube, scope_transformed, Location, Solid};
+/// # use typed_scad::stl::{Facet, StlSolid};
+/// // flattens the children onto the XY plane
+/// let flat = scope_transformed(
+///    |stl_solid: StlSolid| {
+///       stl_solid.into_facets().into_iter()
+///          .map(|f| Facet::new(f.vertexes().map(|v| Point::new(v.x(), v.y(), 0.mm()))))
+///          .collect()
+///    },
+///    |mut s| {
+///       s <<= cube(Location::default(), (1.mm(), 1.mm(), 1.mm()));
+///    }
+/// );
+///
+/// let stl_solid = flat.generate_stl_solid();
+/// assert!(stl_solid.facets().flat_map(|f| f.vertexes()).all(|v| v.z() == 0.mm()));
+/// ```
+pub fn scope_transformed(
+   transform: impl Fn(StlSolid) -> StlSolid + 'static,
+   build_action: impl FnOnce(BuildContext<Scope>)
+) -> Scope {
+   BuildContext::build(
+      Scope::transformed(transform),
+      build_action
+   )
+}
+
+impl Debug for Scope {
+   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+      f.debug_struct("Scope")
+         .field("transform", &self.transform.is_some())
+         .field("children", &self.children.len())
+         .finish()
+   }
+}
+
+impl Solid for Scope {
+   fn generate_stl_solid(&self) -> StlSolid {
+      let mut stl_solid = StlSolid::EMPTY;
+      for c in self.children.iter() {
+         stl_solid.extend(c.generate_stl_solid());
+      }
+
+      if let Some(transform) = &self.transform {
+         stl_solid = transform(stl_solid);
+      }
+
+      for m in &self.movements {
+         m.apply(&mut stl_solid);
+      }
+
+      stl_solid
+   }
+
+   /// Sum of the volumes of the children if this scope has no transform.
+   /// Overlapping parts are counted twice.
+   fn approximate_volume(&self) -> Option<Exp<Size, 3>> {
+      if self.transform.is_some() {
+         return None;
+      }
+
+      self.children.iter()
+         .map(|c| c.approximate_volume())
+         .sum()
+   }
+}
+
+impl SolidParent for Scope {
+   /// Panics if this scope shares its children with a moved scope.
+   fn push<S: Solid + 'static>(&mut self, child: S) -> &mut S {
+      Rc::get_mut(&mut self.children)
+         .expect("Cannot add a child to a scope which is shared with a moved scope.")
+         .push_borrowing(child)
+   }
+}
+
+impl Transform for Scope {
+   fn translated(&self, offset: &Vector) -> Self {
+      self.moved(Movement::Translation(*offset))
+   }
+
+   fn rotated(&self, axis: &Line, angle: Angle) -> Self {
+      self.moved(Movement::Rotation(*axis, angle))
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::{scope, scope_transformed};
+   use crate::geometry::{AngleLiteral, Line, Point, SizeLiteral, Vector};
+   use crate::solid::{cube, sphere, Location, Solid};
+   use crate::stl::StlSolid;
+   use crate::transform::Transform;
+
+   fn vertexes(stl_solid: &StlSolid) -> Vec<Point> {
+      stl_solid.facets.iter().flat_map(|f| f.vertexes()).collect()
+   }
+
+   #[test]
+   fn pass_through() {
+      let children = || (
+         cube(Location::default(), (1.mm(), 2.mm(), 3.mm())),
+         sphere(Location::default(), 2.mm())
+      );
+
+      let scope = scope(|mut s| {
+         let (a, b) = children();
+         s <<= a;
+         s <<= b;
+      });
+
+      let (a, b) = children();
+      let mut expected = a.generate_stl_solid();
+      expected.extend(b.generate_stl_solid());
+
+      assert_eq!(vertexes(&scope.generate_stl_solid()), vertexes(&expected));
+      assert_eq!(
+         scope.approximate_volume(),
+         Some(a.approximate_volume().unwrap() + b.approximate_volume().unwrap())
+      );
+      assert_eq!(format!("{:?}", scope), "Scope { transform: false, children: 2 }");
+   }
+
+   #[test]
+   fn transform() {
+      // reversing the facets shows that the function receives all children
+      let scope = scope_transformed(
+         |stl_solid| stl_solid.into_facets().into_iter().rev().collect(),
+         |mut s| {
+            s <<= cube(Location::default(), (1.mm(), 1.mm(), 1.mm()));
+            s <<= cube(Location::default(), (2.mm(), 2.mm(), 2.mm()));
+         }
+      );
+
+      let mut expected = cube(Location::default(), (1.mm(), 1.mm(), 1.mm()))
+         .generate_stl_solid();
+      expected.extend(
+         cube(Location::default(), (2.mm(), 2.mm(), 2.mm())).generate_stl_solid()
+      );
+      let expected: StlSolid = expected.into_facets().into_iter().rev().collect();
+
+      assert_eq!(vertexes(&scope.generate_stl_solid()), vertexes(&expected));
+      assert_eq!(scope.approximate_volume(), None);
+
+      // moved after the function
+      let moved = scope.rotated(&Line::Z_AXIS, 90.deg());
+      let expected = expected.rotated(&Line::Z_AXIS, 90.deg());
+      assert_eq!(vertexes(&moved.generate_stl_solid()), vertexes(&expected));
+   }
+
+   #[test]
+   fn nested() {
+      let x = Vector::new(1.mm(), 0.mm(), 0.mm());
+      let y = Vector::new(0.mm(), 2.mm(), 0.mm());
+
+      let outer = scope_transformed(
+         move |stl_solid| stl_solid.translated(&y),
+         |mut s| {
+            s <<= scope_transformed(
+               move |stl_solid| stl_solid.translated(&x),
+               |mut s| {
+                  s <<= cube(Location::default(), (1.mm(), 1.mm(), 1.mm()));
+               }
+            );
+            s <<= scope(|mut s| {
+               s <<= cube(Location::default(), (3.mm(), 3.mm(), 3.mm()));
+            });
+         }
+      );
+
+      let mut expected = cube(Location::default(), (1.mm(), 1.mm(), 1.mm()))
+         .generate_stl_solid()
+         .translated(&(x + y));
+      expected.extend(
+         cube(Location::default(), (3.mm(), 3.mm(), 3.mm()))
+            .generate_stl_solid()
+            .translated(&y)
+      );
+
+      assert_eq!(vertexes(&outer.generate_stl_solid()), vertexes(&expected));
+   }
+}
@@ -0,0 +1,146 @@
+use crate::geometry::{Size, Vector};
+use crate::solid::{Solid, SolidParent};
+use crate::solid::builder::BuildContext;
+use crate::solid::solid_parent::PushBorrowing;
+use crate::stl::{Facet, StlSolid};
+use crate::transform::Transform;
+
+/// Hollows its children, leaving walls of `thickness`.
+///
+/// Each vertex is moved inward along the average of the normal vectors of
+/// the facets around it, weighted by their areas. The moved surface is added
+/// facing inward. Every child must generate a closed solid.
+///
+/// Intersections of the inner surface are not cleaned up, so `thickness`
+/// should be small enough compared to the details of the children.
+#[derive(Default)]
+pub struct Shell {
+   pub thickness: Size,
+   pub children: Vec<Box<dyn Solid>>
+}
+
+impl Shell {
+   pub fn new(thickness: Size) -> Shell {
+      Shell {
+         thickness,
+         children: vec![]
+      }
+   }
+}
+
+pub fn shell(
+   thickness: Size,
+   build_action: impl FnOnce(BuildContext<Shell>)
+) -> Shell {
+   BuildContext::build(
+      Shell::new(thickness),
+      build_action
+   )
+}
+
+impl Solid for Shell {
+   fn generate_stl_solid(&self) -> StlSolid {
+      if self.thickness <= Size::ZERO {
+         panic!("The thickness of a shell must be positive.");
+      }
+
+      StlSolid {
+         facets: self.children.iter()
+            .flat_map(|c| hollow(&c.generate_stl_solid(), self.thickness))
+            .collect()
+      }
+   }
+}
+
+fn hollow(stl_solid: &StlSolid, thickness: Size) -> Vec<Facet> {
+   if !stl_solid.validate().is_manifold() {
+      panic!("Only a closed solid can be hollowed.");
+   }
+
+   let ids = stl_solid.vertex_ids();
+   let vertex_count = ids.iter().flatten().max().map_or(0, |&id| id + 1);
+
+   let mut normals = vec![Vector::ZERO; vertex_count];
+   for (facet, ids) in stl_solid.facets.iter().zip(&ids) {
+      let area = facet.area().0.raw();
+      if area <= 0.0 {
+         continue;
+      }
+      let normal = facet.normal_vector() * area;
+      for &id in ids {
+         normals[id] += normal;
+      }
+   }
+
+   let inner_facets: Vec<_> = stl_solid.facets.iter().zip(&ids)
+      .map(|(facet, ids)| {
+         let [a, b, c] = [0, 1, 2].map(|k|
+            facet.vertexes()[k].translated_toward(&-normals[ids[k]], thickness)
+         );
+         Facet::new([a, c, b])
+      })
+      .collect();
+
+   stl_solid.facets.iter()
+      .map(|f| Facet::new(f.vertexes()))
+      .chain(inner_facets)
+      .collect()
+}
+
+impl SolidParent for Shell {
+   fn push<S: Solid + 'static>(&mut self, child: S) -> &mut S {
+      self.children.push_borrowing(child)
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::shell;
+   use crate::geometry::{Point, SizeLiteral};
+   use crate::solid::{sphere, Location, Solid};
+   use crate::stl::{Facet, StlSolid};
+
+   #[test]
+   fn hollow_sphere() {
+      let solid = shell(1.mm(), |mut c| {
+         c <<= sphere(Location::default(), 10.mm());
+      }).generate_stl_solid();
+
+      let half = solid.facets.len() / 2;
+      let assert_radius = |facets: &[Facet], radius: f64| {
+         for v in facets.iter().flat_map(|f| f.vertexes()) {
+            let actual = Point::ORIGIN.distance(&v).to_millimeter().raw();
+            assert!((actual - radius).abs() / radius < 0.03, "{:?}", v);
+         }
+      };
+      assert_radius(&solid.facets[..half], 10.0);
+      assert_radius(&solid.facets[half..], 9.0);
+
+      assert!(solid.validate().is_manifold());
+      assert!(solid.volume().0 > 0.0);
+   }
+
+   #[test]
+   #[should_panic]
+   fn open_child() {
+      struct OpenSolid;
+
+      impl Solid for OpenSolid {
+         fn generate_stl_solid(&self) -> StlSolid {
+            StlSolid {
+               facets: vec![
+                  Facet::new([
+                     Point::ORIGIN,
+                     Point::new(1.mm(), 0.mm(), 0.mm()),
+                     Point::new(0.mm(), 1.mm(), 0.mm())
+                  ])
+               ]
+            }
+         }
+      }
+
+      shell(1.mm(), |mut c| {
+         c <<= OpenSolid;
+      }).generate_stl_solid();
+   }
+}
@@ -1,21 +1,39 @@
-use crate::geometry::{Angle, AngleLiteral, Line, Point, Size, Vector};
+use crate::geometry::{Angle, AngleLiteral, BoundingBox, Line, Point, Size, Vector};
+use crate::math::unit::Exp;
+use crate::scad::{location_prefix, number_literal, write_line, ScadDescribable};
 use crate::solid::{Location, Solid};
-use crate::solid::precision::FRAGMENT_MINIMUM_ANGLE;
+use crate::solid::precision::{fragment_angle, FragmentSpec};
 use crate::stl::{Facet, StlSolid};
 use crate::transform::Transform;
+use anyhow::Result;
 use rayon::prelude::{
    IndexedParallelIterator, IntoParallelIterator, ParallelIterator
 };
+use std::f64::consts::PI;
+use std::io::Write;
 use std::{array, ptr, slice};
 
 pub struct Sphere {
    pub location: Location,
-   pub radius: Size
+   pub radius: Size,
+   pub fragments: Option<FragmentSpec>
 }
 
 impl Sphere {
    pub fn new(location: Location, radius: Size) -> Sphere {
-      Sphere { location, radius }
+      Sphere { location, radius, fragments: None }
+   }
+
+   /// Overrides the precision specified by [env](crate::solid::builder::env).
+   /// See [FragmentSpec].
+   pub fn with_fragment_angle(self, angle: Angle) -> Sphere {
+      Sphere { fragments: Some(FragmentSpec::Angle(angle)), ..self }
+   }
+
+   /// Overrides the precision specified by [env](crate::solid::builder::env).
+   /// See [FragmentSpec].
+   pub fn with_fragment_count(self, count: usize) -> Sphere {
+      Sphere { fragments: Some(FragmentSpec::Count(count)), ..self }
    }
 }
 
@@ -25,8 +43,10 @@ pub fn sphere(location: Location, radius: Size) -> Sphere {
 
 impl Solid for Sphere {
    fn generate_stl_solid(&self) -> StlSolid {
-      let angles = Angle::par_iterate(0.deg()..90.deg())
-         .step(*FRAGMENT_MINIMUM_ANGLE);
+      // read the thread-local env here, not in rayon's threads
+      let fragment_angle = fragment_angle(self.radius, self.fragments);
+
+      let angles = Angle::par_iterate(0.deg()..90.deg()).step(fragment_angle);
       let shifted_angles = angles.clone().skip(1).chain([90.deg()]);
       let zipped_angles = angles.zip(shifted_angles);
 
@@ -43,10 +63,10 @@ impl Solid for Sphere {
                let ba = b.rotated(&Line::Y_AXIS, zx_angle_a);
                let bb = b.rotated(&Line::Y_AXIS, zx_angle_b);
 
-               let aa_facet = Some(Facet { vertexes: [aa, ba, ab] });
+               let aa_facet = Some(Facet::new([aa, ba, ab]));
 
                let bb_facet = if ba != bb { // when `b` is not on Y axis
-                  Some(Facet { vertexes: [bb, ab, ba] })
+                  Some(Facet::new([bb, ab, ba]))
                } else {
                   None
                };
@@ -78,6 +98,43 @@ impl Solid for Sphere {
 
       StlSolid { facets }
    }
+
+   fn approximate_volume(&self) -> Option<Exp<Size, 3>> {
+      Some(self.radius * self.radius * self.radius * (PI * 4.0 / 3.0))
+   }
+
+   /// For each octant, 2 facets for each mesh of n×n, except n facets
+   /// around the pole.
+   fn facet_count(&self) -> usize {
+      let fragment_angle = fragment_angle(self.radius, self.fragments);
+      let n = Angle::iterate(0.deg()..90.deg()).step(fragment_angle).count();
+      (n * n * 2 - n) * 8
+   }
+
+   /// The cube circumscribing this sphere. The vertexes of the generated
+   /// facets are on the sphere, so they are inside of this.
+   fn bounding_box(&self) -> Option<BoundingBox> {
+      let center = self.location.point();
+      let r = Vector::new(self.radius, self.radius, self.radius);
+      Some(BoundingBox::new(center.translated(&-r), center.translated(&r)))
+   }
+
+   fn as_scad_describable(&self) -> Option<&dyn ScadDescribable> {
+      Some(self)
+   }
+}
+
+impl ScadDescribable for Sphere {
+   fn describe_scad(&self, output: &mut dyn Write, indent: usize) -> Result<()> {
+      write_line(
+         output, indent,
+         &format!(
+            "{}sphere(r={});",
+            location_prefix(&self.location),
+            number_literal(self.radius.to_millimeter())
+         )
+      )
+   }
 }
 
 fn copy_elements<T, const COUNT: usize>(
@@ -107,7 +164,8 @@ fn copy_elements<T, const COUNT: usize>(
 }
 
 fn negative(facet: &mut Facet, x: bool, y: bool, z: bool) {
-   for v in &mut facet.vertexes {
+   let mut vertexes = facet.vertexes();
+   for v in &mut vertexes {
       if x {
          v.matrix.0[0][0] = -v.matrix.0[0][0];
       }
@@ -118,33 +176,37 @@ fn negative(facet: &mut Facet, x: bool, y: bool, z: bool) {
          v.matrix.0[2][0] = -v.matrix.0[2][0];
       }
    }
+   facet.set_vertexes(vertexes);
 }
 
 fn reverse(facet: &mut Facet) {
-   let v = facet.vertexes[1];
-   facet.vertexes[1] = facet.vertexes[2];
-   facet.vertexes[2] = v;
+   let [a, b, c] = facet.vertexes();
+   facet.set_vertexes([a, c, b]);
 }
 
 fn locate(facet: &mut Facet, location: &Location) {
    let offset = Vector::between(&Point::ORIGIN, &location.point());
-   for v in &mut facet.vertexes {
+   let mut vertexes = facet.vertexes();
+   for v in &mut vertexes {
       v.translate(&offset);
    }
+   facet.set_vertexes(vertexes);
 }
 
 impl Transform for Sphere {
    fn translated(&self, offset: &Vector) -> Self {
       Self {
          location: self.location.translated(offset),
-         radius: self.radius
+         radius: self.radius,
+         fragments: self.fragments
       }
    }
 
    fn rotated(&self, axis: &Line, angle: Angle) -> Self {
       Self {
          location: self.location.rotated(axis, angle),
-         radius: self.radius
+         radius: self.radius,
+         fragments: self.fragments
       }
    }
 }
@@ -152,8 +214,15 @@ impl Transform for Sphere {
 #[cfg(test)]
 mod tests {
    use super::sphere;
-   use crate::geometry::{AngleLiteral, Point, SizeLiteral, Vector};
+   use crate::geometry::{AngleLiteral, BoundingBox, Line, Point, SizeLiteral, Vector};
+   use crate::math::unit::Exp;
    use crate::solid::{Location, Solid};
+   use crate::solid::builder::env;
+   use crate::solid::precision::FRAGMENT_MINIMUM_ANGLE;
+   use crate::transform::Transform;
+   use noisy_float::prelude::*;
+   use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+   use rayon::ThreadPoolBuilder;
 
    #[test]
    fn normal_vector() {
@@ -161,12 +230,12 @@ mod tests {
       let solid = sphere.generate_stl_solid();
 
       for f in solid.facets {
-         let expected = Vector::between(&Point::ORIGIN, &f.vertexes[0]);
+         let expected = Vector::between(&Point::ORIGIN, &f.vertexes()[0]);
          let actual = f.normal_vector();
          assert!(
             expected.angle_with(&actual) < 10.deg(),
             "expected: {:?}, actual: {:?} at {:?}",
-            expected.to_unit_vector(), actual, f.vertexes[0]
+            expected.to_unit_vector(), actual, f.vertexes()[0]
          );
       }
    }
@@ -177,9 +246,94 @@ mod tests {
       let solid = sphere.generate_stl_solid();
 
       solid.facets.iter()
-         .flat_map(|f| f.vertexes)
+         .flat_map(|f| f.vertexes())
          .for_each(|v|
             assert_eq!(Point::ORIGIN.distance(&v), 3.mm())
          );
    }
+
+   #[test]
+   fn fragments() {
+      let facet_count = |solid: &dyn Solid| solid.generate_stl_solid().facets.len();
+
+      let mut coarse = 0;
+      env(&FRAGMENT_MINIMUM_ANGLE, 90.deg(), || {
+         coarse = facet_count(&sphere(Location::default(), 3.mm()));
+      });
+      let mut fine = 0;
+      env(&FRAGMENT_MINIMUM_ANGLE, 10.deg(), || {
+         fine = facet_count(&sphere(Location::default(), 3.mm()));
+      });
+      assert!(coarse < fine);
+
+      // explicit > env
+      env(&FRAGMENT_MINIMUM_ANGLE, 90.deg(), || {
+         let s = sphere(Location::default(), 3.mm()).with_fragment_angle(10.deg());
+         assert_eq!(facet_count(&s), fine);
+      });
+
+      // env is read before the parallel generation
+      env(&FRAGMENT_MINIMUM_ANGLE, 2.deg(), || {
+         let s = sphere(Location::default(), 3.mm());
+         let expected = sphere(Location::default(), 3.mm())
+            .with_fragment_angle(2.deg());
+         assert_eq!(facet_count(&s), facet_count(&expected));
+         assert!(facet_count(&s) > fine);
+      });
+
+      // env does not reach other threads, but the explicit setting does
+      let default = facet_count(&sphere(Location::default(), 3.mm()));
+      assert_ne!(default, coarse);
+      env(&FRAGMENT_MINIMUM_ANGLE, 90.deg(), || {
+         let pool = ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+         let on_worker = pool.install(|| facet_count(&sphere(Location::default(), 3.mm())));
+         assert_eq!(on_worker, default);
+
+         let counts: Vec<_> = (0..4).into_par_iter()
+            .map(|_| {
+               let s = sphere(Location::default(), 3.mm())
+                  .with_fragment_angle(10.deg());
+               facet_count(&s)
+            })
+            .collect();
+
+         assert!(counts.into_iter().all(|c| c == fine));
+      });
+   }
+
+   #[test]
+   fn approximate_volume() {
+      let sphere = sphere(Location::default(), 3.mm());
+      let expected = unsafe {
+         Exp::new(n64(std::f64::consts::PI * 4.0 / 3.0 * 27.0))
+      };
+      assert_eq!(sphere.approximate_volume(), Some(expected));
+   }
+
+   #[test]
+   fn facet_count() {
+      for angle in [7.deg(), 12.deg(), 45.deg(), 90.deg(), 100.deg()] {
+         let sphere = sphere(Location::default(), 5.mm()).with_fragment_angle(angle);
+         assert_eq!(sphere.facet_count(), sphere.generate_stl_solid().facet_count());
+      }
+   }
+
+   #[test]
+   fn bounding_box() {
+      let sphere = sphere(Location::default(), 3.mm())
+         .translated(&Vector::new(1.mm(), 2.mm(), 3.mm()));
+      let expected = BoundingBox::new(
+         Point::new(-2.mm(), -1.mm(), 0.mm()),
+         Point::new(4.mm(), 5.mm(), 6.mm())
+      );
+      assert_eq!(sphere.bounding_box(), Some(expected));
+
+      // the vertexes on the axes reach the box
+      assert_eq!(sphere.generate_stl_solid().bounding_box(), Some(expected));
+
+      let rotated = sphere.rotated(&Line::X_AXIS, 30.deg());
+      let mesh = rotated.generate_stl_solid().bounding_box().unwrap();
+      let analytic = rotated.bounding_box().unwrap();
+      assert!(analytic.contains_point(&mesh.min) && analytic.contains_point(&mesh.max));
+   }
 }
@@ -0,0 +1,275 @@
+use crate::geometry::{Angle, AngleLiteral, Line, Point, Size, Vector};
+use crate::solid::{Location, Solid};
+use crate::solid::precision::{fragment_angle, FragmentSpec};
+use crate::stl::{Facet, StlSolid};
+use crate::transform::Transform;
+
+/// A portion of a sphere cut off by a plane.
+///
+/// The flat circular face lies on [Location::point] and the dome grows toward
+/// [Location::top_vector]. `height` is measured from the flat face to the top
+/// of the dome, so `height == radius` makes a hemisphere and
+/// `height == radius * 2` makes a whole sphere.
+pub struct SphericalCap {
+   pub location: Location,
+   pub radius: Size,
+   pub height: Size,
+   pub fragments: Option<FragmentSpec>
+}
+
+impl SphericalCap {
+   pub fn new(location: Location, radius: Size, height: Size) -> SphericalCap {
+      SphericalCap { location, radius, height, fragments: None }
+   }
+
+   /// Overrides the precision specified by [env](crate::solid::builder::env).
+   /// See [FragmentSpec].
+   pub fn with_fragment_angle(self, angle: Angle) -> SphericalCap {
+      SphericalCap { fragments: Some(FragmentSpec::Angle(angle)), ..self }
+   }
+
+   /// Overrides the precision specified by [env](crate::solid::builder::env).
+   /// See [FragmentSpec].
+   pub fn with_fragment_count(self, count: usize) -> SphericalCap {
+      SphericalCap { fragments: Some(FragmentSpec::Count(count)), ..self }
+   }
+}
+
+pub fn spherical_cap(
+   location: Location,
+   radius: Size,
+   height: Size
+) -> SphericalCap {
+   SphericalCap::new(location, radius, height)
+}
+
+pub fn hemisphere(location: Location, radius: Size) -> SphericalCap {
+   SphericalCap::new(location, radius, radius)
+}
+
+impl Solid for SphericalCap {
+   fn generate_stl_solid(&self) -> StlSolid {
+      if self.height <= Size::ZERO || self.height > self.radius * 2 {
+         panic!("The height of a spherical cap must be in (0, radius * 2].");
+      }
+
+      let fragment_angle = fragment_angle(self.radius, self.fragments);
+
+      let right = &self.location.right_vector();
+      let top = &self.location.top_vector();
+      let radius = self.radius;
+      let base_point = self.location.point();
+      let center = base_point.translated_toward(top, self.height - radius);
+      let apex = center.translated_toward(top, radius);
+
+      // polar angle from the apex to the edge of the flat face
+      let polar_max = Angle::acos((radius - self.height) / radius);
+      let reaches_pole = polar_max == 180.deg();
+
+      let directions: Vec<_>
+         = Angle::iterate(0.deg()..360.deg()).step(fragment_angle)
+         .map(|a| right.rotated(top, a))
+         .collect();
+
+      let rings: Vec<Vec<Point>>
+         = Angle::iterate(0.deg()..polar_max).step(fragment_angle)
+         .skip(1)
+         .chain([polar_max])
+         .map(|polar|
+            directions.iter()
+               .map(|d|
+                  center
+                     .translated_toward(top, radius * polar.cos())
+                     .translated_toward(d, radius * polar.sin())
+               )
+               .collect()
+         )
+         .collect();
+
+      let zipped = |ring: &Vec<Point>| {
+         let first = ring[0];
+         ring.clone().into_iter()
+            .zip(ring.clone().into_iter().skip(1).chain([first]))
+            .collect::<Vec<_>>()
+      };
+
+      let apex_facets = zipped(&rings[0]).into_iter()
+         .map(|(a, b)| Facet::new([apex, a, b]));
+
+      let side_facets = rings.windows(2)
+         .flat_map(|w| zipped(&w[0]).into_iter().zip(zipped(&w[1])))
+         .flat_map(|((a, b), (c, d))| {
+            let acd_facet = if c != d { // when the lower ring is not a pole
+               Some(Facet::new([a, c, d]))
+            } else {
+               None
+            };
+
+            let adb_facet = Some(Facet::new([a, d, b]));
+
+            [acd_facet, adb_facet].into_iter().flatten()
+         });
+
+      let base_facets = if reaches_pole {
+         vec![]
+      } else {
+         zipped(rings.last().unwrap()).into_iter()
+            .map(|(a, b)| Facet::new([base_point, b, a]))
+            .collect()
+      };
+
+      StlSolid {
+         facets: apex_facets
+            .chain(side_facets)
+            .chain(base_facets)
+            .collect()
+      }
+   }
+}
+
+impl Transform for SphericalCap {
+   fn translated(&self, offset: &Vector) -> Self {
+      Self {
+         location: self.location.translated(offset),
+         radius: self.radius,
+         height: self.height,
+         fragments: self.fragments
+      }
+   }
+
+   fn rotated(&self, axis: &Line, angle: Angle) -> Self {
+      Self {
+         location: self.location.rotated(axis, angle),
+         radius: self.radius,
+         height: self.height,
+         fragments: self.fragments
+      }
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::{hemisphere, spherical_cap};
+   use crate::geometry::{AngleLiteral, Point, SizeLiteral, Vector};
+   use crate::solid::{cube, difference, sphere, Location, Solid};
+   use crate::solid::primitive::test_support::assert_roughly;
+   use crate::solid::precision::FRAGMENT_MINIMUM_ANGLE;
+   use crate::transform::Transform;
+   use noisy_float::prelude::*;
+
+   fn fragment_count() -> usize {
+      (360.deg() / *FRAGMENT_MINIMUM_ANGLE).ceil().raw() as usize
+   }
+
+   #[test]
+   fn base() {
+      let cap = spherical_cap(Location::default(), 5.mm(), 2.mm());
+      let solid = cap.generate_stl_solid();
+
+      let base_facets = &solid.facets[(solid.facets.len() - fragment_count())..];
+
+      base_facets.iter()
+         .flat_map(|f| f.vertexes())
+         .for_each(|v| assert_eq!(v.z(), 0.mm()));
+
+      base_facets.iter()
+         .map(|f| f.normal_vector())
+         .for_each(|v| assert_eq!(v, -Vector::Z_UNIT_VECTOR));
+   }
+
+   #[test]
+   fn base_radius() {
+      let cap = spherical_cap(Location::default(), 5.mm(), 2.mm());
+      let solid = cap.generate_stl_solid();
+
+      // sqrt(2 * 5 * 2 - 2 * 2)
+      let base_radius = 4.mm();
+
+      let base_facets = &solid.facets[(solid.facets.len() - fragment_count())..];
+      base_facets.iter()
+         .flat_map(|f| f.vertexes())
+         .filter(|&v| v != Point::ORIGIN)
+         .for_each(|v| assert_eq!(Point::ORIGIN.distance(&v), base_radius));
+   }
+
+   #[test]
+   fn hemisphere_equivalent() {
+      let cap = spherical_cap(Location::default(), 3.mm(), 3.mm())
+         .generate_stl_solid();
+
+      // the upper half of a sphere, cut by a cube
+      let cut = difference(|mut c| {
+         c <<= sphere(Location::default(), 3.mm());
+         let location = Location::default()
+            .translated(&Vector::new(-4.mm(), -4.mm(), -4.mm()));
+         c <<= cube(location, (8.mm(), 8.mm(), 4.mm()));
+      }).generate_stl_solid();
+
+      assert_roughly(cap.volume(), cut.volume().0.raw());
+      let (a, b) = (cap.bounding_box().unwrap(), cut.bounding_box().unwrap());
+      assert!(Vector::between(&a.min, &b.min).norm() < 0.05.mm(), "{:?} {:?}", a, b);
+      assert!(Vector::between(&a.max, &b.max).norm() < 0.05.mm(), "{:?} {:?}", a, b);
+
+      let inside = [(0.0, 0.0, 1.0), (1.0, 1.0, 1.0), (-2.0, 0.0, 0.5), (0.0, 0.0, 2.9)];
+      let outside = [(0.0, 0.0, -0.5), (2.0, 2.0, 1.0), (0.0, 0.0, 3.1)];
+      let point = |(x, y, z): (f64, f64, f64)| Point::new(x.mm(), y.mm(), z.mm());
+
+      for p in inside.map(point) {
+         assert!(cap.contains_point(&p) && cut.contains_point(&p), "{:?}", p);
+      }
+      for p in outside.map(point) {
+         assert!(!cap.contains_point(&p) && !cut.contains_point(&p), "{:?}", p);
+      }
+   }
+
+   #[test]
+   fn radius() {
+      let cap = spherical_cap(Location::default(), 5.mm(), 2.mm());
+      let solid = cap.generate_stl_solid();
+
+      let center = Point::new(0.mm(), 0.mm(), -3.mm());
+      solid.facets.iter()
+         .flat_map(|f| f.vertexes())
+         .filter(|&v| v != Point::ORIGIN)
+         .for_each(|v| assert_eq!(center.distance(&v), 5.mm()));
+
+      solid.facets.iter()
+         .flat_map(|f| f.vertexes())
+         .for_each(|v| assert!(v.z() >= 0.mm() && v.z() <= 2.mm()));
+   }
+
+   #[test]
+   fn normal_vector() {
+      let cap = hemisphere(Location::default(), 3.mm());
+      let solid = cap.generate_stl_solid();
+
+      let dome_facets = &solid.facets[..(solid.facets.len() - fragment_count())];
+
+      for f in dome_facets {
+         let expected = Vector::between(&Point::ORIGIN, &f.vertexes()[0]);
+         let actual = f.normal_vector();
+         assert!(
+            expected.angle_with(&actual) < 10.deg(),
+            "expected: {:?}, actual: {:?} at {:?}",
+            expected.to_unit_vector(), actual, f.vertexes()[0]
+         );
+      }
+   }
+
+   #[test]
+   fn whole_sphere() {
+      let cap = spherical_cap(Location::default(), 3.mm(), 6.mm());
+      let solid = cap.generate_stl_solid();
+
+      let center = Point::new(0.mm(), 0.mm(), 3.mm());
+      solid.facets.iter()
+         .flat_map(|f| f.vertexes())
+         .for_each(|v| assert_eq!(center.distance(&v), 3.mm()));
+   }
+
+   #[test]
+   #[should_panic]
+   fn too_high() {
+      spherical_cap(Location::default(), 3.mm(), 7.mm()).generate_stl_solid();
+   }
+}
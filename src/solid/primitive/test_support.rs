@@ -0,0 +1,8 @@
+use crate::geometry::Size;
+use crate::math::unit::Exp;
+
+/// Asserts that the volume is within 1% of `expected` mm³.
+pub(in crate::solid) fn assert_roughly(actual: Exp<Size, 3>, expected: f64) {
+   let error = (actual.0.raw() - expected).abs() / expected;
+   assert!(error < 0.01, "actual: {}, expected: {}", actual.0, expected);
+}
@@ -0,0 +1,235 @@
+mod stroke_font;
+
+use crate::geometry::{Angle, Line, Point, Size, Vector};
+use crate::solid::{cube, Location, Solid};
+use crate::stl::StlSolid;
+use crate::transform::Transform;
+use stroke_font::{GLYPH_HEIGHT, GLYPH_WIDTH, REPLACEMENT};
+
+/// Text drawn with a built-in single-stroke font.
+///
+/// Characters are laid out along [Location::right_vector], glyphs stand
+/// toward [Location::back_vector], and strokes are extruded toward
+/// [Location::top_vector] by `depth`.
+/// [Location::point] is the left end of the baseline of the first character.
+///
+/// Digits, alphabets (lower cases are drawn as upper cases), and some symbols
+/// are supported. Other characters are drawn as a box.
+///
+/// Each stroke is a separate prism which overlaps its neighbors. This is fine
+/// to emboss labels, and also to engrave by subtracting the text from another
+/// solid.
+pub struct Text {
+   pub location: Location,
+   pub text: String,
+   pub height: Size,
+   pub depth: Size,
+   pub char_spacing: Size
+}
+
+impl Text {
+   pub fn new(
+      location: Location,
+      text: &str,
+      height: Size,
+      depth: Size,
+      char_spacing: Size
+   ) -> Text {
+      Text {
+         location,
+         text: text.to_string(),
+         height,
+         depth,
+         char_spacing
+      }
+   }
+
+   /// Width of a stroke.
+   pub fn stroke_width(&self) -> Size {
+      self.height / 8
+   }
+
+   /// Width of a character excluding `char_spacing`.
+   pub fn char_width(&self) -> Size {
+      self.height * GLYPH_WIDTH / GLYPH_HEIGHT
+   }
+}
+
+pub fn text(
+   location: Location,
+   text: &str,
+   height: Size,
+   depth: Size,
+   char_spacing: Size
+) -> Text {
+   Text::new(location, text, height, depth, char_spacing)
+}
+
+impl Solid for Text {
+   fn generate_stl_solid(&self) -> StlSolid {
+      let right = self.location.right_vector();
+      let back = self.location.back_vector();
+      let top = self.location.top_vector();
+      let unit = self.height / GLYPH_HEIGHT;
+      let stroke_width = self.stroke_width();
+      let advance = self.char_width() + self.char_spacing;
+
+      let facets = self.text.chars()
+         .enumerate()
+         .flat_map(|(i, c)| {
+            let origin = self.location.point()
+               .translated_toward(&right, advance * i);
+
+            let glyph = stroke_font::glyph(c).unwrap_or(REPLACEMENT);
+
+            let point = move |(x, y): (i8, i8)| {
+               origin
+                  .translated_toward(&right, unit * x)
+                  .translated_toward(&back, unit * y)
+            };
+
+            glyph.iter()
+               .flat_map(move |polyline| {
+                  let segments: Vec<_> = if polyline.len() == 1 {
+                     vec![(point(polyline[0]), point(polyline[0]))]
+                  } else {
+                     polyline.windows(2)
+                        .map(|w| (point(w[0]), point(w[1])))
+                        .collect()
+                  };
+                  segments
+               })
+         })
+         .flat_map(|(a, b)| {
+            stroke(&a, &b, &right, &top, stroke_width, self.depth)
+               .facets
+         })
+         .collect();
+
+      StlSolid { facets }
+   }
+}
+
+/// A square-capped prism from `a` to `b`.
+fn stroke(
+   a: &Point,
+   b: &Point,
+   right: &Vector,
+   top: &Vector,
+   width: Size,
+   depth: Size
+) -> StlSolid {
+   let length = a.distance(b);
+   let direction = if length == Size::ZERO {
+      *right
+   } else {
+      Vector::between(a, b)
+   };
+
+   let side = top.vector_product(&direction);
+
+   let corner = a
+      .translated_toward(&direction, -width / 2)
+      .translated_toward(&side, -width / 2);
+
+   let location = Location::build(corner)
+      .right_vector(direction)
+      .top_vector(*top);
+
+   cube(location, (length + width, width, depth)).generate_stl_solid()
+}
+
+impl Transform for Text {
+   fn translated(&self, offset: &Vector) -> Self {
+      Self {
+         location: self.location.translated(offset),
+         text: self.text.clone(),
+         height: self.height,
+         depth: self.depth,
+         char_spacing: self.char_spacing
+      }
+   }
+
+   fn rotated(&self, axis: &Line, angle: Angle) -> Self {
+      Self {
+         location: self.location.rotated(axis, angle),
+         text: self.text.clone(),
+         height: self.height,
+         depth: self.depth,
+         char_spacing: self.char_spacing
+      }
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::text;
+   use crate::geometry::{Point, Size, SizeLiteral};
+   use crate::solid::{Location, Solid};
+   use crate::stl::StlSolid;
+
+   fn bounding_box(solid: &StlSolid) -> (Point, Point) {
+      let vertexes: Vec<_> = solid.facets.iter()
+         .flat_map(|f| f.vertexes())
+         .collect();
+
+      let min = |f: fn(&Point) -> Size| vertexes.iter().map(f).min().unwrap();
+      let max = |f: fn(&Point) -> Size| vertexes.iter().map(f).max().unwrap();
+
+      (
+         Point::new(min(Point::x), min(Point::y), min(Point::z)),
+         Point::new(max(Point::x), max(Point::y), max(Point::z))
+      )
+   }
+
+   #[test]
+   fn bounding_box_of_i() {
+      let text = text(Location::default(), "I", 6.mm(), 1.mm(), 1.mm());
+      let solid = text.generate_stl_solid();
+
+      let half_stroke: Size = text.stroke_width() / 2;
+      assert_eq!(
+         bounding_box(&solid),
+         (
+            Point::new(1.mm() - half_stroke, -half_stroke, 0.mm()),
+            Point::new(3.mm() + half_stroke, 6.mm() + half_stroke, 1.mm())
+         )
+      );
+   }
+
+   #[test]
+   fn width() {
+      let width = |s: &str| {
+         let text = text(Location::default(), s, 6.mm(), 1.mm(), 1.mm());
+         let (min, max) = bounding_box(&text.generate_stl_solid());
+         max.x() - min.x()
+      };
+
+      // advance = 4mm + 1mm
+      assert_eq!(width("HH") - width("H"), 5.mm());
+      assert_eq!(width("HHHH") - width("HH"), 10.mm());
+      assert_eq!(width("HHHHHHHH") - width("HHHH"), 20.mm());
+   }
+
+   #[test]
+   fn unsupported_char() {
+      let text = text(Location::default(), "\u{3042}", 6.mm(), 1.mm(), 1.mm());
+      let solid = text.generate_stl_solid();
+
+      let half_stroke: Size = text.stroke_width() / 2;
+      assert_eq!(
+         bounding_box(&solid),
+         (
+            Point::new(-half_stroke, -half_stroke, 0.mm()),
+            Point::new(4.mm() + half_stroke, 6.mm() + half_stroke, 1.mm())
+         )
+      );
+   }
+
+   #[test]
+   fn space() {
+      let text = text(Location::default(), " ", 6.mm(), 1.mm(), 1.mm());
+      let solid = text.generate_stl_solid();
+      assert!(solid.facets.is_empty());
+   }
+}
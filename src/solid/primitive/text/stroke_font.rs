@@ -0,0 +1,79 @@
+//! A tiny single-stroke font.
+//!
+//! Each glyph is a list of polylines on a grid which is [GLYPH_WIDTH] wide and
+//! [GLYPH_HEIGHT] high. The origin is the left end of the baseline.
+//! A polyline which has only 1 point is a dot.
+
+pub(super) const GLYPH_WIDTH: i8 = 4;
+pub(super) const GLYPH_HEIGHT: i8 = 6;
+
+pub(super) type Glyph = &'static [&'static [(i8, i8)]];
+
+/// Drawn for characters which this font doesn't support.
+pub(super) const REPLACEMENT: Glyph = &[
+   &[(0, 0), (4, 0), (4, 6), (0, 6), (0, 0)]
+];
+
+/// returns the glyph for the specified character.
+/// Or `None` when this font doesn't support the character.
+pub(super) fn glyph(c: char) -> Option<Glyph> {
+   let glyph: Glyph = match c.to_ascii_uppercase() {
+      ' ' => &[],
+      '0' => &[&[(0, 0), (4, 0), (4, 6), (0, 6), (0, 0)], &[(0, 0), (4, 6)]],
+      '1' => &[&[(1, 5), (2, 6), (2, 0)], &[(1, 0), (3, 0)]],
+      '2' => &[&[(0, 6), (4, 6), (4, 3), (0, 3), (0, 0), (4, 0)]],
+      '3' => &[&[(0, 6), (4, 6), (4, 0), (0, 0)], &[(0, 3), (4, 3)]],
+      '4' => &[&[(0, 6), (0, 3), (4, 3)], &[(4, 6), (4, 0)]],
+      '5' => &[&[(4, 6), (0, 6), (0, 3), (4, 3), (4, 0), (0, 0)]],
+      '6' => &[&[(4, 6), (0, 6), (0, 0), (4, 0), (4, 3), (0, 3)]],
+      '7' => &[&[(0, 6), (4, 6), (4, 0)]],
+      '8' => &[&[(0, 0), (4, 0), (4, 6), (0, 6), (0, 0)], &[(0, 3), (4, 3)]],
+      '9' => &[&[(4, 3), (0, 3), (0, 6), (4, 6), (4, 0), (0, 0)]],
+      'A' => &[&[(0, 0), (0, 4), (2, 6), (4, 4), (4, 0)], &[(0, 3), (4, 3)]],
+      'B' => &[
+         &[(0, 0), (0, 6), (3, 6), (4, 5), (4, 4), (3, 3), (0, 3)],
+         &[(3, 3), (4, 2), (4, 1), (3, 0), (0, 0)]
+      ],
+      'C' => &[&[(4, 6), (0, 6), (0, 0), (4, 0)]],
+      'D' => &[&[(0, 0), (0, 6), (2, 6), (4, 4), (4, 2), (2, 0), (0, 0)]],
+      'E' => &[&[(4, 6), (0, 6), (0, 0), (4, 0)], &[(0, 3), (3, 3)]],
+      'F' => &[&[(4, 6), (0, 6), (0, 0)], &[(0, 3), (3, 3)]],
+      'G' => &[&[(4, 6), (0, 6), (0, 0), (4, 0), (4, 3), (2, 3)]],
+      'H' => &[&[(0, 0), (0, 6)], &[(4, 0), (4, 6)], &[(0, 3), (4, 3)]],
+      'I' => &[&[(1, 6), (3, 6)], &[(2, 6), (2, 0)], &[(1, 0), (3, 0)]],
+      'J' => &[&[(4, 6), (4, 0), (0, 0), (0, 2)]],
+      'K' => &[&[(0, 0), (0, 6)], &[(4, 6), (0, 3), (4, 0)]],
+      'L' => &[&[(0, 6), (0, 0), (4, 0)]],
+      'M' => &[&[(0, 0), (0, 6), (2, 3), (4, 6), (4, 0)]],
+      'N' => &[&[(0, 0), (0, 6), (4, 0), (4, 6)]],
+      'O' => &[&[(0, 0), (4, 0), (4, 6), (0, 6), (0, 0)]],
+      'P' => &[&[(0, 0), (0, 6), (4, 6), (4, 3), (0, 3)]],
+      'Q' => &[&[(0, 0), (4, 0), (4, 6), (0, 6), (0, 0)], &[(2, 2), (4, 0)]],
+      'R' => &[&[(0, 0), (0, 6), (4, 6), (4, 3), (0, 3), (4, 0)]],
+      'S' => &[&[(4, 6), (0, 6), (0, 3), (4, 3), (4, 0), (0, 0)]],
+      'T' => &[&[(0, 6), (4, 6)], &[(2, 6), (2, 0)]],
+      'U' => &[&[(0, 6), (0, 0), (4, 0), (4, 6)]],
+      'V' => &[&[(0, 6), (2, 0), (4, 6)]],
+      'W' => &[&[(0, 6), (1, 0), (2, 3), (3, 0), (4, 6)]],
+      'X' => &[&[(0, 0), (4, 6)], &[(0, 6), (4, 0)]],
+      'Y' => &[&[(0, 6), (2, 3), (4, 6)], &[(2, 3), (2, 0)]],
+      'Z' => &[&[(0, 6), (4, 6), (0, 0), (4, 0)]],
+      '-' => &[&[(1, 3), (3, 3)]],
+      '+' => &[&[(0, 3), (4, 3)], &[(2, 1), (2, 5)]],
+      '_' => &[&[(0, 0), (4, 0)]],
+      '/' => &[&[(0, 0), (4, 6)]],
+      '.' => &[&[(2, 0)]],
+      ',' => &[&[(2, 1), (1, -1)]],
+      ':' => &[&[(2, 1)], &[(2, 5)]],
+      '(' => &[&[(3, 6), (2, 5), (2, 1), (3, 0)]],
+      ')' => &[&[(1, 6), (2, 5), (2, 1), (1, 0)]],
+      '=' => &[&[(0, 2), (4, 2)], &[(0, 4), (4, 4)]],
+      '#' => &[
+         &[(1, 0), (1, 6)], &[(3, 0), (3, 6)],
+         &[(0, 2), (4, 2)], &[(0, 4), (4, 4)]
+      ],
+      _ => return None
+   };
+
+   Some(glyph)
+}
@@ -1,9 +1,13 @@
-use crate::geometry::Vector;
+use crate::geometry::{Size, Vector};
+use crate::math::unit::Exp;
+use crate::scad::{describe_block, vector_literal, ScadDescribable};
 use crate::solid::{Solid, SolidParent};
 use crate::solid::builder::BuildContext;
 use crate::solid::solid_parent::PushBorrowing;
 use crate::stl::StlSolid;
 use crate::transform::Transform;
+use anyhow::Result;
+use std::io::Write;
 
 pub struct Translate {
    pub offset: Vector,
@@ -31,20 +35,40 @@ pub fn translate(
 
 impl Solid for Translate {
    fn generate_stl_solid(&self) -> StlSolid {
-      let mut stl_solid = StlSolid {
-         facets: self.children.iter()
-            .flat_map(|c| c.generate_stl_solid().facets)
-            .collect()
-      };
+      let mut stl_solid = StlSolid::EMPTY;
+      for c in self.children.iter() {
+         stl_solid.extend(c.generate_stl_solid());
+      }
 
       for f in &mut stl_solid.facets {
-         for v in &mut f.vertexes {
-            v.translate(&self.offset);
-         }
+         *f = f.translated(&self.offset);
       }
 
       stl_solid
    }
+
+   /// Sum of the volumes of the children. Overlapping parts are counted
+   /// twice. Returns `None` if any child returns `None`.
+   fn approximate_volume(&self) -> Option<Exp<Size, 3>> {
+      self.children.iter()
+         .map(|c| c.approximate_volume())
+         .sum()
+   }
+
+   fn as_scad_describable(&self) -> Option<&dyn ScadDescribable> {
+      Some(self)
+   }
+}
+
+impl ScadDescribable for Translate {
+   fn describe_scad(&self, output: &mut dyn Write, indent: usize) -> Result<()> {
+      let offset = &self.offset;
+      describe_block(
+         output, indent,
+         &format!("translate({})", vector_literal(offset.x(), offset.y(), offset.z())),
+         &self.children
+      )
+   }
 }
 
 impl SolidParent for Translate {
@@ -57,8 +81,11 @@ impl SolidParent for Translate {
 mod tests {
    use super::translate;
    use crate::geometry::{Point, SizeLiteral, Vector};
-   use crate::solid::Solid;
+   use crate::math::unit::Exp;
+   use crate::solid::{cube, Location, Solid};
    use crate::stl::{Facet, StlSolid};
+   use crate::transform::Transform;
+   use noisy_float::prelude::*;
 
    #[test]
    fn vertexes() {
@@ -67,13 +94,11 @@ mod tests {
          fn generate_stl_solid(&self) -> StlSolid {
             StlSolid {
                facets: vec![
-                  Facet {
-                     vertexes: [
-                        Point::new(0.mm(), 1.mm(), 2.mm()),
-                        Point::new(3.mm(), 4.mm(), 5.mm()),
-                        Point::new(6.mm(), 7.mm(), 8.mm())
-                     ]
-                  }
+                  Facet::new([
+                     Point::new(0.mm(), 1.mm(), 2.mm()),
+                     Point::new(3.mm(), 4.mm(), 5.mm()),
+                     Point::new(6.mm(), 7.mm(), 8.mm())
+                  ])
                ]
             }
          }
@@ -85,7 +110,7 @@ mod tests {
       let s = t.generate_stl_solid();
 
       let actual: Vec<_> = s.facets.iter()
-         .flat_map(|f| f.vertexes)
+         .flat_map(|f| f.vertexes())
          .collect();
       let expected = vec![
          Point::new( 9.mm(), 11.mm(), 13.mm()),
@@ -95,4 +120,49 @@ mod tests {
 
       assert_eq!(expected, actual);
    }
+
+   #[test]
+   fn children_order() {
+      let offset = Vector::new(1.mm(), 2.mm(), 3.mm());
+      let a = || cube(Location::default(), (1.mm(), 1.mm(), 1.mm()));
+      let b = || cube(Location::default(), (2.mm(), 2.mm(), 2.mm()));
+
+      let t = translate(offset, |mut c| {
+         c <<= a();
+         c <<= b();
+      });
+
+      let actual: Vec<_> = t.generate_stl_solid().facets.iter()
+         .flat_map(|f| f.vertexes())
+         .collect();
+      let expected: Vec<_> = [a().generate_stl_solid(), b().generate_stl_solid()]
+         .iter()
+         .flat_map(|s| s.facets.iter().flat_map(|f| f.vertexes()))
+         .map(|v| v.translated(&offset))
+         .collect();
+
+      assert_eq!(actual, expected);
+   }
+
+   #[test]
+   fn approximate_volume() {
+      struct Child;
+      impl Solid for Child {
+         fn generate_stl_solid(&self) -> StlSolid {
+            StlSolid::new(vec![])
+         }
+      }
+
+      let t = translate(Vector::X_UNIT_VECTOR, |mut c| {
+         c <<= cube(Location::default(), (1.mm(), 2.mm(), 3.mm()));
+         c <<= cube(Location::default(), (2.mm(), 2.mm(), 2.mm()));
+      });
+      assert_eq!(t.approximate_volume(), Some(unsafe { Exp::new(n64(14.0)) }));
+
+      let t = translate(Vector::X_UNIT_VECTOR, |mut c| {
+         c <<= cube(Location::default(), (1.mm(), 2.mm(), 3.mm()));
+         c <<= Child;
+      });
+      assert_eq!(t.approximate_volume(), None);
+   }
 }
@@ -0,0 +1,107 @@
+use crate::scad::{describe_block, ScadDescribable};
+use crate::solid::{Solid, SolidParent};
+use crate::solid::builder::BuildContext;
+use crate::solid::solid_parent::PushBorrowing;
+use crate::stl::{boolean, StlSolid};
+use anyhow::Result;
+use std::io::Write;
+
+/// Merges its children into a single solid.
+///
+/// Unlike other parents which just concatenate facets of children, the
+/// overlapping parts of children are removed, so that the generated
+/// [StlSolid] has no crossing facets.
+/// Every child must generate a closed solid.
+#[derive(Default)]
+pub struct Union {
+   pub children: Vec<Box<dyn Solid>>
+}
+
+impl Union {
+   pub fn new() -> Union {
+      Union {
+         children: vec![]
+      }
+   }
+}
+
+pub fn union(build_action: impl FnOnce(BuildContext<Union>)) -> Union {
+   BuildContext::build(
+      Union::new(),
+      build_action
+   )
+}
+
+impl Solid for Union {
+   fn generate_stl_solid(&self) -> StlSolid {
+      self.children.iter()
+         .map(|c| c.generate_stl_solid())
+         .reduce(|a, b| boolean::union(&a, &b))
+         .unwrap_or_else(|| StlSolid::new(vec![]))
+   }
+
+   fn as_scad_describable(&self) -> Option<&dyn ScadDescribable> {
+      Some(self)
+   }
+}
+
+impl ScadDescribable for Union {
+   fn describe_scad(&self, output: &mut dyn Write, indent: usize) -> Result<()> {
+      describe_block(output, indent, "union()", &self.children)
+   }
+}
+
+impl SolidParent for Union {
+   fn push<S: Solid + 'static>(&mut self, child: S) -> &mut S {
+      self.children.push_borrowing(child)
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::union;
+   use crate::geometry::{SizeLiteral, Vector};
+   use crate::math::unit::Exp;
+   use crate::solid::{cube, cylinder, Location, Solid};
+   use crate::solid::primitive::test_support::assert_roughly;
+   use crate::transform::Transform;
+   use noisy_float::prelude::*;
+
+   #[test]
+   fn overlapping_cubes() {
+      let solid = union(|mut c| {
+         c <<= cube(Location::default(), (2.mm(), 2.mm(), 2.mm()));
+
+         let location = Location::default()
+            .translated(&Vector::new(1.mm(), 1.mm(), 1.mm()));
+         c <<= cube(location, (2.mm(), 2.mm(), 2.mm()));
+      }).generate_stl_solid();
+
+      assert!(solid.intersecting_facet_pairs().is_empty());
+      assert_roughly(solid.volume(), 8.0 + 8.0 - 1.0);
+   }
+
+   #[test]
+   fn cube_and_cylinder() {
+      let solid = union(|mut c| {
+         c <<= cube(Location::default(), (4.mm(), 4.mm(), 4.mm()));
+
+         let location = Location::default()
+            .translated(&Vector::new(2.mm(), 2.mm(), -1.mm()));
+         c <<= cylinder(location, 6.mm(), 1.mm());
+      }).generate_stl_solid();
+
+      assert!(solid.intersecting_facet_pairs().is_empty());
+
+      let cylinder_volume = cylinder(Location::default(), 6.mm(), 1.mm())
+         .generate_stl_solid().volume().0.raw();
+      assert_roughly(solid.volume(), 64.0 + cylinder_volume * 2.0 / 6.0);
+   }
+
+   #[test]
+   fn empty() {
+      let solid = union(|_| {}).generate_stl_solid();
+      assert_eq!(solid.facet_count(), 0);
+      assert_eq!(solid.volume(), unsafe { Exp::new(n64(0.0)) });
+   }
+}
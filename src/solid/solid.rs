@@ -1,16 +1,160 @@
-use crate::stl::{StlSolid, write_stl};
+use crate::geometry::{Angle, AngleLiteral, BoundingBox, Point, Size};
+use crate::math::unit::Exp;
+use crate::scad::ScadDescribable;
+use crate::solid::builder::env;
+use crate::solid::precision::{FRAGMENT_MAXIMUM_DEVIATION, FRAGMENT_MINIMUM_ANGLE};
+use crate::stl::{Facet, StlFormat, StlSolid, write_stl, write_stl_as};
 use anyhow::Result;
 use std::io::Write;
 
+/// A solid which can generate its facets.
+///
+/// Solids can be defined also outside of this crate.
+/// ```
+/// use typed_scad::geometry::{Point, SizeLiteral};
+/// use typed_scad::solid::Solid;
+/// use typed_scad::stl::{Facet, StlSolid};
+///
+/// /// A tetrahedron with 1mm edges along the axes.
+/// struct Tetrahedron;
+///
+/// impl Solid for Tetrahedron {
+///    fn generate_stl_solid(&self) -> StlSolid {
+///       let o = Point::ORIGIN;
+///       let x = Point::new(1.mm(), 0.mm(), 0.mm());
+///       let y = Point::new(0.mm(), 1.mm(), 0.mm());
+///       let z = Point::new(0.mm(), 0.mm(), 1.mm());
+///
+///       StlSolid::new(vec![
+///          Facet::new([o, y, x]),
+///          Facet::new([o, x, z]),
+///          Facet::new([o, z, y]),
+///          Facet::new([x, y, z])
+///       ])
+///    }
+/// }
+///
+/// let stl_solid = Tetrahedron.generate_stl_solid();
+/// assert_eq!(stl_solid.facet_count(), 4);
+/// assert_eq!(stl_solid.as_slice()[0].normal_vector().z(), -1.mm());
+/// assert!(stl_solid.is_watertight());
+/// ```
 pub trait Solid {
    fn generate_stl_solid(&self) -> StlSolid;
 
+   /// Volume of this solid calculated without generating facets.
+   ///
+   /// Returns `None` by default. Solids whose volume is known by a formula
+   /// should override this. Use [StlSolid::volume] to get the volume of
+   /// other solids.
+   fn approximate_volume(&self) -> Option<Exp<Size, 3>> {
+      None
+   }
+
+   /// Volume of the generated [StlSolid]. See [StlSolid::volume].
+   /// Use [StlSolid::volume] directly to avoid generating facets twice.
+   fn volume(&self) -> Exp<Size, 3> {
+      self.generate_stl_solid().volume()
+   }
+
+   /// Surface area of the generated [StlSolid].
+   /// See [StlSolid::surface_area].
+   fn surface_area(&self) -> Exp<Size, 2> {
+      self.generate_stl_solid().surface_area()
+   }
+
+   /// The number of facets which [generate_stl_solid](Solid::generate_stl_solid)
+   /// generates.
+   ///
+   /// The default implementation generates facets actually. Solids whose
+   /// facet count is known without generation should override this.
+   fn facet_count(&self) -> usize {
+      self.generate_stl_solid().facet_count()
+   }
+
+   /// Bounding box of the generated [StlSolid].
+   /// See [StlSolid::bounding_box].
+   ///
+   /// Overrides may compute the box analytically from the ideal shape, such
+   /// as the circumscribing cube of a sphere. Such a box contains the
+   /// generated facets but can be larger than the box of them.
+   fn bounding_box(&self) -> Option<BoundingBox> {
+      self.generate_stl_solid().bounding_box()
+   }
+
+   /// Returns `Some(self)` if this solid implements [ScadDescribable].
+   ///
+   /// Used to describe children of a parent, which are `Box<dyn Solid>`.
+   /// Children without [ScadDescribable] are described as `polyhedron`.
+   fn as_scad_describable(&self) -> Option<&dyn ScadDescribable> {
+      None
+   }
+
+   /// Generates facets one by one.
+   ///
+   /// The default implementation generates the whole [StlSolid] first.
+   /// Solids which can compute each facet independently should override this.
+   fn generate_facets_lazy(&self) -> Box<dyn Iterator<Item = Facet> + '_> {
+      Box::new(self.generate_stl_solid().facets.into_iter())
+   }
+
+   fn facets_iter(&self) -> Box<dyn Iterator<Item = Facet> + '_> {
+      self.generate_facets_lazy()
+   }
+
+   /// All vertexes of all facets. Vertexes shared by some facets are
+   /// contained as many times as the facets.
+   /// See also [unique_vertexes](Solid::unique_vertexes).
+   fn vertexes(&self) -> Box<dyn Iterator<Item = Point> + '_> {
+      Box::new(self.facets_iter().flat_map(|f| f.vertexes()))
+   }
+
+   /// All vertexes without duplication. The order is unspecified.
+   fn unique_vertexes(&self) -> Vec<Point> {
+      let mut vertexes: Vec<_> = self.vertexes().collect();
+      vertexes.sort_by(|a, b|
+         a.x().cmp(&b.x())
+            .then(a.y().cmp(&b.y()))
+            .then(a.z().cmp(&b.z()))
+      );
+      vertexes.dedup();
+      vertexes
+   }
+
+   /// Generates facets with [FRAGMENT_MINIMUM_ANGLE] of `angle`, ignoring
+   /// [FRAGMENT_MAXIMUM_DEVIATION] and the env outside. Solids with their own
+   /// [FragmentSpec](crate::solid::precision::FragmentSpec) keep it.
+   fn generate_stl_with_precision(&self, angle: Angle) -> StlSolid {
+      let mut stl_solid = None;
+      env(&FRAGMENT_MINIMUM_ANGLE, angle, || {
+         env(&FRAGMENT_MAXIMUM_DEVIATION, Size::INFINITY, || {
+            stl_solid = Some(self.generate_stl_solid());
+         });
+      });
+      stl_solid.unwrap()
+   }
+
+   /// Generates coarse facets for a quick preview. Circles have at most 12
+   /// fragments, or fewer if [FRAGMENT_MINIMUM_ANGLE] is already coarser.
+   /// See [generate_stl_with_precision](Solid::generate_stl_with_precision).
+   fn generate_preview_stl(&self) -> StlSolid {
+      let angle = Ord::max(*FRAGMENT_MINIMUM_ANGLE, 30.deg());
+      self.generate_stl_with_precision(angle)
+   }
+
    fn write_to(&self, output: &mut dyn Write) -> Result<()> {
       let stl_solid = self.generate_stl_solid();
       write_stl(output, &stl_solid)?;
       Ok(())
    }
 
+   /// Same as [write_to](Solid::write_to), but in the specified format.
+   fn write_to_with(&self, output: &mut dyn Write, format: StlFormat) -> Result<()> {
+      let stl_solid = self.generate_stl_solid();
+      write_stl_as(output, &stl_solid, &format)?;
+      Ok(())
+   }
+
    fn build(builder: impl FnOnce(&mut Self) -> ()) -> Self
       where Self: Default
    {
@@ -20,10 +164,52 @@ pub trait Solid {
    }
 }
 
+/// Concatenates facets of all solids.
+///
+/// Overlapping parts are left as they are, just like children of
+/// [Translate](crate::solid::Translate) etc. Use [union](crate::solid::union)
+/// to remove them.
+pub fn union_all(solids: Vec<Box<dyn Solid>>) -> StlSolid {
+   solids.generate_stl_solid()
+}
+
+impl Solid for Vec<Box<dyn Solid>> {
+   fn generate_stl_solid(&self) -> StlSolid {
+      let mut stl_solid = StlSolid::EMPTY;
+      for s in self {
+         stl_solid.extend(s.generate_stl_solid());
+      }
+      stl_solid
+   }
+}
+
 #[cfg(test)]
 mod test {
-   use super::Solid;
+   use super::{Solid, union_all};
+   use crate::geometry::{AngleLiteral, Point, SizeLiteral};
+   use crate::solid::{cone, cube, cylinder, sphere, Location};
+   use crate::solid::builder::env;
+   use crate::solid::precision::{FRAGMENT_MAXIMUM_DEVIATION, FRAGMENT_MINIMUM_ANGLE};
    use crate::stl::StlSolid;
+   use std::f64::consts::PI;
+
+   #[test]
+   fn vertexes() {
+      let cube = cube(Location::default(), (1.mm(), 2.mm(), 3.mm()));
+
+      assert_eq!(cube.facets_iter().count(), 12);
+      assert_eq!(cube.vertexes().count(), 36);
+
+      let mut expected = vec![];
+      for x in [0, 1] {
+         for y in [0, 2] {
+            for z in [0, 3] {
+               expected.push(Point::new(x.mm(), y.mm(), z.mm()));
+            }
+         }
+      }
+      assert_eq!(cube.unique_vertexes(), expected);
+   }
 
    #[test]
    fn build() {
@@ -45,4 +231,76 @@ mod test {
 
       assert_eq!(solid_impl.0, 42);
    }
+
+   #[test]
+   fn union_all_solids() {
+      let solids: Vec<Box<dyn Solid>> = vec![
+         Box::new(cube(Location::default(), (1.mm(), 2.mm(), 3.mm()))),
+         Box::new(cylinder(Location::default(), 2.mm(), 3.mm())),
+         Box::new(sphere(Location::default(), 3.mm()))
+      ];
+
+      let expected = solids.iter()
+         .map(|s| s.generate_stl_solid().facet_count())
+         .sum::<usize>();
+
+      assert_eq!(solids.generate_stl_solid().facet_count(), expected);
+      assert_eq!(union_all(solids).facet_count(), expected);
+   }
+
+   /// The largest number of vertexes on a horizontal circle around Z-Axis.
+   fn max_fragments(solid: &dyn Solid) -> usize {
+      let mut heights: Vec<_> = solid.generate_preview_stl().to_indexed(0.mm())
+         .vertexes.into_iter()
+         .filter(|v| v.x() != 0.mm() || v.y() != 0.mm())
+         .map(|v| v.z())
+         .collect();
+      heights.sort();
+      heights.chunk_by(|a, b| a == b).map(|c| c.len()).max().unwrap()
+   }
+
+   #[test]
+   fn preview() {
+      let sphere = sphere(Location::default(), 10.mm());
+      let cylinder = cylinder(Location::default(), 5.mm(), 10.mm());
+      let cone = cone(Location::default(), 5.mm(), 10.mm());
+
+      for solid in [&sphere as &dyn Solid, &cylinder, &cone] {
+         assert!(solid.generate_preview_stl().facet_count()
+            < solid.generate_stl_solid().facet_count());
+         assert!(max_fragments(solid) <= 12);
+      }
+      assert_eq!(max_fragments(&cylinder), 12);
+
+      env(&FRAGMENT_MAXIMUM_DEVIATION, 0.01.mm(), || {
+         assert_eq!(max_fragments(&cylinder), 12);
+      });
+      env(&FRAGMENT_MINIMUM_ANGLE, 90.deg(), || {
+         assert_eq!(max_fragments(&cylinder), 4);
+      });
+
+      let precise = cylinder.generate_stl_with_precision(1.deg());
+      assert_eq!(precise.facet_count(), 360 * 4);
+      assert_eq!(cylinder.generate_stl_solid().facet_count(), 30 * 4);
+   }
+
+   #[test]
+   fn volume() {
+      let cube = cube(Location::default(), (2.mm(), 3.mm(), 5.mm()));
+      assert_eq!(cube.volume().0, 30.0);
+
+      let sphere = sphere(Location::default(), 10.mm());
+      let expected = 4.0 / 3.0 * PI * 1000.0;
+      assert!((sphere.volume().0.raw() - expected).abs() / expected < 0.02);
+   }
+
+   #[test]
+   fn surface_area() {
+      let cube = cube(Location::default(), (2.mm(), 3.mm(), 5.mm()));
+      assert_eq!(cube.surface_area().0, 62.0);
+
+      let cylinder = cylinder(Location::default(), 5.mm(), 2.mm());
+      let expected = 2.0 * PI * 4.0 + 2.0 * PI * 2.0 * 5.0;
+      assert!((cylinder.surface_area().0.raw() - expected).abs() / expected < 0.01);
+   }
 }
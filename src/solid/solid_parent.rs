@@ -1,10 +1,28 @@
+use crate::geometry::{Angle, BoundingBox, Point, Size};
+use crate::math::unit::Exp;
+use crate::scad::ScadDescribable;
 use crate::solid::builder::ChildReceiver;
 use crate::solid::Solid;
+use crate::stl::{Facet, StlFormat, StlSolid};
+use anyhow::Result;
+use std::io::Write;
 use std::mem;
 
 pub trait SolidParent: Solid {
    fn push<S: Solid + 'static>(&mut self, child: S) -> &mut S;
 
+   /// Pushes all the children in order.
+   fn push_many<S: Solid + 'static>(&mut self, children: impl IntoIterator<Item = S>) {
+      for child in children {
+         self.push(child);
+      }
+   }
+
+   /// Pushes all the children in order. For children of different types.
+   fn push_many_boxed(&mut self, children: impl IntoIterator<Item = Box<dyn Solid>>) {
+      self.push_many(children.into_iter().map(Boxed));
+   }
+
    fn push_children(
       &mut self,
       generator: impl FnOnce(ChildReceiver<Self>) -> ()
@@ -15,6 +33,72 @@ pub trait SolidParent: Solid {
    }
 }
 
+/// Delegates every method to the boxed solid. `Box<dyn Solid>` itself cannot be a [Solid]
+/// since it conflicts with `Vec<Box<dyn Solid>>` as a child.
+struct Boxed(Box<dyn Solid>);
+
+impl Solid for Boxed {
+   fn generate_stl_solid(&self) -> StlSolid {
+      self.0.generate_stl_solid()
+   }
+
+   fn approximate_volume(&self) -> Option<Exp<Size, 3>> {
+      self.0.approximate_volume()
+   }
+
+   fn volume(&self) -> Exp<Size, 3> {
+      self.0.volume()
+   }
+
+   fn surface_area(&self) -> Exp<Size, 2> {
+      self.0.surface_area()
+   }
+
+   fn facet_count(&self) -> usize {
+      self.0.facet_count()
+   }
+
+   fn bounding_box(&self) -> Option<BoundingBox> {
+      self.0.bounding_box()
+   }
+
+   fn as_scad_describable(&self) -> Option<&dyn ScadDescribable> {
+      self.0.as_scad_describable()
+   }
+
+   fn generate_facets_lazy(&self) -> Box<dyn Iterator<Item = Facet> + '_> {
+      self.0.generate_facets_lazy()
+   }
+
+   fn facets_iter(&self) -> Box<dyn Iterator<Item = Facet> + '_> {
+      self.0.facets_iter()
+   }
+
+   fn vertexes(&self) -> Box<dyn Iterator<Item = Point> + '_> {
+      self.0.vertexes()
+   }
+
+   fn unique_vertexes(&self) -> Vec<Point> {
+      self.0.unique_vertexes()
+   }
+
+   fn generate_stl_with_precision(&self, angle: Angle) -> StlSolid {
+      self.0.generate_stl_with_precision(angle)
+   }
+
+   fn generate_preview_stl(&self) -> StlSolid {
+      self.0.generate_preview_stl()
+   }
+
+   fn write_to(&self, output: &mut dyn Write) -> Result<()> {
+      self.0.write_to(output)
+   }
+
+   fn write_to_with(&self, output: &mut dyn Write, format: StlFormat) -> Result<()> {
+      self.0.write_to_with(output, format)
+   }
+}
+
 pub trait PushBorrowing {
    fn push_borrowing<S: Solid + 'static>(&mut self, value: S) -> &mut S;
 }
@@ -33,7 +117,7 @@ impl PushBorrowing for Vec<Box<dyn Solid>> {
 mod test {
    use super::PushBorrowing;
    use crate::geometry::{Point, SizeLiteral, Vector};
-   use crate::solid::{Solid, SolidParent};
+   use crate::solid::{cube, sphere, translate, Location, Solid, SolidParent, Translate};
    use crate::stl::{Facet, StlSolid};
    use crate::transform::Transform;
 
@@ -49,7 +133,7 @@ mod test {
       fn generate_stl_solid(&self) -> StlSolid {
          StlSolid {
             facets: vec![
-               Facet { vertexes: self.0.vertexes }
+               Facet::new(self.0.vertexes())
             ]
          }
       }
@@ -83,12 +167,16 @@ mod test {
    fn push() {
       let mut solid_parent = SolidParentImpl::new();
       let solid = SolidImpl::new(
-         Facet { vertexes: [Point::ORIGIN, Point::ORIGIN, Point::ORIGIN] }
+         Facet::new([Point::ORIGIN, Point::ORIGIN, Point::ORIGIN])
       );
 
       let r = solid_parent.push(solid);
-      r.0.vertexes[0].translate(&Vector::X_UNIT_VECTOR);
-      r.0.vertexes[1].translate(&Vector::Y_UNIT_VECTOR);
+      let [a, b, c] = r.0.vertexes();
+      r.0.set_vertexes([
+         a.translated(&Vector::X_UNIT_VECTOR),
+         b.translated(&Vector::Y_UNIT_VECTOR),
+         c
+      ]);
 
       let stl_solid = solid_parent.generate_stl_solid();
       let expected = vec![
@@ -98,9 +186,72 @@ mod test {
       ];
       assert_eq!(
          stl_solid.facets.iter()
-            .flat_map(|f| f.vertexes)
+            .flat_map(|f| f.vertexes())
             .collect::<Vec<_>>(),
          expected
       );
    }
+
+   #[test]
+   fn push_many() {
+      let offset = Vector::new(1.mm(), 2.mm(), 3.mm());
+      let cubes = || (0..100).map(|i|
+         cube(Location::default(), (1.mm(), 1.mm(), 1.mm()))
+            .translated(&Vector::new(i.mm(), 0.mm(), 0.mm()))
+      );
+
+      let mut pushed = Translate::new(offset);
+      pushed.push_many(cubes());
+
+      let shl_assigned = translate(offset, |mut t| {
+         for c in cubes() {
+            t <<= c;
+         }
+      });
+
+      let vertexes = |s: &Translate| -> Vec<Point> {
+         s.generate_stl_solid().facets.iter().flat_map(|f| f.vertexes()).collect()
+      };
+      assert_eq!(pushed.children.len(), 100);
+      assert_eq!(vertexes(&pushed), vertexes(&shl_assigned));
+
+      let children: Vec<Box<dyn Solid>> = vec![
+         Box::new(cube(Location::default(), (1.mm(), 1.mm(), 1.mm()))),
+         Box::new(sphere(Location::default(), 1.mm()))
+      ];
+      let mut boxed = Translate::new(offset);
+      boxed.push_many_boxed(children);
+      assert_eq!(boxed.children.len(), 2);
+      assert_eq!(
+         boxed.generate_stl_solid().facet_count(),
+         12 + sphere(Location::default(), 1.mm()).facet_count()
+      );
+   }
+
+   #[test]
+   fn boxed_delegates_overrides() {
+      struct Preview;
+
+      impl Solid for Preview {
+         fn generate_stl_solid(&self) -> StlSolid {
+            cube(Location::default(), (1.mm(), 1.mm(), 1.mm())).generate_stl_solid()
+         }
+
+         fn generate_preview_stl(&self) -> StlSolid {
+            StlSolid::EMPTY
+         }
+
+         fn vertexes(&self) -> Box<dyn Iterator<Item = Point> + '_> {
+            Box::new([Point::ORIGIN].into_iter())
+         }
+      }
+
+      let mut parent = SolidParentImpl::new();
+      parent.push_many_boxed(vec![Box::new(Preview) as Box<dyn Solid>]);
+
+      let child = &parent.0[0];
+      assert_eq!(child.generate_stl_solid().facet_count(), 12);
+      assert!(child.generate_preview_stl().is_empty());
+      assert_eq!(child.vertexes().count(), 1);
+   }
 }
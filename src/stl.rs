@@ -1,7 +1,29 @@
 
+pub(crate) mod boolean;
+pub(crate) mod bvh;
+mod convex_hull;
+mod cross_section;
+mod distance;
+mod indexed_mesh;
+mod mesh_report;
+mod mirror;
+mod raycast;
+mod read_stl;
+mod repair;
 mod stl_solid;
+mod subdivide;
 mod write_stl;
 
-pub use stl_solid::StlSolid;
-pub(crate) use stl_solid::Facet;
-pub use write_stl::write_stl;
+pub use convex_hull::{convex_hull, ConvexHullError};
+pub use cross_section::CrossSection;
+pub use indexed_mesh::IndexedMesh;
+pub use mesh_report::MeshReport;
+pub use raycast::SolidBvh;
+pub use read_stl::{read_stl, StlReadError};
+pub use repair::{RepairError, RepairOptions};
+pub use stl_solid::{Facet, StlSolid};
+pub use write_stl::{
+   write_stl, write_stl_as, write_stl_ascii, write_stl_deterministic,
+   write_stl_parallel, write_stl_parallel_with_warnings, write_stl_with_progress,
+   write_stl_with_warnings, StlFormat, StlWriteWarning
+};
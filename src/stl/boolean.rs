@@ -0,0 +1,623 @@
+//! Boolean operations on closed [StlSolid]s.
+//!
+//! Each facet of a solid is split by the planes of the other solid's facets
+//! which touch it, so that every piece is entirely inside or outside of the
+//! other solid. Then each piece is classified by casting a ray from it, and
+//! only the pieces on the surface of the result are kept.
+//!
+//! The result may contain T-junctions (vertexes on an edge of another facet),
+//! but it is still closed and can be passed to another boolean operation.
+
+use crate::geometry::{BoundingBox, Point, Size, Vector};
+use crate::stl::{Facet, StlSolid};
+use crate::stl::bvh::Bvh;
+use crate::transform::Transform;
+use noisy_float::prelude::*;
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+
+/// Distance regarded as zero while splitting and classifying facets.
+pub(crate) const EPSILON: Size = Size::millimeter(N64::unchecked_new(1e-6));
+
+/// Tolerance of barycentric coordinates to detect a ray passing on an edge.
+pub(crate) const BARYCENTRIC_TOLERANCE: f64 = 1e-9;
+
+/// Directions to cast rays. Chosen not to be parallel to any axis or to any
+/// typical plane. If a ray passes on an edge or a vertex, the next one is used.
+#[allow(clippy::approx_constant)]
+const RAY_DIRECTIONS: [(f64, f64, f64); 6] = [
+   (0.5773, 0.5851, 0.5693),
+   (-0.3113, 0.7237, 0.6159),
+   (0.8211, -0.2659, 0.5051),
+   (-0.4472, -0.6124, 0.6519),
+   (0.2197, 0.4309, -0.8753),
+   (-0.7071, 0.1837, -0.6831)
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Side {
+   Outside,
+   Inside,
+   /// On the surface of the other solid, facing the same direction.
+   SameSurface,
+   /// On the surface of the other solid, facing the opposite direction.
+   OppositeSurface
+}
+
+/// A part of a facet, with its position relative to the other solid.
+type Piece = (Facet, Side);
+
+pub(crate) fn union(a: &StlSolid, b: &StlSolid) -> StlSolid {
+   let (a_pieces, b_pieces) = split_and_classify(a, b);
+
+   a_pieces.into_iter()
+      .filter(|(_, side)| matches!(side, Side::Outside | Side::SameSurface))
+      .chain(
+         b_pieces.into_iter().filter(|(_, side)| *side == Side::Outside)
+      )
+      .map(|(facet, _)| facet)
+      .collect()
+}
+
+pub(crate) fn difference(a: &StlSolid, b: &StlSolid) -> StlSolid {
+   let (a_pieces, b_pieces) = split_and_classify(a, b);
+
+   a_pieces.into_iter()
+      .filter(|(_, side)| matches!(side, Side::Outside | Side::OppositeSurface))
+      .map(|(facet, _)| facet)
+      .chain(
+         b_pieces.into_iter()
+            .filter(|(_, side)| *side == Side::Inside)
+            .map(|(facet, _)| reversed(&facet))
+      )
+      .collect()
+}
+
+pub(crate) fn intersection(a: &StlSolid, b: &StlSolid) -> StlSolid {
+   let (a_pieces, b_pieces) = split_and_classify(a, b);
+
+   a_pieces.into_iter()
+      .filter(|(_, side)| matches!(side, Side::Inside | Side::SameSurface))
+      .chain(
+         b_pieces.into_iter().filter(|(_, side)| *side == Side::Inside)
+      )
+      .map(|(facet, _)| facet)
+      .collect()
+}
+
+/// Whether the 2 facets cross each other. Facets which only touch at their
+/// edges or vertexes are not regarded as crossing.
+pub(crate) fn crosses(a: &Facet, b: &Facet) -> bool {
+   if is_degenerate(a) || is_degenerate(b) {
+      return false;
+   }
+
+   let a_normal = a.normal_vector();
+   let b_normal = b.normal_vector();
+   let da = distances(a, &b.vertexes()[0], &b_normal);
+   let db = distances(b, &a.vertexes()[0], &a_normal);
+
+   if da.iter().all(|d| d.abs() <= EPSILON) {
+      return coplanar_overlaps(a, b, &a_normal);
+   }
+
+   let straddles = |d: &[Size; 3]| {
+      d.iter().any(|&d| d > EPSILON) && d.iter().any(|&d| d < -EPSILON)
+   };
+
+   if !straddles(&da) || !straddles(&db) {
+      return false;
+   }
+
+   match section_intervals(a, &da, b, &db, &a_normal, &b_normal) {
+      Some(((a_min, a_max), (b_min, b_max))) => {
+         let overlap = Ord::min(a_max, b_max) - Ord::max(a_min, b_min);
+         overlap > EPSILON.to_millimeter()
+      }
+      None => false
+   }
+}
+
+fn split_and_classify(
+   a: &StlSolid,
+   b: &StlSolid
+) -> (Vec<Piece>, Vec<Piece>) {
+   let a_bvh = Bvh::new(&a.facets);
+   let b_bvh = Bvh::new(&b.facets);
+
+   (
+      split_and_classify_facets(&a.facets, &b.facets, &b_bvh),
+      split_and_classify_facets(&b.facets, &a.facets, &a_bvh)
+   )
+}
+
+fn split_and_classify_facets(
+   facets: &[Facet],
+   others: &[Facet],
+   others_bvh: &Bvh
+) -> Vec<Piece> {
+   facets.par_iter()
+      .filter(|facet| !is_degenerate(facet))
+      .flat_map(|facet| {
+         let normal = facet.normal_vector();
+
+         let planes: Vec<_> = others_bvh
+            .query(&BoundingBox::of_facet(facet).expanded(EPSILON))
+            .into_iter()
+            .map(|i| &others[i])
+            .filter(|other| touches(facet, other))
+            .map(|other| (other.vertexes()[0], other.normal_vector()))
+            .collect();
+
+         split_polygon_by_planes(facet.vertexes().to_vec(), &planes)
+            .into_iter()
+            .flat_map(|polygon| {
+               let side = classify(&centroid(&polygon), &normal, others, others_bvh);
+               triangulate(&polygon).into_iter().map(move |f| (f, side))
+            })
+            .collect::<Vec<_>>()
+      })
+      .collect()
+}
+
+/// Whether the 2 facets have any common point, including touching at their
+/// edges or vertexes. Coplanar facets are not regarded as touching.
+fn touches(a: &Facet, b: &Facet) -> bool {
+   if is_degenerate(b) {
+      return false;
+   }
+
+   let a_normal = a.normal_vector();
+   let b_normal = b.normal_vector();
+   let da = distances(a, &b.vertexes()[0], &b_normal);
+   let db = distances(b, &a.vertexes()[0], &a_normal);
+
+   let apart = |d: &[Size; 3]| {
+      d.iter().all(|&d| d > EPSILON) || d.iter().all(|&d| d < -EPSILON)
+   };
+
+   if apart(&da) || apart(&db) {
+      return false;
+   }
+
+   if db.iter().all(|d| d.abs() <= EPSILON) {
+      return false;
+   }
+
+   match section_intervals(a, &da, b, &db, &a_normal, &b_normal) {
+      Some(((a_min, a_max), (b_min, b_max))) => {
+         let epsilon = EPSILON.to_millimeter();
+         a_min <= b_max + epsilon && b_min <= a_max + epsilon
+      }
+      // planes are almost parallel. Splitting is harmless even if they don't
+      // touch actually.
+      None => true
+   }
+}
+
+/// Both facets are cut by the line where the 2 planes meet. Returns the
+/// ranges of the cut on the line for each facet.
+fn section_intervals(
+   a: &Facet, da: &[Size; 3],
+   b: &Facet, db: &[Size; 3],
+   a_normal: &Vector, b_normal: &Vector
+) -> Option<((N64, N64), (N64, N64))> {
+   let direction = a_normal.vector_product(b_normal);
+   if direction.norm() <= EPSILON {
+      return None;
+   }
+   let direction = direction.to_unit_vector();
+
+   let interval = |facet: &Facet, d: &[Size; 3]| {
+      let positions: Vec<_> = section(facet, d).iter()
+         .map(|p| Vector::between(&Point::ORIGIN, p).inner_product(&direction).0)
+         .collect();
+
+      let min = positions.iter().copied().min()?;
+      let max = positions.iter().copied().max()?;
+      Some((min, max))
+   };
+
+   Some((interval(a, da)?, interval(b, db)?))
+}
+
+/// Points where the facet meets the plane. `d` is the signed distances of
+/// the vertexes from the plane.
+fn section(facet: &Facet, d: &[Size; 3]) -> Vec<Point> {
+   let mut points = vec![];
+
+   for i in 0..3 {
+      let j = (i + 1) % 3;
+      let (pi, pj) = (facet.vertexes()[i], facet.vertexes()[j]);
+
+      if d[i].abs() <= EPSILON {
+         points.push(pi);
+      }
+
+      if (d[i] > EPSILON && d[j] < -EPSILON) || (d[i] < -EPSILON && d[j] > EPSILON) {
+         points.push(interpolate(&pi, &pj, d[i], d[j]));
+      }
+   }
+
+   points
+}
+
+fn coplanar_overlaps(a: &Facet, b: &Facet, normal: &Vector) -> bool {
+   // Separating axis theorem. The axes are perpendicular to each edge.
+   let separated = |axis: &Vector| {
+      let project = |f: &Facet| {
+         let positions = f.vertexes()
+            .map(|v| Vector::between(&Point::ORIGIN, &v).inner_product(axis).0);
+         let min = positions.iter().copied().min().unwrap();
+         let max = positions.iter().copied().max().unwrap();
+         (min, max)
+      };
+
+      let (a_min, a_max) = project(a);
+      let (b_min, b_max) = project(b);
+      Ord::min(a_max, b_max) - Ord::max(a_min, b_min) <= EPSILON.to_millimeter()
+   };
+
+   let axes = [a, b].into_iter()
+      .flat_map(|f| {
+         let [p, q, r] = f.vertexes();
+         [(p, q), (q, r), (r, p)]
+      })
+      .map(|(p, q)| normal.vector_product(&Vector::between(&p, &q)).to_unit_vector());
+
+   !axes.into_iter().any(|axis| separated(&axis))
+}
+
+fn classify(point: &Point, normal: &Vector, facets: &[Facet], bvh: &Bvh) -> Side {
+   for i in bvh.query(&BoundingBox::of_point(point).expanded(EPSILON)) {
+      let facet = &facets[i];
+      if is_degenerate(facet) || !facet.contains_point(point, EPSILON) {
+         continue;
+      }
+
+      let cos = normal.inner_product(&facet.normal_vector()).0;
+      if cos > 1.0 - 1e-6 {
+         return Side::SameSurface;
+      }
+      if cos < -1.0 + 1e-6 {
+         return Side::OppositeSurface;
+      }
+   }
+
+   match is_inside(point, facets, bvh) {
+      Some(true) => Side::Inside,
+      Some(false) => Side::Outside,
+      None => panic!("Cannot determine whether the point {} is inside the solid. \
+                      The solid may not be closed.", point)
+   }
+}
+
+/// Whether the point is inside the closed solid, by the parity of the number
+/// of facets which a ray from the point hits. `None` if every ray in
+/// [RAY_DIRECTIONS] passes on an edge or a vertex, or if the point is on
+/// the surface.
+pub(crate) fn is_inside(point: &Point, facets: &[Facet], bvh: &Bvh) -> Option<bool> {
+   RAY_DIRECTIONS.into_iter()
+      .map(|(x, y, z)| {
+         Vector::new(
+            Size::millimeter(n64(x)),
+            Size::millimeter(n64(y)),
+            Size::millimeter(n64(z))
+         ).to_unit_vector()
+      })
+      .find_map(|direction| count_ray_hits(point, &direction, facets, bvh))
+      .map(|count| count % 2 == 1)
+}
+
+enum RayHit {
+   Hit,
+   Miss,
+   /// The ray passes on an edge or a vertex, or the origin is on the facet.
+   Ambiguous
+}
+
+/// The number of facets which the ray hits.
+/// `None` if the ray passes on an edge or a vertex.
+fn count_ray_hits(
+   origin: &Point,
+   direction: &Vector,
+   facets: &[Facet],
+   bvh: &Bvh
+) -> Option<usize> {
+   let mut count = 0;
+
+   for i in bvh.query_ray(origin, direction) {
+      match ray_hit(origin, direction, &facets[i]) {
+         RayHit::Hit => count += 1,
+         RayHit::Miss => {}
+         RayHit::Ambiguous => return None
+      }
+   }
+
+   Some(count)
+}
+
+/// `direction` must be a unit vector.
+fn ray_hit(origin: &Point, direction: &Vector, facet: &Facet) -> RayHit {
+   if is_degenerate(facet) {
+      return RayHit::Miss;
+   }
+
+   let Some((t, [w, u, v])) = ray_intersection(origin, direction, facet) else {
+      // the ray is parallel to the facet
+      let normal = facet.normal_vector();
+      let distance = Size::from(
+         Vector::between(&facet.vertexes()[0], origin).inner_product(&normal)
+            / normal.norm()
+      );
+      return if distance.abs() <= EPSILON {
+         RayHit::Ambiguous
+      } else {
+         RayHit::Miss
+      };
+   };
+
+   if u < -BARYCENTRIC_TOLERANCE || v < -BARYCENTRIC_TOLERANCE
+      || w < -BARYCENTRIC_TOLERANCE
+   {
+      return RayHit::Miss;
+   }
+
+   if t < -EPSILON.to_millimeter() {
+      return RayHit::Miss;
+   }
+
+   if t <= EPSILON.to_millimeter()
+      || u <= BARYCENTRIC_TOLERANCE || v <= BARYCENTRIC_TOLERANCE
+      || w <= BARYCENTRIC_TOLERANCE
+   {
+      return RayHit::Ambiguous;
+   }
+
+   RayHit::Hit
+}
+
+/// Möller–Trumbore intersection. The distance in millimeter along the ray,
+/// and the barycentric coordinates of the point where the ray crosses
+/// the plane of the facet. `None` if the ray is parallel to the facet.
+/// `direction` must be a unit vector.
+pub(crate) fn ray_intersection(
+   origin: &Point,
+   direction: &Vector,
+   facet: &Facet
+) -> Option<(N64, [N64; 3])> {
+   let [a, b, c] = facet.vertexes();
+   let e1 = Vector::between(&a, &b);
+   let e2 = Vector::between(&a, &c);
+
+   // vector_product returns Vector whose components are actually mm², so
+   // the following values are only compared as numbers.
+   let p = direction.vector_product(&e2);
+   let det = e1.inner_product(&p).0;
+   let scale = e1.norm().to_millimeter() * e2.norm().to_millimeter();
+
+   if det.abs() <= scale * 1e-9 {
+      return None;
+   }
+
+   let s = Vector::between(&a, origin);
+   let q = s.vector_product(&e1);
+   let u = s.inner_product(&p).0 / det;
+   let v = direction.inner_product(&q).0 / det;
+   let w = n64(1.0) - u - v;
+   let t = e2.inner_product(&q).0 / det;
+
+   Some((t, [w, u, v]))
+}
+
+fn split_polygon_by_planes(
+   polygon: Vec<Point>,
+   planes: &[(Point, Vector)]
+) -> Vec<Vec<Point>> {
+   let mut polygons = vec![polygon];
+
+   for (plane_point, plane_normal) in planes {
+      polygons = polygons.into_iter()
+         .flat_map(|polygon| split_polygon(polygon, plane_point, plane_normal))
+         .collect();
+   }
+
+   polygons
+}
+
+/// Splits a convex polygon into 2 convex polygons by a plane.
+/// The order of vertexes is kept.
+fn split_polygon(
+   polygon: Vec<Point>,
+   plane_point: &Point,
+   plane_normal: &Vector
+) -> Vec<Vec<Point>> {
+   let d: Vec<_> = polygon.iter()
+      .map(|p| signed_distance(p, plane_point, plane_normal))
+      .collect();
+
+   let has_front = d.iter().any(|&d| d > EPSILON);
+   let has_back = d.iter().any(|&d| d < -EPSILON);
+   if !has_front || !has_back {
+      return vec![polygon];
+   }
+
+   let mut front = vec![];
+   let mut back = vec![];
+
+   for i in 0..polygon.len() {
+      let j = (i + 1) % polygon.len();
+      let (pi, pj) = (polygon[i], polygon[j]);
+
+      if d[i] >= -EPSILON {
+         front.push(pi);
+      }
+      if d[i] <= EPSILON {
+         back.push(pi);
+      }
+
+      if (d[i] > EPSILON && d[j] < -EPSILON) || (d[i] < -EPSILON && d[j] > EPSILON) {
+         let p = interpolate(&pi, &pj, d[i], d[j]);
+         front.push(p);
+         back.push(p);
+      }
+   }
+
+   [front, back].into_iter()
+      .filter(|polygon| polygon.len() >= 3)
+      .collect()
+}
+
+/// The point between `p` and `q` on the plane, where `dp` and `dq` are the
+/// signed distances of `p` and `q` from the plane.
+fn interpolate(p: &Point, q: &Point, dp: Size, dq: Size) -> Point {
+   let t = dp / (dp - dq);
+   p.translated_toward(&Vector::between(p, q), p.distance(q) * t)
+}
+
+fn triangulate(polygon: &[Point]) -> Vec<Facet> {
+   (1..(polygon.len() - 1))
+      .map(|i| Facet::new([polygon[0], polygon[i], polygon[i + 1]]))
+      .filter(|f| !is_degenerate(f))
+      .collect()
+}
+
+fn centroid(polygon: &[Point]) -> Point {
+   let n = polygon.len();
+   Point::new(
+      polygon.iter().map(|p| p.x()).sum::<Size>() / n,
+      polygon.iter().map(|p| p.y()).sum::<Size>() / n,
+      polygon.iter().map(|p| p.z()).sum::<Size>() / n
+   )
+}
+
+fn reversed(facet: &Facet) -> Facet {
+   let [a, b, c] = facet.vertexes();
+   Facet::new([a, c, b])
+}
+
+/// Whether the facet has almost no area.
+pub(crate) fn is_degenerate(facet: &Facet) -> bool {
+   let [a, b, c] = facet.vertexes();
+   let cross = Vector::between(&a, &b).vector_product(&Vector::between(&a, &c));
+
+   // vector_product returns Vector whose components are actually mm²
+   cross.norm().to_millimeter() <= EPSILON.to_millimeter().powi(2)
+}
+
+fn distances(facet: &Facet, plane_point: &Point, plane_normal: &Vector) -> [Size; 3] {
+   facet.vertexes().map(|v| signed_distance(&v, plane_point, plane_normal))
+}
+
+/// `plane_normal` must be a unit vector.
+fn signed_distance(point: &Point, plane_point: &Point, plane_normal: &Vector) -> Size {
+   Size::from(
+      Vector::between(plane_point, point).inner_product(plane_normal)
+         / plane_normal.norm()
+   )
+}
+
+#[cfg(test)]
+mod tests {
+   use super::{crosses, difference, intersection, union};
+   use crate::geometry::{Point, SizeLiteral};
+   use crate::math::unit::Exp;
+   use crate::solid::{Cube, Solid};
+   use crate::stl::{Facet, StlSolid};
+   use noisy_float::prelude::*;
+
+   fn cube_solid(min: (f64, f64, f64), size: f64) -> StlSolid {
+      Cube::from_corners(
+         &Point::new(min.0.mm(), min.1.mm(), min.2.mm()),
+         &Point::new((min.0 + size).mm(), (min.1 + size).mm(), (min.2 + size).mm())
+      ).generate_stl_solid()
+   }
+
+   #[test]
+   fn crosses_facets() {
+      let a = Facet::new([
+         Point::new(-1.mm(), -1.mm(), 0.mm()),
+         Point::new( 1.mm(), -1.mm(), 0.mm()),
+         Point::new( 0.mm(),  1.mm(), 0.mm())
+      ]);
+
+      let b = Facet::new([
+         Point::new(0.mm(), 0.mm(), -1.mm()),
+         Point::new(0.mm(), 0.mm(),  1.mm()),
+         Point::new(0.mm(), 2.mm(),  0.mm())
+      ]);
+      assert!(crosses(&a, &b));
+
+      // sharing an edge
+      let b = Facet::new([
+         Point::new(1.mm(), -1.mm(), 0.mm()),
+         Point::new(-1.mm(), -1.mm(), 0.mm()),
+         Point::new(0.mm(), -2.mm(), 1.mm())
+      ]);
+      assert!(!crosses(&a, &b));
+
+      // coplanar
+      let b = Facet::new([
+         Point::new(0.mm(), 0.mm(), 0.mm()),
+         Point::new(2.mm(), 0.mm(), 0.mm()),
+         Point::new(2.mm(), 2.mm(), 0.mm())
+      ]);
+      assert!(crosses(&a, &b));
+
+      let b = Facet::new([
+         Point::new(5.mm(), 0.mm(), 0.mm()),
+         Point::new(7.mm(), 0.mm(), 0.mm()),
+         Point::new(7.mm(), 2.mm(), 0.mm())
+      ]);
+      assert!(!crosses(&a, &b));
+   }
+
+   #[test]
+   fn union_of_cubes() {
+      let a = cube_solid((0.0, 0.0, 0.0), 2.0);
+      let b = cube_solid((1.0, 1.0, 1.0), 2.0);
+      let solid = union(&a, &b);
+
+      assert_eq!(solid.volume(), unsafe { Exp::new(n64(8.0 + 8.0 - 1.0)) });
+      assert!(solid.intersecting_facet_pairs().is_empty());
+   }
+
+   #[test]
+   fn union_of_disjoint_cubes() {
+      let a = cube_solid((0.0, 0.0, 0.0), 1.0);
+      let b = cube_solid((3.0, 0.0, 0.0), 1.0);
+      let solid = union(&a, &b);
+
+      assert_eq!(solid.facet_count(), 24);
+      assert_eq!(solid.volume(), unsafe { Exp::new(n64(2.0)) });
+   }
+
+   #[test]
+   fn union_of_cubes_sharing_faces() {
+      let a = cube_solid((0.0, 0.0, 0.0), 2.0);
+      let b = cube_solid((1.0, 0.0, 0.0), 2.0);
+      let solid = union(&a, &b);
+
+      assert_eq!(solid.volume(), unsafe { Exp::new(n64(12.0)) });
+      assert!(solid.intersecting_facet_pairs().is_empty());
+   }
+
+   #[test]
+   fn difference_of_cubes() {
+      let a = cube_solid((0.0, 0.0, 0.0), 2.0);
+      let b = cube_solid((1.0, 1.0, 1.0), 2.0);
+      let solid = difference(&a, &b);
+
+      assert_eq!(solid.volume(), unsafe { Exp::new(n64(7.0)) });
+      assert!(solid.intersecting_facet_pairs().is_empty());
+   }
+
+   #[test]
+   fn intersection_of_cubes() {
+      let a = cube_solid((0.0, 0.0, 0.0), 2.0);
+      let b = cube_solid((1.0, 1.0, 1.0), 2.0);
+      let solid = intersection(&a, &b);
+
+      assert_eq!(solid.volume(), unsafe { Exp::new(n64(1.0)) });
+      assert!(solid.intersecting_facet_pairs().is_empty());
+   }
+}
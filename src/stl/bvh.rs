@@ -0,0 +1,336 @@
+use crate::geometry::{BoundingBox, Point, Size, Vector};
+use crate::math::unit::Exp;
+use crate::stl::Facet;
+use crate::transform::Transform;
+use noisy_float::prelude::*;
+
+/// Operations of [BoundingBox] for [Bvh].
+impl BoundingBox {
+   pub(crate) fn of_point(point: &Point) -> BoundingBox {
+      BoundingBox::new(*point, *point)
+   }
+
+   pub(crate) fn of_facet(facet: &Facet) -> BoundingBox {
+      BoundingBox::of_points(facet.vertexes()).unwrap()
+   }
+
+   pub(crate) fn expanded(&self, margin: Size) -> BoundingBox {
+      let margin = Vector::new(margin, margin, margin);
+      BoundingBox::new(self.min.translated(&-margin), self.max.translated(&margin))
+   }
+
+   /// Distance between the 2 boxes. 0 if they intersect.
+   pub(crate) fn distance(&self, other: &BoundingBox) -> Size {
+      let gaps = (0..3).map(|axis| {
+         let gap = Ord::max(
+            self.min.matrix.0[axis][0] - other.max.matrix.0[axis][0],
+            other.min.matrix.0[axis][0] - self.max.matrix.0[axis][0]
+         );
+         Ord::max(gap, Size::ZERO)
+      });
+
+      gaps.map(|g| g.sq()).sum::<Exp<Size, 2>>().sqrt()
+   }
+
+   /// Whether the ray from `origin` toward `direction` passes this box.
+   fn intersects_ray(&self, origin: &Point, direction: &Vector) -> bool {
+      let mut near = n64(f64::NEG_INFINITY);
+      let mut far = n64(f64::INFINITY);
+
+      for axis in 0..3 {
+         let o = origin.matrix.0[axis][0];
+         let d = direction.matrix.0[axis][0].to_millimeter();
+
+         if d == 0.0 {
+            // parallel to the slab
+            if o < self.min.matrix.0[axis][0] || o > self.max.matrix.0[axis][0] {
+               return false;
+            }
+            continue;
+         }
+
+         let a = (self.min.matrix.0[axis][0] - o).to_millimeter() / d;
+         let b = (self.max.matrix.0[axis][0] - o).to_millimeter() / d;
+
+         near = Ord::max(near, Ord::min(a, b));
+         far = Ord::min(far, Ord::max(a, b));
+      }
+
+      near <= far && far >= 0.0
+   }
+
+   fn longest_axis(&self) -> usize {
+      let size = Vector::between(&self.min, &self.max);
+      if size.x() >= size.y() && size.x() >= size.z() {
+         0
+      } else if size.y() >= size.z() {
+         1
+      } else {
+         2
+      }
+   }
+}
+
+/// Bounding volume hierarchy over facets, to find facets near a region
+/// without checking every facet.
+pub(crate) struct Bvh {
+   aabbs: Vec<BoundingBox>,
+   root: Option<BvhNode>
+}
+
+enum BvhNode {
+   Leaf {
+      aabb: BoundingBox,
+      facet_indexes: Vec<usize>
+   },
+   Branch {
+      aabb: BoundingBox,
+      children: Box<(BvhNode, BvhNode)>
+   }
+}
+
+const LEAF_SIZE: usize = 4;
+
+impl Bvh {
+   pub(crate) fn new(facets: &[Facet]) -> Bvh {
+      let aabbs: Vec<_> = facets.iter().map(BoundingBox::of_facet).collect();
+      let indexes: Vec<_> = (0..facets.len()).collect();
+
+      let root = if indexes.is_empty() {
+         None
+      } else {
+         Some(BvhNode::new(&aabbs, indexes))
+      };
+
+      Bvh { aabbs, root }
+   }
+
+   /// Indexes of the facets whose bounding box intersects `aabb`.
+   pub(crate) fn query(&self, aabb: &BoundingBox) -> Vec<usize> {
+      let mut result = vec![];
+      if let Some(root) = &self.root {
+         root.visit(
+            &self.aabbs,
+            &|node_aabb| node_aabb.intersects(aabb),
+            &mut result
+         );
+      }
+      result
+   }
+
+   /// Indexes of the facets whose bounding box the ray passes.
+   pub(crate) fn query_ray(&self, origin: &Point, direction: &Vector) -> Vec<usize> {
+      let mut result = vec![];
+      if let Some(root) = &self.root {
+         root.visit(
+            &self.aabbs,
+            &|node_aabb| node_aabb.intersects_ray(origin, direction),
+            &mut result
+         );
+      }
+      result
+   }
+
+   /// The nearest facet from `point` by `distance` to each facet, and its
+   /// index. Nodes farther than the nearest facet found so far are skipped.
+   pub(crate) fn nearest(
+      &self,
+      point: &Point,
+      distance: &dyn Fn(usize) -> Size
+   ) -> Option<(Size, usize)> {
+      let mut best = None;
+      if let Some(root) = &self.root {
+         root.nearest(&BoundingBox::of_point(point), distance, &mut best);
+      }
+      best
+   }
+
+   /// The nearest pair of facets of this and `other` by `distance` between
+   /// them, and their indexes.
+   pub(crate) fn nearest_pair(
+      &self,
+      other: &Bvh,
+      distance: &dyn Fn(usize, usize) -> Size
+   ) -> Option<(Size, usize, usize)> {
+      let mut best = None;
+      if let (Some(a), Some(b)) = (&self.root, &other.root) {
+         BvhNode::nearest_pair(a, b, distance, &mut best);
+      }
+      best
+   }
+}
+
+impl BvhNode {
+   fn aabb(&self) -> &BoundingBox {
+      match self {
+         BvhNode::Leaf { aabb, .. } => aabb,
+         BvhNode::Branch { aabb, .. } => aabb
+      }
+   }
+
+   fn new(aabbs: &[BoundingBox], mut indexes: Vec<usize>) -> BvhNode {
+      let aabb = indexes[1..].iter()
+         .fold(aabbs[indexes[0]], |acc, &i| acc.union(&aabbs[i]));
+
+      if indexes.len() <= LEAF_SIZE {
+         return BvhNode::Leaf { aabb, facet_indexes: indexes };
+      }
+
+      let axis = aabb.longest_axis();
+      let center = |i: &usize| {
+         let a = &aabbs[*i];
+         a.min.matrix.0[axis][0] + a.max.matrix.0[axis][0]
+      };
+      indexes.sort_by_key(center);
+
+      let right = indexes.split_off(indexes.len() / 2);
+      let children = Box::new((
+         BvhNode::new(aabbs, indexes),
+         BvhNode::new(aabbs, right)
+      ));
+
+      BvhNode::Branch { aabb, children }
+   }
+
+   fn visit(
+      &self,
+      aabbs: &[BoundingBox],
+      hits: &dyn Fn(&BoundingBox) -> bool,
+      result: &mut Vec<usize>
+   ) {
+      match self {
+         BvhNode::Leaf { aabb, facet_indexes } => {
+            if hits(aabb) {
+               result.extend(
+                  facet_indexes.iter().filter(|&&i| hits(&aabbs[i]))
+               );
+            }
+         }
+         BvhNode::Branch { aabb, children } => {
+            if hits(aabb) {
+               children.0.visit(aabbs, hits, result);
+               children.1.visit(aabbs, hits, result);
+            }
+         }
+      }
+   }
+   fn nearest(
+      &self,
+      target: &BoundingBox,
+      distance: &dyn Fn(usize) -> Size,
+      best: &mut Option<(Size, usize)>
+   ) {
+      if best.is_some_and(|(d, _)| self.aabb().distance(target) >= d) {
+         return;
+      }
+
+      match self {
+         BvhNode::Leaf { facet_indexes, .. } => {
+            for &i in facet_indexes {
+               let d = distance(i);
+               if best.is_none_or(|(b, _)| d < b) {
+                  *best = Some((d, i));
+               }
+            }
+         }
+         BvhNode::Branch { children, .. } => {
+            let (a, b) = (&children.0, &children.1);
+            let (near, far) = if a.aabb().distance(target) <= b.aabb().distance(target) {
+               (a, b)
+            } else {
+               (b, a)
+            };
+            near.nearest(target, distance, best);
+            far.nearest(target, distance, best);
+         }
+      }
+   }
+
+   fn nearest_pair(
+      a: &BvhNode,
+      b: &BvhNode,
+      distance: &dyn Fn(usize, usize) -> Size,
+      best: &mut Option<(Size, usize, usize)>
+   ) {
+      if best.is_some_and(|(d, ..)| a.aabb().distance(b.aabb()) >= d) {
+         return;
+      }
+
+      match (a, b) {
+         (
+            BvhNode::Leaf { facet_indexes: a_indexes, .. },
+            BvhNode::Leaf { facet_indexes: b_indexes, .. }
+         ) => {
+            for &i in a_indexes {
+               for &j in b_indexes {
+                  let d = distance(i, j);
+                  if best.is_none_or(|(b, ..)| d < b) {
+                     *best = Some((d, i, j));
+                  }
+               }
+            }
+         }
+         (BvhNode::Branch { children, .. }, _) => {
+            let (c, d) = (&children.0, &children.1);
+            let (near, far) = if c.aabb().distance(b.aabb()) <= d.aabb().distance(b.aabb()) {
+               (c, d)
+            } else {
+               (d, c)
+            };
+            BvhNode::nearest_pair(near, b, distance, best);
+            BvhNode::nearest_pair(far, b, distance, best);
+         }
+         (_, BvhNode::Branch { children, .. }) => {
+            let (c, d) = (&children.0, &children.1);
+            let (near, far) = if a.aabb().distance(c.aabb()) <= a.aabb().distance(d.aabb()) {
+               (c, d)
+            } else {
+               (d, c)
+            };
+            BvhNode::nearest_pair(a, near, distance, best);
+            BvhNode::nearest_pair(a, far, distance, best);
+         }
+      }
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::{BoundingBox, Bvh};
+   use crate::geometry::{Point, SizeLiteral, Vector};
+   use crate::solid::{cube, sphere, Location, Solid};
+
+   #[test]
+   fn query() {
+      let solid = cube(Location::default(), (1.mm(), 1.mm(), 1.mm()))
+         .generate_stl_solid();
+      let bvh = Bvh::new(&solid.facets);
+
+      let aabb = BoundingBox::of_point(&Point::new(0.5.mm(), 0.5.mm(), 1.mm()));
+      let mut actual = bvh.query(&aabb);
+      actual.sort();
+
+      let mut expected: Vec<_> = solid.facets.iter().enumerate()
+         .filter(|(_, f)| f.vertexes().iter().all(|v| v.z() == 1.mm()))
+         .map(|(i, _)| i)
+         .collect();
+      expected.sort();
+
+      assert_eq!(actual, expected);
+   }
+
+   #[test]
+   fn query_ray() {
+      let solid = sphere(Location::default(), 3.mm()).generate_stl_solid();
+      let bvh = Bvh::new(&solid.facets);
+
+      let direction = Vector::new(1.mm(), 1e-3.mm(), 1e-3.mm());
+      let origin = Point::new(10.mm(), 0.mm(), 0.mm());
+      assert!(bvh.query_ray(&origin, &direction).is_empty());
+
+      let origin = Point::new(-10.mm(), 0.mm(), 0.mm());
+      let actual = bvh.query_ray(&origin, &direction);
+      assert!(!actual.is_empty());
+      assert!(actual.len() < solid.facets.len());
+   }
+}
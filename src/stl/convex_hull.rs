@@ -0,0 +1,243 @@
+use crate::geometry::{Point, Size, Vector};
+use crate::stl::boolean::EPSILON;
+use crate::stl::{Facet, StlSolid};
+use anyhow::Result;
+use std::collections::HashSet;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ConvexHullError {
+   #[error("A convex hull needs at least 4 points, but {0} points are given.")]
+   TooFewPoints(usize),
+   #[error("All points are on a line. A convex hull must have a volume.")]
+   Collinear,
+   #[error("All points are on a plane. A convex hull must have a volume.")]
+   Coplanar
+}
+
+/// The smallest convex solid which contains all the specified points.
+///
+/// Computed by Quickhull. Points on the surface of the hull are not
+/// necessarily used as vertexes. Returns [ConvexHullError] when the points
+/// don't make a solid with a volume.
+pub fn convex_hull(points: &[Point]) -> Result<StlSolid> {
+   let mut points = points.to_vec();
+   points.sort_by(|a, b|
+      a.x().cmp(&b.x())
+         .then(a.y().cmp(&b.y()))
+         .then(a.z().cmp(&b.z()))
+   );
+   points.dedup();
+
+   if points.len() < 4 {
+      return Err(ConvexHullError::TooFewPoints(points.len()).into());
+   }
+
+   let mut faces = initial_simplex(&points)?;
+
+   for (i, p) in points.iter().enumerate() {
+      assign(&mut faces, i, p);
+   }
+
+   while let Some(face_index) = faces.iter()
+      .position(|f| f.alive && !f.outside.is_empty())
+   {
+      let face = &faces[face_index];
+      let apex_index = *face.outside.iter()
+         .max_by_key(|&&i| face.distance(&points[i]))
+         .unwrap();
+      let apex = points[apex_index];
+
+      let visible: Vec<_> = (0..faces.len())
+         .filter(|&i| faces[i].alive && faces[i].distance(&apex) > EPSILON)
+         .collect();
+
+      let visible_edges: HashSet<_> = visible.iter()
+         .flat_map(|&i| faces[i].edges())
+         .collect();
+
+      let horizon: Vec<_> = visible.iter()
+         .flat_map(|&i| faces[i].edges())
+         .filter(|(a, b)| !visible_edges.contains(&(*b, *a)))
+         .collect();
+
+      let mut orphans = vec![];
+      for &i in &visible {
+         faces[i].alive = false;
+         orphans.append(&mut faces[i].outside);
+      }
+
+      let first_new_face = faces.len();
+      for (a, b) in horizon {
+         faces.push(Face::new(&points, [a, b, apex_index]));
+      }
+
+      for i in orphans {
+         if i != apex_index {
+            assign(&mut faces[first_new_face..], i, &points[i]);
+         }
+      }
+   }
+
+   let facets = faces.iter()
+      .filter(|f| f.alive)
+      .map(|f| Facet::new(f.vertexes.map(|i| points[i])))
+      .collect();
+
+   Ok(StlSolid::new(facets))
+}
+
+struct Face {
+   vertexes: [usize; 3],
+   point: Point,
+   normal: Vector,
+   /// Indexes of the points in front of this face which are not yet in the hull.
+   outside: Vec<usize>,
+   alive: bool
+}
+
+impl Face {
+   fn new(points: &[Point], vertexes: [usize; 3]) -> Face {
+      let [a, b, c] = vertexes.map(|i| points[i]);
+      let normal = Vector::between(&a, &b)
+         .vector_product(&Vector::between(&a, &c))
+         .to_unit_vector();
+
+      Face { vertexes, point: a, normal, outside: vec![], alive: true }
+   }
+
+   fn distance(&self, point: &Point) -> Size {
+      Size::from(
+         Vector::between(&self.point, point).inner_product(&self.normal)
+            / self.normal.norm()
+      )
+   }
+
+   fn edges(&self) -> [(usize, usize); 3] {
+      let [a, b, c] = self.vertexes;
+      [(a, b), (b, c), (c, a)]
+   }
+}
+
+/// Adds the point to the outside list of the first face which the point is
+/// in front of. Does nothing when the point is behind all the faces.
+fn assign(faces: &mut [Face], index: usize, point: &Point) {
+   let face = faces.iter_mut()
+      .find(|f| f.alive && f.distance(point) > EPSILON);
+
+   if let Some(face) = face {
+      face.outside.push(index);
+   }
+}
+
+fn initial_simplex(points: &[Point]) -> Result<Vec<Face>> {
+   let a = 0;
+
+   let b = (1..points.len())
+      .max_by_key(|&i| points[a].distance(&points[i]))
+      .unwrap();
+
+   let ab = Vector::between(&points[a], &points[b]);
+   let distance_from_ab = |p: &Point| {
+      ab.vector_product(&Vector::between(&points[a], p)).norm().to_millimeter()
+         / ab.norm().to_millimeter()
+   };
+   let c = (0..points.len())
+      .max_by_key(|&i| distance_from_ab(&points[i]))
+      .unwrap();
+   if distance_from_ab(&points[c]) <= EPSILON.to_millimeter() {
+      return Err(ConvexHullError::Collinear.into());
+   }
+
+   let abc = Face::new(points, [a, b, c]);
+   let d = (0..points.len())
+      .max_by_key(|&i| abc.distance(&points[i]).abs())
+      .unwrap();
+   if abc.distance(&points[d]).abs() <= EPSILON {
+      return Err(ConvexHullError::Coplanar.into());
+   }
+
+   // make every face turn its front away from d or the opposite vertex
+   let (b, c) = if abc.distance(&points[d]) > Size::ZERO {
+      (c, b)
+   } else {
+      (b, c)
+   };
+
+   Ok(vec![
+      Face::new(points, [a, b, c]),
+      Face::new(points, [a, d, b]),
+      Face::new(points, [b, d, c]),
+      Face::new(points, [c, d, a])
+   ])
+}
+
+#[cfg(test)]
+mod tests {
+   use super::{convex_hull, ConvexHullError};
+   use crate::geometry::{Point, SizeLiteral, Vector};
+   use crate::math::unit::Exp;
+   use crate::solid::{cube, Location, Solid};
+   use noisy_float::prelude::*;
+
+   #[test]
+   fn cube_vertexes() {
+      let cube = cube(Location::default(), (1.mm(), 2.mm(), 3.mm()));
+      let mut vertexes = cube.unique_vertexes();
+      vertexes.push(Point::new(0.5.mm(), 1.mm(), 1.5.mm()));
+
+      let hull = convex_hull(&vertexes).unwrap();
+      assert_eq!(hull.facet_count(), 12);
+      assert_eq!(hull.volume(), unsafe { Exp::new(n64(6.0)) });
+      assert!(hull.intersecting_facet_pairs().is_empty());
+   }
+
+   #[test]
+   fn normal_vector() {
+      let points = [
+         Point::ORIGIN,
+         Point::new(1.mm(), 0.mm(), 0.mm()),
+         Point::new(0.mm(), 1.mm(), 0.mm()),
+         Point::new(0.mm(), 0.mm(), 1.mm())
+      ];
+      let hull = convex_hull(&points).unwrap();
+
+      let center = Point::new(0.25.mm(), 0.25.mm(), 0.25.mm());
+      for f in hull.facets() {
+         let outward = Vector::between(&center, &f.vertexes()[0]);
+         assert!(f.normal_vector().inner_product(&outward).0 > 0.0);
+      }
+   }
+
+   #[test]
+   fn degenerate() {
+      let error = |points: &[Point]| {
+         convex_hull(points).err().unwrap()
+            .downcast::<ConvexHullError>().unwrap()
+      };
+
+      let points = [
+         Point::ORIGIN,
+         Point::new(1.mm(), 0.mm(), 0.mm()),
+         Point::new(1.mm(), 0.mm(), 0.mm()),
+         Point::new(0.mm(), 1.mm(), 0.mm())
+      ];
+      assert_eq!(error(&points), ConvexHullError::TooFewPoints(3));
+
+      let points = [
+         Point::ORIGIN,
+         Point::new(1.mm(), 1.mm(), 1.mm()),
+         Point::new(2.mm(), 2.mm(), 2.mm()),
+         Point::new(3.mm(), 3.mm(), 3.mm())
+      ];
+      assert_eq!(error(&points), ConvexHullError::Collinear);
+
+      let points = [
+         Point::ORIGIN,
+         Point::new(1.mm(), 0.mm(), 0.mm()),
+         Point::new(0.mm(), 1.mm(), 0.mm()),
+         Point::new(1.mm(), 1.mm(), 0.mm())
+      ];
+      assert_eq!(error(&points), ConvexHullError::Coplanar);
+   }
+}
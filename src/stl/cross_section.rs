@@ -0,0 +1,230 @@
+use crate::geometry::{Plane, Point, Polygon2D, Size, Vector};
+use crate::stl::{Facet, StlSolid};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+/// Result of [StlSolid::cross_section].
+///
+/// Coordinates are measured along [Plane::basis] from [Plane::point].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CrossSection {
+   /// Closed outlines. Counterclockwise for outer borders and clockwise for
+   /// holes, seen from the side which the normal vector of the plane points
+   /// to.
+   pub polygons: Vec<Polygon2D>,
+   /// Outlines which could not be closed, because the solid is not
+   /// watertight.
+   pub open_chains: Vec<Vec<(Size, Size)>>
+}
+
+impl StlSolid {
+   /// Cuts this solid by the plane, and returns the outlines of the cut
+   /// surface.
+   pub fn cross_section(&self, plane: &Plane) -> CrossSection {
+      let segments: Vec<(Point, Point)> = self.facets.iter()
+         .filter_map(|f| facet_segment(f, plane))
+         .collect();
+
+      let mut starts: HashMap<Point, Vec<usize>> = HashMap::new();
+      for (i, (start, _)) in segments.iter().enumerate() {
+         starts.entry(*start).or_default().push(i);
+      }
+      let ends: HashSet<Point> = segments.iter().map(|(_, end)| *end).collect();
+
+      let mut used = vec![false; segments.len()];
+      let walk = |first: usize, used: &mut Vec<bool>| -> (Vec<Point>, bool) {
+         let start = segments[first].0;
+         let mut chain = vec![start];
+         let mut current = first;
+
+         loop {
+            used[current] = true;
+            let end = segments[current].1;
+            if end == start {
+               return (chain, true);
+            }
+            chain.push(end);
+
+            let next = starts.get(&end)
+               .and_then(|c| c.iter().find(|&&j| !used[j]));
+
+            match next {
+               Some(&j) => current = j,
+               None => return (chain, false)
+            }
+         }
+      };
+
+      // chains which start where no segment ends are never closed. Walk them
+      // first so that the rest are all loops
+      let heads: Vec<usize> = (0..segments.len())
+         .filter(|&i| !ends.contains(&segments[i].0))
+         .collect();
+
+      let mut chains: Vec<(Vec<Point>, bool)> = heads.into_iter()
+         .map(|i| walk(i, &mut used))
+         .collect();
+
+      for i in 0..segments.len() {
+         if !used[i] {
+            chains.push(walk(i, &mut used));
+         }
+      }
+
+      let origin = plane.point();
+      let (u, v) = plane.basis();
+      let project = |p: &Point| {
+         let offset = Vector::between(&origin, p);
+         (
+            Size::from(offset.inner_product(&u) / u.norm()),
+            Size::from(offset.inner_product(&v) / v.norm())
+         )
+      };
+
+      let mut cross_section = CrossSection::default();
+      for (chain, closed) in chains {
+         let vertexes: Vec<_> = simplify(chain, closed).iter().map(project).collect();
+
+         if closed {
+            cross_section.polygons.push(Polygon2D::new(vertexes));
+         } else {
+            cross_section.open_chains.push(vertexes);
+         }
+      }
+
+      cross_section
+   }
+}
+
+/// The segment where the facet crosses the plane. Directed so that
+/// the solid is on the left, seen from the side which the normal vector of
+/// the plane points to.
+fn facet_segment(facet: &Facet, plane: &Plane) -> Option<(Point, Point)> {
+   // vertexes on the plane are counted as above, so that an edge on the plane
+   // is taken only from the facet below it
+   let above = facet.vertexes().map(|v| plane.side_of(&v) != Ordering::Less);
+   if above.iter().all(|&a| a) || above.iter().all(|&a| !a) {
+      return None;
+   }
+
+   // always interpolate from the vertex above, so that adjacent facets get
+   // the same point on their common edge
+   let points: Vec<Point> = (0..3)
+      .filter_map(|i| {
+         let j = (i + 1) % 3;
+         let (p, q) = (facet.vertexes()[i], facet.vertexes()[j]);
+         match (above[i], above[j]) {
+            (true, false) => Some(plane.segment_intersection(&p, &q)),
+            (false, true) => Some(plane.segment_intersection(&q, &p)),
+            _ => None
+         }
+      })
+      .collect();
+
+   let (p, q) = (points[0], points[1]);
+   if p == q {
+      return None;
+   }
+
+   let direction = plane.normal_vector().vector_product(&facet.normal_vector());
+   if Vector::between(&p, &q).inner_product(&direction).0 >= 0.0 {
+      Some((p, q))
+   } else {
+      Some((q, p))
+   }
+}
+
+/// Removes vertexes on straight edges.
+fn simplify(chain: Vec<Point>, closed: bool) -> Vec<Point> {
+   let mut simplified: Vec<Point> = vec![];
+   for p in chain {
+      while let [.., a, b] = simplified[..] {
+         if !Point::are_collinear(&a, &b, &p) {
+            break;
+         }
+         simplified.pop();
+      }
+      simplified.push(p);
+   }
+
+   if closed {
+      while simplified.len() >= 3 {
+         let n = simplified.len();
+         if Point::are_collinear(&simplified[n - 2], &simplified[n - 1], &simplified[0]) {
+            simplified.pop();
+         } else if Point::are_collinear(&simplified[n - 1], &simplified[0], &simplified[1]) {
+            simplified.remove(0);
+         } else {
+            break;
+         }
+      }
+   }
+
+   simplified
+}
+
+#[cfg(test)]
+mod tests {
+   use crate::geometry::{Plane, Point, SizeLiteral, Vector};
+   use crate::solid::{cube, cylinder, difference, Location, Solid};
+   use crate::transform::Transform;
+
+   fn z_plane(z: f64) -> Plane {
+      Plane::new(&Point::new(0.mm(), 0.mm(), z.mm()), &Vector::Z_UNIT_VECTOR)
+   }
+
+   #[test]
+   fn square() {
+      let stl_solid = cube(Location::default(), (10.mm(), 10.mm(), 10.mm()))
+         .generate_stl_solid();
+      let cross_section = stl_solid.cross_section(&z_plane(5.0));
+
+      assert!(cross_section.open_chains.is_empty());
+      assert_eq!(cross_section.polygons.len(), 1);
+
+      let square = &cross_section.polygons[0];
+      assert_eq!(square.vertexes.len(), 4);
+      assert_eq!(square.signed_area(), 10.mm() * 10.mm());
+      assert_eq!(square.perimeter(), 40.mm());
+   }
+
+   #[test]
+   fn tube() {
+      let tube = difference(|mut d| {
+         d <<= cylinder(Location::default(), 10.mm(), 5.mm());
+         d <<= cylinder(Location::default(), 12.mm(), 3.mm())
+            .translated(&Vector::new(0.mm(), 0.mm(), -1.mm()));
+      });
+      let cross_section = tube.generate_stl_solid().cross_section(&z_plane(4.0));
+
+      assert!(cross_section.open_chains.is_empty());
+      assert_eq!(cross_section.polygons.len(), 2);
+
+      let mut polygons = cross_section.polygons;
+      polygons.sort_by_key(|p| p.signed_area().0);
+      let (inner, outer) = (&polygons[0], &polygons[1]);
+
+      assert!(inner.signed_area().0 < 0.0);
+      assert!(outer.signed_area().0 > 0.0);
+      for (x, y) in &inner.vertexes {
+         assert_eq!((x.sq() + y.sq()).sqrt(), 3.mm());
+      }
+      for (x, y) in &outer.vertexes {
+         assert_eq!((x.sq() + y.sq()).sqrt(), 5.mm());
+      }
+   }
+
+   #[test]
+   fn open_chain() {
+      let mut stl_solid = cube(Location::default(), (10.mm(), 10.mm(), 10.mm()))
+         .generate_stl_solid();
+      let side = stl_solid.facets.iter()
+         .position(|f| f.normal_vector() == -Vector::X_UNIT_VECTOR)
+         .unwrap();
+      stl_solid.facets.remove(side);
+
+      let cross_section = stl_solid.cross_section(&z_plane(5.0));
+      assert!(cross_section.polygons.is_empty());
+      assert_eq!(cross_section.open_chains.len(), 1);
+   }
+}
@@ -0,0 +1,276 @@
+use crate::geometry::{Point, Size, Vector};
+use crate::stl::{Facet, SolidBvh, StlSolid};
+use crate::stl::boolean::{crosses, is_degenerate};
+use crate::transform::Transform;
+
+impl StlSolid {
+   /// Distance from the point to the nearest facet.
+   /// See [SolidBvh::distance_to_point].
+   pub fn distance_to_point(&self, point: &Point) -> Size {
+      self.build_bvh().distance_to_point(point)
+   }
+
+   /// Distance from the point to the surface, negative inside.
+   /// See [SolidBvh::signed_distance_to_point].
+   pub fn signed_distance_to_point(&self, point: &Point) -> Size {
+      self.build_bvh().signed_distance_to_point(point)
+   }
+
+   /// The minimum distance between the surfaces of the 2 solids.
+   /// See [SolidBvh::min_distance_to].
+   pub fn min_distance_to(&self, other: &StlSolid) -> Size {
+      self.build_bvh().min_distance_to(&other.build_bvh())
+   }
+}
+
+impl SolidBvh<'_> {
+   /// Distance from the point to the nearest facet.
+   /// [Size::INFINITY] for an empty solid.
+   pub fn distance_to_point(&self, point: &Point) -> Size {
+      let facets = &self.stl_solid.facets;
+      self.bvh
+         .nearest(point, &|i| facet_distance_to_point(&facets[i], point))
+         .map_or(Size::INFINITY, |(distance, _)| distance)
+   }
+
+   /// Distance from the point to the surface, negative inside the closed
+   /// solid. Panics in the same cases as [contains_point].
+   ///
+   /// [contains_point]: SolidBvh::contains_point
+   pub fn signed_distance_to_point(&self, point: &Point) -> Size {
+      let distance = self.distance_to_point(point);
+      if self.contains_point(point) { -distance } else { distance }
+   }
+
+   /// The minimum distance between the surfaces of the 2 solids. 0 if they
+   /// touch or cross. [Size::INFINITY] if either is empty.
+   ///
+   /// Note that this is the clearance between the surfaces. A solid entirely
+   /// inside the other is apart from its surface.
+   pub fn min_distance_to(&self, other: &SolidBvh) -> Size {
+      let a = &self.stl_solid.facets;
+      let b = &other.stl_solid.facets;
+      self.bvh
+         .nearest_pair(&other.bvh, &|i, j| facet_distance(&a[i], &b[j]))
+         .map_or(Size::INFINITY, |(distance, ..)| distance)
+   }
+}
+
+fn dot(a: &Vector, b: &Vector) -> f64 {
+   a.inner_product(b).0.raw()
+}
+
+fn facet_distance_to_point(facet: &Facet, point: &Point) -> Size {
+   closest_point_on_facet(facet, point).distance(point)
+}
+
+/// The point on the facet nearest from `p`, by the regions of the vertexes
+/// and the edges. (Ericson, Real-Time Collision Detection, 5.1.5)
+fn closest_point_on_facet(facet: &Facet, p: &Point) -> Point {
+   let [a, b, c] = facet.vertexes();
+
+   if is_degenerate(facet) {
+      return [(a, b), (b, c), (c, a)]
+         .map(|(s, t)| closest_point_on_segment(&s, &t, p))
+         .into_iter()
+         .min_by_key(|q| q.distance(p))
+         .unwrap();
+   }
+
+   let ab = Vector::between(&a, &b);
+   let ac = Vector::between(&a, &c);
+
+   let ap = Vector::between(&a, p);
+   let d1 = dot(&ab, &ap);
+   let d2 = dot(&ac, &ap);
+   if d1 <= 0.0 && d2 <= 0.0 {
+      return a;
+   }
+
+   let bp = Vector::between(&b, p);
+   let d3 = dot(&ab, &bp);
+   let d4 = dot(&ac, &bp);
+   if d3 >= 0.0 && d4 <= d3 {
+      return b;
+   }
+
+   let vc = d1 * d4 - d3 * d2;
+   if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+      return a.translated(&(ab * (d1 / (d1 - d3))));
+   }
+
+   let cp = Vector::between(&c, p);
+   let d5 = dot(&ab, &cp);
+   let d6 = dot(&ac, &cp);
+   if d6 >= 0.0 && d5 <= d6 {
+      return c;
+   }
+
+   let vb = d5 * d2 - d1 * d6;
+   if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+      return a.translated(&(ac * (d2 / (d2 - d6))));
+   }
+
+   let va = d3 * d6 - d5 * d4;
+   if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+      let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+      return b.translated(&(Vector::between(&b, &c) * w));
+   }
+
+   let denom = va + vb + vc;
+   a.translated(&(ab * (vb / denom) + ac * (vc / denom)))
+}
+
+fn closest_point_on_segment(s: &Point, t: &Point, p: &Point) -> Point {
+   let st = Vector::between(s, t);
+   let length = dot(&st, &st);
+   if length <= 0.0 {
+      return *s;
+   }
+
+   let ratio = (dot(&st, &Vector::between(s, p)) / length).clamp(0.0, 1.0);
+   s.translated(&(st * ratio))
+}
+
+/// The points on the 2 segments nearest from each other.
+/// (Ericson, Real-Time Collision Detection, 5.1.9)
+fn closest_points_of_segments(
+   (p1, q1): (&Point, &Point),
+   (p2, q2): (&Point, &Point)
+) -> (Point, Point) {
+   let d1 = Vector::between(p1, q1);
+   let d2 = Vector::between(p2, q2);
+   let r = Vector::between(p2, p1);
+   let a = dot(&d1, &d1);
+   let e = dot(&d2, &d2);
+   let f = dot(&d2, &r);
+
+   if a <= 0.0 {
+      return (*p1, closest_point_on_segment(p2, q2, p1));
+   }
+   if e <= 0.0 {
+      return (closest_point_on_segment(p1, q1, p2), *p2);
+   }
+
+   let b = dot(&d1, &d2);
+   let c = dot(&d1, &r);
+   let denom = a * e - b * b;
+
+   let mut s = if denom > 0.0 {
+      ((b * f - c * e) / denom).clamp(0.0, 1.0)
+   } else {
+      0.0
+   };
+
+   let mut t = (b * s + f) / e;
+   if t < 0.0 {
+      t = 0.0;
+      s = (-c / a).clamp(0.0, 1.0);
+   } else if t > 1.0 {
+      t = 1.0;
+      s = ((b - c) / a).clamp(0.0, 1.0);
+   }
+
+   (p1.translated(&(d1 * s)), p2.translated(&(d2 * t)))
+}
+
+/// The minimum distance between the 2 facets. Unless they cross, the nearest
+/// points are on a vertex and a facet, or on 2 edges.
+fn facet_distance(a: &Facet, b: &Facet) -> Size {
+   if crosses(a, b) {
+      return Size::ZERO;
+   }
+
+   let vertex_distances = a.vertexes().into_iter()
+      .map(|v| facet_distance_to_point(b, &v))
+      .chain(b.vertexes().into_iter().map(|v| facet_distance_to_point(a, &v)));
+
+   let edges = |f: &Facet| {
+      let [p, q, r] = f.vertexes();
+      [(p, q), (q, r), (r, p)]
+   };
+
+   let edge_distances = edges(a).into_iter()
+      .flat_map(|e| edges(b).map(|f| (e, f)))
+      .map(|((p1, q1), (p2, q2))| {
+         let (s, t) = closest_points_of_segments((&p1, &q1), (&p2, &q2));
+         s.distance(&t)
+      });
+
+   vertex_distances.chain(edge_distances).min().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+   use super::{closest_point_on_facet, facet_distance, facet_distance_to_point};
+   use crate::geometry::{Point, SizeLiteral, Vector};
+   use crate::solid::{cube, sphere, Location, Solid};
+   use crate::stl::Facet;
+   use crate::transform::Transform;
+
+   fn point(x: f64, y: f64, z: f64) -> Point {
+      Point::new(x.mm(), y.mm(), z.mm())
+   }
+
+   #[test]
+   fn closest_point() {
+      let facet = Facet::new([point(0.0, 0.0, 0.0), point(2.0, 0.0, 0.0), point(0.0, 2.0, 0.0)]);
+
+      assert_eq!(closest_point_on_facet(&facet, &point(0.5, 0.5, 3.0)), point(0.5, 0.5, 0.0));
+      assert_eq!(closest_point_on_facet(&facet, &point(-1.0, -1.0, 1.0)), point(0.0, 0.0, 0.0));
+      assert_eq!(closest_point_on_facet(&facet, &point(1.0, -1.0, 0.0)), point(1.0, 0.0, 0.0));
+      assert_eq!(closest_point_on_facet(&facet, &point(2.0, 2.0, 0.0)), point(1.0, 1.0, 0.0));
+
+      let crossing = Facet::new([point(0.5, 0.5, -1.0), point(0.5, 0.5, 1.0), point(3.0, 3.0, 0.0)]);
+      assert_eq!(facet_distance(&facet, &crossing), 0.mm());
+
+      // parallel facets 1mm apart
+      let above = facet.translated(&Vector::new(0.mm(), 0.mm(), 1.mm()));
+      assert_eq!(facet_distance(&facet, &above), 1.mm());
+   }
+
+   #[test]
+   fn distance_to_point() {
+      let stl_solid = cube(Location::default(), (10.mm(), 10.mm(), 10.mm()))
+         .generate_stl_solid();
+      let bvh = stl_solid.build_bvh();
+
+      assert_eq!(bvh.distance_to_point(&point(5.0, 5.0, 10.0)), 0.mm());
+      assert_eq!(bvh.distance_to_point(&point(10.0, 10.0, 10.0)), 0.mm());
+      assert_eq!(bvh.distance_to_point(&point(5.0, 5.0, 5.0)), 5.mm());
+      assert_eq!(bvh.distance_to_point(&point(5.0, 5.0, 13.0)), 3.mm());
+      assert_eq!(bvh.distance_to_point(&point(13.0, 14.0, 5.0)), 5.mm());
+
+      assert_eq!(bvh.signed_distance_to_point(&point(5.0, 5.0, 5.0)), -5.mm());
+      assert_eq!(bvh.signed_distance_to_point(&point(5.0, 2.0, 5.0)), -2.mm());
+      assert_eq!(bvh.signed_distance_to_point(&point(5.0, 5.0, 13.0)), 3.mm());
+      assert_eq!(stl_solid.signed_distance_to_point(&point(5.0, 5.0, 10.0)), 0.mm());
+
+      let expected = stl_solid.facets.iter()
+         .map(|f| facet_distance_to_point(f, &point(-3.0, 4.0, 12.0)))
+         .min().unwrap();
+      assert_eq!(stl_solid.distance_to_point(&point(-3.0, 4.0, 12.0)), expected);
+   }
+
+   #[test]
+   fn min_distance_to() {
+      let a = cube(Location::default(), (10.mm(), 10.mm(), 10.mm()))
+         .generate_stl_solid();
+      let b = cube(Location::default(), (10.mm(), 10.mm(), 10.mm()))
+         .translated(&Vector::new(13.mm(), 2.mm(), 0.mm()))
+         .generate_stl_solid();
+      assert_eq!(a.min_distance_to(&b), 3.mm());
+      assert_eq!(b.min_distance_to(&a), 3.mm());
+
+      let crossing = cube(Location::default(), (10.mm(), 10.mm(), 10.mm()))
+         .translated(&Vector::new(5.mm(), 5.mm(), 5.mm()))
+         .generate_stl_solid();
+      assert_eq!(a.min_distance_to(&crossing), 0.mm());
+
+      let ball = sphere(Location::default(), 2.mm())
+         .translated(&Vector::new(5.mm(), 5.mm(), 15.mm()))
+         .generate_stl_solid();
+      let distance = a.min_distance_to(&ball);
+      assert!((distance - 3.mm()).abs() < 0.05.mm(), "{}", distance);
+   }
+}
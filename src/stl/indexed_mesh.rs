@@ -0,0 +1,263 @@
+use crate::geometry::{Octree, Point, Size};
+use crate::stl::{Facet, StlSolid};
+use noisy_float::prelude::*;
+use std::collections::HashMap;
+
+/// A mesh whose facets share their vertexes.
+///
+/// Each triangle is 3 indexes of `vertexes`, in the same winding as [Facet].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IndexedMesh {
+   pub vertexes: Vec<Point>,
+   pub triangles: Vec<[u32; 3]>
+}
+
+impl IndexedMesh {
+   /// Restores independent facets.
+   pub fn to_stl_solid(&self) -> StlSolid {
+      StlSolid {
+         facets: self.triangles.iter()
+            .map(|t| Facet::new(t.map(|i| self.vertexes[i as usize])))
+            .collect()
+      }
+   }
+
+   /// Indexes of the triangles which have the specified vertex.
+   pub fn triangles_around_vertex(&self, vertex: u32) -> Vec<usize> {
+      self.triangles.iter().enumerate()
+         .filter(|(_, t)| t.contains(&vertex))
+         .map(|(i, _)| i)
+         .collect()
+   }
+}
+
+impl StlSolid {
+   /// Merges vertexes shared by facets.
+   ///
+   /// Each vertex is merged into the first vertex within `tolerance`. 0 merges
+   /// only the vertexes which are equal allowing float-point arithmetic errors.
+   /// Vertexes are numbered in order of their first appearance.
+   ///
+   /// Panics if `tolerance` is negative.
+   pub fn to_indexed(&self, tolerance: Size) -> IndexedMesh {
+      if tolerance < Size::ZERO {
+         panic!("The tolerance to merge vertexes must not be negative.");
+      }
+
+      let vertexes: Vec<Point> = self.facets.iter().flat_map(|f| f.vertexes()).collect();
+      let groups = if tolerance == Size::ZERO {
+         group_equal(&vertexes)
+      } else {
+         group_within(&vertexes, tolerance)
+      };
+
+      let mut mesh = IndexedMesh::default();
+      let mut ids = HashMap::new();
+      let indexes: Vec<u32> = vertexes.iter().zip(groups)
+         .map(|(v, group)| {
+            *ids.entry(group).or_insert_with(|| {
+               mesh.vertexes.push(*v);
+               u32::try_from(mesh.vertexes.len() - 1).expect("Too many vertexes.")
+            })
+         })
+         .collect();
+
+      mesh.triangles = indexes.chunks(3)
+         .map(|c| [c[0], c[1], c[2]])
+         .collect();
+
+      mesh
+   }
+
+   /// Moves each vertex onto the first vertex within `tolerance`. The same as
+   /// `to_indexed(tolerance).to_stl_solid()` unless merged vertexes are
+   /// chained, but finds close vertexes by [Octree].
+   ///
+   /// Panics if `tolerance` is negative.
+   pub fn merge_vertexes_fast(self, tolerance: Size) -> StlSolid {
+      if tolerance < Size::ZERO {
+         panic!("The tolerance to merge vertexes must not be negative.");
+      }
+
+      let Some(bounds) = self.bounding_box() else {
+         return self;
+      };
+
+      // numbered in order of their first appearance
+      let mut merged: Octree<(usize, Point)> = Octree::new(bounds);
+
+      let facets = self.facets.into_iter()
+         .map(|f| {
+            let vertexes = f.vertexes().map(|v| {
+               let first = merged.query_sphere(v, tolerance).into_iter()
+                  .min_by_key(|(i, _)| *i)
+                  .map(|&(_, p)| p);
+
+               first.unwrap_or_else(|| {
+                  merged.insert(v, (merged.len(), v));
+                  v
+               })
+            });
+            Facet::new(vertexes)
+         })
+         .collect();
+
+      StlSolid { facets }
+   }
+}
+
+/// Numbers the vertexes. Roughly equal vertexes have the same number.
+fn group_equal(vertexes: &[Point]) -> Vec<usize> {
+   let mut sorted: Vec<_> = (0..vertexes.len()).collect();
+   sorted.sort_by(|&a, &b| {
+      let (a, b) = (vertexes[a], vertexes[b]);
+      a.x().cmp(&b.x())
+         .then(a.y().cmp(&b.y()))
+         .then(a.z().cmp(&b.z()))
+   });
+
+   let mut groups = vec![0; vertexes.len()];
+   let mut group = 0;
+   for (j, &i) in sorted.iter().enumerate() {
+      if j > 0 && vertexes[sorted[j - 1]] != vertexes[i] {
+         group += 1;
+      }
+      groups[i] = group;
+   }
+
+   groups
+}
+
+/// Numbers the vertexes. Each vertex has the number of the first vertex
+/// within `tolerance`.
+fn group_within(vertexes: &[Point], tolerance: Size) -> Vec<usize> {
+   let cell_of = |p: &Point| {
+      [p.x(), p.y(), p.z()].map(|s| (s / tolerance).floor().raw() as i64)
+   };
+
+   let mut cells: HashMap<[i64; 3], Vec<usize>> = HashMap::new();
+
+   vertexes.iter().enumerate()
+      .map(|(i, v)| {
+         let [x, y, z] = cell_of(v);
+
+         let neighbor = (-1..=1)
+            .flat_map(|dx| (-1..=1).map(move |dy| (dx, dy)))
+            .flat_map(|(dx, dy)| (-1..=1).map(move |dz| [x + dx, y + dy, z + dz]))
+            .filter_map(|cell| cells.get(&cell))
+            .flatten()
+            .filter(|&&j| vertexes[j].distance(v) <= tolerance)
+            .min()
+            .copied();
+
+         neighbor.unwrap_or_else(|| {
+            cells.entry([x, y, z]).or_default().push(i);
+            i
+         })
+      })
+      .collect()
+}
+
+#[cfg(test)]
+mod tests {
+   use super::{group_within, IndexedMesh};
+   use crate::geometry::{Point, SizeLiteral, Vector};
+   use crate::solid::{cube, sphere, Location, Solid};
+   use crate::stl::{Facet, StlSolid};
+   use crate::transform::Transform;
+
+   #[test]
+   fn cube_vertexes() {
+      let stl_solid = cube(Location::default(), (1.mm(), 2.mm(), 3.mm()))
+         .generate_stl_solid();
+      let mesh = stl_solid.to_indexed(0.mm());
+
+      assert_eq!(mesh.vertexes.len(), 8);
+      assert_eq!(mesh.triangles.len(), 12);
+      assert_eq!(mesh.vertexes[0], stl_solid.facets[0].vertexes()[0]);
+
+      // each corner is shared by 1 or 2 triangles of each of 3 faces
+      for v in 0..8 {
+         let around = mesh.triangles_around_vertex(v);
+         assert!((3..=6).contains(&around.len()), "{:?}", around);
+         assert!(around.iter().all(|&t| mesh.triangles[t].contains(&v)));
+      }
+   }
+
+   #[test]
+   fn round_trip() {
+      let stl_solid = sphere(Location::default(), 3.mm()).generate_stl_solid();
+      let restored = stl_solid.to_indexed(0.001.mm()).to_stl_solid();
+
+      assert_eq!(restored.facets.len(), stl_solid.facets.len());
+      for (a, b) in restored.facets.iter().zip(&stl_solid.facets) {
+         for (v, w) in a.vertexes().iter().zip(&b.vertexes()) {
+            assert!(v.distance(w) <= 0.001.mm());
+         }
+      }
+      assert!(restored.is_watertight());
+   }
+
+   #[test]
+   fn merge_vertexes_fast() {
+      let stl_solid = sphere(Location::default(), 3.mm()).generate_stl_solid();
+
+      // shift vertexes a little so that they have to be merged
+      let shifted = || StlSolid::new(
+         stl_solid.facets.iter().enumerate()
+            .map(|(i, f)| {
+               let offset = Vector::new((0.00001 * (i % 3) as f64).mm(), 0.mm(), 0.mm());
+               Facet::new(f.vertexes().map(|v| v.translated(&offset)))
+            })
+            .collect()
+      );
+
+      let expected = shifted().to_indexed(0.001.mm()).to_stl_solid();
+      let actual = shifted().merge_vertexes_fast(0.001.mm());
+
+      let vertexes = |s: &StlSolid| -> Vec<Point> {
+         s.facets.iter().flat_map(|f| f.vertexes()).collect()
+      };
+      assert_eq!(vertexes(&actual), vertexes(&expected));
+      assert!(actual.is_watertight());
+      assert!(!shifted().is_watertight());
+
+      assert!(StlSolid::EMPTY.merge_vertexes_fast(1.mm()).is_empty());
+   }
+
+   #[test]
+   fn tolerance() {
+      let a = Point::ORIGIN;
+      let b = Point::new(1.mm(), 0.mm(), 0.mm());
+      let c = Point::new(0.mm(), 1.mm(), 0.mm());
+      let offset = Vector::new(0.01.mm(), 0.mm(), 0.mm());
+      let stl_solid = StlSolid::new(vec![
+         Facet::new([a, b, c]),
+         Facet::new([a.translated(&offset), c, b])
+      ]);
+
+      assert_eq!(stl_solid.to_indexed(0.mm()).vertexes.len(), 4);
+
+      let merged = stl_solid.to_indexed(0.1.mm());
+      assert_eq!(
+         merged,
+         IndexedMesh {
+            vertexes: vec![a, b, c],
+            triangles: vec![[0, 1, 2], [0, 2, 1]]
+         }
+      );
+   }
+
+   #[test]
+   fn group_within_first_vertex() {
+      // the second vertex is in the -1 neighbor cell of the third, which is
+      // searched before the +1 neighbor cell of the first vertex
+      let vertexes = [
+         Point::new(1.2.mm(), 0.mm(), 0.mm()),
+         Point::new(-0.1.mm(), 0.mm(), 0.mm()),
+         Point::new(0.5.mm(), 0.mm(), 0.mm())
+      ];
+
+      assert_eq!(group_within(&vertexes, 1.mm()), vec![0, 1, 0]);
+   }
+}
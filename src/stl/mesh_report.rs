@@ -0,0 +1,96 @@
+use crate::geometry::Point;
+use std::fmt::{self, Display, Formatter};
+
+/// Problems found in an [StlSolid](crate::stl::StlSolid) which make it
+/// unprintable. See [StlSolid::validate](crate::stl::StlSolid::validate).
+///
+/// Edges are pairs of their end points. Vertexes are regarded as the same
+/// when they are roughly equal.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MeshReport {
+   /// Edges shared by more than 2 facets.
+   pub non_manifold_edges: Vec<(Point, Point)>,
+   /// Edges which belong to only 1 facet. These are the borders of holes.
+   pub boundary_edges: Vec<(Point, Point)>,
+   /// Indexes of facets which have the same vertexes as a preceding facet.
+   pub duplicate_facets: Vec<usize>,
+   /// Indexes of facets which have no area.
+   pub degenerate_facets: Vec<usize>,
+   /// Pairs of indexes of facets which traverse their shared edge in the same
+   /// direction, i.e. one of them faces the wrong side.
+   pub inconsistent_windings: Vec<(usize, usize)>
+}
+
+impl MeshReport {
+   /// Whether every edge is shared by exactly 2 facets.
+   pub fn is_manifold(&self) -> bool {
+      self.non_manifold_edges.is_empty() && self.boundary_edges.is_empty()
+   }
+
+   /// Whether the mesh is closed and has no broken facets.
+   pub fn is_watertight(&self) -> bool {
+      self.is_manifold()
+         && self.duplicate_facets.is_empty()
+         && self.degenerate_facets.is_empty()
+         && self.inconsistent_windings.is_empty()
+   }
+}
+
+impl Display for MeshReport {
+   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+      if self.is_watertight() {
+         return write!(f, "watertight");
+      }
+
+      let write_edges = |f: &mut Formatter<'_>, name, edges: &[(Point, Point)]| {
+         if edges.is_empty() {
+            return Ok(());
+         }
+         writeln!(f, "{} {}:", edges.len(), name)?;
+         for (a, b) in edges {
+            writeln!(f, "   {} - {}", a, b)?;
+         }
+         Ok(())
+      };
+
+      write_edges(f, "non-manifold edges", &self.non_manifold_edges)?;
+      write_edges(f, "boundary edges", &self.boundary_edges)?;
+
+      if !self.duplicate_facets.is_empty() {
+         writeln!(f, "duplicate facets: {:?}", self.duplicate_facets)?;
+      }
+      if !self.degenerate_facets.is_empty() {
+         writeln!(f, "degenerate facets: {:?}", self.degenerate_facets)?;
+      }
+      if !self.inconsistent_windings.is_empty() {
+         writeln!(f, "inconsistent windings: {:?}", self.inconsistent_windings)?;
+      }
+      Ok(())
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::MeshReport;
+   use crate::geometry::{Point, SizeLiteral};
+
+   #[test]
+   fn display() {
+      assert_eq!(MeshReport::default().to_string(), "watertight");
+
+      let report = MeshReport {
+         boundary_edges: vec![
+            (Point::ORIGIN, Point::new(1.mm(), 0.mm(), 0.mm()))
+         ],
+         inconsistent_windings: vec![(0, 3)],
+         ..MeshReport::default()
+      };
+      assert_eq!(
+         report.to_string(),
+         format!(
+            "1 boundary edges:\n   {} - {}\ninconsistent windings: [(0, 3)]\n",
+            Point::ORIGIN, Point::new(1.mm(), 0.mm(), 0.mm())
+         )
+      );
+   }
+}
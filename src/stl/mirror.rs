@@ -0,0 +1,84 @@
+use crate::geometry::Plane;
+use crate::stl::{Facet, StlSolid};
+
+impl Facet {
+   /// Reflects this facet across the plane. Also reverses the order of
+   /// vertexes so that the facet faces outward.
+   pub fn mirrored(&self, plane: &Plane) -> Facet {
+      let [a, b, c] = self.vertexes().map(|v| plane.reflect_point(&v));
+      Facet::new([a, c, b])
+   }
+}
+
+impl StlSolid {
+   /// Reflects this solid across the plane. Facets still face outward.
+   pub fn mirrored(&self, plane: &Plane) -> StlSolid {
+      let facets = self.facets.iter()
+         .map(|f| f.mirrored(plane))
+         .collect();
+
+      StlSolid { facets }
+   }
+
+   /// Negates X coordinates. See [Plane::mirror_x].
+   pub fn mirrored_x(&self) -> StlSolid {
+      self.mirrored(&Plane::mirror_x())
+   }
+
+   /// Negates Y coordinates. See [Plane::mirror_y].
+   pub fn mirrored_y(&self) -> StlSolid {
+      self.mirrored(&Plane::mirror_y())
+   }
+
+   /// Negates Z coordinates. See [Plane::mirror_z].
+   pub fn mirrored_z(&self) -> StlSolid {
+      self.mirrored(&Plane::mirror_z())
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use crate::geometry::{Plane, Point, SizeLiteral, Vector};
+   use crate::solid::{cube, Location, Solid};
+   use crate::stl::StlSolid;
+
+   #[test]
+   fn mirrored_x() {
+      let original = cube(Location::default(), (1.mm(), 2.mm(), 3.mm()))
+         .generate_stl_solid();
+      let mirrored = original.mirrored_x();
+
+      for (m, o) in mirrored.facets.iter().zip(&original.facets) {
+         let [a, b, c] = o.vertexes().map(|v| Point::new(-v.x(), v.y(), v.z()));
+         assert_eq!(m.vertexes(), [a, c, b]);
+
+         let n = o.normal_vector();
+         assert_eq!(m.normal_vector(), Vector::new(-n.x(), n.y(), n.z()));
+
+         // outward from the center of the mirrored cube
+         let center = Point::new(-0.5.mm(), 1.mm(), 1.5.mm());
+         let outward = Vector::between(&center, &m.centroid());
+         assert!(m.normal_vector().inner_product(&outward).0 > 0.0);
+      }
+
+      assert_eq!(mirrored.volume(), original.volume());
+      assert!(mirrored.is_watertight());
+   }
+
+   #[test]
+   fn mirrored_axes() {
+      let original = cube(Location::default(), (1.mm(), 2.mm(), 3.mm()))
+         .generate_stl_solid();
+
+      let vertexes = |s: &StlSolid| -> Vec<Point> {
+         s.facets.iter().flat_map(|f| f.vertexes()).collect()
+      };
+
+      assert_eq!(vertexes(&original.mirrored_y()), vertexes(&original.mirrored(&Plane::ZX)));
+      assert_eq!(vertexes(&original.mirrored_z()), vertexes(&original.mirrored(&Plane::XY)));
+
+      let bounding_box = original.mirrored_z().bounding_box().unwrap();
+      assert_eq!(bounding_box.min, Point::new(0.mm(), 0.mm(), -3.mm()));
+      assert_eq!(bounding_box.max, Point::new(1.mm(), 2.mm(), 0.mm()));
+   }
+}
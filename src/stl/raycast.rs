@@ -0,0 +1,149 @@
+use crate::geometry::{BoundingBox, Point, Size, Vector};
+use crate::stl::{Facet, StlSolid};
+use crate::stl::boolean::{is_inside, ray_intersection, BARYCENTRIC_TOLERANCE, EPSILON};
+use crate::stl::bvh::Bvh;
+use std::fmt::{self, Debug, Formatter};
+
+/// [StlSolid] with a bounding volume hierarchy over its facets, to answer
+/// repeated queries without checking every facet.
+/// See [StlSolid::build_bvh].
+pub struct SolidBvh<'a> {
+   pub(in crate::stl) stl_solid: &'a StlSolid,
+   pub(in crate::stl) bvh: Bvh
+}
+
+impl StlSolid {
+   /// Builds a bounding volume hierarchy for [contains_point] and [raycast].
+   /// Use it when querying many times.
+   ///
+   /// ```
+   /// # use typed_scad::geometry::{Point, SizeLiteral};
+   /// # use typed_scad::solid::{cube, Location, Solid};
+   /// let stl_solid = cube(Location::default(), (2.mm(), 2.mm(), 2.mm()))
+   ///    .generate_stl_solid();
+   /// let bvh = stl_solid.build_bvh();
+   ///
+   /// let inside = (0..10)
+   ///    .filter(|&i| bvh.contains_point(&Point::new(i.mm(), 1.mm(), 1.mm())))
+   ///    .count();
+   /// assert_eq!(inside, 3);
+   /// ```
+   ///
+   /// [contains_point]: SolidBvh::contains_point
+   /// [raycast]: SolidBvh::raycast
+   pub fn build_bvh(&self) -> SolidBvh<'_> {
+      SolidBvh {
+         stl_solid: self,
+         bvh: Bvh::new(&self.facets)
+      }
+   }
+
+   /// Whether the point is inside this closed solid. See
+   /// [SolidBvh::contains_point].
+   pub fn contains_point(&self, point: &Point) -> bool {
+      self.build_bvh().contains_point(point)
+   }
+
+   /// The nearest facet which the ray hits. See [SolidBvh::raycast].
+   pub fn raycast(&self, origin: &Point, direction: &Vector) -> Option<(Size, Facet)> {
+      self.build_bvh().raycast(origin, direction)
+   }
+}
+
+impl SolidBvh<'_> {
+   /// Whether the point is inside the closed solid. Points on the surface
+   /// are regarded as inside.
+   ///
+   /// Casts a ray and counts the facets which it hits. When the ray passes on
+   /// an edge or a vertex, another ray toward a different direction is cast.
+   /// Panics if no ray gives the answer, which usually means the solid is
+   /// not closed.
+   pub fn contains_point(&self, point: &Point) -> bool {
+      let facets = &self.stl_solid.facets;
+
+      let on_surface = self.bvh.query(&BoundingBox::of_point(point).expanded(EPSILON))
+         .into_iter()
+         .any(|i| facets[i].area().0 > 0.0 && facets[i].contains_point(point, EPSILON));
+
+      if on_surface {
+         return true;
+      }
+
+      is_inside(point, facets, &self.bvh).unwrap_or_else(|| {
+         panic!("Cannot determine whether the point {} is inside the solid. \
+                 The solid may not be closed.", point)
+      })
+   }
+
+   /// The nearest facet which the ray from `origin` toward `direction` hits,
+   /// with the distance from `origin`. Facets behind `origin` are ignored,
+   /// and rays passing on an edge hit the facets of the edge.
+   pub fn raycast(&self, origin: &Point, direction: &Vector) -> Option<(Size, Facet)> {
+      let direction = direction.to_unit_vector();
+      let facets = &self.stl_solid.facets;
+
+      self.bvh.query_ray(origin, &direction).into_iter()
+         .filter_map(|i| {
+            let (t, barycentric) = ray_intersection(origin, &direction, &facets[i])?;
+            let hits = t >= 0.0 && barycentric.iter().all(|&b| b >= -BARYCENTRIC_TOLERANCE);
+            hits.then_some((Size::millimeter(t), i))
+         })
+         .min_by_key(|&(distance, _)| distance)
+         .map(|(distance, i)| (distance, Facet::new(facets[i].vertexes())))
+   }
+}
+
+impl Debug for SolidBvh<'_> {
+   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+      f.debug_struct("SolidBvh")
+         .field("facets", &self.stl_solid.facets.len())
+         .finish()
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use crate::geometry::{Point, SizeLiteral, Vector};
+   use crate::solid::{cube, sphere, Location, Solid};
+   use crate::transform::Transform;
+
+   #[test]
+   fn contains_point() {
+      let stl_solid = cube(Location::default(), (10.mm(), 10.mm(), 10.mm()))
+         .generate_stl_solid();
+      let bvh = stl_solid.build_bvh();
+      let point = |x: f64, y: f64, z: f64| Point::new(x.mm(), y.mm(), z.mm());
+
+      assert!(bvh.contains_point(&point(5.0, 5.0, 5.0)));
+      assert!(bvh.contains_point(&point(0.001, 9.999, 5.0)));
+      assert!(!bvh.contains_point(&point(15.0, 5.0, 5.0)));
+      assert!(!bvh.contains_point(&point(5.0, 5.0, 10.001)));
+      assert!(!bvh.contains_point(&point(-0.001, -0.001, -0.001)));
+
+      // on the surface, on an edge, and at a corner
+      assert!(bvh.contains_point(&point(5.0, 5.0, 10.0)));
+      assert!(bvh.contains_point(&point(10.0, 5.0, 10.0)));
+      assert!(bvh.contains_point(&point(0.0, 0.0, 0.0)));
+
+      assert!(stl_solid.contains_point(&point(2.0, 2.0, 2.0)));
+      assert!(!stl_solid.contains_point(&point(-2.0, -2.0, -2.0)));
+   }
+
+   #[test]
+   fn raycast() {
+      let stl_solid = sphere(Location::default(), 3.mm()).generate_stl_solid();
+      let origin = Point::new(-10.mm(), 0.mm(), 0.mm());
+
+      let (distance, facet) = stl_solid
+         .raycast(&origin, &Vector::new(2.mm(), 0.mm(), 0.mm()))
+         .unwrap();
+      assert!((distance - 7.mm()).abs() < 0.1.mm(), "{}", distance);
+      assert!(facet.normal_vector().x() < 0.mm());
+
+      let hit = origin.translated_toward(&Vector::X_UNIT_VECTOR, distance);
+      assert!(facet.contains_point(&hit, 1e-6.mm()));
+
+      assert!(stl_solid.raycast(&origin, &-Vector::X_UNIT_VECTOR).is_none());
+      assert!(stl_solid.raycast(&origin, &Vector::Y_UNIT_VECTOR).is_none());
+   }
+}
@@ -0,0 +1,357 @@
+use crate::geometry::{Point, Size, Vector};
+use crate::stl::{Facet, StlSolid};
+use anyhow::Result;
+use std::io::Read;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum StlReadError {
+   #[error("The STL ends unexpectedly.")]
+   UnexpectedEof,
+   #[error("The STL declares {declared} facets, but has {actual} facets.")]
+   CountMismatch { declared: u32, actual: usize },
+   #[error("Cannot parse `{0}` as a number.")]
+   ParseFloat(String),
+   #[error("Expected `{expected}`, but found `{found}`.")]
+   UnexpectedToken { expected: &'static str, found: String },
+   #[error("The STL has a coordinate which is not a finite number.")]
+   NotFinite
+}
+
+const HEADER_BYTES: usize = 80;
+const FACET_BYTES: usize = 4 * 3 * 4 + 2;
+
+/// Reads binary or ASCII STL.
+///
+/// Files starting with `solid` are read as ASCII, unless the length matches
+/// the facet count of binary STL, since some binary files also have `solid`
+/// in their header. Normal vectors in the file are stored in the facets as
+/// unit vectors, so that they are written back. Zero or non-finite normal
+/// vectors, and ones facing against the winding of the vertexes, are not
+/// stored, and computed from the vertexes instead.
+pub fn read_stl(input: &mut dyn Read) -> Result<StlSolid> {
+   let mut bytes = vec![];
+   input.read_to_end(&mut bytes)?;
+
+   let binary_length_matches = binary_facet_count(&bytes)
+      .is_some_and(|count| bytes.len() == HEADER_BYTES + 4 + FACET_BYTES * count as usize);
+
+   if !binary_length_matches && bytes.trim_ascii_start().starts_with(b"solid") {
+      if let Ok(text) = std::str::from_utf8(&bytes) {
+         return read_ascii(text);
+      }
+   }
+
+   read_binary(&bytes)
+}
+
+fn binary_facet_count(bytes: &[u8]) -> Option<u32> {
+   let count = bytes.get(HEADER_BYTES..(HEADER_BYTES + 4))?;
+   Some(u32::from_le_bytes(count.try_into().unwrap()))
+}
+
+fn read_binary(bytes: &[u8]) -> Result<StlSolid> {
+   let declared = binary_facet_count(bytes).ok_or(StlReadError::UnexpectedEof)?;
+   let data = &bytes[(HEADER_BYTES + 4)..];
+
+   if data.len() != FACET_BYTES * declared as usize {
+      return if data.len().is_multiple_of(FACET_BYTES) {
+         Err(StlReadError::CountMismatch {
+            declared, actual: data.len() / FACET_BYTES
+         }.into())
+      } else {
+         Err(StlReadError::UnexpectedEof.into())
+      };
+   }
+
+   let facets = data.chunks(FACET_BYTES)
+      .map(|chunk| {
+         let f = |i: usize| {
+            let start = i * 4;
+            f32::from_le_bytes(chunk[start..(start + 4)].try_into().unwrap())
+         };
+
+         let vertex = |v: usize| point(f(v * 3 + 3), f(v * 3 + 4), f(v * 3 + 5));
+         Ok(facet([vertex(0)?, vertex(1)?, vertex(2)?], [f(0), f(1), f(2)]))
+      })
+      .collect::<Result<Vec<_>>>()?;
+
+   Ok(StlSolid::new(facets))
+}
+
+fn read_ascii(text: &str) -> Result<StlSolid> {
+   let mut tokens = text.split_ascii_whitespace().peekable();
+
+   expect(&mut tokens, "solid")?;
+
+   // the name may contain spaces
+   while tokens.next_if(|&t| t != "facet" && t != "endsolid").is_some() {}
+
+   let mut facets = vec![];
+
+   loop {
+      match tokens.next() {
+         Some("facet") => {}
+         Some("endsolid") => break,
+         Some(found) => {
+            return Err(StlReadError::UnexpectedToken {
+               expected: "facet", found: found.to_string()
+            }.into());
+         }
+         None => return Err(StlReadError::UnexpectedEof.into())
+      }
+
+      expect(&mut tokens, "normal")?;
+      let normal = [number(&mut tokens)?, number(&mut tokens)?, number(&mut tokens)?];
+
+      expect(&mut tokens, "outer")?;
+      expect(&mut tokens, "loop")?;
+
+      let mut vertex = || -> Result<Point> {
+         expect(&mut tokens, "vertex")?;
+         point(number(&mut tokens)?, number(&mut tokens)?, number(&mut tokens)?)
+      };
+      let vertexes = [vertex()?, vertex()?, vertex()?];
+
+      expect(&mut tokens, "endloop")?;
+      expect(&mut tokens, "endfacet")?;
+
+      facets.push(facet(vertexes, normal));
+   }
+
+   Ok(StlSolid::new(facets))
+}
+
+fn expect<'a>(
+   tokens: &mut impl Iterator<Item = &'a str>,
+   expected: &'static str
+) -> Result<()> {
+   match tokens.next() {
+      Some(t) if t == expected => Ok(()),
+      Some(found) => Err(StlReadError::UnexpectedToken {
+         expected, found: found.to_string()
+      }.into()),
+      None => Err(StlReadError::UnexpectedEof.into())
+   }
+}
+
+fn number<'a>(tokens: &mut impl Iterator<Item = &'a str>) -> Result<f32> {
+   let token = tokens.next().ok_or(StlReadError::UnexpectedEof)?;
+   token.parse().map_err(|_| StlReadError::ParseFloat(token.to_string()).into())
+}
+
+fn point(x: f32, y: f32, z: f32) -> Result<Point> {
+   if !(x.is_finite() && y.is_finite() && z.is_finite()) {
+      return Err(StlReadError::NotFinite.into());
+   }
+
+   Ok(Point::new(Size::from(x), Size::from(y), Size::from(z)))
+}
+
+/// The facet with the normal vector in the file as a unit vector. The normal
+/// vector is not stored if it is zero, not finite, or facing against the
+/// winding of the vertexes.
+fn facet(vertexes: [Point; 3], [x, y, z]: [f32; 3]) -> Facet {
+   let facet = Facet::new(vertexes);
+
+   if !(x.is_finite() && y.is_finite() && z.is_finite()) {
+      return facet;
+   }
+
+   let normal = Vector::new(Size::from(x), Size::from(y), Size::from(z));
+   let Ok(normal) = normal.try_to_unit_vector() else {
+      return facet;
+   };
+
+   match facet.try_normal_vector() {
+      Ok(winding) if winding.inner_product(&normal).0 <= 0.0 => facet,
+      _ => Facet::with_normal(vertexes, normal)
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::{read_stl, StlReadError};
+   use crate::geometry::{Point, SizeLiteral, Vector};
+   use crate::math::rough_fp::rough_eq;
+   use crate::solid::{cube, sphere, Location, Solid};
+   use crate::stl::{boolean, write_stl, write_stl_ascii, Facet, StlSolid};
+   use crate::transform::Transform;
+   use noisy_float::prelude::*;
+
+   fn vertexes(stl_solid: &StlSolid) -> Vec<[f32; 3]> {
+      stl_solid.facets.iter()
+         .flat_map(|f| f.vertexes())
+         .map(|v| [v.x(), v.y(), v.z()].map(|s| s.0.raw() as f32))
+         .collect()
+   }
+
+   fn read_error(bytes: &[u8]) -> StlReadError {
+      match read_stl(&mut &bytes[..]) {
+         Ok(_) => panic!("The STL must not be read."),
+         Err(e) => e.downcast().unwrap()
+      }
+   }
+
+   #[test]
+   fn round_trip() {
+      let stl_solid = sphere(Location::default(), 3.7.mm()).generate_stl_solid();
+
+      let mut binary = vec![];
+      write_stl(&mut binary, &stl_solid).unwrap();
+      let read = read_stl(&mut &binary[..]).unwrap();
+      assert_eq!(vertexes(&read), vertexes(&stl_solid));
+
+      let mut ascii = vec![];
+      write_stl_ascii(&mut ascii, &stl_solid, "sphere").unwrap();
+      let read = read_stl(&mut &ascii[..]).unwrap();
+      assert_eq!(vertexes(&read), vertexes(&stl_solid));
+   }
+
+   #[test]
+   fn normal_round_trip() {
+      let point = |x: f64, y: f64, z: f64| Point::new(x.mm(), y.mm(), z.mm());
+      let vertexes = [point(0.0, 0.0, 0.0), point(1.0, 0.0, 0.0), point(0.0, 1.0, 0.0)];
+
+      // normals which are not computed from the vertexes
+      let tilted = Vector::new(0.mm(), 0.6.mm(), 0.8.mm());
+      let stl_solid = StlSolid::new(vec![
+         Facet::with_normal(vertexes, tilted),
+         Facet::with_normal(vertexes, Vector::new(0.8.mm(), 0.mm(), 0.6.mm())),
+         Facet::new(vertexes)
+      ]);
+      let normals = |stl_solid: &StlSolid| -> Vec<[f32; 3]> {
+         stl_solid.facets.iter()
+            .map(|f| f.normal_vector())
+            .map(|n| [n.x(), n.y(), n.z()].map(|s| s.0.raw() as f32))
+            .collect()
+      };
+
+      let mut binary = vec![];
+      write_stl(&mut binary, &stl_solid).unwrap();
+      let read = read_stl(&mut &binary[..]).unwrap();
+      assert_eq!(normals(&read), normals(&stl_solid));
+      assert!(read.facets.iter().all(|f| f.normal().is_some()));
+
+      let mut rewritten = vec![];
+      write_stl(&mut rewritten, &read).unwrap();
+      assert_eq!(rewritten, binary);
+
+      let mut ascii = vec![];
+      write_stl_ascii(&mut ascii, &stl_solid, "tilted").unwrap();
+      let read = read_stl(&mut &ascii[..]).unwrap();
+      assert_eq!(normals(&read), normals(&stl_solid));
+
+      let mut rewritten = vec![];
+      write_stl_ascii(&mut rewritten, &read, "tilted").unwrap();
+      assert_eq!(rewritten, ascii);
+
+      let text = "solid a\nfacet normal 0 0 0\nouter loop\n\
+                  vertex 0 0 0\nvertex 1 0 0\nvertex 0 1 0\n\
+                  endloop\nendfacet\nendsolid a\n";
+      let read = read_stl(&mut text.as_bytes()).unwrap();
+      assert_eq!(read.facets[0].normal(), None);
+      assert_eq!(read.facets[0].normal_vector(), Vector::Z_UNIT_VECTOR);
+   }
+
+   #[test]
+   fn loose_normals() {
+      let cube = cube(Location::default(), (2.mm(), 2.mm(), 2.mm())).generate_stl_solid();
+
+      // normals scaled by 5, or flipped
+      let mut text = "solid loose\n".to_string();
+      for (i, f) in cube.facets().enumerate() {
+         let scale: i32 = if i % 2 == 0 { 5 } else { -1 };
+         let n: Vector = f.normal_vector() * scale;
+         text += &format!("facet normal {} {} {}\nouter loop\n", n.x().0, n.y().0, n.z().0);
+         for v in f.vertexes() {
+            text += &format!("vertex {} {} {}\n", v.x().0, v.y().0, v.z().0);
+         }
+         text += "endloop\nendfacet\n";
+      }
+      text += "endsolid loose\n";
+
+      let read = read_stl(&mut text.as_bytes()).unwrap();
+      for (i, (r, f)) in read.facets().zip(cube.facets()).enumerate() {
+         let expected = if i % 2 == 0 { Some(f.normal_vector()) } else { None };
+         assert_eq!(r.normal(), expected);
+         assert_eq!(r.normal_vector(), f.normal_vector());
+      }
+
+      let other = cube.translated(&Vector::new(1.mm(), 1.mm(), 1.mm()));
+      let union = boolean::union(&read, &other);
+      assert!(rough_eq(union.volume().0, n64(15.0)));
+   }
+
+   #[test]
+   fn ascii() {
+      let text = "  solid my part\n\
+                  facet normal 0 0 -1\n\
+                  \touter loop\n\
+                  \t\tvertex 0 0 0\n\
+                  \t\tvertex 0.0 1.5 0\n\
+                  \t\tvertex 2e0 -0 0.000e+00\n\
+                  \tendloop\n\
+                  endfacet\n\
+                  facet normal 0 0 1\n\
+                  outer loop vertex 0 0 1 vertex 1 0 1 vertex 0 1 1 endloop endfacet\n\
+                  endsolid my part\n";
+
+      let stl_solid = read_stl(&mut text.as_bytes()).unwrap();
+      let point = |x: f64, y: f64, z: f64| Point::new(x.mm(), y.mm(), z.mm());
+
+      assert_eq!(stl_solid.facets.len(), 2);
+      assert_eq!(
+         stl_solid.facets[0].vertexes(),
+         [point(0.0, 0.0, 0.0), point(0.0, 1.5, 0.0), point(2.0, 0.0, 0.0)]
+      );
+      assert_eq!(
+         stl_solid.facets[1].vertexes(),
+         [point(0.0, 0.0, 1.0), point(1.0, 0.0, 1.0), point(0.0, 1.0, 1.0)]
+      );
+
+      assert!(read_stl(&mut "solid\nendsolid\n".as_bytes()).unwrap().is_empty());
+   }
+
+   #[test]
+   fn binary_starting_with_solid() {
+      let stl_solid = sphere(Location::default(), 2.mm()).generate_stl_solid();
+
+      let mut binary = vec![];
+      write_stl(&mut binary, &stl_solid).unwrap();
+      binary[..11].copy_from_slice(b"solid whose");
+
+      let read = read_stl(&mut &binary[..]).unwrap();
+      assert_eq!(vertexes(&read), vertexes(&stl_solid));
+   }
+
+   #[test]
+   fn errors() {
+      let stl_solid = sphere(Location::default(), 2.mm()).generate_stl_solid();
+      let mut binary = vec![];
+      write_stl(&mut binary, &stl_solid).unwrap();
+      let count = stl_solid.facets.len();
+
+      assert_eq!(read_error(&binary[..50]), StlReadError::UnexpectedEof);
+      assert_eq!(read_error(&binary[..(binary.len() - 1)]), StlReadError::UnexpectedEof);
+      assert_eq!(
+         read_error(&binary[..(binary.len() - 50)]),
+         StlReadError::CountMismatch { declared: count as u32, actual: count - 1 }
+      );
+
+      let text = "solid a\nfacet normal 0 0 1\nouter loop\nvertex 0 0 0\n";
+      assert_eq!(read_error(text.as_bytes()), StlReadError::UnexpectedEof);
+
+      let text = "solid a\nfacet normal 0 0 1\nouter loop\nvertex 0 zero 0\n";
+      assert_eq!(read_error(text.as_bytes()), StlReadError::ParseFloat("zero".to_string()));
+
+      let text = "solid a\nfacet normal 0 0 1\nouter loop\nvertex 0 NaN 0\n";
+      assert_eq!(read_error(text.as_bytes()), StlReadError::NotFinite);
+
+      let text = "solid a\nfacet normal 0 0 1\nloop\n";
+      assert_eq!(
+         read_error(text.as_bytes()),
+         StlReadError::UnexpectedToken { expected: "outer", found: "loop".to_string() }
+      );
+   }
+}
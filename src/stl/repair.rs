@@ -0,0 +1,296 @@
+use crate::geometry::{Point, Size, SizeLiteral};
+use crate::math::rough_fp::rough_eq;
+use crate::stl::{Facet, StlSolid};
+use noisy_float::prelude::*;
+use std::collections::{HashMap, HashSet, VecDeque};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RepairError {
+   #[error("The merge tolerance must not be negative, but {0} is given.")]
+   NegativeTolerance(Size),
+   #[error("No facets remain after removing degenerate facets.")]
+   Empty,
+   #[error("The edge {0}-{1} is shared by more than 2 facets.")]
+   NonManifoldEdge(Point, Point),
+   #[error("Facets cannot be oriented consistently, like a Möbius strip.")]
+   NonOrientable,
+   #[error("The solid has holes. {0} edges are not shared with another facet.")]
+   Holes(usize)
+}
+
+/// Steps of [StlSolid::repair_options].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RepairOptions {
+   /// Vertexes closer than this are merged into one. 0 merges only the
+   /// vertexes which are equal allowing float-point arithmetic errors.
+   pub merge_tolerance: Size,
+   /// Removes facets without area, and duplicated facets.
+   pub remove_degenerate: bool,
+   /// Flips facets so that all facets face outward.
+   pub fix_orientation: bool
+}
+
+impl Default for RepairOptions {
+   fn default() -> RepairOptions {
+      RepairOptions {
+         merge_tolerance: 0.0001.mm(),
+         remove_degenerate: true,
+         fix_orientation: true
+      }
+   }
+}
+
+impl StlSolid {
+   /// Fixes common issues of a mesh from external sources, with
+   /// [RepairOptions::default]. See [repair_options](StlSolid::repair_options).
+   pub fn repair(self) -> Result<StlSolid, RepairError> {
+      self.repair_options(RepairOptions::default())
+   }
+
+   /// Fixes common issues of a mesh from external sources.
+   ///
+   /// Merges close vertexes, removes degenerate facets, and orients facets
+   /// consistently, as enabled in `options`. Returns [RepairError] if a step
+   /// fails, or if the result still has holes.
+   pub fn repair_options(self, options: RepairOptions) -> Result<StlSolid, RepairError> {
+      if options.merge_tolerance < Size::ZERO {
+         return Err(RepairError::NegativeTolerance(options.merge_tolerance));
+      }
+
+      let mut stl_solid = self;
+
+      if options.merge_tolerance > Size::ZERO {
+         merge_vertexes(&mut stl_solid, options.merge_tolerance);
+      }
+
+      if options.remove_degenerate {
+         remove_degenerate(&mut stl_solid);
+         if stl_solid.facets.is_empty() {
+            return Err(RepairError::Empty);
+         }
+      }
+
+      if options.fix_orientation {
+         orient(&mut stl_solid)?;
+      }
+
+      let report = stl_solid.validate();
+      if let Some(&(a, b)) = report.non_manifold_edges.first() {
+         return Err(RepairError::NonManifoldEdge(a, b));
+      }
+      if !report.boundary_edges.is_empty() {
+         return Err(RepairError::Holes(report.boundary_edges.len()));
+      }
+
+      Ok(stl_solid)
+   }
+}
+
+/// Moves each vertex onto the first vertex within `tolerance`.
+fn merge_vertexes(stl_solid: &mut StlSolid, tolerance: Size) {
+   *stl_solid = stl_solid.to_indexed(tolerance).to_stl_solid();
+}
+
+fn remove_degenerate(stl_solid: &mut StlSolid) {
+   let vertex_ids = stl_solid.vertex_ids();
+   let mut found = HashSet::new();
+
+   let facets = std::mem::take(&mut stl_solid.facets);
+   stl_solid.facets = facets.into_iter().zip(vertex_ids)
+      .filter(|(facet, ids)| {
+         let mut sorted_ids = *ids;
+         sorted_ids.sort();
+
+         sorted_ids[0] != sorted_ids[1] && sorted_ids[1] != sorted_ids[2]
+            && !rough_eq(facet.area().0, n64(0.0))
+            && found.insert(sorted_ids)
+      })
+      .map(|(facet, _)| facet)
+      .collect();
+}
+
+/// Flips facets so that adjacent facets have the same winding, by breadth
+/// first search over the facets. Then flips each connected part entirely if
+/// it faces inward.
+pub(crate) fn orient(stl_solid: &mut StlSolid) -> Result<(), RepairError> {
+   let vertex_ids = stl_solid.vertex_ids();
+
+   let mut edges: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+   for (i, ids) in vertex_ids.iter().enumerate() {
+      for k in 0..3 {
+         let (a, b) = (ids[k], ids[(k + 1) % 3]);
+         edges.entry((a.min(b), a.max(b))).or_default().push(i);
+      }
+   }
+
+   if let Some(facets) = edges.values().find(|facets| facets.len() > 2) {
+      let ids = &vertex_ids[facets[0]];
+      let shared: Vec<_> = (0..3)
+         .filter(|&k| facets.iter().all(|&f| vertex_ids[f].contains(&ids[k])))
+         .map(|k| stl_solid.facets[facets[0]].vertexes()[k])
+         .collect();
+      return Err(RepairError::NonManifoldEdge(shared[0], shared[1]));
+   }
+
+   let has_edge = |facet: usize, a: usize, b: usize| {
+      let ids = &vertex_ids[facet];
+      (0..3).any(|k| ids[k] == a && ids[(k + 1) % 3] == b)
+   };
+
+   let mut flipped: Vec<Option<bool>> = vec![None; stl_solid.facets.len()];
+
+   for start in 0..stl_solid.facets.len() {
+      if flipped[start].is_some() {
+         continue;
+      }
+
+      flipped[start] = Some(false);
+      let mut component = vec![start];
+      let mut queue = VecDeque::from([start]);
+
+      while let Some(f) = queue.pop_front() {
+         let ids = vertex_ids[f];
+         for k in 0..3 {
+            let (a, b) = (ids[k], ids[(k + 1) % 3]);
+
+            for &g in &edges[&(a.min(b), a.max(b))] {
+               if g == f {
+                  continue;
+               }
+
+               // g must go b to a while f goes a to b
+               let required = flipped[f].unwrap() ^ has_edge(g, a, b);
+               match flipped[g] {
+                  None => {
+                     flipped[g] = Some(required);
+                     component.push(g);
+                     queue.push_back(g);
+                  }
+                  Some(actual) if actual != required => {
+                     return Err(RepairError::NonOrientable);
+                  }
+                  Some(_) => {}
+               }
+            }
+         }
+      }
+
+      let volume: f64 = component.iter()
+         .map(|&f| {
+            let v = stl_solid.facets[f].signed_volume().0.raw();
+            if flipped[f].unwrap() { -v } else { v }
+         })
+         .sum();
+
+      if volume < 0.0 {
+         for &f in &component {
+            flipped[f] = flipped[f].map(|b| !b);
+         }
+      }
+   }
+
+   for (facet, flipped) in stl_solid.facets.iter_mut().zip(flipped) {
+      if flipped.unwrap() {
+         let [a, b, c] = facet.vertexes();
+         *facet = Facet::new([a, c, b]);
+      }
+   }
+
+   Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+   use super::{RepairError, RepairOptions};
+   use crate::geometry::{Point, SizeLiteral, Vector};
+   use crate::solid::{cube, Location, Solid};
+   use crate::stl::{Facet, StlSolid};
+   use crate::transform::Transform;
+
+   fn cube_facets() -> Vec<Facet> {
+      cube(Location::default(), (1.mm(), 2.mm(), 3.mm()))
+         .generate_stl_solid()
+         .into_facets()
+   }
+
+   fn error(stl_solid: StlSolid) -> RepairError {
+      stl_solid.repair().err().unwrap()
+   }
+
+   #[test]
+   fn repair() {
+      let mut facets = cube_facets();
+
+      // mismatched vertex coordinates
+      let [a, b, c] = facets[0].vertexes();
+      let shift = Vector::new(0.00001.mm(), 0.mm(), 0.mm());
+      facets[0].set_vertexes([a.translated(&shift), b, c]);
+
+      // reversed facets
+      for f in &mut facets[2..4] {
+         let [a, b, c] = f.vertexes();
+         *f = Facet::new([a, c, b]);
+      }
+
+      // duplicated and degenerate facets
+      let [a, b, c] = facets[5].vertexes();
+      facets.push(Facet::new([b, c, a]));
+      facets.push(Facet::new([a, a, b]));
+
+      let broken = StlSolid::new(facets);
+      assert!(!broken.validate().is_manifold());
+
+      let repaired = broken.repair().unwrap();
+      assert!(repaired.is_watertight());
+      assert_eq!(repaired.facet_count(), 12);
+      assert!((repaired.volume().0.raw() - 6.0).abs() < 1e-3);
+   }
+
+   #[test]
+   fn inside_out() {
+      let facets = cube_facets().into_iter()
+         .map(|f| {
+            let [a, b, c] = f.vertexes();
+            Facet::new([a, c, b])
+         })
+         .collect();
+
+      let repaired = StlSolid::new(facets).repair().unwrap();
+      assert!((repaired.volume().0.raw() - 6.0).abs() < 1e-9);
+
+      let options = RepairOptions { fix_orientation: false, ..RepairOptions::default() };
+      let facets = cube_facets().into_iter()
+         .map(|f| {
+            let [a, b, c] = f.vertexes();
+            Facet::new([a, c, b])
+         })
+         .collect();
+      let kept = StlSolid::new(facets).repair_options(options).unwrap();
+      assert!((kept.volume().0.raw() + 6.0).abs() < 1e-9);
+   }
+
+   #[test]
+   fn errors() {
+      let mut facets = cube_facets();
+      facets.pop();
+      assert_eq!(error(StlSolid::new(facets)), RepairError::Holes(3));
+
+      let mut facets = cube_facets();
+      let extra = Facet::new([
+         facets[0].vertexes()[0],
+         facets[0].vertexes()[1],
+         Point::new(-5.mm(), -5.mm(), -5.mm())
+      ]);
+      facets.push(extra);
+      assert!(matches!(error(StlSolid::new(facets)), RepairError::NonManifoldEdge(..)));
+
+      let facets = vec![Facet::new([Point::ORIGIN; 3])];
+      assert_eq!(error(StlSolid::new(facets)), RepairError::Empty);
+
+      let options = RepairOptions { merge_tolerance: -1.mm(), ..RepairOptions::default() };
+      let error = StlSolid::new(cube_facets()).repair_options(options)
+         .err().unwrap();
+      assert_eq!(error, RepairError::NegativeTolerance(-1.mm()));
+   }
+}
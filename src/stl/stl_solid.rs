@@ -1,30 +1,608 @@
-use crate::geometry::{Angle, Line, Point, Vector};
-use crate::transform::Transform;
+use anyhow::Result;
+use crate::geometry::{Angle, BoundingBox, GeometryError, Line, Point, Size, Vector};
+use crate::math::Matrix;
+use crate::math::rough_fp::rough_eq;
+use crate::math::unit::Exp;
+use crate::stl::boolean;
+use crate::stl::bvh::Bvh;
+use crate::stl::repair;
+use crate::stl::MeshReport;
+use crate::transform::{InvertThrough, Transform};
+use noisy_float::prelude::*;
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::ops::{Add, AddAssign};
 
 /// STL Solid. This can be written as STL. (See [crate::stl::write_stl])
 pub struct StlSolid {
    pub(crate) facets: Vec<Facet>
 }
 
-pub(crate) struct Facet {
-   pub(crate) vertexes: [Point; 3]
+impl StlSolid {
+   /// A solid without facets.
+   pub const EMPTY: StlSolid = StlSolid { facets: Vec::new() };
+
+   pub fn new(facets: Vec<Facet>) -> StlSolid {
+      StlSolid { facets }
+   }
+
+   pub fn is_empty(&self) -> bool {
+      self.facets.is_empty()
+   }
+
+   /// Facets of this solid followed by the facets of `other`.
+   /// Overlapping parts are left as they are. Same as `self + other`.
+   pub fn merge(mut self, other: StlSolid) -> StlSolid {
+      self.extend(other);
+      self
+   }
+
+   pub fn facets(&self) -> impl Iterator<Item = &Facet> {
+      self.facets.iter()
+   }
+
+   /// Same as [facets](StlSolid::facets).
+   /// ```
+   /// # use typed_scad::geometry::SizeLiteral;
+   /// # use typed_scad::solid::{cube, Location, Solid};
+   /// let solid = cube(Location::default(), (1.mm(), 2.mm(), 3.mm()))
+   ///    .generate_stl_solid();
+   ///
+   /// assert_eq!(solid.iter().count(), 12);
+   ///
+   /// let mut count = 0;
+   /// for _facet in solid {
+   ///    count += 1;
+   /// }
+   /// assert_eq!(count, 12);
+   /// ```
+   pub fn iter(&self) -> impl Iterator<Item = &Facet> {
+      self.facets.iter()
+   }
+
+   /// Same as [iter](StlSolid::iter), but in parallel.
+   pub fn par_iter(&self) -> impl IndexedParallelIterator<Item = &Facet> {
+      self.facets.par_iter()
+   }
+
+   /// All facets as a slice.
+   pub fn as_slice(&self) -> &[Facet] {
+      &self.facets
+   }
+
+   pub fn facets_mut(&mut self) -> impl Iterator<Item = &mut Facet> {
+      self.facets.iter_mut()
+   }
+
+   pub fn into_facets(self) -> Vec<Facet> {
+      self.facets
+   }
+
+   pub fn facet_count(&self) -> usize {
+      self.facets.len()
+   }
+
+   /// Volume of this solid. This solid must be closed, otherwise the result
+   /// is meaningless. See also [try_volume](StlSolid::try_volume).
+   pub fn volume(&self) -> Exp<Size, 3> {
+      self.facets.iter().map(|f| f.signed_volume()).sum()
+   }
+
+   /// Volume of this solid, or `None` if this solid is not closed.
+   pub fn try_volume(&self) -> Option<Exp<Size, 3>> {
+      if self.validate().is_manifold() {
+         Some(self.volume())
+      } else {
+         None
+      }
+   }
+
+   /// Sum of the areas of all facets.
+   pub fn surface_area(&self) -> Exp<Size, 2> {
+      self.facets.iter().map(|f| f.area()).sum()
+   }
+
+   /// The center of mass of this solid, assuming uniform density.
+   /// This solid must be closed. Returns `None` for a solid without volume.
+   pub fn center_of_mass(&self) -> Option<Point> {
+      let volume = self.volume();
+      if rough_eq(volume.0, n64(0.0)) {
+         return None;
+      }
+
+      // Each facet and the origin make a tetrahedron. The center of mass of
+      // the solid is the average of the tetrahedrons' centroids weighted by
+      // their signed volumes.
+      let mut moment: Matrix<Size, 3, 1> = Default::default();
+      for f in &self.facets {
+         let [a, b, c] = f.vertexes;
+         moment += (a.matrix + b.matrix + c.matrix) * (f.signed_volume().0 / 4.0);
+      }
+
+      Some(Point { matrix: moment / volume.0 })
+   }
+
+   /// Pairs of indexes of facets which cross each other.
+   ///
+   /// Facets which only touch at their edges or vertexes are not regarded as
+   /// crossing. A valid closed solid has no such pairs.
+   pub fn intersecting_facet_pairs(&self) -> Vec<(usize, usize)> {
+      let bvh = Bvh::new(&self.facets);
+
+      self.facets.iter().enumerate()
+         .flat_map(|(i, facet)| {
+            bvh.query(&BoundingBox::of_facet(facet))
+               .into_iter()
+               .filter(move |&j| i < j)
+               .filter(|&j| boolean::crosses(facet, &self.facets[j]))
+               .map(move |j| (i, j))
+         })
+         .collect()
+   }
+
+   /// Splits this solid into connected components.
+   ///
+   /// Facets sharing an edge belong to the same shell. Vertexes are regarded as
+   /// the same when they are roughly equal. The shells are ordered by their
+   /// first facets.
+   pub fn split_into_shells(self) -> Vec<StlSolid> {
+      let vertex_ids = self.vertex_ids();
+
+      let mut parents: Vec<_> = (0..self.facets.len()).collect();
+      fn root(parents: &mut [usize], mut i: usize) -> usize {
+         while parents[i] != i {
+            parents[i] = parents[parents[i]];
+            i = parents[i];
+         }
+         i
+      }
+
+      let mut edge_owners = HashMap::new();
+      for (i, ids) in vertex_ids.iter().enumerate() {
+         for k in 0..3 {
+            let (a, b) = (ids[k], ids[(k + 1) % 3]);
+            let edge = (a.min(b), a.max(b));
+
+            let owner = *edge_owners.entry(edge).or_insert(i);
+            let owner_root = root(&mut parents, owner);
+            let facet_root = root(&mut parents, i);
+            parents[facet_root] = owner_root;
+         }
+      }
+
+      let mut shells: Vec<StlSolid> = vec![];
+      let mut shell_indexes = HashMap::new();
+      for (i, facet) in self.facets.into_iter().enumerate() {
+         let index = *shell_indexes.entry(root(&mut parents, i))
+            .or_insert_with(|| {
+               shells.push(StlSolid::new(vec![]));
+               shells.len() - 1
+            });
+
+         shells[index].facets.push(facet);
+      }
+
+      shells
+   }
+
+   /// Checks whether this solid can be printed. See [MeshReport].
+   pub fn validate(&self) -> MeshReport {
+      let vertex_ids = self.vertex_ids();
+
+      let mut edges: HashMap<(usize, usize), (usize, (Point, Point))>
+         = HashMap::new();
+      let mut directed_edges = HashMap::new();
+      let mut facets = HashMap::new();
+      let mut report = MeshReport::default();
+
+      for (i, (facet, ids)) in self.facets.iter().zip(&vertex_ids).enumerate() {
+         for k in 0..3 {
+            let (a, b) = (ids[k], ids[(k + 1) % 3]);
+            if a == b {
+               continue;
+            }
+            let points = (facet.vertexes[k], facet.vertexes[(k + 1) % 3]);
+            edges.entry((a.min(b), a.max(b))).or_insert((0, points)).0 += 1;
+
+            // facets facing the same side traverse their shared edge in
+            // opposite directions
+            if let Some(&j) = directed_edges.get(&(a, b)) {
+               report.inconsistent_windings.push((j, i));
+            } else {
+               directed_edges.insert((a, b), i);
+            }
+         }
+
+         let mut sorted_ids = *ids;
+         sorted_ids.sort();
+         if facets.insert(sorted_ids, i).is_some() {
+            report.duplicate_facets.push(i);
+         }
+
+         if rough_eq(facet.area().0, n64(0.0)) {
+            report.degenerate_facets.push(i);
+         }
+      }
+
+      let mut edges: Vec<_> = edges.into_iter().collect();
+      edges.sort_by_key(|(ids, _)| *ids);
+
+      for (_, (count, points)) in edges {
+         match count {
+            1 => report.boundary_edges.push(points),
+            2 => {}
+            _ => report.non_manifold_edges.push(points)
+         }
+      }
+
+      report
+   }
+
+   /// Whether this solid is closed and has no broken facets.
+   /// Same as `validate().is_watertight()`.
+   pub fn is_watertight(&self) -> bool {
+      self.validate().is_watertight()
+   }
+
+   /// Closes holes in this solid.
+   ///
+   /// Each loop of boundary edges (see [MeshReport::boundary_edges]) is filled
+   /// with a fan of facets. Holes whose borders touch each other at a vertex
+   /// are left as is.
+   pub fn fill_holes(&mut self) {
+      for hole in self.hole_borders() {
+         let origin = hole[0];
+         self.facets.extend(
+            hole[1..].windows(2).map(|w| Facet::new([origin, w[0], w[1]]))
+         );
+      }
+   }
+
+   /// Loops of boundary edges. Each loop goes opposite to the edges of
+   /// the facets around, so that a facet filling the hole faces outward.
+   /// Loops which touch each other at a vertex are not included.
+   pub(crate) fn hole_borders(&self) -> Vec<Vec<Point>> {
+      let vertex_ids = self.vertex_ids();
+
+      let mut points = HashMap::new();
+      let mut half_edges = HashSet::new();
+      for (facet, ids) in self.facets.iter().zip(&vertex_ids) {
+         for k in 0..3 {
+            points.insert(ids[k], facet.vertexes[k]);
+            half_edges.insert((ids[k], ids[(k + 1) % 3]));
+         }
+      }
+
+      let mut next: HashMap<usize, Vec<usize>> = HashMap::new();
+      for &(a, b) in &half_edges {
+         if a != b && !half_edges.contains(&(b, a)) {
+            next.entry(b).or_default().push(a);
+         }
+      }
+
+      let mut starts: Vec<_> = next.keys().copied().collect();
+      starts.sort();
+
+      let mut borders = vec![];
+      let mut visited = HashSet::new();
+      for start in starts {
+         if visited.contains(&start) {
+            continue;
+         }
+
+         let mut hole = vec![start];
+         let is_simple = loop {
+            let v = *hole.last().unwrap();
+            visited.insert(v);
+            match next.get(&v).map(|n| &n[..]) {
+               Some(&[n]) if n == start => break true,
+               Some(&[n]) if !visited.contains(&n) => hole.push(n),
+               _ => break false
+            }
+         };
+
+         if !is_simple || hole.len() < 3 {
+            continue;
+         }
+
+         borders.push(hole.iter().map(|id| points[id]).collect());
+      }
+
+      borders
+   }
+
+   /// Numbers the vertexes of each facet. Roughly equal vertexes have the
+   /// same number.
+   pub(crate) fn vertex_ids(&self) -> Vec<[usize; 3]> {
+      self.to_indexed(Size::ZERO).triangles.into_iter()
+         .map(|t| t.map(|i| i as usize))
+         .collect()
+   }
+
+   /// Translated so that the center of the bounding box is on the origin.
+   pub fn centered(&self) -> StlSolid {
+      let offset = match self.bounding_box() {
+         Some(b) => Vector::between(&b.center(), &Point::ORIGIN),
+         None => Vector::ZERO
+      };
+
+      self.translated(&offset)
+   }
+
+   /// Translated along Z-Axis so that the bottom of this solid is on `z = 0`.
+   pub fn on_bed(&self) -> StlSolid {
+      let offset = match self.bounding_box() {
+         Some(b) => Vector::new(Size::ZERO, Size::ZERO, -b.min.z()),
+         None => Vector::ZERO
+      };
+
+      self.translated(&offset)
+   }
+
+   /// Canonical form of this solid, to write the same STL for the same shape.
+   ///
+   /// Roughly equal vertexes are unified, the facets are oriented
+   /// consistently so that they face outward, each facet starts from its
+   /// smallest vertex keeping its winding, and the facets are sorted by their
+   /// vertexes. So solids of the same facets result in the same order
+   /// regardless of how they were generated.
+   ///
+   /// If this solid has an edge shared by more than 2 facets or cannot be
+   /// oriented consistently like a Möbius strip, no facet is flipped and all
+   /// facets keep their input winding.
+   pub fn normalize(self) -> StlSolid {
+      let cmp_points = |a: &Point, b: &Point| {
+         a.x().cmp(&b.x())
+            .then(a.y().cmp(&b.y()))
+            .then(a.z().cmp(&b.z()))
+      };
+
+      let mut stl_solid = self.to_indexed(Size::ZERO).to_stl_solid();
+      let _ = repair::orient(&mut stl_solid);
+
+      let mut facets: Vec<_> = stl_solid.facets
+         .into_iter()
+         .map(|f| {
+            let first = (0..3)
+               .min_by(|&i, &j| cmp_points(&f.vertexes[i], &f.vertexes[j]))
+               .unwrap();
+            let mut vertexes = f.vertexes;
+            vertexes.rotate_left(first);
+            Facet::new(vertexes)
+         })
+         .collect();
+
+      facets.sort_by(|a, b| {
+         let mut a = a.vertexes;
+         let mut b = b.vertexes;
+         a.sort_by(cmp_points);
+         b.sort_by(cmp_points);
+
+         a.iter().zip(&b)
+            .map(|(a, b)| cmp_points(a, b))
+            .find(|o| o.is_ne())
+            .unwrap_or(Ordering::Equal)
+      });
+
+      StlSolid { facets }
+   }
+
+   /// `None` if this solid has no facets.
+   pub fn bounding_box(&self) -> Option<BoundingBox> {
+      BoundingBox::of_points(self.facets.iter().flat_map(|f| f.vertexes))
+   }
+}
+
+impl IntoIterator for StlSolid {
+   type Item = Facet;
+   type IntoIter = std::vec::IntoIter<Facet>;
+
+   fn into_iter(self) -> Self::IntoIter {
+      self.facets.into_iter()
+   }
+}
+
+impl<'a> IntoIterator for &'a StlSolid {
+   type Item = &'a Facet;
+   type IntoIter = std::slice::Iter<'a, Facet>;
+
+   fn into_iter(self) -> Self::IntoIter {
+      self.facets.iter()
+   }
+}
+
+impl FromIterator<Facet> for StlSolid {
+   fn from_iter<I: IntoIterator<Item = Facet>>(iter: I) -> StlSolid {
+      StlSolid { facets: iter.into_iter().collect() }
+   }
+}
+
+impl Extend<Facet> for StlSolid {
+   fn extend<I: IntoIterator<Item = Facet>>(&mut self, iter: I) {
+      self.facets.extend(iter);
+   }
+}
+
+impl Add for StlSolid {
+   type Output = StlSolid;
+
+   fn add(self, rhs: StlSolid) -> StlSolid {
+      self.merge(rhs)
+   }
+}
+
+impl AddAssign for StlSolid {
+   fn add_assign(&mut self, rhs: StlSolid) {
+      self.extend(rhs);
+   }
+}
+
+/// A triangle in [StlSolid].
+///
+/// Vertexes are in counterclockwise order when seen from outside the solid.
+pub struct Facet {
+   vertexes: [Point; 3],
+
+   /// Normal vector stored with this facet, such as one read from an STL
+   /// file. `None` to compute it from the vertexes. Cleared when the vertexes
+   /// are moved other than by translation.
+   normal: Option<Vector>
 }
 
 impl Facet {
-   pub(crate) fn normal_vector(&self) -> Vector {
+   /// A facet whose front side is the side where the vertexes are seen
+   /// counterclockwise.
+   pub fn new(vertexes: [Point; 3]) -> Facet {
+      Facet { vertexes, normal: None }
+   }
+
+   /// A facet with the normal vector stored, which is returned by
+   /// [normal_vector](Facet::normal_vector) instead of the one computed from
+   /// the vertexes.
+   pub fn with_normal(vertexes: [Point; 3], normal: Vector) -> Facet {
+      Facet { vertexes, normal: Some(normal) }
+   }
+
+   pub fn vertexes(&self) -> [Point; 3] {
+      self.vertexes
+   }
+
+   /// Moves the vertexes. Also clears the stored normal vector, which is no
+   /// longer valid.
+   pub fn set_vertexes(&mut self, vertexes: [Point; 3]) {
+      self.vertexes = vertexes;
+      self.normal = None;
+   }
+
+   /// The normal vector stored with this facet, if any.
+   /// See [with_normal](Facet::with_normal).
+   pub fn normal(&self) -> Option<Vector> {
+      self.normal
+   }
+
+   /// The stored normal vector if any, otherwise computed from the vertexes.
+   /// Panics if this facet is degenerate and has no stored normal vector.
+   /// See [try_normal_vector](Facet::try_normal_vector).
+   pub fn normal_vector(&self) -> Vector {
+      self.try_normal_vector().unwrap_or_else(|e| panic!("{}", e))
+   }
+
+   /// Returns [GeometryError::ZeroVector] if this facet has no area and no
+   /// stored normal vector.
+   pub fn try_normal_vector(&self) -> Result<Vector, GeometryError> {
+      match self.normal {
+         Some(normal) => Ok(normal),
+         None => self.winding_normal_vector()
+      }
+   }
+
+   /// Normal vector computed from the vertexes, ignoring the stored one.
+   fn winding_normal_vector(&self) -> Result<Vector, GeometryError> {
       let v1 = Vector::between(&self.vertexes[0], &self.vertexes[1]);
       let v2 = Vector::between(&self.vertexes[1], &self.vertexes[2]);
-      v1.vector_product(&v2).to_unit_vector()
+      v1.vector_product(&v2).try_to_unit_vector()
+   }
+
+   pub fn area(&self) -> Exp<Size, 2> {
+      let v1 = Vector::between(&self.vertexes[0], &self.vertexes[1]);
+      let v2 = Vector::between(&self.vertexes[0], &self.vertexes[2]);
+
+      // vector_product returns Vector whose components are actually mm²
+      let norm = v1.vector_product(&v2).norm();
+      unsafe { Exp::new(norm.to_millimeter() / 2.0) }
+   }
+
+   pub fn centroid(&self) -> Point {
+      let [a, b, c] = self.vertexes;
+      Point {
+         matrix: (a.matrix + b.matrix + c.matrix) / 3
+      }
+   }
+
+   /// Signed volume of the tetrahedron formed by this facet and the origin.
+   /// Positive when this facet faces away from the origin.
+   pub(crate) fn signed_volume(&self) -> Exp<Size, 3> {
+      let [a, b, c] = self.vertexes.map(|v| Vector::between(&Point::ORIGIN, &v));
+
+      // vector_product returns Vector whose components are actually mm²
+      let triple_product = a.inner_product(&b.vector_product(&c));
+      unsafe { Exp::new(triple_product.0 / 6.0) }
+   }
+
+   pub fn perimeter(&self) -> Size {
+      let [a, b, c] = self.vertexes;
+      a.distance(&b) + b.distance(&c) + c.distance(&a)
+   }
+
+   pub fn longest_edge(&self) -> Size {
+      let [a, b, c] = self.vertexes;
+      a.distance(&b).max(b.distance(&c)).max(c.distance(&a))
+   }
+
+   /// Whether the specified point is on this facet.
+   ///
+   /// `tolerance` allows the point to be apart from this facet up to the
+   /// distance, both in the direction of the normal vector and outward over
+   /// the edges. The distances are compared roughly like other [Size]s, so a
+   /// point on an edge is contained even with the tolerance of 0.
+   ///
+   /// Always false for a degenerate facet, which has no plane.
+   pub fn contains_point(&self, point: &Point, tolerance: Size) -> bool {
+      let Ok(normal_vector) = self.winding_normal_vector() else {
+         return false;
+      };
+      let [a, b, c] = self.vertexes;
+
+      let distance_from_plane = Size::from(
+         Vector::between(&a, point).inner_product(&normal_vector)
+            / normal_vector.norm()
+      );
+
+      if distance_from_plane.abs() > tolerance {
+         return false;
+      }
+
+      // signed distance from the edge `p`→`q`. positive for inside.
+      let distance_from_edge = |p: &Point, q: &Point| {
+         let edge = Vector::between(p, q).to_unit_vector();
+         let inward = normal_vector.vector_product(&edge);
+         Size::from(
+            Vector::between(p, point).inner_product(&inward) / inward.norm()
+         )
+      };
+
+      distance_from_edge(&a, &b) >= -tolerance
+         && distance_from_edge(&b, &c) >= -tolerance
+         && distance_from_edge(&c, &a) >= -tolerance
+   }
+}
+
+impl Transform for Facet {
+   fn translated(&self, offset: &Vector) -> Facet {
+      Facet {
+         vertexes: self.vertexes.map(|v| v.translated(offset)),
+         normal: self.normal
+      }
+   }
+
+   fn rotated(&self, axis: &Line, angle: Angle) -> Facet {
+      Facet::new(self.vertexes.map(|v| v.rotated(axis, angle)))
+   }
+}
+
+/// Also reverses the order of vertexes so that the facet faces outward.
+impl InvertThrough for Facet {
+   fn inverted_through(&self, center: &Point) -> Facet {
+      let [a, b, c] = self.vertexes.map(|v| v.inverted_through(center));
+      Facet::new([a, c, b])
    }
 }
 
 impl Transform for StlSolid {
    fn translated(&self, offset: &Vector) -> StlSolid {
       let facets = self.facets.iter()
-         .map(|f| {
-            let vertexes = f.vertexes.map(|v| v.translated(offset));
-            Facet { vertexes }
-         })
+         .map(|f| f.translated(offset))
          .collect();
 
       StlSolid { facets }
@@ -32,10 +610,17 @@ impl Transform for StlSolid {
 
    fn rotated(&self, axis: &Line, angle: Angle) -> StlSolid {
       let facets = self.facets.iter()
-         .map(|f| {
-            let vertexes = f.vertexes.map(|v| v.rotated(axis, angle));
-            Facet { vertexes }
-         })
+         .map(|f| f.rotated(axis, angle))
+         .collect();
+
+      StlSolid { facets }
+   }
+}
+
+impl InvertThrough for StlSolid {
+   fn inverted_through(&self, center: &Point) -> StlSolid {
+      let facets = self.facets.iter()
+         .map(|f| f.inverted_through(center))
          .collect();
 
       StlSolid { facets }
@@ -44,35 +629,500 @@ impl Transform for StlSolid {
 
 #[cfg(test)]
 mod tests {
-   use crate::geometry::{Point, SizeLiteral, Vector};
-   use super::Facet;
+   use crate::geometry::{AngleLiteral, BoundingBox, Line, Point, SizeLiteral, Vector};
+   use crate::math::unit::Exp;
+   use crate::solid::{cube, cylinder, sphere, union, Cube, Location, Solid};
+   use crate::stl::MeshReport;
+   use crate::transform::{InvertThrough, Transform};
+   use noisy_float::prelude::*;
+   use super::{Facet, StlSolid};
 
-   #[test]
-   fn facet_normal_vector() {
-      let facet = Facet {
-         vertexes: [
+   fn stl_solid() -> StlSolid {
+      StlSolid::new(vec![
+         Facet::new([
             Point::ORIGIN,
-            Point::new(2.mm(), 4.mm(), 0.mm()),
-            Point::new(-2.mm(), 6.mm(), 0.mm())
+            Point::new(1.mm(), 0.mm(), 0.mm()),
+            Point::new(0.mm(), 1.mm(), 0.mm())
+         ]),
+         Facet::new([
+            Point::ORIGIN,
+            Point::new(0.mm(), 0.mm(), 1.mm()),
+            Point::new(1.mm(), 0.mm(), 0.mm())
+         ])
+      ])
+   }
+
+   #[test]
+   fn facets() {
+      let mut solid = stl_solid();
+      assert_eq!(solid.facet_count(), 2);
+
+      assert_eq!(
+         solid.facets().map(|f| f.normal_vector()).collect::<Vec<_>>(),
+         vec![Vector::Z_UNIT_VECTOR, Vector::Y_UNIT_VECTOR]
+      );
+
+      for f in solid.facets_mut() {
+         f.translate(&Vector::X_UNIT_VECTOR);
+      }
+
+      let facets = solid.into_facets();
+      assert_eq!(facets.len(), 2);
+      assert_eq!(facets[0].vertexes[0], Point::new(1.mm(), 0.mm(), 0.mm()));
+      assert_eq!(facets[1].vertexes[2], Point::new(2.mm(), 0.mm(), 0.mm()));
+   }
+
+   #[test]
+   fn into_iter() {
+      let normal_vectors: Vec<_> = (&stl_solid()).into_iter()
+         .map(|f| f.normal_vector())
+         .collect();
+      assert_eq!(normal_vectors, vec![Vector::Z_UNIT_VECTOR, Vector::Y_UNIT_VECTOR]);
+
+      let facets: Vec<Facet> = stl_solid().into_iter().collect();
+      assert_eq!(facets.len(), 2);
+   }
+
+   #[test]
+   fn from_iter_and_extend() {
+      let facets = stl_solid().into_facets();
+      let mut solid: StlSolid = facets.into_iter().collect();
+      assert_eq!(solid.facet_count(), 2);
+
+      solid.extend(stl_solid().into_facets());
+      assert_eq!(solid.facet_count(), 4);
+      assert_eq!(
+         solid.facets().map(|f| f.normal_vector()).collect::<Vec<_>>(),
+         vec![
+            Vector::Z_UNIT_VECTOR, Vector::Y_UNIT_VECTOR,
+            Vector::Z_UNIT_VECTOR, Vector::Y_UNIT_VECTOR
          ]
-      };
+      );
+   }
+
+   #[test]
+   fn validate() {
+      let cube = cube(Location::default(), (1.mm(), 2.mm(), 3.mm()))
+         .generate_stl_solid();
+      let report = cube.validate();
+      assert!(report.is_manifold());
+      assert!(report.is_watertight());
+      assert!(cube.is_watertight());
+
+      let mut facets = cube.into_facets();
+      let removed = facets.pop().unwrap();
+      let holed = StlSolid::new(facets);
+      let report = holed.validate();
+      assert!(!report.is_watertight());
+      assert!(report.non_manifold_edges.is_empty());
+      assert_eq!(report.boundary_edges.len(), 3);
+      for (a, b) in report.boundary_edges {
+         assert!(removed.vertexes.contains(&a));
+         assert!(removed.vertexes.contains(&b));
+      }
+
+      let mut facets = holed.into_facets();
+      facets.push(Facet::new(removed.vertexes));
+      facets.push(Facet::new(removed.vertexes));
+      facets.push(Facet::new([Point::ORIGIN; 3]));
+      let report = StlSolid::new(facets).validate();
+      assert_eq!(report.non_manifold_edges.len(), 3);
+      assert_eq!(report.duplicate_facets, vec![12]);
+      assert_eq!(report.degenerate_facets, vec![13]);
+   }
+
+   #[test]
+   fn validate_winding() {
+      let cylinder = cylinder(Location::default(), 3.mm(), 2.mm())
+         .generate_stl_solid();
+      assert_eq!(cylinder.validate(), MeshReport::default());
+
+      let mut facets = cylinder.into_facets();
+      let [a, b, c] = facets[5].vertexes;
+      facets[5] = Facet::new([a, c, b]);
+      let report = StlSolid::new(facets).validate();
+      assert!(report.is_manifold());
+      assert!(!report.is_watertight());
+      assert_eq!(report.inconsistent_windings.len(), 3);
+      assert!(report.inconsistent_windings.iter().all(|&(i, j)| i == 5 || j == 5));
+   }
+
+   #[test]
+   fn fill_holes() {
+      let cube = cube(Location::default(), (1.mm(), 2.mm(), 3.mm()));
+
+      let mut facets = cube.generate_stl_solid().into_facets();
+      facets.pop();
+      let mut solid = StlSolid::new(facets);
+      assert!(!solid.is_watertight());
+
+      solid.fill_holes();
+      assert!(solid.is_watertight());
+      assert_eq!(solid.facet_count(), 12);
+      assert_eq!(solid.volume(), unsafe { Exp::new(n64(6.0)) });
+
+      // a square hole on the top
+      let mut facets = cube.generate_stl_solid().into_facets();
+      facets.truncate(10);
+      let mut solid = StlSolid::new(facets);
+      solid.fill_holes();
+      assert!(solid.is_watertight());
+      assert_eq!(solid.volume(), unsafe { Exp::new(n64(6.0)) });
+   }
+
+   #[test]
+   fn inverted_through() {
+      let location = Location::default()
+         .translated(&Vector::new(1.mm(), 2.mm(), 3.mm()));
+      let solid = cube(location, (2.mm(), 4.mm(), 6.mm())).generate_stl_solid();
+      let center = Point::new(3.mm(), 2.mm(), 1.mm());
+      let inverted = solid.inverted_through(&center);
+
+      for (original, inverted) in solid.facets().zip(inverted.facets()) {
+         let [a, b, c] = original.vertexes;
+         let expected = [a, c, b].map(|v| Point {
+            matrix: center.matrix * 2 - v.matrix
+         });
+         assert_eq!(inverted.vertexes, expected);
+         assert_eq!(inverted.normal_vector(), -original.normal_vector());
+      }
+
+      assert_eq!(inverted.volume(), unsafe { Exp::new(n64(48.0)) });
+      assert_eq!(
+         inverted.center_of_mass(),
+         Some(Point::new(4.mm(), 0.mm(), -4.mm()))
+      );
+
+      // through its own center, a cube is the same cube
+      let center = Point::new(2.mm(), 4.mm(), 6.mm());
+      let inverted = solid.inverted_through(&center);
+      let mut expected: Vec<_> = solid.facets().flat_map(|f| f.vertexes).collect();
+      let mut actual: Vec<_> = inverted.facets().flat_map(|f| f.vertexes).collect();
+      let key = |p: &Point| (p.x(), p.y(), p.z());
+      expected.sort_by_key(key);
+      expected.dedup();
+      actual.sort_by_key(key);
+      actual.dedup();
+      assert_eq!(actual, expected);
+      assert!(inverted.is_watertight());
+   }
+
+   #[test]
+   fn facet_normal_vector() {
+      let facet = Facet::new([
+         Point::ORIGIN,
+         Point::new(2.mm(), 4.mm(), 0.mm()),
+         Point::new(-2.mm(), 6.mm(), 0.mm())
+      ]);
 
       assert_eq!(
          facet.normal_vector(),
          Vector::Z_UNIT_VECTOR
       );
 
-      let facet = Facet {
-         vertexes: [
-            Point::ORIGIN,
-            Point::new(0.mm(), 0.mm(), 3.mm()),
-            Point::new(2.mm(), 2.mm(), 0.mm())
-         ]
-      };
+      let facet = Facet::new([
+         Point::ORIGIN,
+         Point::new(0.mm(), 0.mm(), 3.mm()),
+         Point::new(2.mm(), 2.mm(), 0.mm())
+      ]);
 
       assert_eq!(
          facet.normal_vector(),
          Vector::new(-1.mm(), 1.mm(), 0.mm()).to_unit_vector()
       );
    }
+
+   #[test]
+   fn facet_stored_normal() {
+      let vertexes = [
+         Point::ORIGIN,
+         Point::new(1.mm(), 0.mm(), 0.mm()),
+         Point::new(0.mm(), 1.mm(), 0.mm())
+      ];
+      let stored = Vector::new(0.mm(), 0.6.mm(), 0.8.mm());
+
+      let facet = Facet::with_normal(vertexes, stored);
+      assert_eq!(facet.normal(), Some(stored));
+      assert_eq!(facet.normal_vector(), stored);
+
+      let translated = facet.translated(&Vector::X_UNIT_VECTOR);
+      assert_eq!(translated.normal(), Some(stored));
+
+      let rotated = facet.rotated(&Line::X_AXIS, 90.deg());
+      assert_eq!(rotated.normal(), None);
+      assert_eq!(rotated.normal_vector(), -Vector::Y_UNIT_VECTOR);
+
+      let mut moved = facet;
+      moved.set_vertexes([vertexes[0], vertexes[2], vertexes[1]]);
+      assert_eq!(moved.normal(), None);
+      assert_eq!(moved.normal_vector(), -Vector::Z_UNIT_VECTOR);
+   }
+
+   #[test]
+   fn volume() {
+      let cube = Cube::new(Location::default(), (2.mm(), 4.mm(), 6.mm()));
+      assert_eq!(
+         cube.generate_stl_solid().volume(),
+         unsafe { Exp::new(n64(48.0)) }
+      );
+
+      let cube = cube.translated(&Vector::new(-5.mm(), 3.mm(), 8.mm()));
+      assert_eq!(
+         cube.generate_stl_solid().volume(),
+         unsafe { Exp::new(n64(48.0)) }
+      );
+   }
+
+   #[test]
+   fn try_volume_and_surface_area() {
+      let cube = Cube::new(Location::default(), (2.mm(), 4.mm(), 6.mm()));
+      let solid = cube.generate_stl_solid();
+      assert_eq!(solid.try_volume(), Some(unsafe { Exp::new(n64(48.0)) }));
+      assert_eq!(solid.surface_area(), unsafe { Exp::new(n64(88.0)) });
+
+      let mut facets = solid.into_facets();
+      facets.pop();
+      let open = StlSolid::new(facets);
+      assert_eq!(open.try_volume(), None);
+      // the removed facet is a half of the 2×4 face
+      assert_eq!(open.surface_area(), unsafe { Exp::new(n64(88.0 - 4.0)) });
+   }
+
+   #[test]
+   fn center_of_mass() {
+      let cube = Cube::centered(Location::default(), (1.mm(), 1.mm(), 1.mm()));
+      assert_eq!(
+         cube.generate_stl_solid().center_of_mass(),
+         Some(Point::ORIGIN)
+      );
+
+      let location = Location::default()
+         .translated(&Vector::new(1.mm(), 2.mm(), 3.mm()));
+      let cube = Cube::new(location, (2.mm(), 4.mm(), 6.mm()));
+      assert_eq!(
+         cube.generate_stl_solid().center_of_mass(),
+         Some(Point::new(2.mm(), 4.mm(), 6.mm()))
+      );
+
+      assert_eq!(stl_solid().center_of_mass(), None);
+
+      let location = Location::default()
+         .translated(&Vector::new(1.mm(), 2.mm(), 3.mm()));
+      let center = sphere(location, 5.mm()).generate_stl_solid()
+         .center_of_mass().unwrap();
+      assert!(center.distance(&Point::new(1.mm(), 2.mm(), 3.mm())) < 0.001.mm());
+   }
+
+   #[test]
+   fn merge() {
+      let vertexes = |s: &StlSolid| -> Vec<Point> {
+         s.iter().flat_map(|f| f.vertexes).collect()
+      };
+      let a = cube(Location::default(), (1.mm(), 1.mm(), 1.mm()))
+         .generate_stl_solid();
+      let b = sphere(Location::default(), 1.mm()).generate_stl_solid();
+      let expected: Vec<_> = vertexes(&a).into_iter().chain(vertexes(&b)).collect();
+
+      let merged = StlSolid::EMPTY
+         .merge(a.translated(&Vector::ZERO))
+         .merge(b.translated(&Vector::ZERO));
+      assert_eq!(vertexes(&merged), expected);
+
+      let added = a.translated(&Vector::ZERO) + b.translated(&Vector::ZERO);
+      assert_eq!(vertexes(&added), expected);
+
+      let mut assigned = StlSolid::EMPTY;
+      assert!(assigned.is_empty());
+      assigned += a;
+      assigned += b;
+      assert!(!assigned.is_empty());
+      assert_eq!(vertexes(&assigned), expected);
+   }
+
+   #[test]
+   fn bounding_box() {
+      let location = Location::default()
+         .translated(&Vector::new(1.mm(), 2.mm(), 3.mm()));
+      let cube = Cube::new(location, (2.mm(), 4.mm(), 6.mm()));
+      let expected = BoundingBox::new(
+         Point::new(1.mm(), 2.mm(), 3.mm()),
+         Point::new(3.mm(), 6.mm(), 9.mm())
+      );
+
+      assert_eq!(cube.generate_stl_solid().bounding_box(), Some(expected));
+      assert_eq!(cube.bounding_box(), Some(expected));
+      assert_eq!(StlSolid::new(vec![]).bounding_box(), None);
+   }
+
+   #[test]
+   fn centered() {
+      let location = Location::default()
+         .translated(&Vector::new(-7.mm(), 3.mm(), 12.mm()))
+         .rotated(&Line::Z_AXIS, 30.deg());
+      let cube = Cube::new(location, (2.mm(), 4.mm(), 6.mm()));
+      let solid = cube.generate_stl_solid().centered();
+
+      let BoundingBox { min, max } = solid.bounding_box().unwrap();
+      assert_eq!(min.matrix + max.matrix, Point::ORIGIN.matrix);
+      assert_eq!(min.z(), -3.mm());
+   }
+
+   #[test]
+   fn on_bed() {
+      let location = Location::default()
+         .translated(&Vector::new(-7.mm(), 3.mm(), 12.mm()));
+      let cube = Cube::new(location, (2.mm(), 4.mm(), 6.mm()));
+      let solid = cube.generate_stl_solid().on_bed();
+
+      let BoundingBox { min, max } = solid.bounding_box().unwrap();
+      assert_eq!(min, Point::new(-7.mm(), 3.mm(), 0.mm()));
+      assert_eq!(max, Point::new(-5.mm(), 7.mm(), 6.mm()));
+   }
+
+   #[test]
+   fn facet_area() {
+      let facet = Facet::new([
+         Point::ORIGIN,
+         Point::new(1.mm(), 0.mm(), 0.mm()),
+         Point::new(0.mm(), 1.mm(), 0.mm())
+      ]);
+
+      assert_eq!(facet.area(), unsafe { Exp::new(n64(0.5)) });
+
+      let facet = Facet::new([
+         Point::new(1.mm(), 1.mm(), 1.mm()),
+         Point::new(1.mm(), 4.mm(), 1.mm()),
+         Point::new(1.mm(), 1.mm(), 5.mm())
+      ]);
+
+      assert_eq!(facet.area(), unsafe { Exp::new(n64(6.0)) });
+   }
+
+   #[test]
+   fn facet_centroid() {
+      let facet = Facet::new([
+         Point::ORIGIN,
+         Point::new(3.mm(), 0.mm(), 0.mm()),
+         Point::new(0.mm(), 6.mm(), 9.mm())
+      ]);
+
+      assert_eq!(facet.centroid(), Point::new(1.mm(), 2.mm(), 3.mm()));
+   }
+
+   #[test]
+   fn facet_area_and_centroid_of_right_triangle() {
+      let facet = Facet::new([
+         Point::ORIGIN,
+         Point::new(3.mm(), 0.mm(), 0.mm()),
+         Point::new(0.mm(), 4.mm(), 0.mm())
+      ]);
+
+      assert_eq!(facet.area(), unsafe { Exp::new(n64(6.0)) });
+      assert_eq!(
+         facet.centroid(),
+         Point::new(1.mm(), (4.0 / 3.0).mm(), 0.mm())
+      );
+   }
+
+   #[test]
+   fn facet_perimeter() {
+      let facet = Facet::new([
+         Point::ORIGIN,
+         Point::new(3.mm(), 0.mm(), 0.mm()),
+         Point::new(0.mm(), 4.mm(), 0.mm())
+      ]);
+
+      assert_eq!(facet.perimeter(), 12.mm());
+   }
+
+   #[test]
+   fn facet_contains_point() {
+      let facet = Facet::new([
+         Point::ORIGIN,
+         Point::new(4.mm(), 0.mm(), 0.mm()),
+         Point::new(0.mm(), 4.mm(), 0.mm())
+      ]);
+
+      let tolerance = 0.01.mm();
+
+      // interior
+      assert!(facet.contains_point(&Point::new(1.mm(), 1.mm(), 0.mm()), tolerance));
+      // boundary
+      assert!(facet.contains_point(&Point::new(2.mm(), 0.mm(), 0.mm()), tolerance));
+      assert!(facet.contains_point(&Point::new(2.mm(), 2.mm(), 0.mm()), tolerance));
+      assert!(facet.contains_point(&Point::new(4.mm(), 0.mm(), 0.mm()), tolerance));
+      // exterior
+      assert!(!facet.contains_point(&Point::new(3.mm(), 3.mm(), 0.mm()), tolerance));
+      assert!(!facet.contains_point(&Point::new(-1.mm(), 1.mm(), 0.mm()), tolerance));
+      assert!(!facet.contains_point(&Point::new(1.mm(), 1.mm(), 1.mm()), tolerance));
+      // within tolerance
+      assert!(facet.contains_point(&Point::new(1.mm(), -0.005.mm(), 0.mm()), tolerance));
+      assert!(facet.contains_point(&Point::new(1.mm(), 1.mm(), 0.005.mm()), tolerance));
+
+      // on an edge, whose coordinates have rounding errors
+      let rotated = facet.rotated(&Line::X_AXIS, 30.deg());
+      let [_, b, c] = rotated.vertexes;
+      let middle = b.translated(&(Vector::between(&b, &c) * 0.5));
+      assert!(rotated.contains_point(&middle, 0.mm()));
+
+      let degenerate = Facet::new([
+         Point::ORIGIN,
+         Point::new(2.mm(), 0.mm(), 0.mm()),
+         Point::new(4.mm(), 0.mm(), 0.mm())
+      ]);
+      assert!(!degenerate.contains_point(&Point::new(1.mm(), 0.mm(), 0.mm()), tolerance));
+   }
+
+   #[test]
+   fn split_into_shells() {
+      let shells = union(|mut c| {
+         c <<= cube(Location::default(), (1.mm(), 1.mm(), 1.mm()));
+
+         let location = Location::default()
+            .translated(&Vector::new(3.mm(), 0.mm(), 0.mm()));
+         c <<= cube(location, (1.mm(), 1.mm(), 1.mm()));
+      }).generate_stl_solid().split_into_shells();
+
+      assert_eq!(shells.len(), 2);
+      for (shell, x) in shells.iter().zip([0.mm(), 3.mm()]) {
+         assert_eq!(shell.facet_count(), 12);
+         assert!(shell.facets().flat_map(|f| f.vertexes).all(|v| v.x() >= x));
+         assert!(shell.facets().flat_map(|f| f.vertexes).all(|v| v.x() <= x + 1.mm()));
+      }
+
+      let shells = cube(Location::default(), (1.mm(), 2.mm(), 3.mm()))
+         .generate_stl_solid().split_into_shells();
+      assert_eq!(shells.len(), 1);
+      assert_eq!(shells[0].facet_count(), 12);
+
+      assert!(StlSolid::new(vec![]).split_into_shells().is_empty());
+   }
+
+   #[test]
+   fn normalize_keeps_winding_of_non_manifold() {
+      fn tetrahedron(a: Point, b: Point, c: Point) -> Vec<Facet> {
+         vec![
+            Facet::new([Point::ORIGIN, b, a]),
+            Facet::new([Point::ORIGIN, a, c]),
+            Facet::new([Point::ORIGIN, c, b]),
+            Facet::new([a, b, c])
+         ]
+      }
+
+      let x = Point::new(6.mm(), 0.mm(), 0.mm());
+      let y = Point::new(0.mm(), 6.mm(), 0.mm());
+      let z = Point::new(0.mm(), 0.mm(), 6.mm());
+      let minus_y = Point::new(0.mm(), -6.mm(), 0.mm());
+      let minus_z = Point::new(0.mm(), 0.mm(), -6.mm());
+
+      let solid = StlSolid::new(tetrahedron(x, minus_z, minus_y)).normalize();
+      assert_eq!(solid.volume(), unsafe { Exp::new(n64(36.0)) });
+
+      // the edge from the origin to x is shared by 4 facets
+      let mut facets = tetrahedron(x, y, z);
+      facets.extend(tetrahedron(x, minus_z, minus_y));
+      let solid = StlSolid::new(facets).normalize();
+      assert_eq!(solid.facet_count(), 8);
+      assert_eq!(solid.volume(), unsafe { Exp::new(n64(0.0)) });
+   }
 }
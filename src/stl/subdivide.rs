@@ -0,0 +1,120 @@
+use crate::geometry::{Point, Size};
+use crate::stl::{IndexedMesh, StlSolid};
+use std::collections::HashMap;
+
+impl StlSolid {
+   /// Splits facets until no edge is longer than `max_edge_length`.
+   ///
+   /// Each long edge is split at its midpoint, together with the facets on
+   /// both sides, so that a watertight solid stays watertight. The surface
+   /// does not change since the facets are split on their planes.
+   ///
+   /// Panics if `max_edge_length` is not positive.
+   pub fn subdivided(&self, max_edge_length: Size) -> StlSolid {
+      if max_edge_length <= Size::ZERO {
+         panic!("The maximum edge length must be positive.");
+      }
+
+      let mut mesh = self.to_indexed(Size::ZERO);
+      while split_long_edges(&mut mesh, max_edge_length) {}
+      mesh.to_stl_solid()
+   }
+}
+
+/// Splits every edge longer than `max_edge_length` once.
+/// Returns `false` if there are no such edges.
+fn split_long_edges(mesh: &mut IndexedMesh, max_edge_length: Size) -> bool {
+   let mut midpoints: HashMap<(u32, u32), u32> = HashMap::new();
+
+   for t in &mesh.triangles {
+      for k in 0..3 {
+         let (a, b) = (t[k], t[(k + 1) % 3]);
+         let key = (a.min(b), a.max(b));
+         if midpoints.contains_key(&key) {
+            continue;
+         }
+
+         let (p, q) = (mesh.vertexes[a as usize], mesh.vertexes[b as usize]);
+         if p.distance(&q) > max_edge_length {
+            mesh.vertexes.push(midpoint(&p, &q));
+            let index = u32::try_from(mesh.vertexes.len() - 1).expect("Too many vertexes.");
+            midpoints.insert(key, index);
+         }
+      }
+   }
+
+   if midpoints.is_empty() {
+      return false;
+   }
+
+   let midpoint_of = |a: u32, b: u32| midpoints.get(&(a.min(b), a.max(b))).copied();
+
+   mesh.triangles = mesh.triangles.iter()
+      .flat_map(|&t| {
+         let m = [0, 1, 2].map(|k| midpoint_of(t[k], t[(k + 1) % 3]));
+
+         // rotate so that the split edges come first
+         let r = (0..3)
+            .find(|&r| {
+               let split = [0, 1, 2].map(|k| m[(k + r) % 3].is_some());
+               matches!(split, [true, false, false] | [true, true, false] | [true, true, true])
+            })
+            .unwrap_or(0);
+         let [a, b, c] = [0, 1, 2].map(|k| t[(k + r) % 3]);
+         let [ab, bc, ca] = [0, 1, 2].map(|k| m[(k + r) % 3]);
+
+         match (ab, bc, ca) {
+            (None, None, None) => vec![[a, b, c]],
+            (Some(ab), None, None) => vec![[a, ab, c], [ab, b, c]],
+            (Some(ab), Some(bc), None) => vec![[ab, b, bc], [a, ab, bc], [a, bc, c]],
+            (Some(ab), Some(bc), Some(ca)) => {
+               vec![[a, ab, ca], [ab, b, bc], [ca, bc, c], [ab, bc, ca]]
+            }
+            _ => unreachable!()
+         }
+      })
+      .collect();
+
+   true
+}
+
+fn midpoint(p: &Point, q: &Point) -> Point {
+   Point::new(
+      (p.x() + q.x()) / 2,
+      (p.y() + q.y()) / 2,
+      (p.z() + q.z()) / 2
+   )
+}
+
+#[cfg(test)]
+mod tests {
+   use crate::geometry::SizeLiteral;
+   use crate::solid::{cube, cylinder, Location, Solid};
+
+   #[test]
+   fn max_edge_length() {
+      let stl_solid = cube(Location::default(), (1.mm(), 2.mm(), 3.mm()))
+         .generate_stl_solid();
+      let subdivided = stl_solid.subdivided(0.3.mm());
+
+      assert!(subdivided.facets.iter().all(|f| f.longest_edge() <= 0.3.mm()));
+      assert!(subdivided.validate().is_watertight());
+      assert_eq!(subdivided.volume(), stl_solid.volume());
+      assert_eq!(subdivided.surface_area(), stl_solid.surface_area());
+      assert_eq!(subdivided.bounding_box(), stl_solid.bounding_box());
+   }
+
+   #[test]
+   fn cap_fan() {
+      let stl_solid = cylinder(Location::default(), 2.mm(), 5.mm()).generate_stl_solid();
+      let subdivided = stl_solid.subdivided(1.mm());
+
+      assert!(subdivided.facet_count() > stl_solid.facet_count());
+      assert!(subdivided.facets.iter().all(|f| f.longest_edge() <= 1.mm()));
+      assert!(subdivided.validate().is_watertight());
+      assert_eq!(subdivided.volume(), stl_solid.volume());
+
+      let unchanged = stl_solid.subdivided(100.mm());
+      assert_eq!(unchanged.facet_count(), stl_solid.facet_count());
+   }
+}
@@ -1,7 +1,15 @@
 use crate::geometry::{Point, Size, Vector};
+use crate::solid::Solid;
 use crate::stl::stl_solid::{Facet, StlSolid};
 use anyhow::Result;
+use memmap2::MmapMut;
+use rayon::prelude::{
+   IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator,
+   ParallelSliceMut
+};
+use std::fs::OpenOptions;
 use std::io::Write;
+use std::path::Path;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -10,17 +18,212 @@ pub enum StlWriteError {
    TooManyFacets,
 }
 
+/// Something wrong in the written solid, which did not stop writing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StlWriteWarning {
+   /// The facet at the index has no area. Written with a zero normal vector.
+   DegenerateFacet(usize),
+}
+
+/// Format of STL files.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum StlFormat {
+   #[default]
+   Binary,
+   /// Human readable format, for diffs and debugging. `precision` is
+   /// the number of digits after the decimal point, or `None` for the fewest
+   /// digits which are read back as the same `f32`.
+   Ascii { name: String, precision: Option<usize> }
+}
+
+/// Number of facets written between calls of the progress callback.
+const PROGRESS_INTERVAL: usize = 1024;
+
+const HEADER_BYTES: usize = 80 + 4;
+const FACET_BYTES: usize = 4 * 3 * 4 + 2;
+
 /// Write the specified Solid as binary STL.
+///
+/// Degenerate facets without stored normal vectors are written with a zero
+/// normal vector. See [write_stl_with_warnings] to know which facets are.
 pub fn write_stl(output: &mut dyn Write, solid: &StlSolid) -> Result<()> {
+   write_stl_with_progress(output, solid, |_, _| {})
+}
+
+/// Same as [write_stl], but returns warnings for the facets which could not
+/// be written correctly.
+pub fn write_stl_with_warnings(
+   output: &mut dyn Write,
+   solid: &StlSolid
+) -> Result<Vec<StlWriteWarning>> {
+   write_binary(output, solid, |_, _| {})
+}
+
+/// Same as [write_stl], but calls `progress` with
+/// `(facets_written, total)` periodically.
+///
+/// `progress` is called with `0` first, and with `total` at last.
+pub fn write_stl_with_progress(
+   output: &mut dyn Write,
+   solid: &StlSolid,
+   progress: impl FnMut(usize, usize)
+) -> Result<()> {
+   write_binary(output, solid, progress)?;
+   Ok(())
+}
+
+fn write_binary(
+   output: &mut dyn Write,
+   solid: &StlSolid,
+   mut progress: impl FnMut(usize, usize)
+) -> Result<Vec<StlWriteWarning>> {
+   let total = solid.facets.len();
+   let mut warnings = vec![];
+
    write_header(output)?;
-   write_facets_count(output, solid.facets.len())?;
-   for f in &solid.facets {
-      write_facet(output, f)?;
+   write_facets_count(output, total)?;
+   progress(0, total);
+
+   for (i, f) in solid.facets.iter().enumerate() {
+      if !write_facet(output, f)? {
+         warnings.push(degenerate_facet(i));
+      }
+
+      let written = i + 1;
+      if written % PROGRESS_INTERVAL == 0 || written == total {
+         progress(written, total);
+      }
+   }
+
+   Ok(warnings)
+}
+
+/// Same as [write_stl], but writes the [normalized](StlSolid::normalize)
+/// solid, so that the same shape is always written as the same bytes.
+pub fn write_stl_deterministic(output: &mut dyn Write, solid: &StlSolid) -> Result<()> {
+   let normalized = solid.facets.iter()
+      .map(|f| Facet::new(f.vertexes()))
+      .collect::<StlSolid>()
+      .normalize();
+
+   write_stl(output, &normalized)
+}
+
+/// Same as [write_stl], but writes the facets into the file concurrently.
+/// The output is the same bytes as [write_stl].
+///
+/// Since every facet is 50 bytes in binary STL, the offset of each facet is
+/// known before writing. The file is extended to the whole length first, and
+/// mapped to the memory so that the facets are written in parallel.
+/// Worth for solids of millions of facets.
+pub fn write_stl_parallel(path: impl AsRef<Path>, solid: &dyn Solid) -> Result<()> {
+   write_stl_parallel_with_warnings(path, solid)?;
+   Ok(())
+}
+
+/// Same as [write_stl_parallel], but returns warnings for the facets which
+/// could not be written correctly, in the order of the facets.
+pub fn write_stl_parallel_with_warnings(
+   path: impl AsRef<Path>,
+   solid: &dyn Solid
+) -> Result<Vec<StlWriteWarning>> {
+   let stl_solid = solid.generate_stl_solid();
+   let total = stl_solid.facets.len();
+   if total > u32::MAX as usize {
+      return Err(StlWriteError::TooManyFacets.into());
+   }
+
+   let file = OpenOptions::new()
+      .read(true)
+      .write(true)
+      .create(true)
+      .truncate(true)
+      .open(path)?;
+   file.set_len((HEADER_BYTES + FACET_BYTES * total) as u64)?;
+
+   // SAFETY: The file has just been truncated and extended by this function.
+   // Modifying it from other processes while mapped is undefined behavior,
+   // as with any memory-mapped file, and is not expected for an output file.
+   let mut mmap = unsafe { MmapMut::map_mut(&file)? };
+   let (mut header, body) = mmap.split_at_mut(HEADER_BYTES);
+
+   write_header(&mut header)?;
+   write_facets_count(&mut header, total)?;
+
+   let warnings = body.par_chunks_mut(FACET_BYTES)
+      .zip(stl_solid.facets.par_iter())
+      .enumerate()
+      .map(|(i, (mut chunk, f))| {
+         let written = write_facet(&mut chunk, f)?;
+         Ok((!written).then(|| degenerate_facet(i)))
+      })
+      .collect::<Result<Vec<_>>>()?;
+
+   mmap.flush()?;
+
+   Ok(warnings.into_iter().flatten().collect())
+}
+
+/// Write the specified Solid as ASCII STL, whose numbers have the fewest
+/// digits to be read back as the same `f32`.
+pub fn write_stl_ascii(output: &mut dyn Write, solid: &StlSolid, name: &str) -> Result<()> {
+   write_stl_as(output, solid, &StlFormat::Ascii { name: name.to_string(), precision: None })
+}
+
+/// Write the specified Solid in the specified format.
+pub fn write_stl_as(
+   output: &mut dyn Write,
+   solid: &StlSolid,
+   format: &StlFormat
+) -> Result<()> {
+   let (name, precision) = match format {
+      StlFormat::Binary => return write_stl(output, solid),
+      StlFormat::Ascii { name, precision } => (name, *precision)
+   };
+
+   let number = |size: Size| {
+      // + 0.0 turns -0 into 0
+      let f = size.0.raw() as f32 + 0.0;
+      match precision {
+         Some(precision) => format!("{:.*e}", precision, f),
+         None => format!("{:e}", f)
+      }
+   };
+   let numbers = |x: Size, y: Size, z: Size| {
+      format!("{} {} {}", number(x), number(y), number(z))
+   };
+
+   writeln!(output, "solid {}", name)?;
+
+   for (i, f) in solid.facets.iter().enumerate() {
+      let normal_vector = f.try_normal_vector().unwrap_or_else(|_| {
+         degenerate_facet(i);
+         Vector::ZERO
+      });
+
+      writeln!(
+         output, "  facet normal {}",
+         numbers(normal_vector.x(), normal_vector.y(), normal_vector.z())
+      )?;
+      writeln!(output, "    outer loop")?;
+      for v in &f.vertexes() {
+         writeln!(output, "      vertex {}", numbers(v.x(), v.y(), v.z()))?;
+      }
+      writeln!(output, "    endloop")?;
+      writeln!(output, "  endfacet")?;
    }
 
+   writeln!(output, "endsolid {}", name)?;
+
    Ok(())
 }
 
+fn degenerate_facet(index: usize) -> StlWriteWarning {
+   #[cfg(feature = "tracing")]
+   tracing::warn!(index, "degenerate facet is written with a zero normal vector");
+   StlWriteWarning::DegenerateFacet(index)
+}
+
 fn write_header(output: &mut dyn Write) -> Result<()> {
    output.write_all(&[0; 80])?;
    Ok(())
@@ -37,14 +240,16 @@ fn write_facets_count(output: &mut dyn Write, facets_count: usize) -> Result<()>
    Ok(())
 }
 
-fn write_facet(output: &mut dyn Write, facet: &Facet) -> Result<()> {
-   write_vector(output, &facet.normal_vector())?;
-   for v in &facet.vertexes {
+/// Returns false if the facet is degenerate.
+fn write_facet(output: &mut dyn Write, facet: &Facet) -> Result<bool> {
+   let normal_vector = facet.try_normal_vector().ok();
+   write_vector(output, &normal_vector.unwrap_or(Vector::ZERO))?;
+   for v in &facet.vertexes() {
       write_point(output, v)?;
    }
    output.write(&[0; 2])?;
 
-   Ok(())
+   Ok(normal_vector.is_some())
 }
 
 fn write_vector(output: &mut dyn Write, vector: &Vector) -> Result<()> {
@@ -69,11 +274,18 @@ fn write_size(output: &mut dyn Write, size: Size) -> Result<()> {
 
 #[cfg(test)]
 mod tests {
-   use super::write_stl;
-   use crate::geometry::{Point, Size};
+   use super::{
+      write_stl, write_stl_as, write_stl_ascii, write_stl_deterministic,
+      write_stl_parallel, write_stl_parallel_with_warnings, write_stl_with_progress,
+      write_stl_with_warnings, StlFormat, StlWriteWarning
+   };
+   use crate::geometry::{Point, Size, SizeLiteral, Vector};
    use crate::math::conversion::ToN64;
    use crate::math::rough_fp::rough_eq;
+   use crate::solid::{cube, sphere, Location, Solid};
    use crate::stl::stl_solid::{Facet, StlSolid};
+   use crate::transform::Transform;
+   use std::io::Write;
 
    macro_rules! solid {
       ($($f:expr),+) => (
@@ -84,7 +296,7 @@ mod tests {
    }
 
    fn facet(v1: Point, v2: Point, v3: Point) -> Facet {
-      Facet { vertexes: [v1, v2, v3] }
+      Facet::new([v1, v2, v3])
    }
 
    fn vertex(x: i32, y: i32, z: i32) -> Point {
@@ -151,23 +363,268 @@ mod tests {
          assert_rough_eq(f32_at(&output, facet_start +  4), normal_vector.y().0.raw() as f32);
          assert_rough_eq(f32_at(&output, facet_start +  8), normal_vector.z().0.raw() as f32);
 
-         let vertex1 = solid.facets[i].vertexes[0];
+         let vertex1 = solid.facets[i].vertexes()[0];
          assert_rough_eq(f32_at(&output, facet_start + 12), vertex1.x().0.raw() as f32);
          assert_rough_eq(f32_at(&output, facet_start + 16), vertex1.y().0.raw() as f32);
          assert_rough_eq(f32_at(&output, facet_start + 20), vertex1.z().0.raw() as f32);
 
-         let vertex2 = solid.facets[i].vertexes[1];
+         let vertex2 = solid.facets[i].vertexes()[1];
          assert_rough_eq(f32_at(&output, facet_start + 24), vertex2.x().0.raw() as f32);
          assert_rough_eq(f32_at(&output, facet_start + 28), vertex2.y().0.raw() as f32);
          assert_rough_eq(f32_at(&output, facet_start + 32), vertex2.z().0.raw() as f32);
 
-         let vertex3 = solid.facets[i].vertexes[2];
+         let vertex3 = solid.facets[i].vertexes()[2];
          assert_rough_eq(f32_at(&output, facet_start + 36), vertex3.x().0.raw() as f32);
          assert_rough_eq(f32_at(&output, facet_start + 40), vertex3.y().0.raw() as f32);
          assert_rough_eq(f32_at(&output, facet_start + 44), vertex3.z().0.raw() as f32);
       }
    }
 
+   #[test]
+   fn degenerate_facet() {
+      let solid = solid!(
+         facet(vertex(0, 0, 0), vertex(1, 0, 0), vertex(0, 1, 0)),
+         facet(vertex(0, 0, 0), vertex(1, 1, 1), vertex(2, 2, 2)),
+         facet(vertex(3, 0, 0), vertex(3, 0, 0), vertex(3, 0, 0))
+      );
+
+      let mut output = vec![];
+      let warnings = write_stl_with_warnings(&mut output, &solid).unwrap();
+      assert_eq!(
+         warnings,
+         vec![StlWriteWarning::DegenerateFacet(1), StlWriteWarning::DegenerateFacet(2)]
+      );
+
+      let facet_start = |i: usize| 80 + 4 + 50 * i;
+      let normal_at = |i: usize| [0, 4, 8].map(|o| f32_at(&output, facet_start(i) + o));
+
+      assert_eq!(normal_at(0), [0.0, 0.0, 1.0]);
+      assert_eq!(normal_at(1), [0.0; 3]);
+      assert_eq!(normal_at(2), [0.0; 3]);
+      assert_eq!(f32_at(&output, facet_start(2) + 12), 3.0);
+   }
+
+   #[test]
+   fn stored_normal() {
+      let stored = Vector::new(0.mm(), 1.mm(), 0.mm());
+      let solid = solid!(
+         Facet::with_normal([vertex(0, 0, 0), vertex(1, 0, 0), vertex(0, 1, 0)], stored),
+         Facet::with_normal([vertex(0, 0, 0), vertex(1, 1, 1), vertex(2, 2, 2)], stored),
+         facet(vertex(0, 0, 0), vertex(1, 0, 0), vertex(0, 1, 0))
+      );
+
+      let mut output = vec![];
+      let warnings = write_stl_with_warnings(&mut output, &solid).unwrap();
+      assert!(warnings.is_empty());
+
+      let facet_start = |i: usize| 80 + 4 + 50 * i;
+      let normal_at = |i: usize| [0, 4, 8].map(|o| f32_at(&output, facet_start(i) + o));
+
+      assert_eq!(normal_at(0), [0.0, 1.0, 0.0]);
+      assert_eq!(normal_at(1), [0.0, 1.0, 0.0]);
+      assert_eq!(normal_at(2), [0.0, 0.0, 1.0]);
+   }
+
+   #[test]
+   fn progress() {
+      let facets = (0..2500)
+         .map(|i| facet(vertex(i, 0, 0), vertex(i, 1, 0), vertex(i, 0, 1)))
+         .collect();
+      let solid = StlSolid { facets };
+
+      let mut calls = vec![];
+      let mut output = vec![];
+      write_stl_with_progress(&mut output, &solid, |written, total| {
+         calls.push((written, total));
+      }).unwrap();
+
+      assert_eq!(calls, vec![(0, 2500), (1024, 2500), (2048, 2500), (2500, 2500)]);
+
+      let mut expected = vec![];
+      write_stl(&mut expected, &solid).unwrap();
+      assert_eq!(output, expected);
+
+      let mut calls = vec![];
+      let empty = StlSolid { facets: vec![] };
+      write_stl_with_progress(&mut vec![], &empty, |written, total| {
+         calls.push((written, total));
+      }).unwrap();
+      assert_eq!(calls, vec![(0, 0)]);
+   }
+
+   #[test]
+   fn deterministic() {
+      let cube = cube(Location::default(), (1.mm(), 2.mm(), 3.mm()));
+      let offset = Vector::new(5.mm(), 0.mm(), 0.mm());
+
+      // generated at another location and moved back, in reversed order
+      // and starting from another vertex of each facet
+      let moved: StlSolid = cube.translated(&offset)
+         .generate_stl_solid()
+         .translated(&-offset)
+         .into_facets().into_iter()
+         .rev()
+         .map(|f| {
+            let [a, b, c] = f.vertexes();
+            facet(b, c, a)
+         })
+         .collect();
+
+      let mut expected = vec![];
+      write_stl_deterministic(&mut expected, &cube.generate_stl_solid()).unwrap();
+      let mut actual = vec![];
+      write_stl_deterministic(&mut actual, &moved).unwrap();
+      assert_eq!(actual, expected);
+
+      let mut original = vec![];
+      write_stl(&mut original, &moved).unwrap();
+      assert_ne!(original, expected);
+
+      // some facets are flipped
+      let flipped: StlSolid = cube.generate_stl_solid()
+         .into_facets().into_iter()
+         .enumerate()
+         .map(|(i, f)| {
+            let [a, b, c] = f.vertexes();
+            if i % 3 == 0 { facet(a, c, b) } else { f }
+         })
+         .collect();
+
+      let mut actual = vec![];
+      write_stl_deterministic(&mut actual, &flipped).unwrap();
+      assert_eq!(actual, expected);
+   }
+
+   #[test]
+   fn ascii() {
+      let solid = solid!(
+         facet(vertex(0, 0, 0), vertex(10, 0, 0), vertex(0, 10, 0))
+      );
+
+      let mut output = vec![];
+      write_stl_ascii(&mut output, &solid, "triangle").unwrap();
+      assert_eq!(
+         String::from_utf8(output).unwrap(),
+         "solid triangle\n\
+          \x20 facet normal 0e0 0e0 1e0\n\
+          \x20   outer loop\n\
+          \x20     vertex 0e0 0e0 0e0\n\
+          \x20     vertex 1e1 0e0 0e0\n\
+          \x20     vertex 0e0 1e1 0e0\n\
+          \x20   endloop\n\
+          \x20 endfacet\n\
+          endsolid triangle\n"
+      );
+
+      let mut output = vec![];
+      let format = StlFormat::Ascii { name: "triangle".to_string(), precision: Some(2) };
+      write_stl_as(&mut output, &solid, &format).unwrap();
+      let output = String::from_utf8(output).unwrap();
+      assert!(output.contains("      vertex 1.00e1 0.00e0 0.00e0\n"), "{}", output);
+   }
+
+   #[test]
+   fn ascii_round_trip() {
+      let solid = sphere(Location::default(), 3.7.mm()).generate_stl_solid();
+
+      let mut output = vec![];
+      write_stl_ascii(&mut output, &solid, "sphere").unwrap();
+
+      let output = String::from_utf8(output).unwrap();
+      let read: Vec<[f32; 3]> = output.lines()
+         .filter_map(|l| l.trim().strip_prefix("vertex "))
+         .map(|l| {
+            let numbers: Vec<f32> = l.split(' ').map(|n| n.parse().unwrap()).collect();
+            [numbers[0], numbers[1], numbers[2]]
+         })
+         .collect();
+
+      let expected: Vec<[f32; 3]> = solid.facets.iter()
+         .flat_map(|f| f.vertexes())
+         .map(|v| [v.x(), v.y(), v.z()].map(|s| s.0.raw() as f32))
+         .collect();
+
+      assert_eq!(read, expected);
+
+      let mut binary = vec![];
+      write_stl_as(&mut binary, &solid, &StlFormat::Binary).unwrap();
+      let mut expected = vec![];
+      write_stl(&mut expected, &solid).unwrap();
+      assert_eq!(binary, expected);
+   }
+
+   #[test]
+   fn parallel() {
+      struct Facets(StlSolid);
+
+      impl Solid for Facets {
+         fn generate_stl_solid(&self) -> StlSolid {
+            self.0.facets.iter().map(|f| Facet::new(f.vertexes())).collect()
+         }
+      }
+
+      let path = std::env::temp_dir()
+         .join(format!("typed-scad-write-stl-parallel-{}.stl", std::process::id()));
+
+      let assert_same_as_sequential = |solid: StlSolid| {
+         let mut expected = vec![];
+         let expected_warnings = write_stl_with_warnings(&mut expected, &solid).unwrap();
+
+         let warnings = write_stl_parallel_with_warnings(&path, &Facets(solid)).unwrap();
+         assert_eq!(std::fs::read(&path).unwrap(), expected);
+         assert_eq!(warnings, expected_warnings);
+         warnings
+      };
+
+      assert_eq!(assert_same_as_sequential(StlSolid::EMPTY), vec![]);
+
+      let warnings = assert_same_as_sequential(solid!(
+         facet(vertex(0, 0, 0), vertex(10, 0, 0), vertex(0, 10, 0)),
+         facet(vertex(0, 0, 0), vertex(5, 0, 0), vertex(10, 0, 0)),
+         facet(vertex(0, 0, 0), vertex(0, 10, 0), vertex(0, 0, 10))
+      ));
+      assert_eq!(warnings, vec![StlWriteWarning::DegenerateFacet(1)]);
+
+      // more than 100k facets
+      let large = sphere(Location::default(), 100.mm())
+         .with_fragment_count(400)
+         .generate_stl_solid();
+      assert!(large.facets.len() > 100_000);
+      assert_same_as_sequential(large);
+
+      std::fs::remove_file(&path).unwrap();
+   }
+
+   /// Run with `cargo test --release parallel_speedup -- --ignored --nocapture`
+   /// on a machine with multiple cores.
+   #[test]
+   #[ignore]
+   fn parallel_speedup() {
+      let solid = sphere(Location::default(), 100.mm()).with_fragment_count(2000);
+      let path = std::env::temp_dir()
+         .join(format!("typed-scad-parallel-speedup-{}.stl", std::process::id()));
+
+      let start = std::time::Instant::now();
+      let stl_solid = solid.generate_stl_solid();
+      let mut output = std::io::BufWriter::new(std::fs::File::create(&path).unwrap());
+      write_stl(&mut output, &stl_solid).unwrap();
+      output.flush().unwrap();
+      drop(output);
+      let sequential = start.elapsed();
+
+      let start = std::time::Instant::now();
+      write_stl_parallel(&path, &solid).unwrap();
+      let parallel = start.elapsed();
+
+      println!(
+         "{} facets: write_stl {:?}, write_stl_parallel {:?}",
+         stl_solid.facets.len(), sequential, parallel
+      );
+      assert!(parallel < sequential);
+
+      std::fs::remove_file(&path).unwrap();
+   }
+
    fn u32_at(vec: &Vec<u8>, index: usize) -> u32 {
       u32::from_le_bytes(vec[index..(index + 4)].try_into().unwrap())
    }
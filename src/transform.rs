@@ -1,3 +1,7 @@
+mod invert_through;
 mod transform;
+mod transform_all;
 
+pub use invert_through::InvertThrough;
 pub use transform::Transform;
+pub use transform_all::{transform_all, TransformAll};
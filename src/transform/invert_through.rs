@@ -0,0 +1,15 @@
+use crate::geometry::Point;
+
+/// Central inversion. Moves every point `p` to `center * 2 - p`.
+///
+/// This is not a rotation; a solid is turned inside out by this.
+/// So this is not a part of [Transform](crate::transform::Transform), and
+/// solids whose facets are generated from a [Location](crate::solid::Location)
+/// don't implement this.
+pub trait InvertThrough: Sized {
+   fn inverted_through(&self, center: &Point) -> Self;
+
+   fn invert_through(&mut self, center: &Point) {
+      *self = self.inverted_through(center);
+   }
+}
@@ -0,0 +1,157 @@
+use crate::geometry::{Angle, Line, Size, Vector};
+use crate::transform::Transform;
+use rayon::prelude::{IntoParallelRefMutIterator, ParallelIterator};
+
+/// Applies `f` to all items and replaces them with the results.
+pub fn transform_all<T: Transform>(items: &mut [T], f: impl Fn(&T) -> T) {
+   for item in items {
+      *item = f(item);
+   }
+}
+
+/// [Transform] for multiple items at once.
+///
+/// ```
+/// # use typed_scad::geometry::{Point, SizeLiteral, Vector};
+/// # use typed_scad::transform::TransformAll;
+/// let mut points = vec![Point::ORIGIN, Point::new(1.mm(), 2.mm(), 3.mm())];
+/// points.translate_all(&Vector::X_UNIT_VECTOR);
+/// assert_eq!(
+///    points,
+///    vec![Point::new(1.mm(), 0.mm(), 0.mm()), Point::new(2.mm(), 2.mm(), 3.mm())]
+/// );
+/// ```
+///
+/// `par_` variants do the same with [Rayon]. They are useful for large slices,
+/// and need the items to be [Send] and [Sync].
+///
+/// [Rayon]: https://docs.rs/rayon/latest/rayon/
+pub trait TransformAll {
+   type Item: Transform;
+
+   fn translate_all(&mut self, offset: &Vector);
+   fn translate_all_toward(&mut self, direction: &Vector, distance: Size);
+   fn rotate_all(&mut self, axis: &Line, angle: Angle);
+
+   fn par_translate_all(&mut self, offset: &Vector)
+      where Self::Item: Send + Sync;
+
+   fn par_rotate_all(&mut self, axis: &Line, angle: Angle)
+      where Self::Item: Send + Sync;
+}
+
+impl<T: Transform> TransformAll for [T] {
+   type Item = T;
+
+   fn translate_all(&mut self, offset: &Vector) {
+      for item in self {
+         item.translate(offset);
+      }
+   }
+
+   fn translate_all_toward(&mut self, direction: &Vector, distance: Size) {
+      let offset = *direction * (distance / direction.norm());
+      self.translate_all(&offset);
+   }
+
+   fn rotate_all(&mut self, axis: &Line, angle: Angle) {
+      for item in self {
+         item.rotate(axis, angle);
+      }
+   }
+
+   fn par_translate_all(&mut self, offset: &Vector)
+      where T: Send + Sync
+   {
+      self.par_iter_mut().for_each(|item| item.translate(offset));
+   }
+
+   fn par_rotate_all(&mut self, axis: &Line, angle: Angle)
+      where T: Send + Sync
+   {
+      self.par_iter_mut().for_each(|item| item.rotate(axis, angle));
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::{transform_all, TransformAll};
+   use crate::geometry::{Angle, AngleLiteral, Line, Point, SizeLiteral, Vector};
+   use crate::transform::Transform;
+   use std::rc::Rc;
+
+   fn points() -> Vec<Point> {
+      (0..100)
+         .map(|i| Point::new(i.mm(), (i * 2).mm(), (i * 3).mm()))
+         .collect()
+   }
+
+   #[test]
+   fn translate_all() {
+      let offset = Vector::new(1.mm(), 2.mm(), 3.mm());
+      let expected: Vec<_> = points().iter()
+         .map(|p| p.translated(&offset))
+         .collect();
+
+      let mut actual = points();
+      actual.translate_all(&offset);
+      assert_eq!(actual, expected);
+
+      let mut actual = points();
+      actual.par_translate_all(&offset);
+      assert_eq!(actual, expected);
+
+      let mut actual = points();
+      actual.translate_all_toward(&Vector::new(2.mm(), 4.mm(), 6.mm()), offset.norm());
+      assert_eq!(actual, expected);
+   }
+
+   #[test]
+   fn rotate_all() {
+      let axis = Line::new(&Point::new(1.mm(), 1.mm(), 0.mm()), &Vector::Z_UNIT_VECTOR);
+      let expected: Vec<_> = points().iter()
+         .map(|p| p.rotated(&axis, 30.deg()))
+         .collect();
+
+      let mut actual = points();
+      actual.rotate_all(&axis, 30.deg());
+      assert_eq!(actual, expected);
+
+      let mut actual = points();
+      actual.par_rotate_all(&axis, 30.deg());
+      assert_eq!(actual, expected);
+   }
+
+   #[test]
+   fn transform_all_fn() {
+      let offset = Vector::new(1.mm(), 2.mm(), 3.mm());
+      let expected: Vec<_> = points().iter()
+         .map(|p| p.translated(&offset))
+         .collect();
+
+      let mut actual = points();
+      transform_all(&mut actual, |p| p.translated(&offset));
+      assert_eq!(actual, expected);
+   }
+
+   #[test]
+   fn not_send() {
+      // Rc is neither Send nor Sync, but the sequential methods are available
+      #[derive(Debug, PartialEq)]
+      struct Shared(Rc<Point>);
+
+      impl Transform for Shared {
+         fn translated(&self, offset: &Vector) -> Self {
+            Shared(Rc::new(self.0.translated(offset)))
+         }
+
+         fn rotated(&self, axis: &Line, angle: Angle) -> Self {
+            Shared(Rc::new(self.0.rotated(axis, angle)))
+         }
+      }
+
+      let mut items = vec![Shared(Rc::new(Point::ORIGIN))];
+      items.translate_all(&Vector::X_UNIT_VECTOR);
+      assert_eq!(items, vec![Shared(Rc::new(Point::new(1.mm(), 0.mm(), 0.mm())))]);
+   }
+}